@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use futures::future::join_all;
+
+use crate::config::Credentials;
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::types::request::trade::OrderRequest;
+use crate::types::response::trade::OrderResult;
+use crate::ws::types::WsConfig;
+use crate::ws::WebsocketClient;
+
+/// REST and WebSocket clients for one credential set.
+pub struct Account {
+    pub label: String,
+    pub rest: RestClient,
+    /// WebSocket client for this account's private and business channels.
+    /// Subscribe to market data through [`AccountManager::public_ws`] instead.
+    pub ws: WebsocketClient,
+}
+
+/// Owns the clients of several accounts, keyed by label.
+///
+/// All accounts share the region, trading mode, and connection settings of
+/// the template config. REST clients of every account share one connection
+/// pool, and public market data goes through a single shared WebSocket
+/// client so each account does not open its own public connection.
+pub struct AccountManager {
+    template: WsConfig,
+    public_rest: RestClient,
+    public_ws: WebsocketClient,
+    accounts: BTreeMap<String, Account>,
+}
+
+impl AccountManager {
+    /// Create an empty manager. Credentials in `template` are ignored.
+    pub fn new(mut template: WsConfig) -> OkxResult<Self> {
+        template.client_config.credentials = None;
+        Ok(Self {
+            public_rest: RestClient::new(template.client_config.clone())?,
+            public_ws: WebsocketClient::new(template.clone()),
+            template,
+            accounts: BTreeMap::new(),
+        })
+    }
+
+    /// Add an account under `label`.
+    pub fn add_account(&mut self, label: &str, credentials: Credentials) -> OkxResult<&Account> {
+        if self.accounts.contains_key(label) {
+            return Err(OkxError::Config(format!("account {label} already exists")));
+        }
+
+        let rest = self.public_rest.with_credentials(credentials.clone())?;
+        let mut config = self.template.clone();
+        config.client_config.credentials = Some(credentials);
+        let account = Account {
+            label: label.to_string(),
+            rest,
+            ws: WebsocketClient::new(config),
+        };
+        Ok(self.accounts.entry(label.to_string()).or_insert(account))
+    }
+
+    /// Remove an account, returning its clients.
+    pub fn remove_account(&mut self, label: &str) -> Option<Account> {
+        self.accounts.remove(label)
+    }
+
+    pub fn account(&self, label: &str) -> Option<&Account> {
+        self.accounts.get(label)
+    }
+
+    /// Account labels in sorted order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.accounts.keys().map(String::as_str)
+    }
+
+    /// Unauthenticated REST client sharing the accounts' connection pool.
+    pub fn public_rest(&self) -> &RestClient {
+        &self.public_rest
+    }
+
+    /// Shared WebSocket client for public market data.
+    pub fn public_ws(&self) -> &WebsocketClient {
+        &self.public_ws
+    }
+
+    /// Run `f` against every account concurrently and collect the results
+    /// by label. A failure on one account does not affect the others.
+    pub async fn fan_out<'a, T, F, Fut>(&'a self, f: F) -> BTreeMap<String, OkxResult<T>>
+    where
+        F: Fn(&'a Account) -> Fut,
+        Fut: Future<Output = OkxResult<T>>,
+    {
+        let calls = self.accounts.values().map(|account| {
+            let call = f(account);
+            async move { (account.label.clone(), call.await) }
+        });
+        join_all(calls).await.into_iter().collect()
+    }
+
+    /// Place the same order on every account.
+    pub async fn place_order_all(
+        &self,
+        params: &OrderRequest,
+    ) -> BTreeMap<String, OkxResult<Vec<OrderResult>>> {
+        self.fan_out(|account| account.rest.place_order(params)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> AccountManager {
+        let mut manager = AccountManager::new(WsConfig::default()).unwrap();
+        manager
+            .add_account("a", Credentials::new("key-a", "secret", "pass"))
+            .unwrap();
+        manager
+            .add_account("b", Credentials::new("key-b", "secret", "pass"))
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn rejects_duplicate_labels() {
+        let mut manager = manager();
+        let err = manager
+            .add_account("a", Credentials::new("key", "secret", "pass"))
+            .err()
+            .unwrap();
+        assert!(matches!(err, OkxError::Config(_)));
+        assert_eq!(manager.labels().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert!(manager
+            .public_ws()
+            .config()
+            .client_config
+            .credentials
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn fan_out_collects_per_account_results() {
+        let manager = manager();
+        let results = manager
+            .fan_out(|account| async move {
+                let creds = account.ws.config().client_config.credentials.as_ref();
+                if account.label == "a" {
+                    Ok(creds.unwrap().api_key.clone())
+                } else {
+                    Err(OkxError::Config("boom".into()))
+                }
+            })
+            .await;
+
+        assert_eq!(results["a"].as_ref().unwrap(), "key-a");
+        assert!(results["b"].is_err());
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::OkxResult;
+use crate::rest::RestClient;
+use crate::types::enums::{BillType, InstrumentType};
+use crate::types::request::account::{GetBalanceRequest, GetBillsRequest, GetPositionsRequest};
+use crate::types::request::public::GetInstrumentsRequest;
+use crate::types::request::trade::GetFillsRequest;
+use crate::types::response::account::{AccountBalance, Bill, Position};
+use crate::types::response::public::Instrument;
+use crate::types::response::trade::Fill;
+
+/// Profit and loss of one instrument, in its settlement currency.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstrumentPnl {
+    pub inst_id: String,
+    /// Currency the amounts are in; empty until an instrument, fill or bill
+    /// names it.
+    pub ccy: String,
+    /// PnL of closing fills.
+    pub realized: f64,
+    /// Trading fees. Negative means fees charged.
+    pub fees: f64,
+    /// PnL of the open position at the latest mark price.
+    pub unrealized: f64,
+    /// Funding fees. Negative means funding paid.
+    pub funding: f64,
+}
+
+impl InstrumentPnl {
+    /// Sum of realized, unrealized, fees and funding.
+    pub fn total(&self) -> f64 {
+        self.realized + self.unrealized + self.fees + self.funding
+    }
+}
+
+/// Net delta to one currency, in units of that currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exposure {
+    pub ccy: String,
+    pub delta: f64,
+}
+
+/// Positions, balances, mark prices and fills of an account, and the PnL
+/// and exposure derived from them.
+///
+/// Load a REST snapshot with [`from_rest`](Self::from_rest), or keep it live
+/// by applying `positions`, `balance_and_position`, `mark-price` and `fills`
+/// pushes as they arrive. Contract specs come from
+/// [`add_instrument`](Self::add_instrument), e.g. fed from an
+/// [`InstrumentRegistry`](crate::InstrumentRegistry).
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    instruments: HashMap<String, Instrument>,
+    /// Keyed by instrument ID and position side.
+    positions: HashMap<(String, String), Position>,
+    marks: HashMap<String, f64>,
+    balances: HashMap<String, f64>,
+    /// Realized, fee and funding totals per instrument.
+    booked: HashMap<String, InstrumentPnl>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch balances, positions and their instruments, and the latest page
+    /// of fills and of funding fee bills.
+    pub async fn from_rest(rest: &RestClient) -> OkxResult<Self> {
+        let mut portfolio = Self::new();
+
+        for balance in rest.get_balance(&GetBalanceRequest::default()).await? {
+            portfolio.apply_balance(&balance);
+        }
+
+        let positions = rest.get_positions(&GetPositionsRequest::default()).await?;
+        for position in &positions {
+            let Some(inst_type) = derivative_type(&position.inst_type) else {
+                continue;
+            };
+            if portfolio.instruments.contains_key(&position.inst_id) {
+                continue;
+            }
+            let inst_family =
+                (inst_type == InstrumentType::Option).then(|| option_family(&position.inst_id));
+            for instrument in rest
+                .get_instruments(&GetInstrumentsRequest {
+                    inst_type,
+                    inst_id: Some(position.inst_id.clone()),
+                    inst_family,
+                    ..Default::default()
+                })
+                .await?
+            {
+                portfolio.add_instrument(instrument);
+            }
+        }
+        for position in positions {
+            portfolio.apply_position(position);
+        }
+
+        for fill in rest.get_fills(&GetFillsRequest::default()).await? {
+            portfolio.apply_fill(&fill);
+        }
+        for bill in rest
+            .get_bills(&GetBillsRequest {
+                type_: Some(BillType::FundingFee),
+                ..Default::default()
+            })
+            .await?
+        {
+            portfolio.apply_bill(&bill);
+        }
+
+        Ok(portfolio)
+    }
+
+    /// Register the contract specs of an instrument.
+    pub fn add_instrument(&mut self, instrument: Instrument) {
+        if let Some(pnl) = self.booked.get_mut(&instrument.inst_id) {
+            if pnl.ccy.is_empty() {
+                pnl.ccy = settle_ccy(&instrument).to_string();
+            }
+        }
+        self.instruments
+            .insert(instrument.inst_id.clone(), instrument);
+    }
+
+    /// Insert or replace a position. A zero position removes it.
+    ///
+    /// The position's mark price is used until a newer one is applied.
+    pub fn apply_position(&mut self, position: Position) {
+        if let Ok(mark) = position.mark_px.parse::<f64>() {
+            self.marks.insert(position.inst_id.clone(), mark);
+        }
+        let key = (position.inst_id.clone(), position.pos_side.clone());
+        if num(&position.pos) == 0.0 {
+            self.positions.remove(&key);
+        } else {
+            self.positions.insert(key, position);
+        }
+    }
+
+    /// Replace the per-currency equity with an account balance.
+    pub fn apply_balance(&mut self, balance: &AccountBalance) {
+        for detail in &balance.details {
+            self.balances.insert(detail.ccy.clone(), num(&detail.eq));
+        }
+    }
+
+    /// Record the latest mark price of an instrument.
+    pub fn apply_mark_price(&mut self, inst_id: &str, mark_px: f64) {
+        self.marks.insert(inst_id.to_string(), mark_px);
+    }
+
+    /// Book the realized PnL and fee of a fill.
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        let pnl = self.booked_mut(&fill.inst_id);
+        pnl.realized += num(&fill.fill_pnl);
+        pnl.fees += num(&fill.fee);
+        if pnl.ccy.is_empty() {
+            pnl.ccy = fill.fee_ccy.clone();
+        }
+    }
+
+    /// Book a funding fee bill. Other bill types are ignored.
+    pub fn apply_bill(&mut self, bill: &Bill) {
+        if bill.type_ != BillType::FundingFee {
+            return;
+        }
+        let pnl = self.booked_mut(&bill.inst_id);
+        pnl.funding += num(&bill.bal_chg);
+        if pnl.ccy.is_empty() {
+            pnl.ccy = bill.ccy.clone();
+        }
+    }
+
+    /// PnL of every instrument with an open position or booked fills or
+    /// funding, ordered by instrument ID.
+    pub fn pnl(&self) -> Vec<InstrumentPnl> {
+        let mut by_inst: BTreeMap<&str, InstrumentPnl> = self
+            .booked
+            .iter()
+            .map(|(inst_id, pnl)| (inst_id.as_str(), pnl.clone()))
+            .collect();
+        for ((inst_id, _), position) in &self.positions {
+            let pnl = by_inst.entry(inst_id).or_insert_with(|| InstrumentPnl {
+                inst_id: inst_id.clone(),
+                ..Default::default()
+            });
+            pnl.unrealized += self.unrealized(position);
+            if pnl.ccy.is_empty() {
+                pnl.ccy = match self.instruments.get(inst_id) {
+                    Some(instrument) => settle_ccy(instrument).to_string(),
+                    None => position.ccy.clone(),
+                };
+            }
+        }
+        by_inst.into_values().collect()
+    }
+
+    /// Net delta per currency: balance equity plus the delta of SWAP,
+    /// FUTURES and OPTION positions, ordered by currency.
+    ///
+    /// MARGIN positions are not added separately since their borrowings are
+    /// already part of the balance equity. Positions without contract specs
+    /// or, for inverse contracts, a mark price are skipped.
+    pub fn exposures(&self) -> Vec<Exposure> {
+        let mut by_ccy: BTreeMap<String, f64> = self
+            .balances
+            .iter()
+            .map(|(ccy, eq)| (ccy.clone(), *eq))
+            .collect();
+        for position in self.positions.values() {
+            if let Some((ccy, delta)) = self.delta(position) {
+                *by_ccy.entry(ccy).or_default() += delta;
+            }
+        }
+        by_ccy
+            .into_iter()
+            .map(|(ccy, delta)| Exposure { ccy, delta })
+            .collect()
+    }
+
+    /// Funding paid per currency; negative if more was received than paid.
+    pub fn funding_paid(&self) -> BTreeMap<String, f64> {
+        let mut paid = BTreeMap::new();
+        for pnl in self.booked.values().filter(|p| p.funding != 0.0) {
+            *paid.entry(pnl.ccy.clone()).or_default() -= pnl.funding;
+        }
+        paid
+    }
+
+    fn booked_mut(&mut self, inst_id: &str) -> &mut InstrumentPnl {
+        let ccy = self
+            .instruments
+            .get(inst_id)
+            .map(|i| settle_ccy(i).to_string())
+            .unwrap_or_default();
+        self.booked
+            .entry(inst_id.to_string())
+            .or_insert_with(|| InstrumentPnl {
+                inst_id: inst_id.to_string(),
+                ccy,
+                ..Default::default()
+            })
+    }
+
+    /// Unrealized PnL of a SWAP or FUTURES position at the latest mark
+    /// price; the exchange-reported `upl` for anything else or when specs or
+    /// the mark are missing.
+    fn unrealized(&self, position: &Position) -> f64 {
+        let reported = num(&position.upl);
+        let (Some(instrument), Some(&mark)) = (
+            self.instruments.get(&position.inst_id),
+            self.marks.get(&position.inst_id),
+        ) else {
+            return reported;
+        };
+        if !matches!(instrument.inst_type.as_str(), "SWAP" | "FUTURES") {
+            return reported;
+        }
+        let avg_px = num(&position.avg_px);
+        if avg_px == 0.0 || mark == 0.0 {
+            return reported;
+        }
+        let contracts = signed_pos(position) * contract_size(instrument);
+        if is_inverse(instrument) {
+            contracts * (1.0 / avg_px - 1.0 / mark)
+        } else {
+            contracts * (mark - avg_px)
+        }
+    }
+
+    /// Currency and delta of a derivative position.
+    fn delta(&self, position: &Position) -> Option<(String, f64)> {
+        let instrument = self.instruments.get(&position.inst_id)?;
+        let ccy = underlying_ccy(instrument)?;
+        let delta = match instrument.inst_type.as_str() {
+            "OPTION" => position.delta_pa.parse().ok()?,
+            "SWAP" | "FUTURES" => {
+                let contracts = signed_pos(position) * contract_size(instrument);
+                if is_inverse(instrument) {
+                    let mark = *self.marks.get(&position.inst_id)?;
+                    if mark == 0.0 {
+                        return None;
+                    }
+                    contracts / mark
+                } else {
+                    contracts
+                }
+            }
+            _ => return None,
+        };
+        Some((ccy.to_string(), delta))
+    }
+}
+
+fn num(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+/// Position size, negative for shorts. Net-mode positions already carry
+/// their sign.
+fn signed_pos(position: &Position) -> f64 {
+    let pos = num(&position.pos);
+    match position.pos_side.as_str() {
+        "short" => -pos.abs(),
+        "long" => pos.abs(),
+        _ => pos,
+    }
+}
+
+fn contract_size(instrument: &Instrument) -> f64 {
+    instrument.contract_size().unwrap_or_default()
+}
+
+fn is_inverse(instrument: &Instrument) -> bool {
+    instrument.ct_type == "inverse"
+}
+
+fn settle_ccy(instrument: &Instrument) -> &str {
+    &instrument.settle_ccy
+}
+
+/// Base currency of the underlying, e.g. "BTC" for "BTC-USD".
+fn underlying_ccy(instrument: &Instrument) -> Option<&str> {
+    let uly = if instrument.uly.is_empty() {
+        &instrument.inst_family
+    } else {
+        &instrument.uly
+    };
+    uly.split('-').next().filter(|ccy| !ccy.is_empty())
+}
+
+fn derivative_type(inst_type: &str) -> Option<InstrumentType> {
+    match inst_type {
+        "SWAP" => Some(InstrumentType::Swap),
+        "FUTURES" => Some(InstrumentType::Futures),
+        "OPTION" => Some(InstrumentType::Option),
+        _ => None,
+    }
+}
+
+/// Instrument family of an option ID, e.g. "BTC-USD" for
+/// "BTC-USD-240329-50000-C".
+fn option_family(inst_id: &str) -> String {
+    inst_id.splitn(3, '-').take(2).collect::<Vec<_>>().join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument(
+        inst_id: &str,
+        uly: &str,
+        ct_type: &str,
+        ct_val: &str,
+        settle: &str,
+    ) -> Instrument {
+        serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": inst_id, "uly": uly, "instFamily": uly,
+            "ctType": ct_type, "ctVal": ct_val, "ctMult": "1", "settleCcy": settle,
+        }))
+        .unwrap()
+    }
+
+    fn position(inst_id: &str, pos_side: &str, pos: &str, avg_px: &str, mark_px: &str) -> Position {
+        serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": inst_id, "posSide": pos_side, "pos": pos,
+            "avgPx": avg_px, "markPx": mark_px, "upl": "999",
+        }))
+        .unwrap()
+    }
+
+    fn portfolio() -> Portfolio {
+        let mut p = Portfolio::new();
+        p.add_instrument(instrument(
+            "BTC-USDT-SWAP",
+            "BTC-USDT",
+            "linear",
+            "0.01",
+            "USDT",
+        ));
+        p.add_instrument(instrument(
+            "BTC-USD-SWAP",
+            "BTC-USD",
+            "inverse",
+            "100",
+            "BTC",
+        ));
+        p
+    }
+
+    #[test]
+    fn unrealized_pnl_of_linear_and_inverse_swaps() {
+        let mut p = portfolio();
+        p.apply_position(position("BTC-USDT-SWAP", "long", "10", "40000", "41000"));
+        p.apply_position(position("BTC-USD-SWAP", "net", "-50", "40000", "50000"));
+
+        let pnl = p.pnl();
+        assert_eq!(pnl.len(), 2);
+        assert_eq!(pnl[0].inst_id, "BTC-USD-SWAP");
+        assert_eq!(pnl[0].ccy, "BTC");
+        // Short 5000 USD from 40000 to 50000: 5000 * (1/40000 - 1/50000) = 0.025 BTC lost.
+        assert!((pnl[0].unrealized + 0.025).abs() < 1e-12);
+        assert_eq!(pnl[1].ccy, "USDT");
+        assert!((pnl[1].unrealized - 100.0).abs() < 1e-9);
+
+        p.apply_mark_price("BTC-USDT-SWAP", 39_000.0);
+        assert!((p.pnl()[1].unrealized + 100.0).abs() < 1e-9);
+
+        p.apply_position(position("BTC-USDT-SWAP", "long", "0", "", ""));
+        assert_eq!(p.pnl().len(), 1);
+    }
+
+    #[test]
+    fn fills_and_funding_are_booked() {
+        let mut p = portfolio();
+        let fill: Fill = serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": "BTC-USDT-SWAP", "tradeId": "1", "ordId": "2",
+            "clOrdId": "", "billId": "3", "tag": "", "fillPx": "41000", "fillSz": "10",
+            "side": "sell", "posSide": "long", "execType": "T", "feeCcy": "USDT", "fee": "-0.5",
+            "ts": "1", "fillTime": "1", "fillPnl": "12.5", "fillPxVol": "", "fillPxUsd": "",
+            "fillMarkVol": "", "fillFwdPx": "", "fillMarkPx": "",
+        }))
+        .unwrap();
+        p.apply_fill(&fill);
+        p.apply_fill(&fill);
+        for bal_chg in ["-1.5", "0.25"] {
+            let bill: Bill = serde_json::from_value(serde_json::json!({
+                "instId": "BTC-USDT-SWAP", "ccy": "USDT", "type": "8", "subType": "173",
+                "balChg": bal_chg,
+            }))
+            .unwrap();
+            p.apply_bill(&bill);
+        }
+
+        let pnl = &p.pnl()[0];
+        assert_eq!(pnl.realized, 25.0);
+        assert_eq!(pnl.fees, -1.0);
+        assert_eq!(pnl.funding, -1.25);
+        assert_eq!(pnl.total(), 22.75);
+        assert_eq!(p.funding_paid()["USDT"], 1.25);
+    }
+
+    #[test]
+    fn exposure_nets_balances_and_contracts() {
+        let mut p = portfolio();
+        let balance: AccountBalance = serde_json::from_value(serde_json::json!({
+            "details": [{"ccy": "BTC", "eq": "0.2"}, {"ccy": "USDT", "eq": "1000"}],
+        }))
+        .unwrap();
+        p.apply_balance(&balance);
+        p.apply_position(position("BTC-USDT-SWAP", "short", "10", "40000", "40000"));
+        p.apply_position(position("BTC-USD-SWAP", "net", "-4", "40000", "40000"));
+
+        let exposures = p.exposures();
+        assert_eq!(exposures[0].ccy, "BTC");
+        // 0.2 held, -0.1 linear, -400 USD / 40000 = -0.01 inverse.
+        assert!((exposures[0].delta - 0.09).abs() < 1e-12);
+        assert_eq!(exposures[1].ccy, "USDT");
+        assert_eq!(exposures[1].delta, 1000.0);
+    }
+
+    #[test]
+    fn option_family_is_first_two_segments() {
+        assert_eq!(option_family("BTC-USD-240329-50000-C"), "BTC-USD");
+    }
+}
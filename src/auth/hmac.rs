@@ -6,9 +6,17 @@ use crate::error::OkxError;
 
 /// Sign a message with HMAC-SHA256 and return the base64-encoded signature.
 pub fn sign_hmac_sha256(message: &str, secret: &SecretString) -> Result<String, OkxError> {
-    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.expose_secret().as_bytes());
-    let signature = hmac::sign(&key, message.as_bytes());
-    Ok(base64::engine::general_purpose::STANDARD.encode(signature.as_ref()))
+    Ok(sign_with_key(&key(secret), message))
+}
+
+/// Derive the HMAC-SHA256 key from the secret.
+pub(crate) fn key(secret: &SecretString) -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, secret.expose_secret().as_bytes())
+}
+
+pub(crate) fn sign_with_key(key: &hmac::Key, message: &str) -> String {
+    let signature = hmac::sign(key, message.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(signature.as_ref())
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use base64::Engine;
+use rsa::pkcs1v15::SigningKey;
 use rsa::pkcs8::DecodePrivateKey;
 use rsa::RsaPrivateKey;
 use secrecy::{ExposeSecret, SecretString};
@@ -8,13 +9,19 @@ use crate::error::OkxError;
 
 /// Sign a message with RSA-PKCS1v1.5-SHA256 and return the base64-encoded signature.
 pub fn sign_rsa(message: &str, secret: &SecretString) -> Result<String, OkxError> {
-    use rsa::pkcs1v15::SigningKey;
-    use rsa::signature::{SignatureEncoding, Signer};
+    Ok(sign_with_key(&signing_key(secret)?, message))
+}
 
+/// Parse the PKCS8 PEM secret into a signing key.
+pub(crate) fn signing_key(secret: &SecretString) -> Result<SigningKey<Sha256>, OkxError> {
     let private_key = RsaPrivateKey::from_pkcs8_pem(secret.expose_secret())
         .map_err(|e| OkxError::Auth(format!("Invalid RSA key: {e}")))?;
-    let signing_key = SigningKey::<Sha256>::new(private_key);
-    let signature = signing_key
-        .sign(message.as_bytes());
-    Ok(base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()))
+    Ok(SigningKey::<Sha256>::new(private_key))
+}
+
+pub(crate) fn sign_with_key(key: &SigningKey<Sha256>, message: &str) -> String {
+    use rsa::signature::{SignatureEncoding, Signer};
+
+    let signature = key.sign(message.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
 }
@@ -0,0 +1,244 @@
+use std::sync::{Arc, RwLock};
+
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::types::response::market::Ticker;
+use crate::types::response::public::{FundingRate, MarkPrice};
+use crate::types::ws::channels::{WsChannel, WsSubscriptionArg};
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+/// Funding interval assumed when a rate does not say, in milliseconds.
+const DEFAULT_FUNDING_INTERVAL_MS: i64 = 8 * 3_600_000;
+
+const YEAR_MS: f64 = 365.0 * 24.0 * 3_600_000.0;
+
+/// Spot-perp basis and funding at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Basis {
+    pub spot_inst_id: String,
+    pub swap_inst_id: String,
+    /// Last traded spot price.
+    pub spot_px: f64,
+    /// Swap mark price.
+    pub mark_px: f64,
+    /// `mark_px - spot_px`.
+    pub basis: f64,
+    /// Basis as a fraction of the spot price.
+    pub basis_rate: f64,
+    /// Funding rate of the current interval.
+    pub funding_rate: f64,
+    /// Funding rate scaled to a year of intervals.
+    pub annualized_funding: f64,
+    /// Time of the newest input, Unix timestamp in milliseconds.
+    pub ts: i64,
+}
+
+#[derive(Debug, Default)]
+struct Inputs {
+    spot: Option<(f64, i64)>,
+    mark: Option<(f64, i64)>,
+    /// Rate and interval length in milliseconds.
+    funding: Option<(f64, i64, i64)>,
+}
+
+enum Update {
+    Spot(Ticker),
+    Mark(MarkPrice),
+    Funding(FundingRate),
+}
+
+/// Live basis between a spot pair and its perpetual swap, e.g. `BTC-USDT`
+/// and `BTC-USDT-SWAP`.
+///
+/// Combines the spot `tickers`, swap `mark-price` and `funding-rate`
+/// channels. A [`Basis`] is emitted on every update once all three have
+/// pushed.
+pub struct BasisMonitor {
+    latest: Arc<RwLock<Option<Basis>>>,
+    updates: broadcast::Sender<Basis>,
+    _task: FeedTask,
+}
+
+impl BasisMonitor {
+    /// Subscribe to the spot ticker and the swap mark price and funding rate.
+    pub async fn start(
+        ws: &WebsocketClient,
+        spot_inst_id: &str,
+        swap_inst_id: &str,
+    ) -> OkxResult<Self> {
+        let spot = ws
+            .subscribe_typed::<Ticker>(
+                WsSubscriptionArg::new(WsChannel::Tickers).inst_id(spot_inst_id),
+            )
+            .await?;
+        let mark = ws
+            .subscribe_typed::<MarkPrice>(
+                WsSubscriptionArg::new(WsChannel::MarkPrice).inst_id(swap_inst_id),
+            )
+            .await?;
+        let funding = ws
+            .subscribe_typed::<FundingRate>(WsSubscriptionArg::funding_rate(swap_inst_id))
+            .await?;
+
+        let streams: Vec<BoxStream<'static, OkxResult<Vec<Update>>>> = vec![
+            spot.into_stream()
+                .map(|batch| batch.map(|v| v.into_iter().map(Update::Spot).collect()))
+                .boxed(),
+            mark.into_stream()
+                .map(|batch| batch.map(|v| v.into_iter().map(Update::Mark).collect()))
+                .boxed(),
+            funding
+                .into_stream()
+                .map(|batch| batch.map(|v| v.into_iter().map(Update::Funding).collect()))
+                .boxed(),
+        ];
+
+        let latest = Arc::new(RwLock::new(None));
+        let (updates, _) = broadcast::channel(256);
+        let task = {
+            let latest = latest.clone();
+            let updates = updates.clone();
+            let spot_inst_id = spot_inst_id.to_string();
+            let swap_inst_id = swap_inst_id.to_string();
+            let mut merged = stream::select_all(streams);
+            FeedTask::spawn(async move {
+                let mut inputs = Inputs::default();
+                while let Some(batch) = merged.next().await {
+                    let batch = match batch {
+                        Ok(batch) => batch,
+                        Err(e) => {
+                            warn!("Failed to parse basis input push: {e}");
+                            continue;
+                        }
+                    };
+                    for update in batch {
+                        inputs.apply(update);
+                    }
+                    if let Some(basis) = inputs.basis(&spot_inst_id, &swap_inst_id) {
+                        *latest.write().unwrap_or_else(|e| e.into_inner()) = Some(basis.clone());
+                        let _ = updates.send(basis);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            latest,
+            updates,
+            _task: task,
+        })
+    }
+
+    /// Receive every basis update from now on.
+    pub fn updates(&self) -> broadcast::Receiver<Basis> {
+        self.updates.subscribe()
+    }
+
+    /// Latest basis, once spot, mark and funding have all been received.
+    pub fn latest(&self) -> Option<Basis> {
+        self.latest
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl Inputs {
+    /// Pushes with unparseable prices are ignored.
+    fn apply(&mut self, update: Update) {
+        match update {
+            Update::Spot(ticker) => {
+                if let Ok(px) = ticker.last.parse() {
+                    self.spot = Some((px, ticker.ts.parse().unwrap_or_default()));
+                }
+            }
+            Update::Mark(mark) => {
+                if let Ok(px) = mark.mark_px.parse() {
+                    self.mark = Some((px, mark.ts.parse().unwrap_or_default()));
+                }
+            }
+            Update::Funding(rate) => {
+                if let Ok(r) = rate.funding_rate.parse() {
+                    let ts = rate.ts.parse().unwrap_or_default();
+                    self.funding = Some((r, funding_interval_ms(&rate), ts));
+                }
+            }
+        }
+    }
+
+    fn basis(&self, spot_inst_id: &str, swap_inst_id: &str) -> Option<Basis> {
+        let (spot_px, spot_ts) = self.spot?;
+        let (mark_px, mark_ts) = self.mark?;
+        let (funding_rate, interval_ms, funding_ts) = self.funding?;
+        let basis = mark_px - spot_px;
+        Some(Basis {
+            spot_inst_id: spot_inst_id.to_string(),
+            swap_inst_id: swap_inst_id.to_string(),
+            spot_px,
+            mark_px,
+            basis,
+            basis_rate: if spot_px == 0.0 { 0.0 } else { basis / spot_px },
+            funding_rate,
+            annualized_funding: funding_rate * YEAR_MS / interval_ms as f64,
+            ts: spot_ts.max(mark_ts).max(funding_ts),
+        })
+    }
+}
+
+/// Length of the funding interval, from `fundingTime` to `nextFundingTime`.
+fn funding_interval_ms(rate: &FundingRate) -> i64 {
+    match (
+        rate.funding_time.parse::<i64>(),
+        rate.next_funding_time.parse::<i64>(),
+    ) {
+        (Ok(current), Ok(next)) if next > current => next - current,
+        _ => DEFAULT_FUNDING_INTERVAL_MS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update<T: serde::de::DeserializeOwned>(json: serde_json::Value) -> T {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn basis_needs_all_inputs_and_annualizes_funding() {
+        let mut inputs = Inputs::default();
+        inputs.apply(Update::Spot(update(
+            serde_json::json!({"instId": "BTC-USDT", "last": "40000", "ts": "10"}),
+        )));
+        inputs.apply(Update::Mark(update(
+            serde_json::json!({"instId": "BTC-USDT-SWAP", "markPx": "40100", "ts": "20"}),
+        )));
+        assert!(inputs.basis("BTC-USDT", "BTC-USDT-SWAP").is_none());
+
+        let hour = 3_600_000;
+        inputs.apply(Update::Funding(update(serde_json::json!({
+            "instId": "BTC-USDT-SWAP", "fundingRate": "0.0001",
+            "fundingTime": (4 * hour).to_string(), "nextFundingTime": (8 * hour).to_string(),
+            "ts": "15",
+        }))));
+
+        let basis = inputs.basis("BTC-USDT", "BTC-USDT-SWAP").unwrap();
+        assert_eq!(basis.basis, 100.0);
+        assert_eq!(basis.basis_rate, 0.0025);
+        // Six 4-hour intervals a day.
+        assert!((basis.annualized_funding - 0.0001 * 6.0 * 365.0).abs() < 1e-12);
+        assert_eq!(basis.ts, 20);
+    }
+
+    #[test]
+    fn unknown_interval_defaults_to_eight_hours() {
+        let rate: FundingRate = update(serde_json::json!({
+            "instId": "BTC-USDT-SWAP", "fundingRate": "0.0001", "fundingTime": "",
+        }));
+        assert_eq!(funding_interval_ms(&rate), DEFAULT_FUNDING_INTERVAL_MS);
+    }
+}
@@ -0,0 +1,315 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::types::ws::channels::{WsChannel, WsSubscriptionArg};
+use crate::types::ws::data::{DepthLevel, DepthSnapshot};
+use crate::types::ws::events::WsMessage;
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+/// Price usable as a map key. Ordered by `f64::total_cmp`.
+#[derive(Debug, Clone, Copy)]
+struct Price(f64);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Metrics of a [`LocalOrderBook`] at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookMetrics {
+    pub inst_id: String,
+    /// Book time, Unix timestamp in milliseconds.
+    pub ts: i64,
+    pub mid: Option<f64>,
+    pub spread: Option<f64>,
+    /// Bid size within the depth band below the mid.
+    pub bid_depth: f64,
+    /// Ask size within the depth band above the mid.
+    pub ask_depth: f64,
+    /// `(bid_depth - ask_depth) / (bid_depth + ask_depth)`, from -1 (all
+    /// asks) to 1 (all bids).
+    pub imbalance: Option<f64>,
+    /// Mid weighted towards the side with less size at the touch.
+    pub microprice: Option<f64>,
+}
+
+/// Order book of one instrument rebuilt from snapshot and incremental
+/// pushes.
+#[derive(Debug, Clone, Default)]
+pub struct LocalOrderBook {
+    inst_id: String,
+    bids: BTreeMap<Price, f64>,
+    asks: BTreeMap<Price, f64>,
+    ts: i64,
+}
+
+impl LocalOrderBook {
+    pub fn new(inst_id: &str) -> Self {
+        Self {
+            inst_id: inst_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn inst_id(&self) -> &str {
+        &self.inst_id
+    }
+
+    /// Book time of the last applied push, Unix timestamp in milliseconds.
+    pub fn ts(&self) -> i64 {
+        self.ts
+    }
+
+    /// Apply a push. A snapshot replaces the book; an update sets the size
+    /// of each level it carries, removing levels with size zero.
+    pub fn apply(&mut self, push: &DepthSnapshot, snapshot: bool) {
+        if snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        }
+        apply_levels(&mut self.bids, &push.bids);
+        apply_levels(&mut self.asks, &push.asks);
+        if let Ok(ts) = push.ts.parse() {
+            self.ts = ts;
+        }
+    }
+
+    /// Best bid price and size.
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids.iter().next_back().map(|(px, sz)| (px.0, *sz))
+    }
+
+    /// Best ask price and size.
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks.iter().next().map(|(px, sz)| (px.0, *sz))
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.0 + self.best_ask()?.0) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.0 - self.best_bid()?.0)
+    }
+
+    /// Cumulative bid and ask size within `bps` basis points of the mid.
+    pub fn depth_within_bps(&self, bps: f64) -> (f64, f64) {
+        let Some(mid) = self.mid() else {
+            return (0.0, 0.0);
+        };
+        let band = mid * bps / 10_000.0;
+        let bid = self.bids.range(Price(mid - band)..).map(|(_, sz)| sz).sum();
+        let ask = self
+            .asks
+            .range(..=Price(mid + band))
+            .map(|(_, sz)| sz)
+            .sum();
+        (bid, ask)
+    }
+
+    /// Bid/ask imbalance of the size within `bps` basis points of the mid.
+    pub fn imbalance(&self, bps: f64) -> Option<f64> {
+        let (bid, ask) = self.depth_within_bps(bps);
+        let total = bid + ask;
+        (total > 0.0).then(|| (bid - ask) / total)
+    }
+
+    /// Touch prices weighted by the opposite side's size.
+    pub fn microprice(&self) -> Option<f64> {
+        let (bid_px, bid_sz) = self.best_bid()?;
+        let (ask_px, ask_sz) = self.best_ask()?;
+        let total = bid_sz + ask_sz;
+        (total > 0.0).then(|| (bid_px * ask_sz + ask_px * bid_sz) / total)
+    }
+
+    /// All metrics, with depth and imbalance taken within `bps` of the mid.
+    pub fn metrics(&self, bps: f64) -> BookMetrics {
+        let (bid_depth, ask_depth) = self.depth_within_bps(bps);
+        BookMetrics {
+            inst_id: self.inst_id.clone(),
+            ts: self.ts,
+            mid: self.mid(),
+            spread: self.spread(),
+            bid_depth,
+            ask_depth,
+            imbalance: self.imbalance(bps),
+            microprice: self.microprice(),
+        }
+    }
+}
+
+fn apply_levels(side: &mut BTreeMap<Price, f64>, levels: &[DepthLevel]) {
+    for level in levels {
+        let (Ok(px), Ok(sz)) = (level.px.parse::<f64>(), level.sz.parse::<f64>()) else {
+            continue;
+        };
+        if sz == 0.0 {
+            side.remove(&Price(px));
+        } else {
+            side.insert(Price(px), sz);
+        }
+    }
+}
+
+/// Live [`LocalOrderBook`] of one instrument, fed by the `books` channel.
+///
+/// After every push the book's [`BookMetrics`] are sent to
+/// [`updates`](Self::updates) for signal generation. Depth and imbalance
+/// are measured within the configured band around the mid.
+pub struct OrderBookFeed {
+    book: Arc<RwLock<LocalOrderBook>>,
+    updates: broadcast::Sender<BookMetrics>,
+    _task: FeedTask,
+}
+
+impl OrderBookFeed {
+    /// Subscribe to the `books` channel of `inst_id`, measuring depth within
+    /// `depth_bps` basis points of the mid.
+    pub async fn start(ws: &WebsocketClient, inst_id: &str, depth_bps: f64) -> OkxResult<Self> {
+        let arg = WsSubscriptionArg::new(WsChannel::Books).inst_id(inst_id);
+        let mut rx = ws.subscribe(vec![arg.clone()]).await?;
+
+        let book = Arc::new(RwLock::new(LocalOrderBook::new(inst_id)));
+        let (updates, _) = broadcast::channel(1024);
+        let task = {
+            let book = book.clone();
+            let updates = updates.clone();
+            let arg = arg.clone();
+            FeedTask::spawn(async move {
+                loop {
+                    let msg = match rx.recv().await {
+                        Ok(msg) => msg,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Order book feed lagged, {n} events dropped");
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    let WsMessage::Data(evt) = msg.as_ref() else {
+                        continue;
+                    };
+                    if !arg.matches(&evt.arg) {
+                        continue;
+                    }
+                    let pushes = match evt.parse_data::<DepthSnapshot>() {
+                        Ok(pushes) => pushes,
+                        Err(e) => {
+                            warn!("Failed to parse books push: {e}");
+                            continue;
+                        }
+                    };
+                    let snapshot = evt.action.as_deref() != Some("update");
+                    let metrics = {
+                        let mut book = book.write().unwrap_or_else(|e| e.into_inner());
+                        for push in &pushes {
+                            book.apply(push, snapshot);
+                        }
+                        book.metrics(depth_bps)
+                    };
+                    let _ = updates.send(metrics);
+                }
+            })
+        };
+
+        Ok(Self {
+            book,
+            updates,
+            _task: task.releasing(ws, vec![arg]),
+        })
+    }
+
+    /// Receive the book metrics after every push from now on.
+    pub fn updates(&self) -> broadcast::Receiver<BookMetrics> {
+        self.updates.subscribe()
+    }
+
+    /// Copy of the current book.
+    pub fn book(&self) -> LocalOrderBook {
+        self.book.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(bids: serde_json::Value, asks: serde_json::Value, ts: &str) -> DepthSnapshot {
+        serde_json::from_value(serde_json::json!({"bids": bids, "asks": asks, "ts": ts})).unwrap()
+    }
+
+    fn book() -> LocalOrderBook {
+        let mut book = LocalOrderBook::new("BTC-USDT");
+        book.apply(
+            &push(
+                serde_json::json!([
+                    ["99", "3", "0", "1"],
+                    ["98", "5", "0", "1"],
+                    ["90", "9", "0", "1"]
+                ]),
+                serde_json::json!([["101", "1", "0", "1"], ["102", "2", "0", "1"]]),
+                "1",
+            ),
+            true,
+        );
+        book
+    }
+
+    #[test]
+    fn updates_set_and_remove_levels() {
+        let mut book = book();
+        book.apply(
+            &push(
+                serde_json::json!([["99", "0", "0", "0"], ["100", "4", "0", "1"]]),
+                serde_json::json!([]),
+                "2",
+            ),
+            false,
+        );
+        assert_eq!(book.best_bid(), Some((100.0, 4.0)));
+        assert_eq!(book.best_ask(), Some((101.0, 1.0)));
+        assert_eq!(book.ts(), 2);
+
+        book.apply(
+            &push(serde_json::json!([]), serde_json::json!([]), "3"),
+            true,
+        );
+        assert_eq!(book.mid(), None);
+    }
+
+    #[test]
+    fn depth_imbalance_and_microprice() {
+        let book = book();
+        assert_eq!(book.mid(), Some(100.0));
+        // 2% band: bids down to 98, asks up to 102.
+        assert_eq!(book.depth_within_bps(200.0), (8.0, 3.0));
+        assert_eq!(book.imbalance(200.0), Some(5.0 / 11.0));
+        // 3 bid vs 1 ask at the touch pulls the microprice towards the ask.
+        assert_eq!(book.microprice(), Some((99.0 * 1.0 + 101.0 * 3.0) / 4.0));
+
+        let metrics = book.metrics(100.0);
+        assert_eq!((metrics.bid_depth, metrics.ask_depth), (3.0, 1.0));
+        assert_eq!(metrics.spread, Some(2.0));
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::time::now_ms;
+use crate::types::response::market::Trade;
+use crate::types::ws::channels::{WsChannel, WsSubscriptionArg};
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+/// OHLCV bar built from public trades.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeBar {
+    pub inst_id: String,
+    /// Bar open time, Unix timestamp in milliseconds.
+    pub ts: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Traded size, in contracts (derivatives) or base currency (spot).
+    pub vol: f64,
+    /// Sum of price times size.
+    pub vol_quote: f64,
+    /// Number of trades in the bar.
+    pub trades: u64,
+    /// False while the bar is still forming, true once its interval is over.
+    pub confirmed: bool,
+}
+
+impl TradeBar {
+    /// Volume-weighted average price of the bar.
+    pub fn vwap(&self) -> Option<f64> {
+        (self.vol > 0.0).then(|| self.vol_quote / self.vol)
+    }
+}
+
+type Bars = Arc<RwLock<HashMap<String, TradeBar>>>;
+
+/// Builds bars of any length, including ones OKX has no candle channel for
+/// such as 10 seconds, from the `trades` channel.
+///
+/// Every trade sends the updated forming bar to [`updates`](Self::updates).
+/// A bar is sent once more with `confirmed` set when its interval ends, by
+/// the local clock or on the first trade of a later interval. Intervals
+/// without trades produce no bar, and trades older than the forming or last
+/// confirmed bar are dropped.
+pub struct CandleAggregator {
+    interval: Duration,
+    bars: Bars,
+    updates: broadcast::Sender<TradeBar>,
+    _task: FeedTask,
+}
+
+impl CandleAggregator {
+    /// Subscribe to the trades of `inst_ids` and aggregate them into bars of
+    /// `interval`, which must be a whole number of milliseconds.
+    pub async fn start(
+        ws: &WebsocketClient,
+        inst_ids: &[&str],
+        interval: Duration,
+    ) -> OkxResult<Self> {
+        let interval_ms = interval.as_millis() as i64;
+        if interval_ms == 0 || Duration::from_millis(interval_ms as u64) != interval {
+            return Err(OkxError::Config(format!(
+                "bar interval must be a positive whole number of milliseconds, got {interval:?}"
+            )));
+        }
+
+        let mut subscriptions = Vec::with_capacity(inst_ids.len());
+        for inst_id in inst_ids {
+            subscriptions.push(
+                ws.subscribe_typed::<Trade>(
+                    WsSubscriptionArg::new(WsChannel::Trades).inst_id(inst_id),
+                )
+                .await?,
+            );
+        }
+
+        let bars: Bars = Arc::default();
+        let (updates, _) = broadcast::channel(1024);
+        let task = {
+            let bars = bars.clone();
+            let updates = updates.clone();
+            let mut merged = stream::select_all(subscriptions.into_iter().map(|s| s.into_stream()));
+            FeedTask::spawn(async move {
+                let mut aggregator = Aggregator::new(interval_ms);
+                loop {
+                    let now = now_ms();
+                    let until_close = interval_ms - now.rem_euclid(interval_ms);
+                    let emitted = tokio::select! {
+                        batch = merged.next() => match batch {
+                            Some(Ok(trades)) => {
+                                let mut bars = bars.write().unwrap_or_else(|e| e.into_inner());
+                                trades
+                                    .iter()
+                                    .flat_map(|trade| aggregator.apply(&mut bars, trade))
+                                    .collect::<Vec<_>>()
+                            }
+                            Some(Err(e)) => {
+                                warn!("Failed to parse trades push: {e}");
+                                continue;
+                            }
+                            None => break,
+                        },
+                        _ = tokio::time::sleep(Duration::from_millis(until_close as u64)) => {
+                            let mut bars = bars.write().unwrap_or_else(|e| e.into_inner());
+                            aggregator.close_due(&mut bars, now_ms())
+                        }
+                    };
+                    for bar in emitted {
+                        let _ = updates.send(bar);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            interval,
+            bars,
+            updates,
+            _task: task,
+        })
+    }
+
+    /// Length of the bars.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Receive every forming and confirmed bar from now on.
+    pub fn updates(&self) -> broadcast::Receiver<TradeBar> {
+        self.updates.subscribe()
+    }
+
+    /// Bar currently forming for an instrument, if it has traded in the
+    /// current interval.
+    pub fn current(&self, inst_id: &str) -> Option<TradeBar> {
+        self.bars
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(inst_id)
+            .cloned()
+    }
+}
+
+#[derive(Debug)]
+struct Aggregator {
+    interval_ms: i64,
+    /// Start of the last confirmed bar per instrument, so trades arriving
+    /// after their bar was closed by the timer are dropped.
+    last_closed: HashMap<String, i64>,
+}
+
+impl Aggregator {
+    fn new(interval_ms: i64) -> Self {
+        Self {
+            interval_ms,
+            last_closed: HashMap::new(),
+        }
+    }
+
+    /// Add a trade to its instrument's forming bar. Returns the bar it
+    /// confirmed, if the trade opened a new interval, then the forming bar.
+    fn apply(&mut self, bars: &mut HashMap<String, TradeBar>, trade: &Trade) -> Vec<TradeBar> {
+        let (Ok(px), Ok(sz), Ok(ts)) = (
+            trade.px.parse::<f64>(),
+            trade.sz.parse::<f64>(),
+            trade.ts.parse::<i64>(),
+        ) else {
+            warn!("Skipping trade with unparseable fields: {trade:?}");
+            return Vec::new();
+        };
+        let start = ts - ts.rem_euclid(self.interval_ms);
+        if self
+            .last_closed
+            .get(&trade.inst_id)
+            .is_some_and(|&closed| start <= closed)
+        {
+            return Vec::new();
+        }
+
+        let mut emitted = Vec::new();
+        if let Some(bar) = bars.get_mut(&trade.inst_id) {
+            if start < bar.ts {
+                return emitted;
+            }
+            if start == bar.ts {
+                bar.high = bar.high.max(px);
+                bar.low = bar.low.min(px);
+                bar.close = px;
+                bar.vol += sz;
+                bar.vol_quote += px * sz;
+                bar.trades += 1;
+                emitted.push(bar.clone());
+                return emitted;
+            }
+            let mut closed = bars.remove(&trade.inst_id).expect("bar exists");
+            closed.confirmed = true;
+            self.last_closed.insert(closed.inst_id.clone(), closed.ts);
+            emitted.push(closed);
+        }
+
+        let bar = TradeBar {
+            inst_id: trade.inst_id.clone(),
+            ts: start,
+            open: px,
+            high: px,
+            low: px,
+            close: px,
+            vol: sz,
+            vol_quote: px * sz,
+            trades: 1,
+            confirmed: false,
+        };
+        emitted.push(bar.clone());
+        bars.insert(trade.inst_id.clone(), bar);
+        emitted
+    }
+
+    /// Confirm and remove every bar whose interval ended by `now_ms`.
+    fn close_due(&mut self, bars: &mut HashMap<String, TradeBar>, now_ms: i64) -> Vec<TradeBar> {
+        let due: Vec<String> = bars
+            .values()
+            .filter(|bar| bar.ts + self.interval_ms <= now_ms)
+            .map(|bar| bar.inst_id.clone())
+            .collect();
+        let mut closed: Vec<TradeBar> = due
+            .iter()
+            .filter_map(|inst_id| bars.remove(inst_id))
+            .map(|bar| TradeBar {
+                confirmed: true,
+                ..bar
+            })
+            .collect();
+        for bar in &closed {
+            self.last_closed.insert(bar.inst_id.clone(), bar.ts);
+        }
+        closed.sort_by(|a, b| a.inst_id.cmp(&b.inst_id));
+        closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(px: &str, sz: &str, ts: i64) -> Trade {
+        serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT", "tradeId": "1", "px": px, "sz": sz, "side": "buy",
+            "ts": ts.to_string(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn trades_build_ten_second_bars() {
+        let mut aggregator = Aggregator::new(10_000);
+        let mut bars = HashMap::new();
+
+        aggregator.apply(&mut bars, &trade("100", "1", 20_001));
+        aggregator.apply(&mut bars, &trade("104", "2", 25_000));
+        let forming = aggregator.apply(&mut bars, &trade("98", "1", 29_999));
+        assert_eq!(forming.len(), 1);
+        let bar = &forming[0];
+        assert_eq!((bar.ts, bar.confirmed, bar.trades), (20_000, false, 3));
+        assert_eq!(
+            (bar.open, bar.high, bar.low, bar.close),
+            (100.0, 104.0, 98.0, 98.0)
+        );
+        assert_eq!(bar.vol, 4.0);
+        assert_eq!(bar.vwap(), Some(101.5));
+
+        // Older than the forming bar.
+        assert!(aggregator
+            .apply(&mut bars, &trade("1", "1", 19_999))
+            .is_empty());
+
+        let emitted = aggregator.apply(&mut bars, &trade("99", "1", 40_000));
+        assert_eq!(emitted.len(), 2);
+        assert!(emitted[0].confirmed && emitted[0].ts == 20_000 && emitted[0].close == 98.0);
+        assert!(!emitted[1].confirmed && emitted[1].ts == 40_000 && emitted[1].open == 99.0);
+    }
+
+    #[test]
+    fn bars_close_when_their_interval_ends() {
+        let mut aggregator = Aggregator::new(10_000);
+        let mut bars = HashMap::new();
+        aggregator.apply(&mut bars, &trade("100", "1", 20_001));
+
+        assert!(aggregator.close_due(&mut bars, 29_999).is_empty());
+        let closed = aggregator.close_due(&mut bars, 30_000);
+        assert_eq!(closed.len(), 1);
+        assert!(closed[0].confirmed);
+        assert!(bars.is_empty());
+    }
+
+    #[test]
+    fn late_trades_for_a_closed_bar_are_dropped() {
+        let mut aggregator = Aggregator::new(10_000);
+        let mut bars = HashMap::new();
+        aggregator.apply(&mut bars, &trade("100", "1", 20_001));
+        assert_eq!(aggregator.close_due(&mut bars, 30_000).len(), 1);
+
+        // Trade from the closed interval, delivered after the timer fired.
+        assert!(aggregator
+            .apply(&mut bars, &trade("101", "1", 29_999))
+            .is_empty());
+        assert!(bars.is_empty());
+
+        let emitted = aggregator.apply(&mut bars, &trade("102", "1", 30_000));
+        assert_eq!(emitted.len(), 1);
+        assert!(!emitted[0].confirmed && emitted[0].ts == 30_000);
+    }
+}
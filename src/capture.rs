@@ -0,0 +1,269 @@
+//! Persisting channel pushes for later analysis or replay.
+//!
+//! A [`Capture`] subscribes to a set of channels and hands every push, with
+//! its receive time, to a [`CaptureSink`] running on its own thread.
+//! [`JsonLinesSink`] writes them to a JSON Lines file, which
+//! [`read_json_lines`] reads back.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::jsonl;
+use crate::time::now_ms;
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::{WsDataEvent, WsMessage};
+use crate::ws::typed::release_in_background;
+use crate::ws::WebsocketClient;
+
+/// One captured push with the time it was received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// Receive time, Unix timestamp in milliseconds.
+    pub ts: u64,
+    /// The push as received, with its subscription arg.
+    #[serde(flatten)]
+    pub event: WsDataEvent,
+}
+
+impl CaptureRecord {
+    /// Deserialize the payload into typed channel data.
+    pub fn parse_data<T: DeserializeOwned>(&self) -> OkxResult<Vec<T>> {
+        self.event.parse_data()
+    }
+}
+
+/// Destination for captured pushes, e.g. a file or a database table.
+///
+/// Sinks run on a dedicated thread, so blocking I/O is fine.
+pub trait CaptureSink: Send + 'static {
+    /// Persist one record.
+    fn write(&mut self, record: &CaptureRecord) -> OkxResult<()>;
+
+    /// Persist anything buffered. Called when the capture stops.
+    fn flush(&mut self) -> OkxResult<()> {
+        Ok(())
+    }
+}
+
+/// Writes one JSON object per line.
+pub struct JsonLinesSink<W: Write> {
+    out: BufWriter<W>,
+}
+
+impl JsonLinesSink<File> {
+    /// Create (or truncate) a file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> OkxResult<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .map_err(|e| OkxError::io(format!("cannot create {}", path.display()), e))?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    /// Write to any writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            out: BufWriter::new(writer),
+        }
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(self) -> OkxResult<W> {
+        self.out
+            .into_inner()
+            .map_err(|e| OkxError::io("capture flush failed", e.into_error()))
+    }
+}
+
+impl<W: Write + Send + 'static> CaptureSink for JsonLinesSink<W> {
+    fn write(&mut self, record: &CaptureRecord) -> OkxResult<()> {
+        jsonl::write_line(&mut self.out, record)
+            .map_err(|e| OkxError::io("capture write failed", e))
+    }
+
+    fn flush(&mut self) -> OkxResult<()> {
+        self.out
+            .flush()
+            .map_err(|e| OkxError::io("capture flush failed", e))
+    }
+}
+
+/// Read a file written by [`JsonLinesSink`].
+pub fn read_json_lines(path: impl AsRef<Path>) -> OkxResult<Vec<CaptureRecord>> {
+    jsonl::read(path.as_ref())
+}
+
+/// Persists every push of a set of channels to a [`CaptureSink`].
+///
+/// Subscribes on start and writes on a background thread until
+/// [`stop`](Self::stop) is called. Dropping the capture also stops it, but
+/// the sink is then flushed in the background and its errors are only logged.
+pub struct Capture {
+    args: Vec<WsSubscriptionArg>,
+    ws: WebsocketClient,
+    captured: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+    writer: Option<std::thread::JoinHandle<OkxResult<()>>>,
+}
+
+impl Capture {
+    /// Subscribe to `args` and write their pushes to `sink`.
+    pub async fn start(
+        ws: &WebsocketClient,
+        args: Vec<WsSubscriptionArg>,
+        mut sink: impl CaptureSink,
+    ) -> OkxResult<Self> {
+        // Take the receiver before subscribing so no early push is missed.
+        let mut rx = ws.event_receiver();
+        ws.subscribe(args.clone()).await?;
+
+        let (tx, mut records) = mpsc::unbounded_channel::<CaptureRecord>();
+        let writer = std::thread::Builder::new()
+            .name("okx-capture".into())
+            .spawn(move || {
+                let mut first_error = None;
+                while let Some(record) = records.blocking_recv() {
+                    if let Err(e) = sink.write(&record) {
+                        warn!("Failed to capture {} push: {e}", record.event.arg.channel);
+                        first_error.get_or_insert(e);
+                    }
+                }
+                let flushed = sink.flush();
+                match first_error {
+                    Some(e) => Err(e),
+                    None => flushed,
+                }
+            })
+            .map_err(|e| OkxError::io("cannot start capture writer thread", e))?;
+
+        let captured = Arc::new(AtomicU64::new(0));
+        let task = {
+            let args = args.clone();
+            let captured = captured.clone();
+            tokio::spawn(async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(msg) => {
+                            let WsMessage::Data(evt) = msg.as_ref() else {
+                                continue;
+                            };
+                            if !args.iter().any(|arg| arg.matches(&evt.arg)) {
+                                continue;
+                            }
+                            let record = CaptureRecord {
+                                ts: now_ms() as u64,
+                                event: evt.clone(),
+                            };
+                            if tx.send(record).is_err() {
+                                break;
+                            }
+                            captured.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Capture lagged, {n} events dropped");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            args,
+            ws: ws.clone(),
+            captured,
+            task,
+            writer: Some(writer),
+        })
+    }
+
+    /// Number of pushes handed to the sink so far.
+    pub fn captured(&self) -> u64 {
+        self.captured.load(Ordering::Relaxed)
+    }
+
+    /// Unsubscribe, write out everything captured so far and flush the sink.
+    ///
+    /// Returns the first error the sink reported, if any.
+    pub async fn stop(mut self) -> OkxResult<()> {
+        let unsubscribed = self.ws.unsubscribe(std::mem::take(&mut self.args)).await;
+        self.task.abort();
+        let _ = (&mut self.task).await;
+
+        let written = match self.writer.take() {
+            Some(writer) => tokio::task::spawn_blocking(move || writer.join())
+                .await
+                .map_err(|e| OkxError::io("capture writer thread failed", e.into()))?
+                .unwrap_or_else(|_| {
+                    Err(OkxError::io(
+                        "capture writer thread failed",
+                        std::io::Error::other("panicked"),
+                    ))
+                }),
+            None => Ok(()),
+        };
+        written.and(unsubscribed)
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        self.task.abort();
+        if !self.args.is_empty() {
+            release_in_background(self.ws.clone(), std::mem::take(&mut self.args));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::response::market::Ticker;
+
+    fn record() -> CaptureRecord {
+        CaptureRecord {
+            ts: 1_700_000_000_000,
+            event: WsDataEvent {
+                arg: WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT"),
+                data: vec![serde_json::json!({"instId": "BTC-USDT", "last": "42000"})],
+                action: None,
+            },
+        }
+    }
+
+    #[test]
+    fn json_lines_round_trip() {
+        let mut sink = JsonLinesSink::new(Vec::new());
+        sink.write(&record()).unwrap();
+        sink.write(&record()).unwrap();
+        let bytes = sink.into_inner().unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(lines[0]).unwrap(),
+            serde_json::json!({
+                "ts": 1_700_000_000_000u64,
+                "arg": {"channel": "tickers", "instId": "BTC-USDT"},
+                "data": [{"instId": "BTC-USDT", "last": "42000"}],
+            })
+        );
+
+        let back: CaptureRecord = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(back, record());
+        let tickers: Vec<Ticker> = back.parse_data().unwrap();
+        assert_eq!(tickers[0].last, "42000");
+    }
+}
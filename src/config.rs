@@ -1,8 +1,19 @@
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use secrecy::SecretString;
+use serde::Deserialize;
+use serde_json::{Map, Value};
 
+use crate::auth::SigningAlgorithm;
 use crate::constants;
+use crate::error::{OkxError, OkxResult};
+use crate::maintenance::MaintenanceGuard;
+use crate::retry::RetryPolicy;
+use crate::risk::RiskGuard;
+use crate::throttle::OrderThrottleConfig;
 
 /// OKX regional endpoint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -36,6 +47,77 @@ pub struct Credentials {
     pub api_key: String,
     pub api_secret: SecretString,
     pub passphrase: SecretString,
+    /// Signing algorithm to use instead of detecting it from the secret.
+    pub algorithm: Option<SigningAlgorithm>,
+}
+
+/// Environment variable holding the API key.
+pub const ENV_API_KEY: &str = "OKX_API_KEY";
+/// Environment variable holding the API secret.
+pub const ENV_API_SECRET: &str = "OKX_API_SECRET";
+/// Environment variable holding the API passphrase.
+pub const ENV_API_PASSPHRASE: &str = "OKX_API_PASSPHRASE";
+
+/// On-disk credentials file layout.
+#[derive(Deserialize)]
+struct CredentialsFile {
+    api_key: String,
+    api_secret: String,
+    passphrase: String,
+}
+
+impl Credentials {
+    pub fn new(api_key: &str, api_secret: &str, passphrase: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            api_secret: SecretString::from(api_secret.to_string()),
+            passphrase: SecretString::from(passphrase.to_string()),
+            algorithm: None,
+        }
+    }
+
+    /// Force a signing algorithm, bypassing detection from the secret format.
+    pub fn with_algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Read credentials from `OKX_API_KEY`, `OKX_API_SECRET`, and
+    /// `OKX_API_PASSPHRASE`.
+    pub fn from_env() -> OkxResult<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|e| OkxError::Config(format!("{name} is not set: {e}")))
+        };
+        Ok(Self::new(
+            &var(ENV_API_KEY)?,
+            &var(ENV_API_SECRET)?,
+            &var(ENV_API_PASSPHRASE)?,
+        ))
+    }
+
+    /// Read credentials from a file with `api_key`, `api_secret`, and
+    /// `passphrase` keys.
+    ///
+    /// Files ending in `.json` are parsed as JSON, anything else as TOML.
+    pub fn from_file(path: impl AsRef<Path>) -> OkxResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| OkxError::Config(format!("failed to read {}: {e}", path.display())))?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let file: CredentialsFile = if is_json {
+            serde_json::from_str(&contents).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&contents).map_err(|e| e.to_string())
+        }
+        .map_err(|e| {
+            OkxError::Config(format!("invalid credentials file {}: {e}", path.display()))
+        })?;
+
+        Ok(Self::new(&file.api_key, &file.api_secret, &file.passphrase))
+    }
 }
 
 impl std::fmt::Debug for Credentials {
@@ -44,10 +126,50 @@ impl std::fmt::Debug for Credentials {
             .field("api_key", &self.api_key)
             .field("api_secret", &"[REDACTED]")
             .field("passphrase", &"[REDACTED]")
+            .field("algorithm", &self.algorithm)
             .finish()
     }
 }
 
+/// Maximum length of an order tag.
+const MAX_TAG_LEN: usize = 16;
+
+/// Chooses the tag of an order request from its JSON body, e.g. a
+/// per-strategy identifier derived from the `clOrdId`, so fills can be
+/// attributed through `Fill::tag`.
+///
+/// Returning `None` falls back to [`ClientConfig::program_tag`]. Requests
+/// that already carry a tag are left alone.
+#[derive(Clone)]
+pub struct TagProvider(Arc<TagFn>);
+
+type TagFn = dyn Fn(&Map<String, Value>) -> Option<String> + Send + Sync;
+
+impl TagProvider {
+    pub fn new(
+        provider: impl Fn(&Map<String, Value>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl std::fmt::Debug for TagProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TagProvider(..)")
+    }
+}
+
+/// Check that a tag is 1 to 16 ASCII letters and digits, as OKX requires.
+pub fn validate_tag(tag: &str) -> OkxResult<()> {
+    if tag.is_empty() || tag.len() > MAX_TAG_LEN || !tag.bytes().all(|b| b.is_ascii_alphanumeric())
+    {
+        return Err(OkxError::Config(format!(
+            "order tag must be 1 to {MAX_TAG_LEN} letters and digits, got {tag:?}"
+        )));
+    }
+    Ok(())
+}
+
 /// Configuration for `RestClient` and `WebsocketClient`.
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
@@ -56,6 +178,112 @@ pub struct ClientConfig {
     pub credentials: Option<Credentials>,
     pub base_url_override: Option<String>,
     pub request_timeout: Duration,
+    /// Local per-instrument order throttle. Disabled when `None`.
+    pub order_throttle: Option<OrderThrottleConfig>,
+    /// Pauses order placement during OKX maintenance. Disabled when `None`.
+    pub maintenance_guard: Option<MaintenanceGuard>,
+    /// Pre-trade risk limits on order placement. Disabled when `None`.
+    pub risk_guard: Option<RiskGuard>,
+    /// Retry policy for REST requests and WS API requests.
+    pub retry_policy: RetryPolicy,
+    /// Signing algorithm for all requests, overriding detection and
+    /// [`Credentials::algorithm`]. Auto-detected when `None`.
+    pub signing_algorithm: Option<SigningAlgorithm>,
+    /// Request gzip/deflate compressed REST responses and decompress them
+    /// transparently.
+    pub compression: bool,
+    /// TLS settings for REST and, unless overridden in
+    /// [`WsConfig`](crate::ws::types::WsConfig), WebSocket connections.
+    pub tls: TlsConfig,
+    /// `User-Agent` sent on REST requests and WebSocket handshakes. The HTTP
+    /// library's default when `None`.
+    pub user_agent: Option<String>,
+    /// Headers added to every REST request and WebSocket handshake, e.g. for
+    /// corporate proxies or broker integrations.
+    pub extra_headers: Vec<(String, String)>,
+    /// Tag injected into REST and WS API order requests that do not set one
+    /// (default: [`constants::PROGRAM_ID`]). `None` disables injection.
+    /// OKX accepts up to 16 alphanumeric characters.
+    pub program_tag: Option<String>,
+    /// Per-request tag, taking precedence over `program_tag` (default: none).
+    pub tag_provider: Option<TagProvider>,
+}
+
+/// TLS settings for outgoing connections.
+///
+/// The TLS backend itself is chosen with the `rustls-tls` (default) or
+/// `native-tls` crate feature.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM-encoded root certificates trusted in addition to the backend's
+    /// built-in roots, e.g. the CA of a TLS-intercepting proxy.
+    pub extra_root_certificates: Vec<Vec<u8>>,
+}
+
+impl ClientConfig {
+    /// The signing algorithm forced by the config or credentials, if any.
+    pub fn effective_signing_algorithm(&self) -> Option<SigningAlgorithm> {
+        self.signing_algorithm
+            .or_else(|| self.credentials.as_ref().and_then(|c| c.algorithm))
+    }
+
+    /// The `User-Agent` and extra headers, validated.
+    pub fn custom_headers(&self) -> OkxResult<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let user_agent = self
+            .user_agent
+            .as_ref()
+            .map(|ua| ("User-Agent", ua.as_str()));
+        let extra = self
+            .extra_headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()));
+        for (name, value) in user_agent.into_iter().chain(extra) {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| OkxError::Config(format!("invalid header name {name:?}: {e}")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| OkxError::Config(format!("invalid value for header {name}: {e}")))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Tag an order request body, or each object of a batch, that has no
+    /// `tag` yet, using the tag provider and then the program tag.
+    ///
+    /// Fails if the provider returns an invalid tag.
+    pub fn tag_request(&self, body: &mut Value) -> OkxResult<()> {
+        self.inject_tag(body, self.tag_provider.as_ref())
+    }
+
+    /// Tag a request that does not place orders with the program tag only.
+    pub(crate) fn tag_program(&self, body: &mut Value) -> OkxResult<()> {
+        self.inject_tag(body, None)
+    }
+
+    fn inject_tag(&self, body: &mut Value, provider: Option<&TagProvider>) -> OkxResult<()> {
+        let tag = |map: &mut Map<String, Value>| -> OkxResult<()> {
+            if map.contains_key("tag") {
+                return Ok(());
+            }
+            let provided = provider.and_then(|p| (p.0)(map));
+            if let Some(tag) = &provided {
+                validate_tag(tag)?;
+            }
+            if let Some(tag) = provided.or_else(|| self.program_tag.clone()) {
+                map.insert("tag".to_string(), Value::String(tag));
+            }
+            Ok(())
+        };
+        match body {
+            Value::Object(map) => tag(map),
+            Value::Array(items) => items.iter_mut().try_for_each(|item| match item {
+                Value::Object(map) => tag(map),
+                _ => Ok(()),
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Default for ClientConfig {
@@ -66,6 +294,17 @@ impl Default for ClientConfig {
             credentials: None,
             base_url_override: None,
             request_timeout: Duration::from_secs(30),
+            order_throttle: None,
+            maintenance_guard: None,
+            risk_guard: None,
+            retry_policy: RetryPolicy::default(),
+            signing_algorithm: None,
+            compression: true,
+            tls: TlsConfig::default(),
+            user_agent: None,
+            extra_headers: Vec::new(),
+            program_tag: Some(constants::PROGRAM_ID.to_string()),
+            tag_provider: None,
         }
     }
 }
@@ -98,14 +337,22 @@ impl ClientConfigBuilder {
     }
 
     pub fn credentials(mut self, api_key: &str, api_secret: &str, passphrase: &str) -> Self {
-        self.config.credentials = Some(Credentials {
-            api_key: api_key.to_string(),
-            api_secret: SecretString::from(api_secret.to_string()),
-            passphrase: SecretString::from(passphrase.to_string()),
-        });
+        self.config.credentials = Some(Credentials::new(api_key, api_secret, passphrase));
         self
     }
 
+    /// Load credentials from the `OKX_API_*` environment variables.
+    pub fn credentials_from_env(mut self) -> OkxResult<Self> {
+        self.config.credentials = Some(Credentials::from_env()?);
+        Ok(self)
+    }
+
+    /// Load credentials from a TOML or JSON file. See [`Credentials::from_file`].
+    pub fn credentials_from_file(mut self, path: impl AsRef<Path>) -> OkxResult<Self> {
+        self.config.credentials = Some(Credentials::from_file(path)?);
+        Ok(self)
+    }
+
     pub fn base_url(mut self, url: &str) -> Self {
         self.config.base_url_override = Some(url.to_string());
         self
@@ -116,6 +363,83 @@ impl ClientConfigBuilder {
         self
     }
 
+    pub fn order_throttle(mut self, throttle: OrderThrottleConfig) -> Self {
+        self.config.order_throttle = Some(throttle);
+        self
+    }
+
+    pub fn maintenance_guard(mut self, guard: MaintenanceGuard) -> Self {
+        self.config.maintenance_guard = Some(guard);
+        self
+    }
+
+    pub fn risk_guard(mut self, guard: RiskGuard) -> Self {
+        self.config.risk_guard = Some(guard);
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.config.retry_policy = policy;
+        self
+    }
+
+    /// Ask for gzip or deflate compressed REST responses (default: true).
+    /// Has no effect in the browser, where the fetch API decides.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.config.compression = enabled;
+        self
+    }
+
+    /// Trust an additional PEM-encoded root certificate (or bundle).
+    pub fn add_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.config.tls.extra_root_certificates.push(pem.into());
+        self
+    }
+
+    /// Force a signing algorithm instead of detecting it from the secret.
+    pub fn signing_algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+        self.config.signing_algorithm = Some(algorithm);
+        self
+    }
+
+    /// Send this `User-Agent` on REST requests and WebSocket handshakes.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.config.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Add a header to every REST request and WebSocket handshake.
+    pub fn extra_header(mut self, name: &str, value: &str) -> Self {
+        self.config
+            .extra_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Inject this tag instead of the crate's program ID, e.g. a broker's own
+    /// tag. Fails unless the tag is 1 to 16 letters and digits.
+    pub fn program_tag(mut self, tag: &str) -> OkxResult<Self> {
+        validate_tag(tag)?;
+        self.config.program_tag = Some(tag.to_string());
+        Ok(self)
+    }
+
+    /// Choose each order request's tag with `provider`, falling back to the
+    /// program tag when it returns `None`.
+    pub fn tag_provider(
+        mut self,
+        provider: impl Fn(&Map<String, Value>) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.config.tag_provider = Some(TagProvider::new(provider));
+        self
+    }
+
+    /// Send requests without injecting a tag.
+    pub fn disable_program_tag(mut self) -> Self {
+        self.config.program_tag = None;
+        self
+    }
+
     pub fn build(self) -> ClientConfig {
         self.config
     }
@@ -126,3 +450,140 @@ impl Default for ClientConfigBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use secrecy::ExposeSecret;
+
+    use super::*;
+
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("okx-client-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_credentials_from_toml_and_json() {
+        let toml_path = write_temp(
+            "creds.toml",
+            "api_key = \"key\"\napi_secret = \"secret\"\npassphrase = \"pass\"\n",
+        );
+        let json_path = write_temp(
+            "creds.json",
+            r#"{"api_key":"key","api_secret":"secret","passphrase":"pass"}"#,
+        );
+
+        for path in [&toml_path, &json_path] {
+            let creds = Credentials::from_file(path).unwrap();
+            assert_eq!(creds.api_key, "key");
+            assert_eq!(creds.api_secret.expose_secret(), "secret");
+            assert_eq!(creds.passphrase.expose_secret(), "pass");
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_incomplete_credentials_file() {
+        let path = write_temp("partial.toml", "api_key = \"key\"\n");
+        let err = ClientConfigBuilder::new()
+            .credentials_from_file(&path)
+            .err()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(err, OkxError::Config(_)));
+    }
+
+    #[test]
+    fn custom_headers_are_validated() {
+        let headers = ClientConfigBuilder::new()
+            .user_agent("my-bot/1.0")
+            .extra_header("X-Broker", "acme")
+            .build()
+            .custom_headers()
+            .unwrap();
+        assert_eq!(headers["user-agent"], "my-bot/1.0");
+        assert_eq!(headers["x-broker"], "acme");
+
+        let err = ClientConfigBuilder::new()
+            .extra_header("bad header", "x")
+            .build()
+            .custom_headers()
+            .unwrap_err();
+        assert!(matches!(err, OkxError::Config(_)));
+    }
+
+    #[test]
+    fn tag_request_prefers_provider_then_program_tag() {
+        let config = ClientConfigBuilder::new()
+            .tag_provider(|req| {
+                let cl_ord_id = req.get("clOrdId")?.as_str()?;
+                let strategy = cl_ord_id.split_once('x')?.0;
+                Some(format!("mm{strategy}"))
+            })
+            .build();
+        let mut batch = serde_json::json!([
+            {"clOrdId": "grid1x42"},
+            {"clOrdId": "plain"},
+            {"clOrdId": "grid1x43", "tag": "custom"},
+        ]);
+        config.tag_request(&mut batch).unwrap();
+        assert_eq!(batch[0]["tag"], "mmgrid1");
+        assert_eq!(batch[1]["tag"], constants::PROGRAM_ID);
+        assert_eq!(batch[2]["tag"], "custom");
+
+        let mut order = serde_json::json!({"clOrdId": "waytoolongstrategyx1"});
+        assert!(matches!(
+            config.tag_request(&mut order),
+            Err(OkxError::Config(_))
+        ));
+
+        let mut other = serde_json::json!({"clOrdId": "grid1x44"});
+        config.tag_program(&mut other).unwrap();
+        assert_eq!(other["tag"], constants::PROGRAM_ID);
+
+        let untagged = ClientConfigBuilder::new().disable_program_tag().build();
+        let mut order = serde_json::json!({"instId": "BTC-USDT"});
+        untagged.tag_request(&mut order).unwrap();
+        assert!(order.get("tag").is_none());
+    }
+
+    #[test]
+    fn program_tag_is_validated() {
+        let config = ClientConfigBuilder::new()
+            .program_tag("broker42")
+            .unwrap()
+            .build();
+        assert_eq!(config.program_tag.as_deref(), Some("broker42"));
+
+        for tag in ["", "has space", "seventeenchars123"] {
+            assert!(matches!(
+                ClientConfigBuilder::new().program_tag(tag),
+                Err(OkxError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn config_signing_algorithm_overrides_credentials() {
+        let creds =
+            Credentials::new("key", "secret", "pass").with_algorithm(SigningAlgorithm::Ed25519);
+        let mut config = ClientConfig {
+            credentials: Some(creds),
+            ..ClientConfig::default()
+        };
+        assert_eq!(
+            config.effective_signing_algorithm(),
+            Some(SigningAlgorithm::Ed25519)
+        );
+
+        config = ClientConfigBuilder::new()
+            .credentials("key", "secret", "pass")
+            .signing_algorithm(SigningAlgorithm::RsaPkcs1v15)
+            .build();
+        assert_eq!(
+            config.effective_signing_algorithm(),
+            Some(SigningAlgorithm::RsaPkcs1v15)
+        );
+    }
+}
@@ -1,6 +1,18 @@
 /// OKX program ID tag, auto-injected into private POST requests.
 pub const PROGRAM_ID: &str = "159881cb7207BCDE";
 
+/// REST endpoints that place orders. The configured tag provider applies to
+/// these only; other signed POSTs get the program tag.
+pub const ORDER_ENDPOINTS: &[&str] = &[
+    "/api/v5/trade/order",
+    "/api/v5/trade/batch-orders",
+    "/api/v5/trade/order-algo",
+    "/api/v5/trade/close-position",
+    "/api/v5/sprd/order",
+    "/api/v5/tradingBot/grid/order-algo",
+    "/api/v5/tradingBot/signal/order-algo",
+];
+
 /// HTTP header names used by OKX API.
 pub const HEADER_ACCESS_KEY: &str = "OK-ACCESS-KEY";
 pub const HEADER_ACCESS_SIGN: &str = "OK-ACCESS-SIGN";
@@ -8,6 +20,13 @@ pub const HEADER_ACCESS_TIMESTAMP: &str = "OK-ACCESS-TIMESTAMP";
 pub const HEADER_ACCESS_PASSPHRASE: &str = "OK-ACCESS-PASSPHRASE";
 pub const HEADER_SIMULATED_TRADING: &str = "x-simulated-trading";
 
+/// Response headers identifying a REST request, in order of preference.
+/// Quote the captured value when escalating a failed request to OKX support.
+pub const REQUEST_ID_HEADERS: &[&str] = &["x-request-id", "x-trace-id", "x-cdn-request-id"];
+
+/// WebSocket connections OKX allows per channel and sub-account.
+pub const WS_CHANNEL_CONN_LIMIT: u32 = 30;
+
 /// REST API base URLs by region.
 pub mod rest_urls {
     pub const GLOBAL: &str = "https://www.okx.com";
@@ -31,7 +50,10 @@ pub mod ws_urls {
     pub const US_BUSINESS: &str = "wss://wsus.okx.com:8443/ws/v5/business";
 
     // Demo trading
-    pub const DEMO_PUBLIC: &str = "wss://wspap.okx.com:8443/ws/v5/public?brokerId=9999";
-    pub const DEMO_PRIVATE: &str = "wss://wspap.okx.com:8443/ws/v5/private?brokerId=9999";
-    pub const DEMO_BUSINESS: &str = "wss://wspap.okx.com:8443/ws/v5/business?brokerId=9999";
+    pub const DEMO_PUBLIC: &str = "wss://wspap.okx.com:8443/ws/v5/public";
+    pub const DEMO_PRIVATE: &str = "wss://wspap.okx.com:8443/ws/v5/private";
+    pub const DEMO_BUSINESS: &str = "wss://wspap.okx.com:8443/ws/v5/business";
+
+    /// Default `brokerId` query parameter for demo trading connections.
+    pub const DEMO_BROKER_ID: &str = "9999";
 }
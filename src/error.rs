@@ -1,7 +1,11 @@
 use thiserror::Error;
 
+use crate::risk::RiskViolation;
+use crate::types::ws::events::WsConnectionType;
+
 /// All errors that can occur when using the OKX client.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum OkxError {
     /// OKX API returned an error response (code != "0").
     #[error("OKX API error {code}: {msg}")]
@@ -16,6 +20,7 @@ pub enum OkxError {
     Middleware(#[from] reqwest_middleware::Error),
 
     /// WebSocket transport error.
+    #[cfg(not(target_arch = "wasm32"))]
     #[error("WebSocket error: {0}")]
     WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
 
@@ -27,11 +32,39 @@ pub enum OkxError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    /// Response payload did not match the expected type.
+    ///
+    /// Usually means OKX changed a field's shape. `payload` holds the start
+    /// of the offending JSON.
+    #[error("Failed to deserialize {type_name}: {source} (payload: {payload})")]
+    Deserialize {
+        type_name: &'static str,
+        payload: String,
+        source: serde_json::Error,
+    },
+
     /// WebSocket API request timed out waiting for response.
     #[error("WS API request timed out (id={id}, op={operation})")]
     WsApiTimeout { id: String, operation: String },
 
+    /// An operation did not finish within its deadline.
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// WebSocket connection dropped while a WS API request was in flight.
+    ///
+    /// The request may or may not have been executed by OKX.
+    #[error("WS {conn_type} connection lost with request in flight (id={id}, op={op})")]
+    WsDisconnected {
+        conn_type: WsConnectionType,
+        op: String,
+        id: String,
+    },
+
     /// WebSocket connection lost while a request was in flight.
+    #[deprecated(
+        note = "never returned; in-flight WS API requests fail with `OkxError::WsDisconnected`"
+    )]
     #[error("WebSocket connection lost")]
     WsConnectionLost,
 
@@ -46,6 +79,176 @@ pub enum OkxError {
     /// General WebSocket error (connection, send, etc.).
     #[error("WebSocket error: {0}")]
     Ws(String),
+
+    /// Archive file could not be prepared, downloaded, or parsed.
+    #[error("Archive error: {0}")]
+    Archive(String),
+
+    /// Reading or writing a file failed, e.g. a capture or a WS recording.
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        source: std::io::Error,
+    },
+
+    /// Session state could not be saved to or loaded from storage.
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    /// A successful response lacked data the call needs, e.g. an empty
+    /// `data` array or a missing field.
+    #[error("Unexpected response: {0}")]
+    UnexpectedResponse(String),
+
+    /// Order rejected locally by the order throttle.
+    #[error("Order throttled: {0}")]
+    Throttled(String),
+
+    /// Order held back by the maintenance guard.
+    #[error("Order paused for maintenance: {0}")]
+    Maintenance(String),
+
+    /// Order rejected locally by the risk limits.
+    #[error("Order rejected by risk limits: {0}")]
+    RiskRejected(RiskViolation),
+
+    /// A REST request failed after it was sent. Wraps the underlying error
+    /// with the endpoint, method, HTTP status, and a snippet of the body.
+    ///
+    /// API errors from REST calls arrive wrapped in this variant, so match on
+    /// [`root`](Self::root) or use [`api_code`](Self::api_code) rather than
+    /// matching [`OkxError::Api`] directly.
+    #[error("{context}: {source}")]
+    Request {
+        context: Box<RequestContext>,
+        source: Box<OkxError>,
+    },
+}
+
+impl OkxError {
+    /// The underlying error, with any [`OkxError::Request`] context removed.
+    pub fn root(&self) -> &OkxError {
+        match self {
+            Self::Request { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
+    /// Request context, if the error came from a REST call.
+    pub fn request_context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Request { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Request ID OKX returned for the failed REST call, if any.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_context()?.request_id.as_deref()
+    }
+
+    /// Build an [`OkxError::Io`] saying what was being done.
+    pub(crate) fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Build an [`OkxError::Deserialize`] for a failure to parse `payload` as `T`.
+    pub(crate) fn deserialize<T>(source: serde_json::Error, payload: &str) -> Self {
+        Self::Deserialize {
+            type_name: std::any::type_name::<T>(),
+            payload: snippet(payload),
+            source,
+        }
+    }
+
+    /// OKX error code, if the API returned one.
+    pub fn api_code(&self) -> Option<&str> {
+        match self.root() {
+            Self::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum number of payload characters kept in error messages.
+const SNIPPET_LEN: usize = 512;
+
+/// Truncate a payload to [`SNIPPET_LEN`] characters.
+fn snippet(payload: &str) -> String {
+    match payload.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &payload[..end]),
+        None => payload.to_string(),
+    }
+}
+
+/// Where a failed REST request was sent and what came back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    /// HTTP method, e.g. "GET".
+    pub method: String,
+    /// Endpoint path, e.g. "/api/v5/trade/order".
+    pub endpoint: String,
+    /// HTTP status, if a response was received.
+    pub status: Option<u16>,
+    /// Start of the response body, if one was received.
+    pub body: Option<String>,
+    /// Request ID from the response headers, if OKX sent one. Quote it when
+    /// escalating to OKX support.
+    pub request_id: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(method: &str, endpoint: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            status: None,
+            body: None,
+            request_id: None,
+        }
+    }
+
+    /// Record the HTTP status.
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Record the response body, truncated to a short snippet.
+    pub fn body(mut self, body: &str) -> Self {
+        self.body = Some(snippet(body));
+        self
+    }
+
+    /// Record the request ID returned by OKX.
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Wrap an error with this context.
+    pub fn wrap(self, source: impl Into<OkxError>) -> OkxError {
+        OkxError::Request {
+            context: Box::new(self),
+            source: Box::new(source.into()),
+        }
+    }
+}
+
+impl std::fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.endpoint)?;
+        if let Some(status) = self.status {
+            write!(f, " (HTTP {status})")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " [request {request_id}]")?;
+        }
+        Ok(())
+    }
 }
 
 /// Convenience alias for `Result<T, OkxError>`.
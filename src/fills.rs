@@ -0,0 +1,442 @@
+//! Gap-free stream of the account's fills.
+//!
+//! Fills pushed on the private `orders` channel are lost while the
+//! connection is down. A [`FillFeed`] merges those pushes with periodic
+//! `GET /api/v5/trade/fills` backfills, drops duplicates, and hands the
+//! fills out ordered by fill time.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::rest::RestClient;
+use crate::time::now_ms;
+use crate::types::request::trade::GetFillsRequest;
+use crate::types::response::trade::Fill;
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::{WsConnectionType, WsDataEvent, WsMessage};
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+/// Maximum number of fills returned by one fills request.
+const FILLS_PAGE_LIMIT: usize = 100;
+
+/// Settings of a [`FillFeed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillFeedOptions {
+    /// How often fills are fetched over REST (default: 30 seconds).
+    pub backfill_interval: Duration,
+    /// How long a fill is held before it is handed out, so fills arriving
+    /// slightly out of order can be sorted (default: 1 second).
+    pub settle_delay: Duration,
+    /// How far before the last handed-out fill each backfill starts
+    /// (default: 60 seconds).
+    pub lookback: Duration,
+    /// Replay fills from this Unix timestamp in milliseconds. Without it,
+    /// only fills from the start of the feed are handed out.
+    pub since: Option<i64>,
+}
+
+impl Default for FillFeedOptions {
+    fn default() -> Self {
+        Self {
+            backfill_interval: Duration::from_secs(30),
+            settle_delay: Duration::from_secs(1),
+            lookback: Duration::from_secs(60),
+            since: None,
+        }
+    }
+}
+
+/// The fill fields of an `orders` channel push. Pushes without a trade ID
+/// are order updates that did not fill.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderFillPush {
+    #[serde(default)]
+    inst_type: String,
+    #[serde(default)]
+    inst_id: String,
+    #[serde(default)]
+    trade_id: String,
+    #[serde(default)]
+    ord_id: String,
+    #[serde(default)]
+    cl_ord_id: String,
+    #[serde(default)]
+    tag: String,
+    #[serde(default)]
+    fill_px: String,
+    #[serde(default)]
+    fill_sz: String,
+    #[serde(default)]
+    side: String,
+    #[serde(default)]
+    pos_side: String,
+    #[serde(default)]
+    exec_type: String,
+    #[serde(default)]
+    fill_fee_ccy: String,
+    #[serde(default)]
+    fill_fee: String,
+    #[serde(default)]
+    u_time: String,
+    #[serde(default)]
+    fill_time: String,
+    #[serde(default)]
+    fill_pnl: String,
+    #[serde(default)]
+    fill_px_vol: String,
+    #[serde(default)]
+    fill_px_usd: String,
+    #[serde(default)]
+    fill_mark_vol: String,
+    #[serde(default)]
+    fill_fwd_px: String,
+    #[serde(default)]
+    fill_mark_px: String,
+}
+
+impl OrderFillPush {
+    fn into_fill(self) -> Option<Fill> {
+        let filled = self.fill_sz.parse::<f64>().is_ok_and(|sz| sz > 0.0);
+        if self.trade_id.is_empty() || !filled {
+            return None;
+        }
+        Some(Fill {
+            inst_type: self.inst_type,
+            inst_id: self.inst_id,
+            trade_id: self.trade_id,
+            ord_id: self.ord_id,
+            cl_ord_id: self.cl_ord_id,
+            // Bills only exist in the REST fills.
+            bill_id: String::new(),
+            tag: self.tag,
+            fill_px: self.fill_px,
+            fill_sz: self.fill_sz,
+            side: self.side,
+            pos_side: self.pos_side,
+            exec_type: self.exec_type,
+            fee_ccy: self.fill_fee_ccy,
+            fee: self.fill_fee,
+            ts: self.u_time,
+            fill_time: self.fill_time,
+            fill_pnl: self.fill_pnl,
+            fill_px_vol: self.fill_px_vol,
+            fill_px_usd: self.fill_px_usd,
+            fill_mark_vol: self.fill_mark_vol,
+            fill_fwd_px: self.fill_fwd_px,
+            fill_mark_px: self.fill_mark_px,
+        })
+    }
+}
+
+/// Identifies a fill in both sources: a trade has one fill per order on it.
+type FillKey = (String, String, String);
+
+fn fill_key(fill: &Fill) -> FillKey {
+    (
+        fill.inst_id.clone(),
+        fill.trade_id.clone(),
+        fill.ord_id.clone(),
+    )
+}
+
+fn fill_time(fill: &Fill) -> i64 {
+    fill.fill_time.parse().unwrap_or(0)
+}
+
+#[derive(Debug)]
+struct Merger {
+    options: FillFeedOptions,
+    /// Fill time of the last fill handed out.
+    watermark: i64,
+    /// Fills older than this are dropped; their keys are no longer kept.
+    floor: i64,
+    /// Fill time of every fill seen above the floor.
+    seen: HashMap<FillKey, i64>,
+    /// Fills waiting to settle, ordered by fill time.
+    pending: BTreeMap<(i64, FillKey), Fill>,
+    /// Whether fills may have been missed since the last backfill.
+    paused: bool,
+}
+
+impl Merger {
+    fn new(options: FillFeedOptions, start_ms: i64) -> Self {
+        let since = options.since.unwrap_or(start_ms);
+        Self {
+            options,
+            watermark: since,
+            floor: since,
+            seen: HashMap::new(),
+            pending: BTreeMap::new(),
+            paused: true,
+        }
+    }
+
+    /// Queue a fill unless it was seen before. A REST copy replaces a
+    /// pending WebSocket copy, as only it carries the bill ID.
+    fn insert(&mut self, fill: Fill) {
+        let time = fill_time(&fill);
+        if time < self.floor {
+            return;
+        }
+        let key = fill_key(&fill);
+        if self.seen.insert(key.clone(), time).is_some() {
+            if let Some(pending) = self.pending.get_mut(&(time, key)) {
+                if pending.bill_id.is_empty() && !fill.bill_id.is_empty() {
+                    *pending = fill;
+                }
+            }
+            return;
+        }
+        if time < self.watermark {
+            warn!(
+                "Fill {} of order {} arrived after later fills were handed out",
+                fill.trade_id, fill.ord_id
+            );
+        }
+        self.pending.insert((time, key), fill);
+    }
+
+    /// Where the next backfill starts.
+    fn backfill_begin(&self) -> i64 {
+        self.watermark - self.options.lookback.as_millis() as i64
+    }
+
+    /// Queue the fills of a completed backfill, which closes any gap.
+    fn backfilled(&mut self, fills: Vec<Fill>) {
+        fills.into_iter().for_each(|f| self.insert(f));
+        self.paused = false;
+    }
+
+    /// Fills settled by `now_ms`, in fill time order. Nothing is released
+    /// while a gap is open.
+    fn release(&mut self, now_ms: i64) -> Vec<Fill> {
+        if self.paused {
+            return Vec::new();
+        }
+        let settled = now_ms - self.options.settle_delay.as_millis() as i64;
+        let mut released = Vec::new();
+        while let Some(entry) = self.pending.first_entry() {
+            if entry.key().0 > settled {
+                break;
+            }
+            self.watermark = self.watermark.max(entry.key().0);
+            released.push(entry.remove());
+        }
+
+        let floor = self.watermark - 2 * self.options.lookback.as_millis() as i64;
+        if floor > self.floor {
+            self.floor = floor;
+            self.seen.retain(|_, time| *time >= floor);
+        }
+        released
+    }
+}
+
+/// Ordered stream of the account's fills without gaps or duplicates.
+///
+/// Fills come from the private `orders` channel and from backfills every
+/// [`backfill_interval`](FillFeedOptions::backfill_interval), plus one as
+/// soon as the private connection is back after a disconnect. Duplicates
+/// are recognised by instrument, trade ID and order ID. While fills may
+/// have been missed, nothing is handed out until a backfill succeeds, so
+/// consumers never see a fill before an earlier one that was missed.
+///
+/// Fills are handed out [`settle_delay`](FillFeedOptions::settle_delay)
+/// after their fill time. A fill that shows up later than that, e.g. from a
+/// periodic backfill, is still handed out, after later fills, with a
+/// warning. Fills handed out from a WebSocket push have an empty
+/// `bill_id`.
+pub struct FillFeed {
+    rx: mpsc::UnboundedReceiver<Fill>,
+    _task: FeedTask,
+}
+
+impl FillFeed {
+    /// Subscribe to the `orders` channel of all instrument types and start
+    /// backfilling. `ws` and `rest` need credentials.
+    pub async fn start(
+        rest: Arc<RestClient>,
+        ws: &WebsocketClient,
+        options: FillFeedOptions,
+    ) -> OkxResult<Self> {
+        // Take the receiver first so no push after the subscribe is missed.
+        let mut events = ws.event_receiver();
+        let orders = WsSubscriptionArg::channel_only("orders").inst_type("ANY");
+        ws.subscribe(vec![orders.clone()]).await?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = FeedTask::spawn(async move {
+            let mut merger = Merger::new(options, now_ms());
+            let mut backfills = tokio::time::interval(options.backfill_interval);
+            let mut releases = tokio::time::interval(options.settle_delay.min(options.lookback));
+            // Set from a private disconnect until the orders channel is
+            // resubscribed; a backfill before that cannot close the gap.
+            let mut disconnected = false;
+            loop {
+                let backfill = tokio::select! {
+                    msg = events.recv() => match msg {
+                        Ok(msg) => match msg.as_ref() {
+                            WsMessage::Data(evt) if evt.arg.channel == "orders" => {
+                                push_fills(evt).into_iter().for_each(|f| merger.insert(f));
+                                false
+                            }
+                            WsMessage::Disconnected(info)
+                                if info.conn_type == WsConnectionType::Private =>
+                            {
+                                merger.paused = true;
+                                disconnected = true;
+                                false
+                            }
+                            WsMessage::Event(evt)
+                                if evt.event == "subscribe"
+                                    && evt
+                                        .arg
+                                        .as_ref()
+                                        .and_then(|arg| arg.get("channel")?.as_str())
+                                        == Some("orders") =>
+                            {
+                                disconnected = false;
+                                merger.paused
+                            }
+                            _ => continue,
+                        },
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Fill feed lagged, {n} events dropped");
+                            merger.paused = true;
+                            !disconnected
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = backfills.tick() => !disconnected,
+                    // Retry failed catch-up backfills without waiting for
+                    // the next periodic one.
+                    _ = releases.tick() => merger.paused && !disconnected,
+                };
+
+                if backfill {
+                    match backfill_fills(&rest, merger.backfill_begin()).await {
+                        Ok(fills) => merger.backfilled(fills),
+                        Err(e) => warn!("Fill backfill failed: {e}"),
+                    }
+                }
+                for fill in merger.release(now_ms()) {
+                    if tx.send(fill).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            rx,
+            _task: task.releasing(ws, vec![orders]),
+        })
+    }
+
+    /// Next fill, or `None` once the WebSocket client is shut down.
+    pub async fn recv(&mut self) -> Option<Fill> {
+        self.rx.recv().await
+    }
+}
+
+fn push_fills(evt: &WsDataEvent) -> Vec<Fill> {
+    match evt.parse_data::<OrderFillPush>() {
+        Ok(pushes) => pushes
+            .into_iter()
+            .filter_map(OrderFillPush::into_fill)
+            .collect(),
+        Err(e) => {
+            warn!("Failed to parse orders push: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Page back through the fills since `begin`, newest first.
+async fn backfill_fills(rest: &RestClient, begin: i64) -> OkxResult<Vec<Fill>> {
+    let mut fills = Vec::new();
+    let mut after = None;
+    loop {
+        let page = rest
+            .get_fills(&GetFillsRequest {
+                after: after.take(),
+                begin: Some(begin.to_string()),
+                limit: Some(FILLS_PAGE_LIMIT.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        let done = page.len() < FILLS_PAGE_LIMIT;
+        after = page.last().map(|f| f.bill_id.clone());
+        fills.extend(page);
+        if done || after.is_none() {
+            return Ok(fills);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(trade_id: &str, time: i64, bill_id: &str) -> Fill {
+        OrderFillPush {
+            inst_id: "BTC-USDT".into(),
+            trade_id: trade_id.into(),
+            ord_id: "1".into(),
+            fill_sz: "1".into(),
+            fill_time: time.to_string(),
+            ..Default::default()
+        }
+        .into_fill()
+        .map(|f| Fill {
+            bill_id: bill_id.into(),
+            ..f
+        })
+        .unwrap()
+    }
+
+    fn trade_ids(fills: &[Fill]) -> Vec<&str> {
+        fills.iter().map(|f| f.trade_id.as_str()).collect()
+    }
+
+    #[test]
+    fn fills_are_deduplicated_and_released_in_order() {
+        let options = FillFeedOptions {
+            settle_delay: Duration::from_millis(1_000),
+            lookback: Duration::from_millis(5_000),
+            ..Default::default()
+        };
+        let mut merger = Merger::new(options, 10_000);
+
+        // Nothing is released before the first backfill.
+        merger.insert(fill("3", 12_000, ""));
+        assert!(merger.release(20_000).is_empty());
+        assert_eq!(merger.backfill_begin(), 5_000);
+
+        merger.backfilled(vec![
+            fill("3", 12_000, "b3"),
+            fill("2", 11_000, "b2"),
+            fill("0", 9_000, "b0"),
+        ]);
+        merger.insert(fill("4", 19_500, ""));
+        let released = merger.release(20_000);
+        assert_eq!(trade_ids(&released), ["2", "3"]);
+        assert_eq!(released[1].bill_id, "b3");
+
+        // A disconnect holds back fills until the gap is backfilled.
+        merger.paused = true;
+        merger.insert(fill("6", 21_000, ""));
+        assert!(merger.release(30_000).is_empty());
+        merger.backfilled(vec![fill("6", 21_000, "b6"), fill("5", 20_000, "b5")]);
+        merger.insert(fill("3", 12_000, ""));
+        assert_eq!(trade_ids(&merger.release(30_000)), ["4", "5", "6"]);
+        assert!(merger.release(40_000).is_empty());
+    }
+}
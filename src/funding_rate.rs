@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::rest::RestClient;
+use crate::time::now_ms;
+use crate::types::request::public::GetFundingRateRequest;
+use crate::types::response::public::FundingRate;
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+type Rates = Arc<RwLock<HashMap<String, FundingRate>>>;
+
+/// Live funding rates of a set of perpetual swaps.
+///
+/// Seeded from REST and kept current from the `funding-rate` WebSocket
+/// channel.
+pub struct FundingRateFeed {
+    rates: Rates,
+    updates: broadcast::Sender<FundingRate>,
+    _task: FeedTask,
+}
+
+impl FundingRateFeed {
+    /// Fetch the current rates of `inst_ids` and subscribe to updates.
+    pub async fn start(
+        rest: &RestClient,
+        ws: &WebsocketClient,
+        inst_ids: &[&str],
+    ) -> OkxResult<Self> {
+        let rates: Rates = Arc::default();
+        let (updates, _) = broadcast::channel(256);
+
+        let mut subscriptions = Vec::with_capacity(inst_ids.len());
+        for inst_id in inst_ids {
+            subscriptions.push(
+                ws.subscribe_typed::<FundingRate>(WsSubscriptionArg::funding_rate(inst_id))
+                    .await?,
+            );
+        }
+
+        for inst_id in inst_ids {
+            let current = rest
+                .get_funding_rate(&GetFundingRateRequest {
+                    inst_id: inst_id.to_string(),
+                })
+                .await?;
+            for rate in current {
+                apply(&rates, &updates, rate);
+            }
+        }
+
+        let task = {
+            let rates = rates.clone();
+            let updates = updates.clone();
+            let mut merged = stream::select_all(subscriptions.into_iter().map(|s| s.into_stream()));
+            FeedTask::spawn(async move {
+                while let Some(batch) = merged.next().await {
+                    match batch {
+                        Ok(batch) => {
+                            for rate in batch {
+                                apply(&rates, &updates, rate);
+                            }
+                        }
+                        Err(e) => warn!("Failed to parse funding rate push: {e}"),
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            rates,
+            updates,
+            _task: task,
+        })
+    }
+
+    /// Receive every funding rate update from now on.
+    pub fn updates(&self) -> broadcast::Receiver<FundingRate> {
+        self.updates.subscribe()
+    }
+
+    /// Latest known funding rate of an instrument.
+    pub fn latest(&self, inst_id: &str) -> Option<FundingRate> {
+        read(&self.rates).get(inst_id).cloned()
+    }
+
+    /// Time left until the next funding settlement of an instrument.
+    ///
+    /// Zero if the settlement time has passed but no newer rate has been
+    /// received yet; `None` if the instrument is unknown.
+    pub fn time_to_next_funding(&self, inst_id: &str) -> Option<Duration> {
+        time_to_next_funding_at(&self.latest(inst_id)?, now_ms())
+    }
+}
+
+fn read(rates: &Rates) -> std::sync::RwLockReadGuard<'_, HashMap<String, FundingRate>> {
+    rates.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn apply(rates: &Rates, updates: &broadcast::Sender<FundingRate>, rate: FundingRate) {
+    rates
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(rate.inst_id.clone(), rate.clone());
+    let _ = updates.send(rate);
+}
+
+/// `fundingTime` is the upcoming settlement; fall back to `nextFundingTime`
+/// once it has passed.
+fn time_to_next_funding_at(rate: &FundingRate, now_ms: i64) -> Option<Duration> {
+    let funding_time: i64 = rate.funding_time.parse().ok()?;
+    let target = if funding_time > now_ms {
+        funding_time
+    } else {
+        rate.next_funding_time.parse().unwrap_or(funding_time)
+    };
+    Some(Duration::from_millis(
+        target.saturating_sub(now_ms).max(0) as u64
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(inst_id: &str, funding_time: i64, next_funding_time: &str) -> FundingRate {
+        serde_json::from_value(serde_json::json!({
+            "instId": inst_id,
+            "fundingRate": "0.0001",
+            "fundingTime": funding_time.to_string(),
+            "nextFundingTime": next_funding_time,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn countdown_uses_upcoming_settlement() {
+        let hour = 3_600_000;
+        let r = rate("BTC-USDT-SWAP", 8 * hour, &(16 * hour).to_string());
+        assert_eq!(
+            time_to_next_funding_at(&r, 7 * hour),
+            Some(Duration::from_millis(hour as u64))
+        );
+        assert_eq!(
+            time_to_next_funding_at(&r, 9 * hour),
+            Some(Duration::from_millis(7 * hour as u64))
+        );
+
+        let r = rate("BTC-USDT-SWAP", 8 * hour, "");
+        assert_eq!(time_to_next_funding_at(&r, 9 * hour), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn apply_keeps_latest_rate_and_broadcasts() {
+        let rates: Rates = Arc::default();
+        let (updates, mut rx) = broadcast::channel(4);
+
+        apply(&rates, &updates, rate("BTC-USDT-SWAP", 1, "2"));
+        apply(&rates, &updates, rate("BTC-USDT-SWAP", 3, "4"));
+
+        assert_eq!(read(&rates)["BTC-USDT-SWAP"].funding_time, "3");
+        assert_eq!(rx.try_recv().unwrap().funding_time, "1");
+        assert_eq!(rx.try_recv().unwrap().funding_time, "3");
+    }
+}
@@ -0,0 +1,455 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::time::now_ms;
+use crate::types::enums::InstrumentType;
+use crate::types::request::public::{GetInstrumentsRequest, GetUnitConvertRequest};
+use crate::types::response::public::Instrument;
+use crate::ws::typed::FeedTask;
+use crate::ws::WebsocketClient;
+
+type Instruments = Arc<RwLock<HashMap<String, Instrument>>>;
+
+/// Instrument metadata kept current from the `instruments` WebSocket channel.
+///
+/// Seeded from REST, then updated whenever OKX lists an instrument, changes
+/// its state (e.g. `suspend` or delivery) or its parameters such as
+/// `tick_sz` and `lot_sz`.
+pub struct InstrumentRegistry {
+    instruments: Instruments,
+    updates: broadcast::Sender<Instrument>,
+    _task: FeedTask,
+}
+
+impl InstrumentRegistry {
+    /// Fetch all instruments of `inst_types` and subscribe to changes.
+    pub async fn start(
+        rest: &RestClient,
+        ws: &WebsocketClient,
+        inst_types: &[InstrumentType],
+    ) -> OkxResult<Self> {
+        let instruments: Instruments = Arc::default();
+        let (updates, _) = broadcast::channel(1024);
+
+        let mut subscriptions = Vec::with_capacity(inst_types.len());
+        for inst_type in inst_types {
+            subscriptions.push(ws.subscribe_instruments(*inst_type).await?);
+        }
+
+        for inst_type in inst_types {
+            let current = rest
+                .get_instruments(&GetInstrumentsRequest {
+                    inst_type: *inst_type,
+                    ..Default::default()
+                })
+                .await?;
+            for instrument in current {
+                apply(&instruments, &updates, instrument);
+            }
+        }
+
+        let task = {
+            let instruments = instruments.clone();
+            let updates = updates.clone();
+            let mut merged = stream::select_all(subscriptions.into_iter().map(|s| s.into_stream()));
+            FeedTask::spawn(async move {
+                while let Some(batch) = merged.next().await {
+                    match batch {
+                        Ok(batch) => {
+                            for instrument in batch {
+                                apply(&instruments, &updates, instrument);
+                            }
+                        }
+                        Err(e) => warn!("Failed to parse instruments push: {e}"),
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            instruments,
+            updates,
+            _task: task,
+        })
+    }
+
+    /// Receive every instrument pushed from now on.
+    pub fn updates(&self) -> broadcast::Receiver<Instrument> {
+        self.updates.subscribe()
+    }
+
+    /// Latest metadata of an instrument.
+    pub fn get(&self, inst_id: &str) -> Option<Instrument> {
+        read(&self.instruments).get(inst_id).cloned()
+    }
+
+    /// All known instruments of a type.
+    pub fn by_type(&self, inst_type: InstrumentType) -> Vec<Instrument> {
+        read(&self.instruments)
+            .values()
+            .filter(|i| i.inst_type == inst_type.as_str())
+            .cloned()
+            .collect()
+    }
+
+    /// Number of known instruments.
+    pub fn len(&self) -> usize {
+        read(&self.instruments).len()
+    }
+
+    /// Whether no instruments are known.
+    pub fn is_empty(&self) -> bool {
+        read(&self.instruments).is_empty()
+    }
+
+    /// Convert contracts to coins with the cached contract specs, asking
+    /// `GET /api/v5/public/convert-contract-coin` for unknown instruments.
+    ///
+    /// See [`Instrument::contracts_to_coin`].
+    pub async fn contracts_to_coin(
+        &self,
+        rest: &RestClient,
+        inst_id: &str,
+        contracts: f64,
+        px: f64,
+    ) -> OkxResult<f64> {
+        if let Some(coin) = self
+            .get(inst_id)
+            .and_then(|i| i.contracts_to_coin(contracts, px))
+        {
+            return Ok(coin);
+        }
+        convert_remote(rest, "2", inst_id, contracts, px).await
+    }
+
+    /// Convert coins to contracts with the cached contract specs, asking
+    /// `GET /api/v5/public/convert-contract-coin` for unknown instruments.
+    ///
+    /// See [`Instrument::coin_to_contracts`].
+    pub async fn coin_to_contracts(
+        &self,
+        rest: &RestClient,
+        inst_id: &str,
+        coin: f64,
+        px: f64,
+    ) -> OkxResult<f64> {
+        if let Some(contracts) = self
+            .get(inst_id)
+            .and_then(|i| i.coin_to_contracts(coin, px))
+        {
+            return Ok(contracts);
+        }
+        convert_remote(rest, "1", inst_id, coin, px).await
+    }
+
+    /// Notional value in USD of a number of contracts of a known instrument.
+    ///
+    /// See [`Instrument::notional_usd`].
+    pub fn notional_usd(&self, inst_id: &str, contracts: f64, px: f64) -> Option<f64> {
+        self.get(inst_id)?.notional_usd(contracts, px)
+    }
+}
+
+/// `type_` is `"1"` for coin to contracts and `"2"` for contracts to coin.
+async fn convert_remote(
+    rest: &RestClient,
+    type_: &str,
+    inst_id: &str,
+    sz: f64,
+    px: f64,
+) -> OkxResult<f64> {
+    let result = rest
+        .get_unit_convert(&GetUnitConvertRequest {
+            type_: Some(type_.to_string()),
+            inst_id: Some(inst_id.to_string()),
+            sz: Some(sz.to_string()),
+            px: (px > 0.0).then(|| px.to_string()),
+            unit: Some("coin".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let converted = result.first().map(|r| r.sz.as_str()).unwrap_or_default();
+    converted.parse().map_err(|_| {
+        OkxError::UnexpectedResponse(format!(
+            "unexpected converted size {converted:?} for {inst_id}"
+        ))
+    })
+}
+
+fn read(instruments: &Instruments) -> std::sync::RwLockReadGuard<'_, HashMap<String, Instrument>> {
+    instruments.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn apply(
+    instruments: &Instruments,
+    updates: &broadcast::Sender<Instrument>,
+    instrument: Instrument,
+) {
+    instruments
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(instrument.inst_id.clone(), instrument.clone());
+    let _ = updates.send(instrument);
+}
+
+/// Longest the expiry watcher sleeps before re-checking the clock.
+const MAX_EXPIRY_POLL: Duration = Duration::from_secs(60);
+
+/// Event emitted by an [`ExpiryWatcher`].
+#[derive(Debug, Clone)]
+pub enum InstrumentEvent {
+    /// An instrument the registry did not know before was listed.
+    Listed(Instrument),
+    /// A FUTURES or OPTION instrument expires within the watcher's lead time.
+    ExpiringSoon {
+        instrument: Instrument,
+        expires_in: Duration,
+    },
+}
+
+/// Watches a registry for new listings and upcoming FUTURES and OPTION
+/// expiries, e.g. to roll positions ahead of delivery.
+///
+/// Each instrument is reported at most once as expiring. Watching stops
+/// when the watcher is dropped, but the registry keeps running.
+pub struct ExpiryWatcher {
+    events: broadcast::Sender<InstrumentEvent>,
+    _task: FeedTask,
+}
+
+impl ExpiryWatcher {
+    /// Start watching. Instruments already in the registry are not
+    /// reported as listed.
+    pub fn start(registry: &InstrumentRegistry, lead: Duration) -> Self {
+        let (events, _) = broadcast::channel(256);
+        let mut updates = registry.updates();
+        let instruments = registry.instruments.clone();
+        let lead_ms = lead.as_millis() as i64;
+
+        let task = {
+            let events = events.clone();
+            FeedTask::spawn(async move {
+                let mut known: HashSet<String> = read(&instruments).keys().cloned().collect();
+                let mut alerted = HashSet::new();
+                loop {
+                    let now = now_ms();
+                    for (instrument, expires_in) in
+                        due_expiries(read(&instruments).values(), &alerted, now, lead_ms)
+                    {
+                        alerted.insert(instrument.inst_id.clone());
+                        let _ = events.send(InstrumentEvent::ExpiringSoon {
+                            instrument,
+                            expires_in,
+                        });
+                    }
+                    let wait = next_alert_ms(read(&instruments).values(), &alerted, now, lead_ms)
+                        .map_or(MAX_EXPIRY_POLL, |at| {
+                            Duration::from_millis((at - now).max(0) as u64).min(MAX_EXPIRY_POLL)
+                        });
+
+                    tokio::select! {
+                        update = updates.recv() => match update {
+                            Ok(instrument) => {
+                                if known.insert(instrument.inst_id.clone()) {
+                                    let _ = events.send(InstrumentEvent::Listed(instrument));
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("Expiry watcher lagged, {n} instrument updates dropped");
+                                known.extend(read(&instruments).keys().cloned());
+                            }
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        },
+                        _ = tokio::time::sleep(wait) => {}
+                    }
+                }
+            })
+        };
+
+        Self {
+            events,
+            _task: task,
+        }
+    }
+
+    /// Receive every event from now on.
+    pub fn events(&self) -> broadcast::Receiver<InstrumentEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// Expiry time of a FUTURES or OPTION instrument, in milliseconds.
+fn expiry_ms(instrument: &Instrument) -> Option<i64> {
+    if !matches!(instrument.inst_type.as_str(), "FUTURES" | "OPTION") {
+        return None;
+    }
+    instrument.exp_time.parse().ok()
+}
+
+/// Instruments not yet alerted that expire within `lead_ms` of `now_ms`.
+fn due_expiries<'a>(
+    instruments: impl Iterator<Item = &'a Instrument>,
+    alerted: &HashSet<String>,
+    now_ms: i64,
+    lead_ms: i64,
+) -> Vec<(Instrument, Duration)> {
+    instruments
+        .filter(|i| !alerted.contains(&i.inst_id))
+        .filter_map(|i| {
+            let exp = expiry_ms(i)?;
+            (exp > now_ms && exp - lead_ms <= now_ms)
+                .then(|| (i.clone(), Duration::from_millis((exp - now_ms) as u64)))
+        })
+        .collect()
+}
+
+/// Earliest time an instrument not yet alerted enters its lead window.
+fn next_alert_ms<'a>(
+    instruments: impl Iterator<Item = &'a Instrument>,
+    alerted: &HashSet<String>,
+    now_ms: i64,
+    lead_ms: i64,
+) -> Option<i64> {
+    instruments
+        .filter(|i| !alerted.contains(&i.inst_id))
+        .filter_map(expiry_ms)
+        .filter(|exp| *exp > now_ms)
+        .map(|exp| exp - lead_ms)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument(inst_id: &str, tick_sz: &str, state: &str) -> Instrument {
+        serde_json::from_value(serde_json::json!({
+            "instType": "SPOT",
+            "instId": inst_id,
+            "tickSz": tick_sz,
+            "state": state,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn conversions_use_cache_before_rest() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v5/public/convert-contract-coin"))
+            .and(query_param("instId", "ETH-USDT-SWAP"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": "0", "msg": "",
+                "data": [{"type": "2", "instId": "ETH-USDT-SWAP", "px": "", "sz": "0.3", "unit": "coin"}],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let rest = RestClient::new(
+            crate::config::ClientConfigBuilder::new()
+                .base_url(&server.uri())
+                .build(),
+        )
+        .unwrap();
+
+        let (updates, _) = broadcast::channel(4);
+        let registry = InstrumentRegistry {
+            instruments: Arc::default(),
+            updates,
+            _task: FeedTask::spawn(async {}),
+        };
+        apply(
+            &registry.instruments,
+            &registry.updates,
+            serde_json::from_value(serde_json::json!({
+                "instType": "SWAP", "instId": "BTC-USDT-SWAP", "uly": "BTC-USDT",
+                "ctVal": "0.01", "ctMult": "1", "ctValCcy": "BTC",
+            }))
+            .unwrap(),
+        );
+
+        let coin = registry
+            .contracts_to_coin(&rest, "BTC-USDT-SWAP", 30.0, 0.0)
+            .await
+            .unwrap();
+        assert!((coin - 0.3).abs() < 1e-12);
+        let coin = registry
+            .contracts_to_coin(&rest, "ETH-USDT-SWAP", 3.0, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(coin, 0.3);
+        assert_eq!(
+            registry.notional_usd("BTC-USDT-SWAP", 30.0, 40_000.0),
+            Some(12_000.0)
+        );
+    }
+
+    #[test]
+    fn expiries_are_due_inside_the_lead_window() {
+        let future = |inst_id: &str, exp: i64| -> Instrument {
+            serde_json::from_value(serde_json::json!({
+                "instType": "FUTURES", "instId": inst_id, "expTime": exp.to_string(),
+            }))
+            .unwrap()
+        };
+        let instruments = [
+            future("BTC-USD-A", 1_000),
+            future("BTC-USD-B", 5_000),
+            future("BTC-USD-EXPIRED", 100),
+            instrument("BTC-USDT", "0.1", "live"),
+        ];
+        let mut alerted = HashSet::new();
+
+        let due = due_expiries(instruments.iter(), &alerted, 500, 600);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0.inst_id, "BTC-USD-A");
+        assert_eq!(due[0].1, Duration::from_millis(500));
+        assert_eq!(next_alert_ms(instruments.iter(), &alerted, 500, 600), Some(400));
+
+        alerted.insert("BTC-USD-A".to_string());
+        assert!(due_expiries(instruments.iter(), &alerted, 500, 600).is_empty());
+        assert_eq!(next_alert_ms(instruments.iter(), &alerted, 500, 600), Some(4_400));
+    }
+
+    #[test]
+    fn apply_replaces_changed_instruments() {
+        let instruments: Instruments = Arc::default();
+        let (updates, mut rx) = broadcast::channel(4);
+
+        apply(
+            &instruments,
+            &updates,
+            instrument("BTC-USDT", "0.1", "live"),
+        );
+        apply(
+            &instruments,
+            &updates,
+            instrument("ETH-USDT", "0.01", "live"),
+        );
+        apply(
+            &instruments,
+            &updates,
+            instrument("BTC-USDT", "0.01", "suspend"),
+        );
+
+        let map = read(&instruments);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["BTC-USDT"].tick_sz, "0.01");
+        assert_eq!(map["BTC-USDT"].state, "suspend");
+        drop(map);
+
+        assert_eq!(rx.try_recv().unwrap().inst_id, "BTC-USDT");
+        assert_eq!(rx.try_recv().unwrap().inst_id, "ETH-USDT");
+        assert_eq!(rx.try_recv().unwrap().state, "suspend");
+    }
+}
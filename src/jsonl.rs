@@ -0,0 +1,33 @@
+//! JSON Lines files, one JSON value per line, as written by
+//! [`JsonLinesSink`](crate::capture::JsonLinesSink) and
+//! [`WsRecorder`](crate::ws::replay::WsRecorder).
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{OkxError, OkxResult};
+
+/// Write `value` followed by a newline.
+pub(crate) fn write_line<T: Serialize>(out: &mut impl Write, value: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, value)?;
+    out.write_all(b"\n")
+}
+
+/// Read every non-empty line of the file at `path` as a `T`.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> OkxResult<Vec<T>> {
+    let file =
+        File::open(path).map_err(|e| OkxError::io(format!("cannot open {}", path.display()), e))?;
+    let mut values = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| OkxError::io(format!("cannot read {}", path.display()), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        values.push(serde_json::from_str(&line).map_err(|e| OkxError::deserialize::<T>(e, &line))?);
+    }
+    Ok(values)
+}
@@ -1,14 +1,73 @@
+// The WebSocket client and everything built on it need tokio's networking,
+// so on `wasm32` (the `wasm` feature) only the REST layer is available.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod accounts;
+pub mod analytics;
 pub mod auth;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod basis;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod book;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod candles;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod capture;
 pub mod config;
 pub mod constants;
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fills;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod funding_rate;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod instruments;
+#[cfg(not(target_arch = "wasm32"))]
+mod jsonl;
+pub mod maintenance;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod oco;
+pub mod orders;
+pub mod persist;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod quoting;
+pub mod reconcile;
 pub mod rest;
+pub mod retry;
+pub mod risk;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod staleness;
+pub mod stats;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod throttle;
+mod time;
+#[cfg(not(target_arch = "wasm32"))]
+mod tls;
 pub mod types;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod ws;
 
 // Re-export primary types for convenience.
-pub use config::{ClientConfig, ClientConfigBuilder, Credentials, Region, TradingMode};
+#[cfg(not(target_arch = "wasm32"))]
+pub use accounts::AccountManager;
+#[cfg(not(target_arch = "wasm32"))]
+pub use basis::BasisMonitor;
+#[cfg(not(target_arch = "wasm32"))]
+pub use book::{LocalOrderBook, OrderBookFeed};
+#[cfg(not(target_arch = "wasm32"))]
+pub use candles::CandleAggregator;
+#[cfg(not(target_arch = "wasm32"))]
+pub use capture::Capture;
+pub use config::{ClientConfig, ClientConfigBuilder, Credentials, Region, TlsConfig, TradingMode};
 pub use error::{OkxError, OkxResult};
+#[cfg(not(target_arch = "wasm32"))]
+pub use fills::FillFeed;
+#[cfg(not(target_arch = "wasm32"))]
+pub use funding_rate::FundingRateFeed;
+#[cfg(not(target_arch = "wasm32"))]
+pub use instruments::{ExpiryWatcher, InstrumentRegistry};
 pub use rest::RestClient;
+#[cfg(not(target_arch = "wasm32"))]
 pub use ws::api_client::WsApiClient;
+#[cfg(not(target_arch = "wasm32"))]
 pub use ws::WebsocketClient;
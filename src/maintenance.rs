@@ -0,0 +1,350 @@
+//! Pausing order submissions during announced OKX maintenance.
+//!
+//! A [`MaintenanceGuard`] holds the trading-service maintenance windows
+//! known from [`RestClient::get_system_status`] and the `status` WebSocket
+//! channel. Set it on [`ClientConfig`](crate::config::ClientConfig) and the
+//! REST and WS API order placement methods reject, or hold, orders whose
+//! instrument family is under maintenance.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tracing::warn;
+
+use crate::config::TradingMode;
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::time::{now_ms, sleep};
+use crate::types::response::system::SystemStatus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::types::ws::channels::{WsChannel, WsSubscriptionArg};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ws::typed::FeedTask;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ws::WebsocketClient;
+
+/// `serviceType` values of the trading service: `5` for all accounts, `8`
+/// and `9` for accounts upgraded in batches.
+const TRADING_SERVICE_TYPES: [&str; 3] = ["5", "8", "9"];
+
+/// What happens to an order submitted during maintenance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenancePolicy {
+    /// Fail with [`OkxError::Maintenance`].
+    Reject,
+    /// Hold the submission until the window ends, failing with
+    /// [`OkxError::Maintenance`] if it is still blocked after this long.
+    Wait(Duration),
+}
+
+/// Settings of a [`MaintenanceGuard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceConfig {
+    /// Default: [`MaintenancePolicy::Reject`].
+    pub policy: MaintenancePolicy,
+    /// Instrument families to pause, e.g. `BTC-USDT`. All instruments when
+    /// `None` (default).
+    pub inst_families: Option<HashSet<String>>,
+    /// Start pausing this long before a scheduled window begins
+    /// (default: zero).
+    pub lead_time: Duration,
+    /// How often a held submission checks again (default: 1 second).
+    pub poll_interval: Duration,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            policy: MaintenancePolicy::Reject,
+            inst_families: None,
+            lead_time: Duration::ZERO,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    /// Only pause instruments of these families.
+    pub fn inst_families<I, S>(mut self, families: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.inst_families = Some(families.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// Shared set of known maintenance windows.
+///
+/// Clones share the same windows, so keep one to [`refresh`](Self::refresh)
+/// or [`watch`](Self::watch) after handing another to the client config.
+#[derive(Debug, Clone)]
+pub struct MaintenanceGuard {
+    config: Arc<MaintenanceConfig>,
+    windows: Arc<Mutex<Vec<SystemStatus>>>,
+}
+
+impl MaintenanceGuard {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            windows: Arc::default(),
+        }
+    }
+
+    /// Returns the guard configuration.
+    pub fn config(&self) -> &MaintenanceConfig {
+        &self.config
+    }
+
+    /// Known unfinished trading-service windows.
+    pub fn windows(&self) -> Vec<SystemStatus> {
+        self.lock().clone()
+    }
+
+    /// Replace the known windows, e.g. with a fresh system status response.
+    pub fn update(&self, statuses: impl IntoIterator<Item = SystemStatus>) {
+        let mut windows = self.lock();
+        windows.clear();
+        windows.extend(statuses.into_iter().filter(is_tracked));
+    }
+
+    /// Merge one status notice, e.g. a push of the `status` channel.
+    ///
+    /// Replaces the notice with the same title, service and system, and
+    /// drops it once completed or canceled.
+    pub fn apply(&self, status: SystemStatus) {
+        let mut windows = self.lock();
+        windows.retain(|w| {
+            (&w.title, &w.service_type, &w.system)
+                != (&status.title, &status.service_type, &status.system)
+        });
+        if is_tracked(&status) {
+            windows.push(status);
+        }
+    }
+
+    /// Reload the windows from the system status endpoint, keeping those of
+    /// the client's trading mode.
+    pub async fn refresh(&self, rest: &RestClient) -> OkxResult<()> {
+        let mode = rest.config().trading_mode;
+        let statuses = rest.get_system_status().await?;
+        self.update(statuses.into_iter().filter(|s| matches_env(s, mode)));
+        Ok(())
+    }
+
+    /// Keep the windows current from the `status` WebSocket channel.
+    ///
+    /// Updates stop when the returned watcher is dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn watch(&self, ws: &WebsocketClient) -> OkxResult<MaintenanceWatcher> {
+        let mode = ws.config().client_config.trading_mode;
+        let mut sub = ws
+            .subscribe_typed::<SystemStatus>(WsSubscriptionArg::new(WsChannel::Status))
+            .await?;
+        let guard = self.clone();
+        let task = FeedTask::spawn(async move {
+            while let Some(batch) = sub.recv().await {
+                match batch {
+                    Ok(batch) => batch
+                        .into_iter()
+                        .filter(|s| matches_env(s, mode))
+                        .for_each(|s| guard.apply(s)),
+                    Err(e) => warn!("Failed to parse status push: {e}"),
+                }
+            }
+        });
+        Ok(MaintenanceWatcher { _task: task })
+    }
+
+    /// The window pausing orders for `inst_id` right now, if any.
+    pub fn active(&self, inst_id: &str) -> Option<SystemStatus> {
+        self.active_at(inst_id, now_ms())
+    }
+
+    fn active_at(&self, inst_id: &str, now_ms: i64) -> Option<SystemStatus> {
+        if let Some(families) = &self.config.inst_families {
+            if !families.contains(inst_family(inst_id)) {
+                return None;
+            }
+        }
+        let lead_ms = self.config.lead_time.as_millis() as i64;
+        self.lock()
+            .iter()
+            .find(|w| is_active(w, now_ms, lead_ms))
+            .cloned()
+    }
+
+    /// Fail if any of the instruments is under maintenance.
+    pub fn check<'a>(&self, inst_ids: impl IntoIterator<Item = &'a str>) -> OkxResult<()> {
+        self.check_at(inst_ids, now_ms())
+    }
+
+    fn check_at<'a>(
+        &self,
+        inst_ids: impl IntoIterator<Item = &'a str>,
+        now_ms: i64,
+    ) -> OkxResult<()> {
+        for inst_id in inst_ids {
+            if let Some(window) = self.active_at(inst_id, now_ms) {
+                return Err(OkxError::Maintenance(format!(
+                    "{inst_id} paused by {:?} until {}",
+                    window.title, window.end
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the policy to an order submission for the instruments.
+    pub async fn admit<'a>(&self, inst_ids: impl IntoIterator<Item = &'a str>) -> OkxResult<()> {
+        let inst_ids: Vec<&str> = inst_ids.into_iter().collect();
+        let mut waited = Duration::ZERO;
+        loop {
+            let blocked = self.check(inst_ids.iter().copied());
+            match self.config.policy {
+                MaintenancePolicy::Wait(max_wait) if blocked.is_err() => {
+                    if waited >= max_wait {
+                        return blocked;
+                    }
+                    sleep(self.config.poll_interval).await;
+                    waited += self.config.poll_interval;
+                }
+                _ => return blocked,
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<SystemStatus>> {
+        self.windows.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Background task feeding a [`MaintenanceGuard`] from the `status` channel,
+/// returned by [`MaintenanceGuard::watch`].
+#[cfg(not(target_arch = "wasm32"))]
+pub struct MaintenanceWatcher {
+    _task: FeedTask,
+}
+
+/// Instrument family of an instrument ID: its first two segments, e.g.
+/// `BTC-USDT` for `BTC-USDT-SWAP`.
+pub fn inst_family(inst_id: &str) -> &str {
+    match inst_id.match_indices('-').nth(1) {
+        Some((i, _)) => &inst_id[..i],
+        None => inst_id,
+    }
+}
+
+fn is_tracked(status: &SystemStatus) -> bool {
+    TRADING_SERVICE_TYPES.contains(&status.service_type.as_str()) && !status.is_finished()
+}
+
+fn matches_env(status: &SystemStatus, mode: TradingMode) -> bool {
+    let env = match mode {
+        TradingMode::Live => "1",
+        TradingMode::Demo => "2",
+    };
+    status.env.is_empty() || status.env == env
+}
+
+/// `ongoing` windows block until OKX reports them finished or their end
+/// passes; `scheduled` ones from `lead_ms` before their begin. `pre_open`
+/// does not block, as OKX accepts orders again then.
+fn is_active(window: &SystemStatus, now_ms: i64, lead_ms: i64) -> bool {
+    let ended = window.end_ms().is_some_and(|end| now_ms >= end);
+    match window.state.as_str() {
+        "ongoing" => !ended,
+        "scheduled" => {
+            !ended
+                && window
+                    .begin_ms()
+                    .is_some_and(|begin| now_ms >= begin - lead_ms)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(state: &str, service_type: &str, begin: i64, end: i64) -> SystemStatus {
+        SystemStatus {
+            title: "Trading system upgrade".into(),
+            state: state.into(),
+            begin: begin.to_string(),
+            end: end.to_string(),
+            service_type: service_type.into(),
+            env: "1".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn inst_family_takes_first_two_segments() {
+        assert_eq!(inst_family("BTC-USDT-SWAP"), "BTC-USDT");
+        assert_eq!(inst_family("BTC-USD-240628-50000-C"), "BTC-USD");
+        assert_eq!(inst_family("ETH-USDT"), "ETH-USDT");
+    }
+
+    #[test]
+    fn scheduled_window_blocks_from_lead_time_until_end() {
+        let guard = MaintenanceGuard::new(MaintenanceConfig {
+            lead_time: Duration::from_secs(60),
+            ..Default::default()
+        });
+        guard.update([window("scheduled", "5", 100_000, 200_000)]);
+
+        assert!(guard.check_at(["BTC-USDT"], 30_000).is_ok());
+        assert!(matches!(
+            guard.check_at(["BTC-USDT"], 50_000),
+            Err(OkxError::Maintenance(_))
+        ));
+        assert!(guard.check_at(["BTC-USDT"], 150_000).is_err());
+        assert!(guard.check_at(["BTC-USDT"], 200_000).is_ok());
+    }
+
+    #[test]
+    fn only_unfinished_trading_windows_are_tracked() {
+        let guard = MaintenanceGuard::new(MaintenanceConfig::default());
+        guard.update([
+            window("ongoing", "1", 0, 200_000),
+            window("completed", "5", 0, 200_000),
+        ]);
+        assert!(guard.windows().is_empty());
+
+        guard.apply(window("ongoing", "8", 0, 200_000));
+        assert!(guard.check_at(["ETH-USDT"], 100_000).is_err());
+        guard.apply(window("completed", "8", 0, 200_000));
+        assert!(guard.check_at(["ETH-USDT"], 100_000).is_ok());
+    }
+
+    #[test]
+    fn only_configured_families_are_paused() {
+        let guard = MaintenanceGuard::new(MaintenanceConfig::default().inst_families(["BTC-USDT"]));
+        guard.update([window("ongoing", "5", 0, 200_000)]);
+
+        assert!(guard.check_at(["BTC-USDT-SWAP"], 100_000).is_err());
+        assert!(guard.check_at(["ETH-USDT-SWAP"], 100_000).is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_policy_gives_up_after_max_wait() {
+        let guard = MaintenanceGuard::new(MaintenanceConfig {
+            policy: MaintenancePolicy::Wait(Duration::from_secs(3)),
+            ..Default::default()
+        });
+        guard.update([window("ongoing", "5", 0, i64::MAX)]);
+
+        let started = tokio::time::Instant::now();
+        assert!(guard.admit(["BTC-USDT"]).await.is_err());
+        assert!(started.elapsed() >= Duration::from_secs(3));
+
+        guard.update([]);
+        assert!(guard.admit(["BTC-USDT"]).await.is_ok());
+    }
+}
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::types::request::trade::{
+    AlgoOrderRequest, CancelAlgoOrderRequest, CancelOrderRequest, OrderRequest,
+};
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::ws::typed::TypedSubscription;
+use crate::ws::WebsocketClient;
+
+/// One side of a client-side OCO pair.
+#[derive(Debug, Clone)]
+pub enum OcoLeg {
+    /// Regular order, e.g. a resting limit take-profit.
+    Order(OrderRequest),
+    /// Trigger algo order, e.g. a stop that sends a market order when hit.
+    Trigger(AlgoOrderRequest),
+}
+
+impl OcoLeg {
+    fn inst_id(&self) -> &str {
+        match self {
+            Self::Order(req) => &req.inst_id,
+            Self::Trigger(req) => &req.inst_id,
+        }
+    }
+}
+
+/// Exchange ID of a placed leg.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlacedLeg {
+    Order { ord_id: String },
+    Trigger { algo_id: String },
+}
+
+impl PlacedLeg {
+    /// Whether an `orders` channel push belongs to this leg. Orders sent by
+    /// a trigger carry its `algoId`.
+    fn owns(&self, push: &OrderPush) -> bool {
+        match self {
+            Self::Order { ord_id } => !ord_id.is_empty() && push.ord_id == *ord_id,
+            Self::Trigger { algo_id } => !algo_id.is_empty() && push.algo_id == *algo_id,
+        }
+    }
+
+    async fn cancel(&self, rest: &RestClient, inst_id: &str) -> OkxResult<()> {
+        match self {
+            Self::Order { ord_id } => rest
+                .cancel_order(&CancelOrderRequest {
+                    inst_id: inst_id.to_string(),
+                    ord_id: Some(ord_id.clone()),
+                    cl_ord_id: None,
+                })
+                .await
+                .map(drop),
+            Self::Trigger { algo_id } => rest
+                .cancel_algo_orders(&vec![CancelAlgoOrderRequest {
+                    inst_id: inst_id.to_string(),
+                    algo_id: algo_id.clone(),
+                }])
+                .await
+                .map(drop),
+        }
+    }
+}
+
+/// Which leg of the pair executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcoSide {
+    TakeProfit,
+    Stop,
+}
+
+/// How an OCO pair finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcoOutcome {
+    /// One leg (partially) filled. `sibling_cancelled` is false if cancelling
+    /// the other leg failed; it may still be live.
+    Filled {
+        side: OcoSide,
+        sibling_cancelled: bool,
+    },
+    /// Both legs were cancelled through [`OcoHandle::cancel`].
+    Cancelled,
+}
+
+/// The fields of an `orders` channel push the monitor needs.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderPush {
+    #[serde(default)]
+    ord_id: String,
+    #[serde(default)]
+    algo_id: String,
+    #[serde(default)]
+    state: String,
+}
+
+impl OrderPush {
+    fn has_fill(&self) -> bool {
+        matches!(self.state.as_str(), "filled" | "partially_filled")
+    }
+}
+
+/// A live OCO pair placed by [`place_oco`].
+///
+/// Dropping the handle stops monitoring but leaves both legs on the
+/// exchange; use [`cancel`](Self::cancel) to remove them.
+pub struct OcoHandle {
+    inst_id: String,
+    take_profit: PlacedLeg,
+    stop: PlacedLeg,
+    rest: Arc<RestClient>,
+    outcome: watch::Receiver<Option<OcoOutcome>>,
+    task: JoinHandle<()>,
+}
+
+impl OcoHandle {
+    /// The placed take-profit leg.
+    pub fn take_profit(&self) -> &PlacedLeg {
+        &self.take_profit
+    }
+
+    /// The placed stop leg.
+    pub fn stop(&self) -> &PlacedLeg {
+        &self.stop
+    }
+
+    /// Outcome so far; `None` while both legs are live.
+    pub fn outcome(&self) -> Option<OcoOutcome> {
+        self.outcome.borrow().clone()
+    }
+
+    /// Wait until one leg fills and the sibling has been cancelled.
+    ///
+    /// Returns `None` if monitoring ended first, e.g. because the WebSocket
+    /// client was shut down.
+    pub async fn wait(&mut self) -> Option<OcoOutcome> {
+        self.outcome
+            .wait_for(Option::is_some)
+            .await
+            .ok()
+            .and_then(|outcome| outcome.clone())
+    }
+
+    /// Stop monitoring and cancel both legs.
+    ///
+    /// Does nothing if a leg already filled.
+    pub async fn cancel(self) -> OkxResult<()> {
+        self.task.abort();
+        if self.outcome().is_some() {
+            return Ok(());
+        }
+        let take_profit = self.take_profit.cancel(&self.rest, &self.inst_id).await;
+        let stop = self.stop.cancel(&self.rest, &self.inst_id).await;
+        take_profit.and(stop)
+    }
+}
+
+impl Drop for OcoHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Place a take-profit and a stop on the same instrument and cancel one as
+/// soon as the other fills.
+///
+/// For cases the exchange-native `oco` algo order does not cover, e.g. a
+/// resting limit take-profit next to a trigger stop. Fills are watched on
+/// the private `orders` channel, so `ws` needs credentials. If the second
+/// leg cannot be placed, the first one is cancelled and the error returned.
+pub async fn place_oco(
+    rest: &Arc<RestClient>,
+    ws: &WebsocketClient,
+    take_profit: OcoLeg,
+    stop: OcoLeg,
+) -> OkxResult<OcoHandle> {
+    let inst_id = take_profit.inst_id().to_string();
+    if stop.inst_id() != inst_id {
+        return Err(OkxError::Config(format!(
+            "OCO legs must share an instrument, got {inst_id} and {}",
+            stop.inst_id()
+        )));
+    }
+
+    // Subscribe before placing so no fill is missed.
+    let orders = ws
+        .subscribe_typed::<OrderPush>(
+            WsSubscriptionArg::channel_only("orders")
+                .inst_type("ANY")
+                .inst_id(&inst_id),
+        )
+        .await?;
+
+    let placed_take_profit = place_leg(rest, &take_profit).await?;
+    let placed_stop = match place_leg(rest, &stop).await {
+        Ok(placed) => placed,
+        Err(e) => {
+            if let Err(cancel_err) = placed_take_profit.cancel(rest, &inst_id).await {
+                warn!("Failed to cancel OCO take-profit after stop was rejected: {cancel_err}");
+            }
+            return Err(e);
+        }
+    };
+
+    let (outcome_tx, outcome) = watch::channel(None);
+    let task = tokio::spawn(monitor(
+        orders,
+        rest.clone(),
+        inst_id.clone(),
+        placed_take_profit.clone(),
+        placed_stop.clone(),
+        outcome_tx,
+    ));
+
+    Ok(OcoHandle {
+        inst_id,
+        take_profit: placed_take_profit,
+        stop: placed_stop,
+        rest: rest.clone(),
+        outcome,
+        task,
+    })
+}
+
+async fn place_leg(rest: &RestClient, leg: &OcoLeg) -> OkxResult<PlacedLeg> {
+    let placed = match leg {
+        OcoLeg::Order(req) => rest
+            .place_order(req)
+            .await?
+            .into_iter()
+            .next()
+            .map(|r| PlacedLeg::Order { ord_id: r.ord_id }),
+        OcoLeg::Trigger(req) => rest
+            .place_algo_order(req)
+            .await?
+            .into_iter()
+            .next()
+            .map(|r| PlacedLeg::Trigger { algo_id: r.algo_id }),
+    };
+    placed.ok_or_else(|| OkxError::UnexpectedResponse("empty response data".into()))
+}
+
+async fn monitor(
+    mut orders: TypedSubscription<OrderPush>,
+    rest: Arc<RestClient>,
+    inst_id: String,
+    take_profit: PlacedLeg,
+    stop: PlacedLeg,
+    outcome: watch::Sender<Option<OcoOutcome>>,
+) {
+    while let Some(batch) = orders.recv().await {
+        let pushes = match batch {
+            Ok(pushes) => pushes,
+            Err(e) => {
+                warn!("Failed to parse orders push: {e}");
+                continue;
+            }
+        };
+        let Some((side, sibling)) = pushes.iter().filter(|p| p.has_fill()).find_map(|p| {
+            if take_profit.owns(p) {
+                Some((OcoSide::TakeProfit, &stop))
+            } else if stop.owns(p) {
+                Some((OcoSide::Stop, &take_profit))
+            } else {
+                None
+            }
+        }) else {
+            continue;
+        };
+
+        let sibling_cancelled = match sibling.cancel(&rest, &inst_id).await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to cancel OCO sibling of filled {side:?} leg: {e}");
+                false
+            }
+        };
+        let _ = outcome.send(Some(OcoOutcome::Filled {
+            side,
+            sibling_cancelled,
+        }));
+        break;
+    }
+
+    if let Err(e) = orders.unsubscribe().await {
+        warn!("Failed to unsubscribe OCO orders channel: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(json: serde_json::Value) -> OrderPush {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn legs_own_their_pushes() {
+        let order = PlacedLeg::Order { ord_id: "1".into() };
+        let trigger = PlacedLeg::Trigger {
+            algo_id: "9".into(),
+        };
+
+        let limit_fill = push(serde_json::json!({"ordId": "1", "algoId": "", "state": "filled"}));
+        let triggered =
+            push(serde_json::json!({"ordId": "2", "algoId": "9", "state": "partially_filled"}));
+
+        assert!(order.owns(&limit_fill) && !trigger.owns(&limit_fill));
+        assert!(trigger.owns(&triggered) && !order.owns(&triggered));
+        assert!(limit_fill.has_fill() && triggered.has_fill());
+        assert!(!push(serde_json::json!({"ordId": "1", "state": "live"})).has_fill());
+    }
+}
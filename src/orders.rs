@@ -0,0 +1,429 @@
+//! Local view of the account's open orders.
+//!
+//! An [`OrderTracker`] is seeded from `GET /api/v5/trade/orders-pending` and
+//! kept current from the private `orders` WebSocket channel. It answers how
+//! many orders are open and how much notional they carry per instrument,
+//! and can back the aggregate limits of a [`RiskGuard`](crate::risk::RiskGuard).
+//! With a [`StateStorage`] the orders also survive a restart.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::persist::{self, StateStorage};
+use crate::rest::RestClient;
+use crate::time::now_ms;
+use crate::types::response::trade::OrderDetails;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::types::ws::channels::WsSubscriptionArg;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ws::typed::FeedTask;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ws::WebsocketClient;
+
+/// The fields of an `orders` channel push, or of a pending order from REST,
+/// that the tracker needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OrderUpdate {
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    #[serde(default)]
+    pub ord_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub px: String,
+    #[serde(default)]
+    pub sz: String,
+    #[serde(default)]
+    pub acc_fill_sz: String,
+    /// `live`, `partially_filled`, `filled`, `canceled` or `mmp_canceled`.
+    #[serde(default)]
+    pub state: String,
+    /// `quote_ccy` when `sz` is in the quote currency.
+    #[serde(default)]
+    pub tgt_ccy: String,
+    #[serde(default)]
+    pub u_time: String,
+}
+
+impl OrderUpdate {
+    /// Whether the order is still working on the book.
+    pub fn is_open(&self) -> bool {
+        matches!(self.state.as_str(), "live" | "partially_filled")
+    }
+}
+
+impl From<&OrderDetails> for OrderUpdate {
+    fn from(order: &OrderDetails) -> Self {
+        Self {
+            inst_type: order.inst_type.clone(),
+            inst_id: order.inst_id.clone(),
+            ord_id: order.ord_id.clone(),
+            cl_ord_id: order.cl_ord_id.clone(),
+            side: order.side.clone(),
+            px: order.px.clone(),
+            sz: order.sz.clone(),
+            acc_fill_sz: order.acc_fill_sz.clone(),
+            state: order.state.clone(),
+            tgt_ccy: String::new(),
+            u_time: order.u_time.clone(),
+        }
+    }
+}
+
+/// An order the tracker considers open.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub inst_id: String,
+    pub ord_id: String,
+    pub cl_ord_id: String,
+    /// `buy` or `sell`.
+    pub side: String,
+    /// Limit price; zero for market orders.
+    pub px: f64,
+    /// Size not yet filled, in order size units.
+    pub remaining_sz: f64,
+    /// Whether the size is in the quote currency.
+    pub quote_sized: bool,
+    pub u_time: i64,
+}
+
+impl OpenOrder {
+    fn from_update(update: &OrderUpdate) -> Self {
+        let num = |v: &str| v.parse::<f64>().unwrap_or(0.0);
+        Self {
+            inst_id: update.inst_id.clone(),
+            ord_id: update.ord_id.clone(),
+            cl_ord_id: update.cl_ord_id.clone(),
+            side: update.side.clone(),
+            px: num(&update.px),
+            remaining_sz: (num(&update.sz) - num(&update.acc_fill_sz)).max(0.0),
+            quote_sized: update.tgt_ccy == "quote_ccy",
+            u_time: update.u_time.parse().unwrap_or(0),
+        }
+    }
+
+    /// Whether the order adds to a long position.
+    pub fn is_buy(&self) -> bool {
+        self.side == "buy"
+    }
+
+    /// Price times remaining size. Multiply by the contract size for
+    /// derivatives; see [`RiskGuard`](crate::risk::RiskGuard) for a
+    /// valuation in the quote currency.
+    pub fn notional(&self) -> f64 {
+        if self.quote_sized {
+            self.remaining_sz
+        } else {
+            self.px * self.remaining_sz
+        }
+    }
+}
+
+/// Open order count and notional of one instrument.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OpenOrderStats {
+    pub count: usize,
+    /// Sum of [`OpenOrder::notional`].
+    pub notional: f64,
+}
+
+/// How long a closed order is remembered, so late pushes and stale
+/// snapshots of it are ignored.
+const TOMBSTONE_TTL_MS: i64 = 60 * 60 * 1000;
+
+/// Closed orders are pruned once there are more than this many.
+const TOMBSTONE_PRUNE_LEN: usize = 4096;
+
+#[derive(Debug, Default)]
+struct OrderBook {
+    open: HashMap<String, OpenOrder>,
+    /// Update time of recently closed orders, by order ID.
+    closed: HashMap<String, i64>,
+}
+
+impl OrderBook {
+    fn close(&mut self, ord_id: &str, u_time: i64) {
+        self.open.remove(ord_id);
+        self.closed.insert(ord_id.to_string(), u_time);
+        if self.closed.len() > TOMBSTONE_PRUNE_LEN {
+            let cutoff = u_time - TOMBSTONE_TTL_MS;
+            self.closed.retain(|_, closed| *closed >= cutoff);
+        }
+    }
+
+    /// Whether `order` is older than what is known about it.
+    fn is_stale(&self, order: &OpenOrder) -> bool {
+        if let Some(&closed) = self.closed.get(&order.ord_id) {
+            return order.u_time <= closed;
+        }
+        self.open
+            .get(&order.ord_id)
+            .is_some_and(|known| order.u_time < known.u_time)
+    }
+}
+
+type Orders = Arc<RwLock<OrderBook>>;
+
+/// Shared set of open orders keyed by order ID.
+///
+/// Closed orders are remembered for an hour, so a late push or a stale
+/// snapshot cannot bring them back. Clones share the same orders.
+#[derive(Debug, Clone, Default)]
+pub struct OrderTracker {
+    orders: Orders,
+    storage: Option<Arc<dyn StateStorage>>,
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracker that saves its orders to `storage` after every change,
+    /// starting from the orders saved there by a previous process.
+    ///
+    /// Orders that closed while the process was down stay tracked until an
+    /// update for them arrives or [`load`](Self::load) is called.
+    pub fn with_storage(storage: Arc<dyn StateStorage>) -> OkxResult<Self> {
+        let saved: Vec<OpenOrder> =
+            persist::load_json(storage.as_ref(), persist::OPEN_ORDERS_KEY)?.unwrap_or_default();
+        Ok(Self {
+            orders: Arc::new(RwLock::new(OrderBook {
+                open: saved.into_iter().map(|o| (o.ord_id.clone(), o)).collect(),
+                closed: HashMap::new(),
+            })),
+            storage: Some(storage),
+        })
+    }
+
+    /// Sync the tracked orders with the account's pending orders.
+    ///
+    /// The snapshot is merged by update time: orders the tracker has seen
+    /// close or change since stay as tracked, and tracked orders missing
+    /// from the snapshot are dropped unless they were updated after the
+    /// snapshot was requested.
+    pub async fn load(&self, rest: &RestClient) -> OkxResult<()> {
+        let requested_at = now_ms();
+        let pending = rest.all_pending_orders().await?;
+        let snapshot: HashMap<_, _> = pending
+            .iter()
+            .map(OrderUpdate::from)
+            .filter(OrderUpdate::is_open)
+            .map(|u| (u.ord_id.clone(), OpenOrder::from_update(&u)))
+            .collect();
+        let mut book = self.orders.write().unwrap_or_else(|e| e.into_inner());
+        book.open
+            .retain(|ord_id, o| snapshot.contains_key(ord_id) || o.u_time >= requested_at);
+        for (ord_id, order) in snapshot {
+            if !book.is_stale(&order) {
+                book.open.insert(ord_id, order);
+            }
+        }
+        self.save(&book.open);
+        Ok(())
+    }
+
+    /// Keep the orders current from the `orders` channel of all instrument
+    /// types. `ws` needs credentials.
+    ///
+    /// Updates stop when the returned watcher is dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn watch(&self, ws: &WebsocketClient) -> OkxResult<OrderTrackerWatcher> {
+        let mut sub = ws
+            .subscribe_typed::<OrderUpdate>(
+                WsSubscriptionArg::channel_only("orders").inst_type("ANY"),
+            )
+            .await?;
+        let tracker = self.clone();
+        let task = FeedTask::spawn(async move {
+            while let Some(batch) = sub.recv().await {
+                match batch {
+                    Ok(batch) => batch.iter().for_each(|u| tracker.apply(u)),
+                    Err(e) => warn!("Failed to parse orders push: {e}"),
+                }
+            }
+        });
+        Ok(OrderTrackerWatcher { _task: task })
+    }
+
+    /// Apply an order update. Updates older than the tracked state of the
+    /// order, or of an order that has closed, are ignored.
+    pub fn apply(&self, update: &OrderUpdate) {
+        if update.ord_id.is_empty() {
+            return;
+        }
+        let mut book = self.orders.write().unwrap_or_else(|e| e.into_inner());
+        let order = OpenOrder::from_update(update);
+        if book.is_stale(&order) {
+            return;
+        }
+        if update.is_open() {
+            book.open.insert(update.ord_id.clone(), order);
+        } else {
+            let was_open = book.open.contains_key(&update.ord_id);
+            book.close(&update.ord_id, order.u_time);
+            if !was_open {
+                return;
+            }
+        }
+        self.save(&book.open);
+    }
+
+    /// All open orders.
+    pub fn open_orders(&self) -> Vec<OpenOrder> {
+        self.read().open.values().cloned().collect()
+    }
+
+    /// Open orders of one instrument.
+    pub fn open_orders_for(&self, inst_id: &str) -> Vec<OpenOrder> {
+        self.read()
+            .open
+            .values()
+            .filter(|o| o.inst_id == inst_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of open orders across all instruments.
+    pub fn open_order_count(&self) -> usize {
+        self.read().open.len()
+    }
+
+    /// Open order count and notional of one instrument.
+    pub fn stats(&self, inst_id: &str) -> OpenOrderStats {
+        self.read()
+            .open
+            .values()
+            .filter(|o| o.inst_id == inst_id)
+            .fold(OpenOrderStats::default(), add_order)
+    }
+
+    /// Open order count and notional per instrument.
+    pub fn stats_by_instrument(&self) -> HashMap<String, OpenOrderStats> {
+        let mut stats: HashMap<String, OpenOrderStats> = HashMap::new();
+        for order in self.read().open.values() {
+            let entry = stats.entry(order.inst_id.clone()).or_default();
+            *entry = add_order(*entry, order);
+        }
+        stats
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, OrderBook> {
+        self.orders.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Save the orders to the storage, if any. Called with the write lock
+    /// held so saves happen in the order of the changes; the save blocks
+    /// the calling thread, see [`StateStorage`].
+    fn save(&self, orders: &HashMap<String, OpenOrder>) {
+        let Some(storage) = &self.storage else {
+            return;
+        };
+        let orders: Vec<_> = orders.values().collect();
+        if let Err(e) = persist::save_json(storage.as_ref(), persist::OPEN_ORDERS_KEY, &orders) {
+            warn!("Failed to save open orders: {e}");
+        }
+    }
+}
+
+fn add_order(stats: OpenOrderStats, order: &OpenOrder) -> OpenOrderStats {
+    OpenOrderStats {
+        count: stats.count + 1,
+        notional: stats.notional + order.notional(),
+    }
+}
+
+/// Background task feeding an [`OrderTracker`] from the `orders` channel,
+/// returned by [`OrderTracker::watch`].
+#[cfg(not(target_arch = "wasm32"))]
+pub struct OrderTrackerWatcher {
+    _task: FeedTask,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(ord_id: &str, state: &str, sz: &str, filled: &str, u_time: &str) -> OrderUpdate {
+        OrderUpdate {
+            inst_id: "BTC-USDT".into(),
+            ord_id: ord_id.into(),
+            side: "buy".into(),
+            px: "100".into(),
+            sz: sz.into(),
+            acc_fill_sz: filled.into(),
+            state: state.into(),
+            u_time: u_time.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tracks_remaining_size_until_order_closes() {
+        let tracker = OrderTracker::new();
+        tracker.apply(&update("1", "live", "2", "0", "1"));
+        tracker.apply(&update("2", "live", "1", "0", "1"));
+        tracker.apply(&update("1", "partially_filled", "2", "0.5", "2"));
+        assert_eq!(
+            tracker.stats("BTC-USDT"),
+            OpenOrderStats {
+                count: 2,
+                notional: 250.0
+            }
+        );
+
+        // A late push of an older state does not resurrect the fill.
+        tracker.apply(&update("1", "live", "2", "0", "1"));
+        assert_eq!(tracker.stats("BTC-USDT").notional, 250.0);
+
+        tracker.apply(&update("1", "filled", "2", "2", "3"));
+        tracker.apply(&update("2", "canceled", "1", "0", "3"));
+        assert_eq!(tracker.open_order_count(), 0);
+        assert!(tracker.stats_by_instrument().is_empty());
+    }
+
+    #[test]
+    fn closed_orders_are_not_resurrected_by_late_pushes() {
+        let tracker = OrderTracker::new();
+        tracker.apply(&update("1", "live", "2", "0", "1"));
+        tracker.apply(&update("1", "canceled", "2", "0", "3"));
+        tracker.apply(&update("1", "live", "2", "0", "2"));
+        tracker.apply(&update("1", "live", "2", "0", "3"));
+        assert_eq!(tracker.open_order_count(), 0);
+
+        // Close pushed before the order was ever seen open.
+        tracker.apply(&update("2", "filled", "1", "1", "5"));
+        tracker.apply(&update("2", "live", "1", "0", "4"));
+        assert_eq!(tracker.open_order_count(), 0);
+    }
+
+    #[test]
+    fn orders_are_restored_from_storage() {
+        let dir = std::env::temp_dir().join(format!("okx-orders-{}", std::process::id()));
+        let storage: Arc<dyn StateStorage> = Arc::new(persist::FileStorage::new(&dir).unwrap());
+
+        let tracker = OrderTracker::with_storage(storage.clone()).unwrap();
+        tracker.apply(&update("1", "live", "2", "0", "1"));
+        tracker.apply(&update("2", "live", "1", "0", "1"));
+        tracker.apply(&update("2", "filled", "1", "1", "2"));
+        drop(tracker);
+
+        let restored = OrderTracker::with_storage(storage).unwrap();
+        assert_eq!(
+            restored.open_orders(),
+            vec![OpenOrder::from_update(&update("1", "live", "2", "0", "1"))]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
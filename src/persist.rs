@@ -0,0 +1,135 @@
+//! Session state that survives process restarts.
+//!
+//! A [`StateStorage`] set as [`WsConfig::state_storage`](crate::ws::types::WsConfig::state_storage)
+//! keeps the client's subscribed topics, and one passed to
+//! [`OrderTracker::with_storage`](crate::orders::OrderTracker::with_storage)
+//! keeps the open orders, so a restarted process can resubscribe and resume
+//! tracking without waiting for the exchange.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{OkxError, OkxResult};
+
+/// Key the WebSocket client saves its subscribed topics under.
+pub const SUBSCRIPTIONS_KEY: &str = "ws-subscriptions";
+
+/// Key an order tracker saves its open orders under.
+pub const OPEN_ORDERS_KEY: &str = "open-orders";
+
+/// Key-value store for session state, e.g. files or a database table.
+///
+/// Values are JSON documents. Saves are synchronous and happen whenever the
+/// state changes: an [`OrderTracker`](crate::orders::OrderTracker) saves
+/// while holding its lock, and the WebSocket client saves on the task that
+/// changed its subscriptions, so both block an async runtime thread for
+/// the duration of [`save`](Self::save). [`FileStorage`] writes one small
+/// file, which is fine on a local disk; a slower backend should queue the
+/// value for its own writer thread and return.
+pub trait StateStorage: Send + Sync + fmt::Debug {
+    /// The value saved under `key`, or `None` if there is none.
+    fn load(&self, key: &str) -> OkxResult<Option<Vec<u8>>>;
+
+    /// Replace the value saved under `key`.
+    fn save(&self, key: &str, value: &[u8]) -> OkxResult<()>;
+}
+
+/// Saves each key to `<dir>/<key>.json`.
+///
+/// Values are written to a temporary file first and renamed into place, so
+/// a crash mid-save leaves the previous value intact.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Store files in `dir`, creating it if needed.
+    pub fn new(dir: impl AsRef<Path>) -> OkxResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| OkxError::Storage(format!("cannot create {}: {e}", dir.display())))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+impl StateStorage for FileStorage {
+    fn load(&self, key: &str) -> OkxResult<Option<Vec<u8>>> {
+        let path = self.path(key);
+        match fs::read(&path) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(OkxError::Storage(format!(
+                "cannot read {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    fn save(&self, key: &str, value: &[u8]) -> OkxResult<()> {
+        let path = self.path(key);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, value)
+            .and_then(|()| fs::rename(&tmp, &path))
+            .map_err(|e| OkxError::Storage(format!("cannot write {}: {e}", path.display())))
+    }
+}
+
+/// Serialize `value` as JSON and save it under `key`.
+pub(crate) fn save_json<T: Serialize + ?Sized>(
+    storage: &dyn StateStorage,
+    key: &str,
+    value: &T,
+) -> OkxResult<()> {
+    storage.save(key, &serde_json::to_vec(value)?)
+}
+
+/// Load and deserialize the JSON value saved under `key`.
+pub(crate) fn load_json<T: DeserializeOwned>(
+    storage: &dyn StateStorage,
+    key: &str,
+) -> OkxResult<Option<T>> {
+    let Some(value) = storage.load(key)? else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&value)
+        .map(Some)
+        .map_err(|e| OkxError::deserialize::<T>(e, &String::from_utf8_lossy(&value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_storage_round_trips_and_replaces_values() {
+        let dir = std::env::temp_dir().join(format!("okx-persist-{}", std::process::id()));
+        let storage = FileStorage::new(&dir).unwrap();
+        assert!(load_json::<Vec<String>>(&storage, "topics")
+            .unwrap()
+            .is_none());
+
+        save_json(&storage, "topics", &["a", "b"]).unwrap();
+        save_json(&storage, "topics", &["c"]).unwrap();
+        assert_eq!(
+            load_json::<Vec<String>>(&storage, "topics").unwrap(),
+            Some(vec!["c".to_string()])
+        );
+        assert!(!dir.join("topics.json.tmp").exists());
+
+        fs::write(dir.join("topics.json"), "not json").unwrap();
+        assert!(matches!(
+            load_json::<Vec<String>>(&storage, "topics"),
+            Err(OkxError::Deserialize { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::error::OkxResult;
+use crate::time::now_ms;
+use crate::types::enums::{OrderSide, OrderState, OrderType, TradeMode};
+use crate::types::request::trade::{AmendOrderRequest, CancelOrderRequest, OrderRequest};
+use crate::ws::api_client::WsApiClient;
+
+/// Most orders OKX accepts in one batch request.
+const MAX_BATCH: usize = 20;
+
+/// Price and size of one side of a quote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteLevel {
+    pub px: String,
+    pub sz: String,
+}
+
+impl QuoteLevel {
+    pub fn new(px: &str, sz: &str) -> Self {
+        Self {
+            px: px.to_string(),
+            sz: sz.to_string(),
+        }
+    }
+}
+
+/// Desired two-sided quote for one instrument. A `None` side is pulled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetQuote {
+    pub bid: Option<QuoteLevel>,
+    pub ask: Option<QuoteLevel>,
+}
+
+impl TargetQuote {
+    pub fn two_sided(bid: QuoteLevel, ask: QuoteLevel) -> Self {
+        Self {
+            bid: Some(bid),
+            ask: Some(ask),
+        }
+    }
+
+    fn side(&self, side: OrderSide) -> Option<&QuoteLevel> {
+        match side {
+            OrderSide::Buy => self.bid.as_ref(),
+            OrderSide::Sell => self.ask.as_ref(),
+        }
+    }
+}
+
+/// A resting quote order the engine placed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveQuote {
+    pub inst_id: String,
+    pub side: OrderSide,
+    pub cl_ord_id: String,
+    pub level: QuoteLevel,
+}
+
+/// What one [`QuoteEngine::update`] sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuoteReport {
+    pub placed: usize,
+    pub amended: usize,
+    pub cancelled: usize,
+    /// Orders OKX rejected individually (non-zero `sCode`).
+    pub rejected: usize,
+}
+
+/// Requests needed to move the live quotes to the targets.
+#[derive(Debug, Default)]
+struct QuotePlan {
+    cancel: Vec<CancelOrderRequest>,
+    amend: Vec<(AmendOrderRequest, OrderSide, QuoteLevel)>,
+    place: Vec<(OrderRequest, QuoteLevel)>,
+}
+
+struct State {
+    live: HashMap<(String, OrderSide), LiveQuote>,
+    /// Orders of batches that failed without a per-order result, which may
+    /// or may not be resting. Cancelled before anything else is sent.
+    unknown: Vec<CancelOrderRequest>,
+    last_batch: Option<Instant>,
+    next_id: u64,
+}
+
+/// Keeps a two-sided quote per instrument in line with a target over the
+/// WS API.
+///
+/// Each [`update`](Self::update) diffs the targets against the orders the
+/// engine has resting and sends only what changed: cancels for pulled
+/// sides, amends for moved prices or sizes, and places for new sides, in
+/// batches of up to 20. Batch requests are spaced by at least
+/// [`batch_interval`](Self::batch_interval) to stay inside the per-connection
+/// order rate limits.
+///
+/// The engine only knows about fills and cancels it did not send itself if
+/// `orders` channel pushes are fed to
+/// [`on_order_update`](Self::on_order_update); a side that was filled is
+/// then placed again on the next update.
+pub struct QuoteEngine {
+    api: WsApiClient,
+    td_mode: TradeMode,
+    ord_type: OrderType,
+    batch_interval: Duration,
+    id_prefix: String,
+    state: Mutex<State>,
+}
+
+impl QuoteEngine {
+    /// Quote with `post_only` orders in `td_mode`.
+    pub fn new(api: WsApiClient, td_mode: TradeMode) -> Self {
+        let started = now_ms();
+        Self {
+            api,
+            td_mode,
+            ord_type: OrderType::PostOnly,
+            batch_interval: Duration::from_millis(100),
+            id_prefix: format!("mmq{started}"),
+            state: Mutex::new(State {
+                live: HashMap::new(),
+                unknown: Vec::new(),
+                last_batch: None,
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Order type of placed quotes (default: `post_only`).
+    pub fn ord_type(mut self, ord_type: OrderType) -> Self {
+        self.ord_type = ord_type;
+        self
+    }
+
+    /// Minimum gap between two batch requests (default: 100 ms).
+    pub fn batch_interval(mut self, interval: Duration) -> Self {
+        self.batch_interval = interval;
+        self
+    }
+
+    /// The underlying WS API client.
+    pub fn api(&self) -> &WsApiClient {
+        &self.api
+    }
+
+    /// Quotes currently believed to be resting.
+    pub async fn live_quotes(&self) -> Vec<LiveQuote> {
+        self.state.lock().await.live.values().cloned().collect()
+    }
+
+    /// Move the quotes of the given instruments to their targets.
+    /// Instruments not listed are left alone.
+    ///
+    /// A failed batch aborts the update; batches sent before it are already
+    /// reflected in [`live_quotes`](Self::live_quotes). Orders placed or
+    /// amended by the failed batch may or may not be resting, so they are
+    /// dropped from the live quotes and cancelled at the start of the next
+    /// update, before their sides are placed again.
+    pub async fn update(
+        &self,
+        targets: impl IntoIterator<Item = (String, TargetQuote)>,
+    ) -> OkxResult<QuoteReport> {
+        let mut state = self.state.lock().await;
+        let targets: Vec<_> = targets.into_iter().collect();
+        let plan = self.plan(&mut state, &targets);
+        self.execute(&mut state, plan).await
+    }
+
+    /// Pull every quote the engine has resting.
+    pub async fn cancel_all(&self) -> OkxResult<QuoteReport> {
+        let mut state = self.state.lock().await;
+        let plan = QuotePlan {
+            cancel: state.live.values().map(cancel_request).collect(),
+            ..Default::default()
+        };
+        self.execute(&mut state, plan).await
+    }
+
+    /// Forget a quote that filled or was cancelled outside the engine.
+    ///
+    /// `cl_ord_id` and `state` come from an `orders` channel push. Partial
+    /// fills keep the quote resting.
+    pub async fn on_order_update(&self, cl_ord_id: &str, state: OrderState) {
+        if matches!(state, OrderState::Live | OrderState::PartiallyFilled) {
+            return;
+        }
+        let mut quotes = self.state.lock().await;
+        quotes.live.retain(|_, quote| quote.cl_ord_id != cl_ord_id);
+        quotes
+            .unknown
+            .retain(|req| req.cl_ord_id.as_deref() != Some(cl_ord_id));
+    }
+
+    fn plan(&self, state: &mut State, targets: &[(String, TargetQuote)]) -> QuotePlan {
+        let mut plan = QuotePlan::default();
+        for (inst_id, target) in targets {
+            for side in [OrderSide::Buy, OrderSide::Sell] {
+                let live = state.live.get(&(inst_id.clone(), side));
+                match (live, target.side(side)) {
+                    (Some(live), None) => plan.cancel.push(cancel_request(live)),
+                    (Some(live), Some(level)) if live.level != *level => {
+                        plan.amend
+                            .push((amend_request(live, level), side, level.clone()));
+                    }
+                    (None, Some(level)) => {
+                        state.next_id += 1;
+                        let cl_ord_id = format!("{}{}", self.id_prefix, state.next_id);
+                        plan.place.push((
+                            OrderRequest {
+                                inst_id: inst_id.clone(),
+                                td_mode: self.td_mode,
+                                cl_ord_id: Some(cl_ord_id),
+                                side,
+                                ord_type: self.ord_type,
+                                sz: level.sz.clone(),
+                                px: Some(level.px.clone()),
+                                ..Default::default()
+                            },
+                            level.clone(),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        plan
+    }
+
+    async fn execute(&self, state: &mut State, plan: QuotePlan) -> OkxResult<QuoteReport> {
+        let mut report = QuoteReport::default();
+
+        let unknown = std::mem::take(&mut state.unknown);
+        for chunk in unknown.chunks(MAX_BATCH) {
+            self.pace(state).await;
+            match self.api.cancel_orders(chunk.to_vec()).await {
+                Ok(results) => {
+                    // Rejections mean the order was never placed or is gone.
+                    report.cancelled += results.iter().filter(|r| r.s_code == "0").count();
+                }
+                Err(e) => {
+                    state.unknown.extend_from_slice(chunk);
+                    return Err(e);
+                }
+            }
+        }
+
+        for chunk in plan.cancel.chunks(MAX_BATCH) {
+            self.pace(state).await;
+            let results = self.api.cancel_orders(chunk.to_vec()).await?;
+            for (req, result) in chunk.iter().zip(results) {
+                if result.s_code == "0" {
+                    state
+                        .live
+                        .retain(|_, q| Some(&q.cl_ord_id) != req.cl_ord_id.as_ref());
+                    report.cancelled += 1;
+                } else {
+                    warn!("Quote cancel rejected: {} {}", result.s_code, result.s_msg);
+                    report.rejected += 1;
+                }
+            }
+        }
+
+        for chunk in plan.amend.chunks(MAX_BATCH) {
+            self.pace(state).await;
+            let reqs = chunk.iter().map(|(req, _, _)| req.clone()).collect();
+            let results = match self.api.amend_orders(reqs).await {
+                Ok(results) => results,
+                Err(e) => {
+                    for (req, side, _) in chunk {
+                        if let Some(quote) = state.live.remove(&(req.inst_id.clone(), *side)) {
+                            state.unknown.push(cancel_request(&quote));
+                        }
+                    }
+                    return Err(e);
+                }
+            };
+            for ((req, side, level), result) in chunk.iter().zip(results) {
+                let key = (req.inst_id.clone(), *side);
+                if result.s_code == "0" {
+                    if let Some(quote) = state.live.get_mut(&key) {
+                        quote.level = level.clone();
+                    }
+                    report.amended += 1;
+                } else {
+                    // Sent with cxlOnFail, so the old order is gone.
+                    warn!("Quote amend rejected: {} {}", result.s_code, result.s_msg);
+                    state.live.remove(&key);
+                    report.rejected += 1;
+                }
+            }
+        }
+
+        for chunk in plan.place.chunks(MAX_BATCH) {
+            self.pace(state).await;
+            let reqs = chunk.iter().map(|(req, _)| req.clone()).collect();
+            let results = match self.api.place_orders(reqs).await {
+                Ok(results) => results,
+                Err(e) => {
+                    state
+                        .unknown
+                        .extend(chunk.iter().map(|(req, _)| CancelOrderRequest {
+                            inst_id: req.inst_id.clone(),
+                            ord_id: None,
+                            cl_ord_id: req.cl_ord_id.clone(),
+                        }));
+                    return Err(e);
+                }
+            };
+            for ((req, level), result) in chunk.iter().zip(results) {
+                if result.s_code == "0" {
+                    state.live.insert(
+                        (req.inst_id.clone(), req.side),
+                        LiveQuote {
+                            inst_id: req.inst_id.clone(),
+                            side: req.side,
+                            cl_ord_id: req.cl_ord_id.clone().unwrap_or_default(),
+                            level: level.clone(),
+                        },
+                    );
+                    report.placed += 1;
+                } else {
+                    warn!("Quote rejected: {} {}", result.s_code, result.s_msg);
+                    report.rejected += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Wait until `batch_interval` has passed since the previous batch.
+    async fn pace(&self, state: &mut State) {
+        if let Some(last) = state.last_batch {
+            let next = last + self.batch_interval;
+            tokio::time::sleep_until(next.into()).await;
+        }
+        state.last_batch = Some(Instant::now());
+    }
+}
+
+fn cancel_request(quote: &LiveQuote) -> CancelOrderRequest {
+    CancelOrderRequest {
+        inst_id: quote.inst_id.clone(),
+        ord_id: None,
+        cl_ord_id: Some(quote.cl_ord_id.clone()),
+    }
+}
+
+fn amend_request(quote: &LiveQuote, level: &QuoteLevel) -> AmendOrderRequest {
+    AmendOrderRequest {
+        inst_id: quote.inst_id.clone(),
+        cl_ord_id: Some(quote.cl_ord_id.clone()),
+        cxl_on_fail: Some(true),
+        new_px: (quote.level.px != level.px).then(|| level.px.clone()),
+        new_sz: (quote.level.sz != level.sz).then(|| level.sz.clone()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::types::WsConfig;
+
+    fn live(inst_id: &str, side: OrderSide, px: &str) -> LiveQuote {
+        LiveQuote {
+            inst_id: inst_id.to_string(),
+            side,
+            cl_ord_id: format!("{inst_id}-{side:?}"),
+            level: QuoteLevel::new(px, "1"),
+        }
+    }
+
+    #[tokio::test]
+    async fn plan_sends_only_the_difference() {
+        let engine = QuoteEngine::new(WsApiClient::new(WsConfig::default()), TradeMode::Cross);
+        let mut state = engine.state.lock().await;
+        for quote in [
+            live("BTC-USDT-SWAP", OrderSide::Buy, "100"),
+            live("BTC-USDT-SWAP", OrderSide::Sell, "101"),
+            live("ETH-USDT-SWAP", OrderSide::Buy, "10"),
+        ] {
+            state
+                .live
+                .insert((quote.inst_id.clone(), quote.side), quote);
+        }
+
+        let plan = engine.plan(
+            &mut state,
+            &[
+                (
+                    "BTC-USDT-SWAP".to_string(),
+                    TargetQuote::two_sided(
+                        QuoteLevel::new("100", "1"),
+                        QuoteLevel::new("102", "1"),
+                    ),
+                ),
+                ("ETH-USDT-SWAP".to_string(), TargetQuote::default()),
+                (
+                    "SOL-USDT-SWAP".to_string(),
+                    TargetQuote {
+                        bid: Some(QuoteLevel::new("5", "2")),
+                        ask: None,
+                    },
+                ),
+            ],
+        );
+
+        assert_eq!(plan.cancel.len(), 1);
+        assert_eq!(plan.cancel[0].inst_id, "ETH-USDT-SWAP");
+
+        assert_eq!(plan.amend.len(), 1);
+        let (amend, side, _) = &plan.amend[0];
+        assert_eq!(*side, OrderSide::Sell);
+        assert_eq!(amend.cl_ord_id.as_deref(), Some("BTC-USDT-SWAP-Sell"));
+        assert_eq!(amend.new_px.as_deref(), Some("102"));
+        assert_eq!(amend.new_sz, None);
+
+        assert_eq!(plan.place.len(), 1);
+        let (place, _) = &plan.place[0];
+        assert_eq!(place.inst_id, "SOL-USDT-SWAP");
+        assert_eq!(place.side, OrderSide::Buy);
+        assert_eq!(place.ord_type, OrderType::PostOnly);
+        assert!(place.cl_ord_id.as_ref().unwrap().starts_with("mmq"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Startup reconciliation of expected state against the exchange.
+//!
+//! After a restart or a lost connection, [`reconcile`] compares the orders
+//! and positions a strategy believes it has with what OKX reports, so
+//! leftovers can be cleaned up before trading resumes.
+
+use std::collections::HashMap;
+
+use crate::error::OkxResult;
+use crate::orders::OrderTracker;
+use crate::rest::RestClient;
+use crate::types::request::account::GetPositionsRequest;
+use crate::types::response::account::Position;
+use crate::types::response::trade::OrderDetails;
+
+/// Positions closer than this to the expected size count as matching.
+const POSITION_TOLERANCE: f64 = 1e-9;
+
+/// An order the strategy expects to be open. Matched by order ID, or by
+/// client order ID when the order ID is empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedOrder {
+    pub inst_id: String,
+    pub ord_id: String,
+    pub cl_ord_id: String,
+}
+
+impl ExpectedOrder {
+    fn matches(&self, order: &OrderDetails) -> bool {
+        if !self.ord_id.is_empty() {
+            self.ord_id == order.ord_id
+        } else {
+            !self.cl_ord_id.is_empty() && self.cl_ord_id == order.cl_ord_id
+        }
+    }
+}
+
+/// Orders and positions the strategy expects to have.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpectedState {
+    pub orders: Vec<ExpectedOrder>,
+    /// Expected net position per instrument, negative for shorts.
+    /// Instruments not listed are expected to be flat.
+    pub positions: HashMap<String, f64>,
+}
+
+impl ExpectedState {
+    /// Expect the open orders of a tracker and no positions.
+    pub fn from_tracker(tracker: &OrderTracker) -> Self {
+        Self {
+            orders: tracker
+                .open_orders()
+                .into_iter()
+                .map(|o| ExpectedOrder {
+                    inst_id: o.inst_id,
+                    ord_id: o.ord_id,
+                    cl_ord_id: o.cl_ord_id,
+                })
+                .collect(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Expect a net position on an instrument.
+    pub fn position(mut self, inst_id: &str, pos: f64) -> Self {
+        self.positions.insert(inst_id.to_string(), pos);
+        self
+    }
+}
+
+/// Settings of [`reconcile`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileOptions {
+    /// Cancel orphan orders.
+    pub cancel_orphans: bool,
+}
+
+/// A position whose size differs from the expected one.
+#[derive(Debug, Clone)]
+pub struct UnexpectedPosition {
+    pub inst_id: String,
+    /// Net position on the exchange, negative for shorts.
+    pub actual: f64,
+    /// Zero if the instrument was expected to be flat.
+    pub expected: f64,
+    /// Exchange positions of the instrument; empty if it is flat.
+    pub positions: Vec<Position>,
+}
+
+/// Differences between expected and exchange state.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport {
+    /// Open on the exchange but not expected.
+    pub orphan_orders: Vec<OrderDetails>,
+    /// Expected but not open on the exchange, e.g. filled or cancelled
+    /// while the strategy was down.
+    pub missing_orders: Vec<ExpectedOrder>,
+    pub unexpected_positions: Vec<UnexpectedPosition>,
+    /// Orphans cancelled with [`ReconcileOptions::cancel_orphans`].
+    pub cancelled: Vec<OrderDetails>,
+    /// Orphans that could not be cancelled, with the reason.
+    pub cancel_failures: Vec<(OrderDetails, String)>,
+}
+
+impl ReconcileReport {
+    /// Whether the exchange matches the expected state.
+    pub fn is_consistent(&self) -> bool {
+        self.orphan_orders.is_empty()
+            && self.missing_orders.is_empty()
+            && self.unexpected_positions.is_empty()
+    }
+}
+
+/// Compare expected orders and positions with the pending orders and
+/// positions OKX reports, optionally cancelling orders nobody expects.
+pub async fn reconcile(
+    rest: &RestClient,
+    expected: &ExpectedState,
+    options: &ReconcileOptions,
+) -> OkxResult<ReconcileReport> {
+    let pending = rest.all_pending_orders().await?;
+    let positions = rest.get_positions(&GetPositionsRequest::default()).await?;
+
+    let (known, orphan_orders): (Vec<_>, Vec<_>) = pending
+        .into_iter()
+        .partition(|o| expected.orders.iter().any(|e| e.matches(o)));
+    let missing_orders = expected
+        .orders
+        .iter()
+        .filter(|e| !known.iter().any(|o| e.matches(o)))
+        .cloned()
+        .collect();
+
+    let mut report = ReconcileReport {
+        orphan_orders,
+        missing_orders,
+        unexpected_positions: unexpected_positions(positions, &expected.positions),
+        ..Default::default()
+    };
+
+    if options.cancel_orphans && !report.orphan_orders.is_empty() {
+        let (cancelled, failed) = rest.cancel_orders(report.orphan_orders.clone()).await;
+        report.cancelled = cancelled;
+        report.cancel_failures = failed;
+    }
+    Ok(report)
+}
+
+fn unexpected_positions(
+    positions: Vec<Position>,
+    expected: &HashMap<String, f64>,
+) -> Vec<UnexpectedPosition> {
+    let mut by_inst: HashMap<String, Vec<Position>> = HashMap::new();
+    for position in positions {
+        by_inst
+            .entry(position.inst_id.clone())
+            .or_default()
+            .push(position);
+    }
+    for inst_id in expected.keys() {
+        by_inst.entry(inst_id.clone()).or_default();
+    }
+
+    let mut unexpected: Vec<_> = by_inst
+        .into_iter()
+        .filter_map(|(inst_id, positions)| {
+            let actual = positions.iter().map(signed_pos).sum::<f64>();
+            let expected = expected.get(&inst_id).copied().unwrap_or(0.0);
+            ((actual - expected).abs() > POSITION_TOLERANCE).then_some(UnexpectedPosition {
+                inst_id,
+                actual,
+                expected,
+                positions: positions
+                    .into_iter()
+                    .filter(|p| signed_pos(p) != 0.0)
+                    .collect(),
+            })
+        })
+        .collect();
+    unexpected.sort_by(|a, b| a.inst_id.cmp(&b.inst_id));
+    unexpected
+}
+
+/// Position size, negative for shorts. Net-mode positions already carry
+/// their sign.
+fn signed_pos(position: &Position) -> f64 {
+    let pos = position.pos.parse::<f64>().unwrap_or(0.0);
+    match position.pos_side.as_str() {
+        "short" => -pos.abs(),
+        "long" => pos.abs(),
+        _ => pos,
+    }
+}
@@ -1,7 +1,11 @@
+use futures::stream::BoxStream;
+
 use crate::error::OkxResult;
+use crate::rest::archive::ArchiveOptions;
 use crate::rest::RestClient;
 use crate::types::request::account::*;
 use crate::types::response::account::*;
+use crate::types::shared::{ArchiveApplyResult, ArchiveFile};
 
 impl RestClient {
 
@@ -45,23 +49,48 @@ impl RestClient {
 
     /// Get bills detail (last 7 days).
     /// GET /api/v5/account/bills
-    pub async fn get_bills(
-        &self,
-        params: &GetBillsRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    pub async fn get_bills(&self, params: &GetBillsRequest) -> OkxResult<Vec<Bill>> {
         self.get_signed("/api/v5/account/bills", Some(params)).await
     }
 
     /// Get bills archive (last 3 months).
     /// GET /api/v5/account/bills-archive
-    pub async fn get_bills_archive(
-        &self,
-        params: &GetBillsRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    pub async fn get_bills_archive(&self, params: &GetBillsRequest) -> OkxResult<Vec<Bill>> {
         self.get_signed("/api/v5/account/bills-archive", Some(params))
             .await
     }
 
+    /// Apply for the bills history archive of a quarter (since 2021).
+    /// POST /api/v5/account/bills-history-archive
+    pub async fn apply_bills_history_archive(
+        &self,
+        params: &BillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveApplyResult>> {
+        self.post_signed("/api/v5/account/bills-history-archive", params)
+            .await
+    }
+
+    /// Get the download link of a previously applied bills history archive.
+    /// GET /api/v5/account/bills-history-archive
+    pub async fn get_bills_history_archive(
+        &self,
+        params: &BillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveFile>> {
+        self.get_signed("/api/v5/account/bills-history-archive", Some(params))
+            .await
+    }
+
+    /// Apply for the bills history archive, wait until it is ready, download
+    /// it, and stream the parsed bills.
+    pub async fn download_bills_history_archive(
+        &self,
+        params: &BillsArchiveRequest,
+        options: &ArchiveOptions,
+    ) -> OkxResult<BoxStream<'static, OkxResult<Bill>>> {
+        self.fetch_archive("/api/v5/account/bills-history-archive", params, options)
+            .await
+    }
+
     /// Get account configuration.
     /// GET /api/v5/account/config
     pub async fn get_account_config(&self) -> OkxResult<Vec<AccountConfig>> {
@@ -193,6 +222,13 @@ impl RestClient {
             .await
     }
 
+    /// Reset MMP status to unfreeze an instrument family after MMP has been triggered.
+    /// The current frozen state is reported by [`get_mmp_config`](Self::get_mmp_config).
+    /// POST /api/v5/account/mmp-reset
+    pub async fn reset_mmp(&self, params: &MmpResetRequest) -> OkxResult<Vec<MmpResetResult>> {
+        self.post_signed("/api/v5/account/mmp-reset", params).await
+    }
+
     /// Set account level.
     /// POST /api/v5/account/set-account-level
     pub async fn set_account_level(
@@ -213,28 +249,84 @@ impl RestClient {
             .await
     }
 
-    /// Borrow or repay in quick margin mode.
+    /// Borrow or repay in multi-currency margin or portfolio margin mode.
     /// POST /api/v5/account/borrow-repay
     pub async fn borrow_repay(
         &self,
         params: &BorrowRepayRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<BorrowRepayResult>> {
         self.post_signed("/api/v5/account/borrow-repay", params)
             .await
     }
 
-    /// Get borrow and repay history in quick margin mode.
+    /// Get borrow and repay history in multi-currency margin or portfolio margin mode.
     /// GET /api/v5/account/borrow-repay-history
     pub async fn get_borrow_repay_history(
         &self,
         params: &GetBorrowRepayHistoryRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<BorrowRepayRecord>> {
         self.get_signed("/api/v5/account/borrow-repay-history", Some(params))
             .await
     }
 
-    /// Get greeks.
+    /// Borrow or repay in quick margin mode.
+    /// POST /api/v5/account/quick-margin-borrow-repay
+    pub async fn quick_margin_borrow_repay(
+        &self,
+        params: &QuickMarginBorrowRepayRequest,
+    ) -> OkxResult<Vec<QuickMarginBorrowRepayResult>> {
+        self.post_signed("/api/v5/account/quick-margin-borrow-repay", params)
+            .await
+    }
+
+    /// Get borrow and repay history in quick margin mode for the last 3 months.
+    /// GET /api/v5/account/quick-margin-borrow-repay-history
+    pub async fn get_quick_margin_borrow_repay_history(
+        &self,
+        params: &GetQuickMarginBorrowRepayHistoryRequest,
+    ) -> OkxResult<Vec<QuickMarginBorrowRepayRecord>> {
+        self.get_signed(
+            "/api/v5/account/quick-margin-borrow-repay-history",
+            Some(params),
+        )
+        .await
+    }
+
+    /// Get borrow interest rates and limits.
+    /// GET /api/v5/account/interest-limits
+    pub async fn get_interest_limits(
+        &self,
+        params: &GetInterestLimitsRequest,
+    ) -> OkxResult<Vec<InterestLimits>> {
+        self.get_signed("/api/v5/account/interest-limits", Some(params))
+            .await
+    }
+
+    /// Get the greeks of the account's assets, in both Black-Scholes and
+    /// coin-denominated (PA) terms.
+    /// GET /api/v5/account/greeks
+    pub async fn get_account_greeks(
+        &self,
+        params: &GetGreeksRequest,
+    ) -> OkxResult<Vec<AccountGreeks>> {
+        self.get_signed("/api/v5/account/greeks", Some(params))
+            .await
+    }
+
+    /// Calculate the margin requirement of a portfolio of real and virtual
+    /// positions in portfolio margin mode.
+    /// POST /api/v5/account/simulated_margin
+    pub async fn simulated_margin(
+        &self,
+        params: &SimulatedMarginRequest,
+    ) -> OkxResult<Vec<SimulatedMargin>> {
+        self.post_signed("/api/v5/account/simulated_margin", params)
+            .await
+    }
+
+    /// Get greeks as raw JSON.
     /// GET /api/v5/account/greeks
+    #[deprecated(note = "use `get_account_greeks`, which returns typed greeks")]
     pub async fn get_greeks(
         &self,
         params: &GetGreeksRequest,
@@ -243,6 +335,16 @@ impl RestClient {
             .await
     }
 
+    /// Get the position tiers applied to the account in portfolio margin mode.
+    /// GET /api/v5/account/position-tiers
+    pub async fn get_account_position_tiers(
+        &self,
+        params: &GetAccountPositionTiersRequest,
+    ) -> OkxResult<Vec<AccountPositionTier>> {
+        self.get_signed("/api/v5/account/position-tiers", Some(params))
+            .await
+    }
+
     /// Set whether to automatically borrow when transferring in.
     /// POST /api/v5/account/set-auto-loan
     pub async fn set_auto_loan(
@@ -252,4 +354,21 @@ impl RestClient {
         self.post_signed("/api/v5/account/set-auto-loan", params)
             .await
     }
+
+    /// Set the currency spot trading fees are charged in.
+    /// POST /api/v5/account/set-fee-type
+    pub async fn set_fee_type(&self, params: &SetFeeTypeRequest) -> OkxResult<Vec<FeeTypeResult>> {
+        self.post_signed("/api/v5/account/set-fee-type", params)
+            .await
+    }
+
+    /// Turn auto earn on or off for a currency.
+    /// POST /api/v5/account/set-auto-earn
+    pub async fn set_auto_earn(
+        &self,
+        params: &SetAutoEarnRequest,
+    ) -> OkxResult<Vec<AutoEarnResult>> {
+        self.post_signed("/api/v5/account/set-auto-earn", params)
+            .await
+    }
 }
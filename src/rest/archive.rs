@@ -0,0 +1,188 @@
+use std::io::{Cursor, Read};
+use std::time::Duration;
+
+use futures::stream::{self, BoxStream};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::time::Instant;
+use crate::types::enums::ArchiveState;
+use crate::types::shared::{ArchiveApplyResult, ArchiveFile};
+
+/// Polling behaviour for the apply → poll → download archive workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveOptions {
+    /// Delay between status checks while the file is being prepared.
+    pub poll_interval: Duration,
+    /// Give up if the file is not ready within this duration.
+    pub timeout: Duration,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl RestClient {
+    /// Apply for an archive, wait until it is ready, download it, and stream
+    /// the parsed records.
+    ///
+    /// Archive endpoints use the same path and parameters for applying (POST)
+    /// and for querying the download link (GET).
+    pub(crate) async fn fetch_archive<T, P>(
+        &self,
+        endpoint: &str,
+        params: &P,
+        options: &ArchiveOptions,
+    ) -> OkxResult<BoxStream<'static, OkxResult<T>>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        P: Serialize,
+    {
+        let applied: Vec<ArchiveApplyResult> = self.post_signed(endpoint, params).await?;
+        if applied.first().is_some_and(|r| r.result == "false") {
+            return Err(OkxError::Archive(format!(
+                "application rejected for {endpoint}"
+            )));
+        }
+
+        let started = Instant::now();
+        let href = loop {
+            let files: Vec<ArchiveFile> = self.get_signed(endpoint, Some(params)).await?;
+            match files.first() {
+                Some(file) if file.state == ArchiveState::Finished => {
+                    break file.file_href.clone();
+                }
+                Some(file) if file.state == ArchiveState::Failed => {
+                    return Err(OkxError::Archive(format!(
+                        "archive preparation failed for {endpoint}"
+                    )));
+                }
+                _ => {}
+            }
+            if started.elapsed() >= options.timeout {
+                return Err(OkxError::Archive(format!(
+                    "archive not ready after {:?}",
+                    options.timeout
+                )));
+            }
+            debug!("Archive {endpoint} not ready, polling again");
+            crate::time::sleep(options.poll_interval).await;
+        };
+
+        let bytes = self
+            .http
+            .get(&href)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        parse_archive(bytes.to_vec())
+    }
+}
+
+/// Parse a downloaded archive into a stream of records.
+///
+/// OKX serves archives as a zip containing a single CSV file whose header row
+/// uses the same camelCase field names as the REST API. A bare CSV body is
+/// also accepted.
+pub(crate) fn parse_archive<T>(bytes: Vec<u8>) -> OkxResult<BoxStream<'static, OkxResult<T>>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let csv_bytes = if bytes.starts_with(b"PK\x03\x04") {
+        extract_first_csv(bytes)?
+    } else {
+        bytes
+    };
+
+    let records = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(Cursor::new(csv_bytes))
+        .into_deserialize::<T>()
+        .map(|r| r.map_err(|e| OkxError::Archive(format!("invalid CSV record: {e}"))));
+
+    Ok(Box::pin(stream::iter(records)))
+}
+
+/// Extract the first CSV entry of a zip archive.
+fn extract_first_csv(bytes: Vec<u8>) -> OkxResult<Vec<u8>> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| OkxError::Archive(format!("invalid zip file: {e}")))?;
+
+    let index = (0..zip.len())
+        .find(|&i| {
+            zip.name_for_index(i)
+                .is_some_and(|name| name.to_ascii_lowercase().ends_with(".csv"))
+        })
+        .unwrap_or(0);
+
+    let mut entry = zip
+        .by_index(index)
+        .map_err(|e| OkxError::Archive(format!("invalid zip entry: {e}")))?;
+    let mut out = Vec::with_capacity(entry.size() as usize);
+    entry
+        .read_to_end(&mut out)
+        .map_err(|e| OkxError::Archive(format!("failed to read zip entry: {e}")))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use futures::StreamExt;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Row {
+        inst_id: String,
+        fill_sz: String,
+    }
+
+    const CSV: &str = "instId,fillSz\nBTC-USDT,0.1\nETH-USDT,2\n";
+
+    #[tokio::test]
+    async fn parses_plain_csv() {
+        let rows: Vec<Row> = parse_archive(CSV.as_bytes().to_vec())
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect()
+            .await;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].inst_id, "ETH-USDT");
+        assert_eq!(rows[1].fill_sz, "2");
+    }
+
+    #[tokio::test]
+    async fn parses_zipped_csv() {
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buf);
+            zip.start_file("fills.csv", zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(CSV.as_bytes()).unwrap();
+            zip.finish().unwrap();
+        }
+
+        let rows: Vec<Row> = parse_archive(buf.into_inner())
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect()
+            .await;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].inst_id, "BTC-USDT");
+    }
+}
@@ -1,5 +1,7 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::request::block_trading::*;
+use crate::types::response::block_trading::*;
 
 impl RestClient {
 
@@ -107,4 +109,24 @@ impl RestClient {
     ) -> OkxResult<Vec<serde_json::Value>> {
         self.get("/api/v5/rfq/public-trades", Some(params)).await
     }
+
+    /// Move positions between the master account and its sub-accounts.
+    /// POST /api/v5/rfq/move-positions
+    pub async fn move_positions(
+        &self,
+        params: &MovePositionsRequest,
+    ) -> OkxResult<Vec<MovePositionsResult>> {
+        self.post_signed("/api/v5/rfq/move-positions", params)
+            .await
+    }
+
+    /// Get move positions history for the last 3 days.
+    /// GET /api/v5/rfq/move-positions-history
+    pub async fn get_move_positions_history(
+        &self,
+        params: &GetMovePositionsHistoryRequest,
+    ) -> OkxResult<Vec<MovePositionsRecord>> {
+        self.get_signed("/api/v5/rfq/move-positions-history", Some(params))
+            .await
+    }
 }
@@ -0,0 +1,210 @@
+use crate::error::OkxResult;
+use crate::types::request::account::*;
+use crate::types::response::account::*;
+use crate::types::shared::{ArchiveApplyResult, ArchiveFile};
+
+blocking_methods! {
+    /// Get account balance.
+    /// GET /api/v5/account/balance
+    fn get_balance(&self, params: &GetBalanceRequest) -> OkxResult<Vec<AccountBalance>>;
+
+    /// Get positions. When the account is in `net` mode, net positions will be
+    /// displayed; when in `long/short` mode, long or short positions will be displayed.
+    /// GET /api/v5/account/positions
+    fn get_positions(&self, params: &GetPositionsRequest) -> OkxResult<Vec<Position>>;
+
+    /// Get position history for the last 3 months.
+    /// GET /api/v5/account/positions-history
+    fn get_positions_history(
+        &self,
+        params: &GetPositionsHistoryRequest,
+    ) -> OkxResult<Vec<Position>>;
+
+    /// Get account position risk data.
+    /// GET /api/v5/account/account-position-risk
+    fn get_account_position_risk(
+        &self,
+        params: &GetAccountPositionRiskRequest,
+    ) -> OkxResult<Vec<AccountRiskState>>;
+
+    /// Get bills detail (last 7 days).
+    /// GET /api/v5/account/bills
+    fn get_bills(&self, params: &GetBillsRequest) -> OkxResult<Vec<Bill>>;
+
+    /// Get bills archive (last 3 months).
+    /// GET /api/v5/account/bills-archive
+    fn get_bills_archive(&self, params: &GetBillsRequest) -> OkxResult<Vec<Bill>>;
+
+    /// Apply for the bills history archive of a quarter (since 2021).
+    /// POST /api/v5/account/bills-history-archive
+    fn apply_bills_history_archive(
+        &self,
+        params: &BillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveApplyResult>>;
+
+    /// Get the download link of a previously applied bills history archive.
+    /// GET /api/v5/account/bills-history-archive
+    fn get_bills_history_archive(
+        &self,
+        params: &BillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveFile>>;
+
+    /// Get account configuration.
+    /// GET /api/v5/account/config
+    fn get_account_config(&self) -> OkxResult<Vec<AccountConfig>>;
+
+    /// Set position mode: `long_short_mode` or `net_mode`.
+    /// POST /api/v5/account/set-position-mode
+    fn set_position_mode(&self, params: &SetPositionModeRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Set leverage for an instrument or currency in a given margin mode.
+    /// POST /api/v5/account/set-leverage
+    fn set_leverage(&self, params: &SetLeverageRequest) -> OkxResult<Vec<LeverageInfo>>;
+
+    /// Get leverage of an instrument.
+    /// GET /api/v5/account/leverage-info
+    fn get_leverage(&self, params: &GetLeverageRequest) -> OkxResult<Vec<LeverageInfo>>;
+
+    /// Get maximum buy/sell amount.
+    /// GET /api/v5/account/max-size
+    fn get_max_buy_sell_amount(
+        &self,
+        params: &GetMaxBuySellAmountRequest,
+    ) -> OkxResult<Vec<MaxBuySellAmount>>;
+
+    /// Get maximum available tradeable size.
+    /// GET /api/v5/account/max-avail-size
+    fn get_max_avail_size(
+        &self,
+        params: &GetMaxAvailSizeRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get maximum loan amount.
+    /// GET /api/v5/account/max-loan
+    fn get_max_loan(&self, params: &GetMaxLoanRequest) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get trading fee rates.
+    /// GET /api/v5/account/trade-fee
+    fn get_fee_rates(&self, params: &GetFeeRatesRequest) -> OkxResult<Vec<FeeRate>>;
+
+    /// Get interest accrued data.
+    /// GET /api/v5/account/interest-accrued
+    fn get_interest_accrued(
+        &self,
+        params: &GetInterestAccruedRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get interest rate.
+    /// GET /api/v5/account/interest-rate
+    fn get_interest_rate(&self) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get maximum withdrawals.
+    /// GET /api/v5/account/max-withdrawal
+    fn get_max_withdrawals(
+        &self,
+        params: &GetMaxWithdrawalsRequest,
+    ) -> OkxResult<Vec<MaxWithdrawal>>;
+
+    /// Set Greeks display type.
+    /// POST /api/v5/account/set-greeks
+    fn set_greeks(&self, params: &SetGreeksRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Set isolated margin trading mode.
+    /// POST /api/v5/account/set-isolated-mode
+    fn set_isolated_mode(&self, params: &SetIsolatedModeRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Get account risk state.
+    /// GET /api/v5/account/risk-state
+    fn get_account_risk_state(&self) -> OkxResult<Vec<AccountRiskState>>;
+
+    /// Set MMP (Market Maker Protection) configuration.
+    /// POST /api/v5/account/mmp-config
+    fn set_mmp_config(&self, params: &SetMmpConfigRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Get MMP configuration.
+    /// GET /api/v5/account/mmp-config
+    fn get_mmp_config(&self, params: &GetMmpConfigRequest) -> OkxResult<Vec<MmpConfig>>;
+
+    /// Reset MMP status to unfreeze an instrument family after MMP has been triggered.
+    /// The current frozen state is reported by [`get_mmp_config`](Self::get_mmp_config).
+    /// POST /api/v5/account/mmp-reset
+    fn reset_mmp(&self, params: &MmpResetRequest) -> OkxResult<Vec<MmpResetResult>>;
+
+    /// Set account level.
+    /// POST /api/v5/account/set-account-level
+    fn set_account_level(&self, params: &SetAccountLevelRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Increase or decrease margin for an isolated position.
+    /// POST /api/v5/account/position/margin-balance
+    fn change_position_margin(
+        &self,
+        params: &ChangePositionMarginRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Borrow or repay in multi-currency margin or portfolio margin mode.
+    /// POST /api/v5/account/borrow-repay
+    fn borrow_repay(&self, params: &BorrowRepayRequest) -> OkxResult<Vec<BorrowRepayResult>>;
+
+    /// Get borrow and repay history in multi-currency margin or portfolio margin mode.
+    /// GET /api/v5/account/borrow-repay-history
+    fn get_borrow_repay_history(
+        &self,
+        params: &GetBorrowRepayHistoryRequest,
+    ) -> OkxResult<Vec<BorrowRepayRecord>>;
+
+    /// Borrow or repay in quick margin mode.
+    /// POST /api/v5/account/quick-margin-borrow-repay
+    fn quick_margin_borrow_repay(
+        &self,
+        params: &QuickMarginBorrowRepayRequest,
+    ) -> OkxResult<Vec<QuickMarginBorrowRepayResult>>;
+
+    /// Get borrow and repay history in quick margin mode for the last 3 months.
+    /// GET /api/v5/account/quick-margin-borrow-repay-history
+    fn get_quick_margin_borrow_repay_history(
+        &self,
+        params: &GetQuickMarginBorrowRepayHistoryRequest,
+    ) -> OkxResult<Vec<QuickMarginBorrowRepayRecord>>;
+
+    /// Get borrow interest rates and limits.
+    /// GET /api/v5/account/interest-limits
+    fn get_interest_limits(
+        &self,
+        params: &GetInterestLimitsRequest,
+    ) -> OkxResult<Vec<InterestLimits>>;
+
+    /// Get the greeks of the account's assets, in both Black-Scholes and
+    /// coin-denominated (PA) terms.
+    /// GET /api/v5/account/greeks
+    fn get_account_greeks(&self, params: &GetGreeksRequest) -> OkxResult<Vec<AccountGreeks>>;
+
+    /// Get greeks as raw JSON.
+    /// GET /api/v5/account/greeks
+    #[deprecated(note = "use `get_account_greeks`, which returns typed greeks")]
+    #[allow(deprecated)]
+    fn get_greeks(&self, params: &GetGreeksRequest) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Calculate the margin requirement of a portfolio of real and virtual
+    /// positions in portfolio margin mode.
+    /// POST /api/v5/account/simulated_margin
+    fn simulated_margin(&self, params: &SimulatedMarginRequest) -> OkxResult<Vec<SimulatedMargin>>;
+
+    /// Get the position tiers applied to the account in portfolio margin mode.
+    /// GET /api/v5/account/position-tiers
+    fn get_account_position_tiers(
+        &self,
+        params: &GetAccountPositionTiersRequest,
+    ) -> OkxResult<Vec<AccountPositionTier>>;
+
+    /// Set whether to automatically borrow when transferring in.
+    /// POST /api/v5/account/set-auto-loan
+    fn set_auto_loan(&self, params: &SetAutoLoanRequest) -> OkxResult<Vec<SetResult>>;
+
+    /// Set the currency spot trading fees are charged in.
+    /// POST /api/v5/account/set-fee-type
+    fn set_fee_type(&self, params: &SetFeeTypeRequest) -> OkxResult<Vec<FeeTypeResult>>;
+
+    /// Turn auto earn on or off for a currency.
+    /// POST /api/v5/account/set-auto-earn
+    fn set_auto_earn(&self, params: &SetAutoEarnRequest) -> OkxResult<Vec<AutoEarnResult>>;
+}
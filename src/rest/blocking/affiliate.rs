@@ -0,0 +1,10 @@
+use crate::error::OkxResult;
+
+blocking_methods! {
+    /// Get affiliate invitee detail.
+    /// GET /api/v5/affiliate/invitee/detail
+    fn get_affiliate_invitee_detail(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+}
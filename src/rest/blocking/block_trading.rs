@@ -0,0 +1,67 @@
+use crate::error::OkxResult;
+use crate::types::request::block_trading::*;
+use crate::types::response::block_trading::*;
+
+blocking_methods! {
+    /// Create an RFQ.
+    /// POST /api/v5/rfq/create-rfq
+    fn create_rfq(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel an RFQ.
+    /// POST /api/v5/rfq/cancel-rfq
+    fn cancel_rfq(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel multiple RFQs.
+    /// POST /api/v5/rfq/cancel-batch-rfqs
+    fn cancel_batch_rfqs(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel all RFQs.
+    /// POST /api/v5/rfq/cancel-all-rfqs
+    fn cancel_all_rfqs(&self) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Execute a quote.
+    /// POST /api/v5/rfq/execute-quote
+    fn execute_quote(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Create a quote.
+    /// POST /api/v5/rfq/create-quote
+    fn create_quote(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel a quote.
+    /// POST /api/v5/rfq/cancel-quote
+    fn cancel_quote(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel all quotes.
+    /// POST /api/v5/rfq/cancel-all-quotes
+    fn cancel_all_quotes(&self) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get RFQs.
+    /// GET /api/v5/rfq/rfqs
+    fn get_rfqs(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get quotes.
+    /// GET /api/v5/rfq/quotes
+    fn get_quotes(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get block trades.
+    /// GET /api/v5/rfq/trades
+    fn get_block_trades(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get public block trades.
+    /// GET /api/v5/rfq/public-trades
+    fn get_public_block_trades(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Move positions between the master account and its sub-accounts.
+    /// POST /api/v5/rfq/move-positions
+    fn move_positions(&self, params: &MovePositionsRequest) -> OkxResult<Vec<MovePositionsResult>>;
+
+    /// Get move positions history for the last 3 days.
+    /// GET /api/v5/rfq/move-positions-history
+    fn get_move_positions_history(
+        &self,
+        params: &GetMovePositionsHistoryRequest,
+    ) -> OkxResult<Vec<MovePositionsRecord>>;
+}
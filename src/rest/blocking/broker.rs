@@ -0,0 +1,114 @@
+use crate::error::OkxResult;
+use crate::types::request::broker::*;
+use crate::types::response::broker::*;
+
+blocking_methods! {
+    /// Get broker account info.
+    /// GET /api/v5/broker/nd/info
+    fn get_broker_info(&self) -> OkxResult<Vec<BrokerInfo>>;
+
+    /// Create a sub-account (broker).
+    /// POST /api/v5/broker/nd/create-subaccount
+    fn broker_create_sub_account(
+        &self,
+        params: &BrokerCreateSubAccountRequest,
+    ) -> OkxResult<Vec<BrokerSubAccount>>;
+
+    /// Delete a sub-account (broker).
+    /// POST /api/v5/broker/nd/delete-subaccount
+    fn broker_delete_sub_account(
+        &self,
+        params: &BrokerDeleteSubAccountRequest,
+    ) -> OkxResult<Vec<BrokerDeleteResult>>;
+
+    /// Get the sub-account list (broker).
+    /// GET /api/v5/broker/nd/subaccount-info
+    fn broker_get_sub_accounts(
+        &self,
+        params: &BrokerGetSubAccountsRequest,
+    ) -> OkxResult<Vec<BrokerSubAccountPage>>;
+
+    /// Create API key for a sub-account (broker).
+    /// POST /api/v5/broker/nd/subaccount/apikey
+    fn broker_create_sub_account_api_key(
+        &self,
+        params: &BrokerCreateApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>>;
+
+    /// Query the API keys of a sub-account (broker).
+    /// GET /api/v5/broker/nd/subaccount/apikey
+    fn broker_get_sub_account_api_keys(
+        &self,
+        params: &BrokerGetApiKeysRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>>;
+
+    /// Modify an API key of a sub-account (broker).
+    /// POST /api/v5/broker/nd/subaccount/modify-apikey
+    fn broker_modify_sub_account_api_key(
+        &self,
+        params: &BrokerModifyApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>>;
+
+    /// Delete an API key of a sub-account (broker).
+    /// POST /api/v5/broker/nd/subaccount/delete-apikey
+    fn broker_delete_sub_account_api_key(
+        &self,
+        params: &BrokerDeleteApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerDeleteResult>>;
+
+    /// Set the trading fee rate of a sub-account (broker).
+    /// POST /api/v5/broker/nd/set-subaccount-fee-rate
+    fn broker_set_sub_account_fee_rate(
+        &self,
+        params: &BrokerSetFeeRateRequest,
+    ) -> OkxResult<Vec<BrokerSetFeeRateResult>>;
+
+    /// Create a deposit address for a sub-account (broker).
+    /// POST /api/v5/asset/broker/nd/subaccount-deposit-address
+    fn broker_create_sub_account_deposit_address(
+        &self,
+        params: &BrokerCreateDepositAddressRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>>;
+
+    /// Modify the crediting account of a sub-account deposit address (broker).
+    /// POST /api/v5/asset/broker/nd/modify-subaccount-deposit-address
+    fn broker_modify_sub_account_deposit_address(
+        &self,
+        params: &BrokerModifyDepositAddressRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>>;
+
+    /// Get the deposit addresses of a sub-account (broker).
+    /// GET /api/v5/asset/broker/nd/subaccount-deposit-address
+    fn broker_get_sub_account_deposit_addresses(
+        &self,
+        params: &BrokerGetDepositAddressesRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>>;
+
+    /// Get sub-account deposit history (broker).
+    /// GET /api/v5/broker/nd/subaccount-deposit-history
+    fn broker_get_sub_account_deposit_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get rebate daily (broker).
+    /// GET /api/v5/broker/nd/rebate-daily
+    fn broker_get_rebate_daily(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Request generation of the rebate details file per order (broker).
+    /// POST /api/v5/broker/nd/rebate-per-orders
+    fn broker_apply_rebate_per_orders(
+        &self,
+        params: &BrokerRebatePerOrdersRequest,
+    ) -> OkxResult<Vec<BrokerRebateApplyResult>>;
+
+    /// Get the download link of the rebate details file per order (broker).
+    /// GET /api/v5/broker/nd/rebate-per-orders
+    fn broker_get_rebate_per_orders(
+        &self,
+        params: &BrokerRebatePerOrdersRequest,
+    ) -> OkxResult<Vec<BrokerRebateFile>>;
+}
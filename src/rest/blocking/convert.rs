@@ -0,0 +1,24 @@
+use crate::error::OkxResult;
+use crate::types::request::convert::*;
+use crate::types::response::convert::*;
+
+blocking_methods! {
+    /// Get convert currencies.
+    /// GET /api/v5/asset/convert/currencies
+    fn get_convert_currencies(&self) -> OkxResult<Vec<ConvertCurrency>>;
+
+    /// Estimate conversion quote.
+    /// POST /api/v5/asset/convert/estimate-quote
+    fn estimate_quote(&self, params: &EstimateQuoteRequest) -> OkxResult<Vec<ConvertQuote>>;
+
+    /// Execute a conversion trade.
+    /// POST /api/v5/asset/convert/trade
+    fn convert_trade(&self, params: &ConvertTradeRequest) -> OkxResult<Vec<ConvertTradeResult>>;
+
+    /// Get convert trade history.
+    /// GET /api/v5/asset/convert/history
+    fn get_convert_history(
+        &self,
+        params: &GetConvertHistoryRequest,
+    ) -> OkxResult<Vec<ConvertTradeResult>>;
+}
@@ -0,0 +1,52 @@
+use crate::error::OkxResult;
+
+blocking_methods! {
+    /// Get existing leading positions.
+    /// GET /api/v5/copytrading/current-subpositions
+    fn get_copy_trading_positions(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get leading position history.
+    /// GET /api/v5/copytrading/subpositions-history
+    fn get_copy_trading_positions_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Close a leading position.
+    /// POST /api/v5/copytrading/close-subposition
+    fn close_copy_trading_position(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get lead instruments.
+    /// GET /api/v5/copytrading/instruments
+    fn get_copy_trading_instruments(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Set lead instruments.
+    /// POST /api/v5/copytrading/set-instruments
+    fn set_copy_trading_instruments(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get profit sharing details.
+    /// GET /api/v5/copytrading/profit-sharing-details
+    fn get_copy_trading_profit_sharing(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get total profit sharing.
+    /// GET /api/v5/copytrading/total-profit-sharing
+    fn get_copy_trading_total_profit(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+}
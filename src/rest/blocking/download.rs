@@ -0,0 +1,19 @@
+use crate::error::OkxResult;
+use crate::rest::download::DownloadOptions;
+use crate::types::response::market::Trade;
+
+blocking_methods! {
+    /// Download all trades with a timestamp in `[start, end)` (Unix
+    /// milliseconds), oldest first.
+    ///
+    /// The first page is located by timestamp; later pages continue from
+    /// the oldest trade ID seen so trades sharing a millisecond are not
+    /// skipped. Trades repeated across pages are dropped.
+    fn download_trades(
+        &self,
+        inst_id: &str,
+        start: i64,
+        end: i64,
+        options: &DownloadOptions,
+    ) -> OkxResult<Vec<Trade>>;
+}
@@ -0,0 +1,51 @@
+use crate::error::OkxResult;
+
+blocking_methods! {
+    /// Get staking offers.
+    /// GET /api/v5/finance/staking-defi/offers
+    fn get_staking_offers(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Stake / purchase.
+    /// POST /api/v5/finance/staking-defi/purchase
+    fn stake_purchase(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Redeem staking.
+    /// POST /api/v5/finance/staking-defi/redeem
+    fn stake_redeem(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get staking orders.
+    /// GET /api/v5/finance/staking-defi/orders-active
+    fn get_staking_orders_active(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get staking order history.
+    /// GET /api/v5/finance/staking-defi/orders-history
+    fn get_staking_order_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get savings balance.
+    /// GET /api/v5/finance/savings/balance
+    fn get_savings_balance(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Savings purchase/redemption.
+    /// POST /api/v5/finance/savings/purchase-redempt
+    fn savings_purchase_redempt(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get lending rate summary.
+    /// GET /api/v5/finance/savings/lending-rate-summary
+    fn get_lending_rate_summary(&self) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get lending rate history.
+    /// GET /api/v5/finance/savings/lending-rate-history
+    fn get_lending_rate_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+}
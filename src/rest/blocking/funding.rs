@@ -0,0 +1,102 @@
+use crate::error::OkxResult;
+use crate::types::request::funding::*;
+use crate::types::response::funding::*;
+
+blocking_methods! {
+    /// Get list of currencies.
+    /// GET /api/v5/asset/currencies
+    fn get_currencies(&self, params: &GetCurrenciesRequest) -> OkxResult<Vec<Currency>>;
+
+    /// Get asset balances (funding account).
+    /// GET /api/v5/asset/balances
+    fn get_asset_balances(&self, params: &GetAssetBalancesRequest) -> OkxResult<Vec<AssetBalance>>;
+
+    /// Get the valuation of all assets across account types.
+    /// GET /api/v5/asset/asset-valuation
+    fn get_asset_valuation(
+        &self,
+        params: &GetAssetValuationRequest,
+    ) -> OkxResult<Vec<AssetValuation>>;
+
+    /// Get non-tradable assets in the funding account.
+    /// GET /api/v5/asset/non-tradable-assets
+    fn get_non_tradable_assets(
+        &self,
+        params: &GetNonTradableAssetsRequest,
+    ) -> OkxResult<Vec<NonTradableAsset>>;
+
+    /// Convert small balances to OKB.
+    /// POST /api/v5/asset/convert-dust-assets
+    fn convert_dust_assets(
+        &self,
+        params: &ConvertDustAssetsRequest,
+    ) -> OkxResult<Vec<DustConversion>>;
+
+    /// Submit a withdrawal request.
+    /// POST /api/v5/asset/withdrawal
+    fn withdraw(&self, params: &WithdrawRequest) -> OkxResult<Vec<WithdrawalResult>>;
+
+    /// Cancel a withdrawal that has not been sent to the chain yet.
+    /// POST /api/v5/asset/cancel-withdrawal
+    fn cancel_withdrawal(
+        &self,
+        params: &CancelWithdrawalRequest,
+    ) -> OkxResult<Vec<CancelWithdrawalResult>>;
+
+    /// Create a Lightning Network deposit invoice.
+    /// GET /api/v5/asset/deposit-lightning
+    fn get_lightning_deposit(
+        &self,
+        params: &LightningDepositRequest,
+    ) -> OkxResult<Vec<LightningDeposit>>;
+
+    /// Pay a Lightning Network invoice from the funding account.
+    /// POST /api/v5/asset/withdrawal-lightning
+    fn lightning_withdrawal(
+        &self,
+        params: &LightningWithdrawalRequest,
+    ) -> OkxResult<Vec<LightningWithdrawalResult>>;
+
+    /// Get the detailed status of a deposit or withdrawal.
+    /// GET /api/v5/asset/deposit-withdraw-status
+    fn get_deposit_withdraw_status(
+        &self,
+        params: &GetDepositWithdrawStatusRequest,
+    ) -> OkxResult<Vec<DepositWithdrawStatus>>;
+
+    /// Transfer funds between accounts.
+    /// POST /api/v5/asset/transfer
+    fn funds_transfer(&self, params: &FundsTransferRequest) -> OkxResult<Vec<TransferResult>>;
+
+    /// Get deposit history.
+    /// GET /api/v5/asset/deposit-history
+    fn get_deposit_history(
+        &self,
+        params: &GetDepositHistoryRequest,
+    ) -> OkxResult<Vec<DepositRecord>>;
+
+    /// Get withdrawal history.
+    /// GET /api/v5/asset/withdrawal-history
+    fn get_withdrawal_history(
+        &self,
+        params: &GetWithdrawalHistoryRequest,
+    ) -> OkxResult<Vec<WithdrawalRecord>>;
+
+    /// Get deposit addresses.
+    /// GET /api/v5/asset/deposit-address
+    fn get_deposit_address(
+        &self,
+        params: &GetDepositAddressRequest,
+    ) -> OkxResult<Vec<DepositAddress>>;
+
+    /// Get the state of a funds transfer.
+    /// GET /api/v5/asset/transfer-state
+    fn get_transfer_state(
+        &self,
+        params: &GetTransferStateRequest,
+    ) -> OkxResult<Vec<FundsTransferState>>;
+
+    /// Get funding account bills, newest first.
+    /// GET /api/v5/asset/bills
+    fn get_asset_bills(&self, params: &GetAssetBillsRequest) -> OkxResult<Vec<AssetBill>>;
+}
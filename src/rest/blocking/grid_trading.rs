@@ -0,0 +1,50 @@
+use crate::error::OkxResult;
+
+blocking_methods! {
+    /// Place a grid algo order (spot grid, contract grid, moon grid).
+    /// POST /api/v5/tradingBot/grid/order-algo
+    fn place_grid_algo_order(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Amend a grid algo order.
+    /// POST /api/v5/tradingBot/grid/amend-order-algo
+    fn amend_grid_algo_order(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Stop a grid algo order.
+    /// POST /api/v5/tradingBot/grid/stop-order-algo
+    fn stop_grid_algo_order(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get grid algo order list.
+    /// GET /api/v5/tradingBot/grid/orders-algo-pending
+    fn get_grid_algo_order_list(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get grid algo order history.
+    /// GET /api/v5/tradingBot/grid/orders-algo-history
+    fn get_grid_algo_order_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get grid algo order details.
+    /// GET /api/v5/tradingBot/grid/orders-algo-details
+    fn get_grid_algo_order_details(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get grid algo sub orders.
+    /// GET /api/v5/tradingBot/grid/sub-orders
+    fn get_grid_sub_orders(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get grid algo order positions.
+    /// GET /api/v5/tradingBot/grid/positions
+    fn get_grid_positions(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+}
@@ -0,0 +1,8 @@
+use crate::error::OkxResult;
+use crate::rest::kill_switch::{FlattenOptions, FlattenReport};
+
+blocking_methods! {
+    /// Kill switch: cancel all pending orders of every instrument type, then
+    /// close all open positions at market.
+    fn emergency_flatten(&self, options: &FlattenOptions) -> OkxResult<FlattenReport>;
+}
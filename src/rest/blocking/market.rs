@@ -0,0 +1,49 @@
+use crate::error::OkxResult;
+use crate::types::request::market::*;
+use crate::types::response::market::*;
+
+blocking_methods! {
+    /// Get tickers for all instruments of a given type.
+    /// GET /api/v5/market/tickers
+    fn get_tickers(&self, params: &GetTickersRequest) -> OkxResult<Vec<Ticker>>;
+
+    /// Get ticker for a single instrument.
+    /// GET /api/v5/market/ticker
+    fn get_ticker(&self, params: &GetTickerRequest) -> OkxResult<Vec<Ticker>>;
+
+    /// Get order book for an instrument.
+    /// GET /api/v5/market/books
+    fn get_order_book(&self, params: &GetOrderBookRequest) -> OkxResult<Vec<OrderBook>>;
+
+    /// Get candlestick charts (most recent 1,440 data entries).
+    /// GET /api/v5/market/candles
+    fn get_candles(&self, params: &GetCandlesRequest) -> OkxResult<Vec<Candle>>;
+
+    /// Get historic candlestick charts (older data).
+    /// GET /api/v5/market/history-candles
+    fn get_history_candles(&self, params: &GetCandlesRequest) -> OkxResult<Vec<Candle>>;
+
+    /// Get recent trades.
+    /// GET /api/v5/market/trades
+    fn get_trades(&self, params: &GetTradesRequest) -> OkxResult<Vec<Trade>>;
+
+    /// Get historic trades (last 3 months).
+    /// GET /api/v5/market/history-trades
+    fn get_history_trades(&self, params: &GetHistoricTradesRequest) -> OkxResult<Vec<Trade>>;
+
+    /// Get 24-hour total trading volume on the platform.
+    /// GET /api/v5/market/platform-24-volume
+    fn get_24h_total_volume(&self) -> OkxResult<Vec<PlatformVolume>>;
+
+    /// Get index tickers.
+    /// GET /api/v5/market/index-tickers
+    fn get_index_tickers(&self, params: &GetIndexTickersRequest) -> OkxResult<Vec<IndexTicker>>;
+
+    /// Get index candlestick charts.
+    /// GET /api/v5/market/index-candles
+    fn get_index_candles(&self, params: &GetIndexCandlesRequest) -> OkxResult<Vec<Candle>>;
+
+    /// Get mark price candlestick charts.
+    /// GET /api/v5/market/mark-price-candles
+    fn get_mark_price_candles(&self, params: &GetMarkPriceCandlesRequest) -> OkxResult<Vec<Candle>>;
+}
@@ -0,0 +1,121 @@
+//! Blocking facade over the async [`RestClient`](crate::rest::RestClient).
+//!
+//! For scripts that don't run a tokio runtime. The client owns a small
+//! runtime and blocks the calling thread on each request; the typed method
+//! set mirrors the async client, minus the streaming archive downloads.
+//!
+//! Methods panic when called from within an async runtime, as
+//! [`Runtime::block_on`] does. Use the async client there.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::config::{ClientConfig, Credentials, TradingMode};
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient as AsyncRestClient;
+
+/// Forward each listed method to the async client, blocking on the result.
+macro_rules! blocking_methods {
+    ($(
+        $(#[$meta:meta])*
+        fn $name:ident(&self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;
+    )*) => {
+        impl super::RestClient {
+            $(
+                $(#[$meta])*
+                pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                    self.block_on(self.inner.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+mod account;
+mod affiliate;
+mod block_trading;
+mod broker;
+mod convert;
+mod copy_trading;
+mod download;
+mod finance;
+mod funding;
+mod grid_trading;
+mod kill_switch;
+mod market;
+mod public;
+mod signal_bot;
+mod spread_trading;
+mod subaccount;
+mod system;
+mod trade;
+mod trading_data;
+
+/// Blocking HTTP REST client for the OKX API v5.
+///
+/// Wraps the async client; see [`crate::rest::RestClient`] for the
+/// behaviour of each method.
+pub struct RestClient {
+    inner: AsyncRestClient,
+    runtime: Arc<Runtime>,
+}
+
+impl RestClient {
+    /// Create a new blocking `RestClient` with the given configuration.
+    pub fn new(config: ClientConfig) -> OkxResult<Self> {
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("okx-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| OkxError::Config(format!("failed to start runtime: {e}")))?;
+        Ok(Self {
+            inner: AsyncRestClient::new(config)?,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Create a blocking `RestClient` with default configuration
+    /// (unauthenticated, global, live).
+    pub fn default_client() -> OkxResult<Self> {
+        Self::new(ClientConfig::default())
+    }
+
+    /// A client for the other trading mode that shares this client's
+    /// connection pool and runtime.
+    ///
+    /// See [`crate::rest::RestClient::with_trading_mode`].
+    pub fn with_trading_mode(&self, mode: TradingMode) -> Self {
+        Self {
+            inner: self.inner.with_trading_mode(mode),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// A client signing with other credentials that shares this client's
+    /// connection pool and runtime.
+    pub fn with_credentials(&self, credentials: Credentials) -> OkxResult<Self> {
+        Ok(Self {
+            inner: self.inner.with_credentials(credentials)?,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// Returns a reference to the client configuration.
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
+    }
+
+    /// Returns the wrapped async client.
+    pub fn as_async(&self) -> &AsyncRestClient {
+        &self.inner
+    }
+
+    /// Run a future on the client's runtime, e.g. a call to an async-only
+    /// method on [`as_async`](Self::as_async).
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
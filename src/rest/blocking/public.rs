@@ -0,0 +1,87 @@
+use crate::error::OkxResult;
+use crate::types::request::public::*;
+use crate::types::response::public::*;
+
+blocking_methods! {
+    /// Get instruments (list of trading instruments).
+    /// GET /api/v5/public/instruments
+    fn get_instruments(&self, params: &GetInstrumentsRequest) -> OkxResult<Vec<Instrument>>;
+
+    /// Get delivery/exercise history.
+    /// GET /api/v5/public/delivery-exercise-history
+    fn get_delivery_exercise_history(
+        &self,
+        params: &GetDeliveryExerciseHistoryRequest,
+    ) -> OkxResult<Vec<DeliveryExerciseHistory>>;
+
+    /// Get open interest.
+    /// GET /api/v5/public/open-interest
+    fn get_open_interest(&self, params: &GetOpenInterestRequest) -> OkxResult<Vec<OpenInterest>>;
+
+    /// Get funding rate for a perpetual swap instrument.
+    /// GET /api/v5/public/funding-rate
+    fn get_funding_rate(&self, params: &GetFundingRateRequest) -> OkxResult<Vec<FundingRate>>;
+
+    /// Get funding rate history.
+    /// GET /api/v5/public/funding-rate-history
+    fn get_funding_rate_history(
+        &self,
+        params: &GetFundingRateHistoryRequest,
+    ) -> OkxResult<Vec<FundingRate>>;
+
+    /// Get mark price.
+    /// GET /api/v5/public/mark-price
+    fn get_mark_price(&self, params: &GetMarkPriceRequest) -> OkxResult<Vec<MarkPrice>>;
+
+    /// Get position tiers (margin tiers).
+    /// GET /api/v5/public/position-tiers
+    fn get_position_tiers(&self, params: &GetPositionTiersRequest) -> OkxResult<Vec<PositionTier>>;
+
+    /// Get insurance fund balance.
+    /// GET /api/v5/public/insurance-fund
+    fn get_insurance_fund(&self, params: &GetInsuranceFundRequest) -> OkxResult<Vec<InsuranceFund>>;
+
+    /// Convert between coin and contract units.
+    /// GET /api/v5/public/convert-contract-coin
+    fn get_unit_convert(&self, params: &GetUnitConvertRequest) -> OkxResult<Vec<UnitConvertResult>>;
+
+    /// Get option tick bands.
+    /// GET /api/v5/public/instrument-tick-bands
+    fn get_option_tick_bands(
+        &self,
+        params: &GetOptionTickBandsRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get estimated delivery/exercise price.
+    /// GET /api/v5/public/estimated-price
+    fn get_estimated_price(
+        &self,
+        params: &GetEstimatedPriceRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get discount rate and interest-free quota.
+    /// GET /api/v5/public/discount-rate-interest-free-quota
+    fn get_discount_rate(&self, params: &GetDiscountRateRequest) -> OkxResult<Vec<DiscountRate>>;
+
+    /// Get premium history.
+    /// GET /api/v5/public/premium-history
+    fn get_premium_history(
+        &self,
+        params: &GetPremiumHistoryRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get liquidation orders.
+    /// GET /api/v5/public/liquidation-orders
+    fn get_liquidation_orders(
+        &self,
+        params: &GetLiquidationOrdersRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get server time.
+    /// GET /api/v5/public/time
+    fn get_server_time(&self) -> OkxResult<Vec<ServerTime>>;
+
+    /// Server clock minus local clock in milliseconds, measured against the
+    /// midpoint of a [`get_server_time`](Self::get_server_time) round trip.
+    fn server_time_offset(&self) -> OkxResult<i64>;
+}
@@ -0,0 +1,84 @@
+use crate::error::OkxResult;
+use crate::types::request::signal_bot::*;
+use crate::types::response::signal_bot::*;
+
+blocking_methods! {
+    /// Create a signal.
+    /// POST /api/v5/tradingBot/signal/create-signal
+    fn create_signal(&self, params: &CreateSignalRequest) -> OkxResult<Vec<CreatedSignal>>;
+
+    /// Get signals.
+    /// GET /api/v5/tradingBot/signal/signals
+    fn get_signals(&self, params: &GetSignalsRequest) -> OkxResult<Vec<Signal>>;
+
+    /// Create a signal bot order.
+    /// POST /api/v5/tradingBot/signal/order-algo
+    fn create_signal_bot(&self, params: &CreateSignalBotRequest) -> OkxResult<Vec<SignalBotResult>>;
+
+    /// Stop signal bot orders. Maximum 10 bots per request.
+    /// POST /api/v5/tradingBot/signal/stop-order-algo
+    fn stop_signal_bot(&self, params: &[SignalBotId]) -> OkxResult<Vec<SignalBotResult>>;
+
+    /// Add or reduce the margin of a signal bot.
+    /// POST /api/v5/tradingBot/signal/margin-balance
+    fn adjust_signal_bot_margin(
+        &self,
+        params: &SignalMarginBalanceRequest,
+    ) -> OkxResult<Vec<SignalBotAmendResult>>;
+
+    /// Amend the take-profit / stop-loss settings of a signal bot.
+    /// POST /api/v5/tradingBot/signal/amendTPSL
+    fn amend_signal_bot_tp_sl(
+        &self,
+        params: &SignalAmendTpSlRequest,
+    ) -> OkxResult<Vec<SignalBotAmendResult>>;
+
+    /// Get signal bot order list.
+    /// GET /api/v5/tradingBot/signal/orders-algo-pending
+    fn get_signal_bot_order_list(
+        &self,
+        params: &GetSignalBotOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotOrder>>;
+
+    /// Get signal bot order history.
+    /// GET /api/v5/tradingBot/signal/orders-algo-history
+    fn get_signal_bot_order_history(
+        &self,
+        params: &GetSignalBotOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotOrder>>;
+
+    /// Get the open positions of a signal bot.
+    /// GET /api/v5/tradingBot/signal/positions
+    fn get_signal_bot_positions(
+        &self,
+        params: &GetSignalBotPositionsRequest,
+    ) -> OkxResult<Vec<SignalBotPosition>>;
+
+    /// Get the closed positions of a signal bot.
+    /// GET /api/v5/tradingBot/signal/positions-history
+    fn get_signal_bot_positions_history(
+        &self,
+        params: &GetSignalBotPositionsHistoryRequest,
+    ) -> OkxResult<Vec<SignalBotPositionHistory>>;
+
+    /// Get signal bot sub-orders.
+    /// GET /api/v5/tradingBot/signal/sub-orders
+    fn get_signal_bot_sub_orders(
+        &self,
+        params: &GetSignalBotSubOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotSubOrder>>;
+
+    /// Cancel a pending sub-order of a signal bot.
+    /// POST /api/v5/tradingBot/signal/cancel-sub-order
+    fn cancel_signal_bot_sub_order(
+        &self,
+        params: &CancelSignalSubOrderRequest,
+    ) -> OkxResult<Vec<CancelledSignalSubOrder>>;
+
+    /// Get the signal event history of a signal bot.
+    /// GET /api/v5/tradingBot/signal/event-history
+    fn get_signal_bot_event_history(
+        &self,
+        params: &GetSignalBotEventHistoryRequest,
+    ) -> OkxResult<Vec<SignalBotEvent>>;
+}
@@ -0,0 +1,72 @@
+use crate::error::OkxResult;
+use crate::types::request::spread_trading::{
+    SpreadAmendRequest, SpreadCancelRequest, SpreadOrderRequest,
+};
+
+blocking_methods! {
+    /// Place a spread order.
+    /// POST /api/v5/sprd/order
+    fn place_spread_order(&self, params: &SpreadOrderRequest) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel a spread order.
+    /// POST /api/v5/sprd/cancel-order
+    fn cancel_spread_order(
+        &self,
+        params: &SpreadCancelRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Amend a spread order.
+    /// POST /api/v5/sprd/amend-order
+    fn amend_spread_order(&self, params: &SpreadAmendRequest) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Cancel all spread orders.
+    /// POST /api/v5/sprd/mass-cancel
+    fn cancel_all_spread_orders(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread order details.
+    /// GET /api/v5/sprd/order
+    fn get_spread_order(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread order list (active).
+    /// GET /api/v5/sprd/orders-pending
+    fn get_spread_order_list(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread order history.
+    /// GET /api/v5/sprd/orders-history
+    fn get_spread_order_history(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread trades.
+    /// GET /api/v5/sprd/trades
+    fn get_spread_trades(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spreads.
+    /// GET /api/v5/sprd/spreads
+    fn get_spreads(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread order book.
+    /// GET /api/v5/sprd/books
+    fn get_spread_order_book(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread ticker.
+    /// GET /api/v5/sprd/ticker
+    fn get_spread_ticker(&self, params: &serde_json::Value) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get spread public trades.
+    /// GET /api/v5/sprd/public-trades
+    fn get_spread_public_trades(
+        &self,
+        params: &serde_json::Value,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+}
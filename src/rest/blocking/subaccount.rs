@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use crate::error::OkxResult;
+use crate::rest::subaccount::AggregatedBalance;
+use crate::types::request::subaccount::*;
+use crate::types::response::subaccount::*;
+
+blocking_methods! {
+    /// Get sub-account list.
+    /// GET /api/v5/users/subaccount/list
+    fn get_sub_account_list(&self, params: &GetSubAccountListRequest) -> OkxResult<Vec<SubAccount>>;
+
+    /// Get sub-account trading balance.
+    /// GET /api/v5/account/subaccount/balances
+    fn get_sub_account_balance(
+        &self,
+        params: &GetSubAccountBalanceRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Get sub-account funding balance.
+    /// GET /api/v5/asset/subaccount/balances
+    fn get_sub_account_funding_balance(
+        &self,
+        params: &GetSubAccountFundingBalanceRequest,
+    ) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Transfer between sub-accounts.
+    /// POST /api/v5/asset/subaccount/transfer
+    fn sub_account_transfer(
+        &self,
+        params: &SubAccountTransferRequest,
+    ) -> OkxResult<Vec<SubAccountTransferResult>>;
+
+    /// List every sub-account, following pagination.
+    fn get_all_sub_accounts(&self) -> OkxResult<Vec<SubAccount>>;
+
+    /// Trading and funding balances of every sub-account, summed per
+    /// currency.
+    ///
+    /// Requests are sent one at a time and paced to stay within the balance
+    /// endpoints' rate limits, so this takes roughly 0.7 s per sub-account.
+    /// The master account's own balances are not included; see
+    /// [`get_balance`](Self::get_balance) and
+    /// [`get_asset_balances`](Self::get_asset_balances).
+    fn get_all_subaccount_balances(&self) -> OkxResult<BTreeMap<String, AggregatedBalance>>;
+}
@@ -0,0 +1,8 @@
+use crate::error::OkxResult;
+use crate::types::response::system::SystemStatus;
+
+blocking_methods! {
+    /// Get system status.
+    /// GET /api/v5/system/status
+    fn get_system_status(&self) -> OkxResult<Vec<SystemStatus>>;
+}
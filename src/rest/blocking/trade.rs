@@ -0,0 +1,207 @@
+use crate::error::OkxResult;
+use crate::rest::ApiResponse;
+use crate::types::request::trade::*;
+use crate::types::response::trade::*;
+use crate::types::shared::{ArchiveApplyResult, ArchiveFile};
+
+blocking_methods! {
+    /// Place a single order.
+    /// POST /api/v5/trade/order
+    fn place_order(&self, params: &OrderRequest) -> OkxResult<Vec<OrderResult>>;
+
+    /// SPOT market buy sized in the quote currency, e.g. spend 100 USDT on BTC.
+    /// POST /api/v5/trade/order
+    fn buy_spot_with_quote(&self, inst_id: &str, quote_amount: &str) -> OkxResult<Vec<OrderResult>>;
+
+    /// Place multiple orders (up to 20) in a single request.
+    /// POST /api/v5/trade/batch-orders
+    fn place_multiple_orders(&self, params: &Vec<OrderRequest>) -> OkxResult<Vec<OrderResult>>;
+
+    /// Place multiple orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/batch-orders
+    fn place_multiple_orders_with_meta(
+        &self,
+        params: &Vec<OrderRequest>,
+    ) -> OkxResult<ApiResponse<OrderResult>>;
+
+    /// Cancel a single order.
+    /// POST /api/v5/trade/cancel-order
+    fn cancel_order(&self, params: &CancelOrderRequest) -> OkxResult<Vec<CancelledOrder>>;
+
+    /// Cancel multiple orders (up to 20) in a single request.
+    /// POST /api/v5/trade/cancel-batch-orders
+    fn cancel_multiple_orders(
+        &self,
+        params: &Vec<CancelOrderRequest>,
+    ) -> OkxResult<Vec<CancelledOrder>>;
+
+    /// Cancel multiple orders, succeeding on partial failure so each
+    /// order's `sCode` can be checked.
+    /// POST /api/v5/trade/cancel-batch-orders
+    fn cancel_multiple_orders_with_meta(
+        &self,
+        params: &Vec<CancelOrderRequest>,
+    ) -> OkxResult<ApiResponse<CancelledOrder>>;
+
+    /// Amend an existing order.
+    /// POST /api/v5/trade/amend-order
+    fn amend_order(&self, params: &AmendOrderRequest) -> OkxResult<Vec<AmendedOrder>>;
+
+    /// Amend multiple orders (up to 20) in a single request.
+    /// POST /api/v5/trade/amend-batch-orders
+    fn amend_multiple_orders(
+        &self,
+        params: &Vec<AmendOrderRequest>,
+    ) -> OkxResult<Vec<AmendedOrder>>;
+
+    /// Amend multiple orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/amend-batch-orders
+    fn amend_multiple_orders_with_meta(
+        &self,
+        params: &Vec<AmendOrderRequest>,
+    ) -> OkxResult<ApiResponse<AmendedOrder>>;
+
+    /// Close a position.
+    /// POST /api/v5/trade/close-position
+    fn close_position(&self, params: &ClosePositionRequest) -> OkxResult<Vec<ClosedPosition>>;
+
+    /// Get details of a single order.
+    /// GET /api/v5/trade/order
+    fn get_order(&self, params: &GetOrderRequest) -> OkxResult<Vec<OrderDetails>>;
+
+    /// Get a list of pending (unfilled/partially filled) orders.
+    /// GET /api/v5/trade/orders-pending
+    fn get_order_list(&self, params: &GetOrderListRequest) -> OkxResult<Vec<OrderDetails>>;
+
+    /// Get order history for the last 7 days.
+    /// GET /api/v5/trade/orders-history
+    fn get_order_history(&self, params: &GetOrderHistoryRequest) -> OkxResult<Vec<OrderDetails>>;
+
+    /// Get order history archive (last 3 months).
+    /// GET /api/v5/trade/orders-history-archive
+    fn get_order_history_archive(
+        &self,
+        params: &GetOrderHistoryRequest,
+    ) -> OkxResult<Vec<OrderDetails>>;
+
+    /// Get recent transaction (fill) details for the last 3 days.
+    /// GET /api/v5/trade/fills
+    fn get_fills(&self, params: &GetFillsRequest) -> OkxResult<Vec<Fill>>;
+
+    /// Get transaction (fill) details history for the last 3 months.
+    /// GET /api/v5/trade/fills-history
+    fn get_fills_history(&self, params: &GetFillsRequest) -> OkxResult<Vec<Fill>>;
+
+    /// Apply for the fills archive of a quarter (since 2022).
+    /// POST /api/v5/trade/fills-archive
+    fn apply_fills_archive(
+        &self,
+        params: &FillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveApplyResult>>;
+
+    /// Get the download link of a previously applied fills archive.
+    /// GET /api/v5/trade/fills-archive
+    fn get_fills_archive(&self, params: &FillsArchiveRequest) -> OkxResult<Vec<ArchiveFile>>;
+
+    /// Mass cancel all pending orders for an instrument type.
+    /// POST /api/v5/trade/mass-cancel
+    fn mass_cancel(&self, params: &MassCancelRequest) -> OkxResult<Vec<MassCancelResult>>;
+
+    /// Cancel all orders after a countdown timer (dead man's switch).
+    /// POST /api/v5/trade/cancel-all-after
+    fn cancel_all_after(&self, params: &CancelAllAfterRequest) -> OkxResult<Vec<serde_json::Value>>;
+
+    /// Place an algo order (trigger, OCO, conditional, iceberg, TWAP, etc.).
+    /// POST /api/v5/trade/order-algo
+    fn place_algo_order(&self, params: &AlgoOrderRequest) -> OkxResult<Vec<AlgoOrderResult>>;
+
+    /// Cancel algo orders.
+    /// POST /api/v5/trade/cancel-algos
+    fn cancel_algo_orders(
+        &self,
+        params: &Vec<CancelAlgoOrderRequest>,
+    ) -> OkxResult<Vec<AlgoOrderResult>>;
+
+    /// Cancel algo orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/cancel-algos
+    fn cancel_algo_orders_with_meta(
+        &self,
+        params: &Vec<CancelAlgoOrderRequest>,
+    ) -> OkxResult<ApiResponse<AlgoOrderResult>>;
+
+    /// Amend an algo order.
+    /// POST /api/v5/trade/amend-algos
+    fn amend_algo_order(&self, params: &AmendAlgoOrderRequest) -> OkxResult<Vec<AlgoOrderResult>>;
+
+    /// Get details of a single algo order.
+    /// GET /api/v5/trade/order-algo
+    fn get_algo_order(&self, params: &GetAlgoOrderRequest) -> OkxResult<Vec<AlgoOrderDetails>>;
+
+    /// Get a list of pending algo orders.
+    /// GET /api/v5/trade/orders-algo-pending
+    fn get_algo_order_list(
+        &self,
+        params: &GetAlgoOrderListRequest,
+    ) -> OkxResult<Vec<AlgoOrderDetails>>;
+
+    /// Get algo order history.
+    /// GET /api/v5/trade/orders-algo-history
+    fn get_algo_order_history(
+        &self,
+        params: &GetAlgoOrderListRequest,
+    ) -> OkxResult<Vec<AlgoOrderDetails>>;
+
+    /// Get the list of currencies available for easy convert.
+    /// GET /api/v5/trade/easy-convert-currency-list
+    fn get_easy_convert_currency_list(&self) -> OkxResult<Vec<EasyConvertCurrencyList>>;
+
+    /// Convert small balances into OKB or USDT.
+    /// POST /api/v5/trade/easy-convert
+    fn easy_convert(&self, params: &EasyConvertRequest) -> OkxResult<Vec<EasyConvertResult>>;
+
+    /// Get easy convert history.
+    /// GET /api/v5/trade/easy-convert-history
+    fn get_easy_convert_history(
+        &self,
+        params: &GetEasyConvertHistoryRequest,
+    ) -> OkxResult<Vec<EasyConvertResult>>;
+
+    /// Get the list of debt and repayment currencies for one-click repay.
+    /// GET /api/v5/trade/one-click-repay-currency-list
+    fn get_one_click_repay_currency_list(
+        &self,
+        params: &GetOneClickRepayCurrencyListRequest,
+    ) -> OkxResult<Vec<OneClickRepayCurrencyList>>;
+
+    /// Repay cross margin debt with a single click.
+    /// POST /api/v5/trade/one-click-repay
+    fn one_click_repay(&self, params: &OneClickRepayRequest) -> OkxResult<Vec<OneClickRepayResult>>;
+
+    /// Get one-click repay history.
+    /// GET /api/v5/trade/one-click-repay-history
+    fn get_one_click_repay_history(
+        &self,
+        params: &GetOneClickRepayHistoryRequest,
+    ) -> OkxResult<Vec<OneClickRepayResult>>;
+
+    /// Get the debt currencies and their repayment currencies for one-click repay v2.
+    /// GET /api/v5/trade/one-click-repay-currency-list-v2
+    fn get_one_click_repay_currency_list_v2(&self) -> OkxResult<Vec<OneClickRepayCurrencyListV2>>;
+
+    /// Repay a debt with one or more currencies.
+    /// POST /api/v5/trade/one-click-repay-v2
+    fn one_click_repay_v2(
+        &self,
+        params: &OneClickRepayV2Request,
+    ) -> OkxResult<Vec<OneClickRepayResultV2>>;
+
+    /// Get one-click repay v2 history.
+    /// GET /api/v5/trade/one-click-repay-history-v2
+    fn get_one_click_repay_history_v2(
+        &self,
+        params: &GetOneClickRepayHistoryRequest,
+    ) -> OkxResult<Vec<OneClickRepayRecordV2>>;
+}
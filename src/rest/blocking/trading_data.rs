@@ -0,0 +1,83 @@
+use crate::error::OkxResult;
+use crate::types::request::trading_data::*;
+use crate::types::response::trading_data::*;
+
+blocking_methods! {
+    /// Get support coin.
+    /// GET /api/v5/rubik/stat/trading-data/support-coin
+    fn get_support_coin(&self) -> OkxResult<Vec<SupportCoin>>;
+
+    /// Get taker volume.
+    /// GET /api/v5/rubik/stat/taker-volume
+    fn get_taker_volume(&self, params: &GetTakerVolumeRequest) -> OkxResult<Vec<TakerVolume>>;
+
+    /// Get margin lending ratio.
+    /// GET /api/v5/rubik/stat/margin/loan-ratio
+    fn get_margin_lending_ratio(
+        &self,
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>>;
+
+    /// Get long/short ratio.
+    /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio
+    fn get_long_short_ratio(
+        &self,
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>>;
+
+    /// Get open interest and volume.
+    /// GET /api/v5/rubik/stat/contracts/open-interest-volume
+    fn get_open_interest_volume(
+        &self,
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestVolume>>;
+
+    /// Get put/call ratio.
+    /// GET /api/v5/rubik/stat/option/open-interest-volume-ratio
+    fn get_put_call_ratio(
+        &self,
+        params: &GetOptionTradingDataRequest,
+    ) -> OkxResult<Vec<PutCallRatio>>;
+
+    /// Get open interest and volume (options).
+    /// GET /api/v5/rubik/stat/option/open-interest-volume
+    fn get_option_open_interest_volume(
+        &self,
+        params: &GetOptionTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestVolume>>;
+
+    /// Get taker volume (contracts).
+    /// GET /api/v5/rubik/stat/taker-volume-contract
+    fn get_taker_volume_contracts(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<TakerVolume>>;
+
+    /// Get contract open interest history.
+    /// GET /api/v5/rubik/stat/contracts/open-interest-history
+    fn get_contract_open_interest_history(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestHistory>>;
+
+    /// Get the long/short account ratio of a contract.
+    /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio-contract
+    fn get_contract_long_short_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>>;
+
+    /// Get the long/short account ratio of a contract's top traders.
+    /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio-contract-top-trader
+    fn get_top_traders_long_short_account_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>>;
+
+    /// Get the long/short position ratio of a contract's top traders.
+    /// GET /api/v5/rubik/stat/contracts/long-short-position-ratio-contract-top-trader
+    fn get_top_traders_long_short_position_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>>;
+}
@@ -1,12 +1,14 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::request::broker::*;
+use crate::types::response::broker::*;
 
 impl RestClient {
 
     /// Get broker account info.
     /// GET /api/v5/broker/nd/info
-    pub async fn get_broker_info(&self) -> OkxResult<Vec<serde_json::Value>> {
-        self.get_signed::<serde_json::Value, ()>("/api/v5/broker/nd/info", None)
+    pub async fn get_broker_info(&self) -> OkxResult<Vec<BrokerInfo>> {
+        self.get_signed::<BrokerInfo, ()>("/api/v5/broker/nd/info", None)
             .await
     }
 
@@ -14,22 +16,118 @@ impl RestClient {
     /// POST /api/v5/broker/nd/create-subaccount
     pub async fn broker_create_sub_account(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &BrokerCreateSubAccountRequest,
+    ) -> OkxResult<Vec<BrokerSubAccount>> {
         self.post_signed("/api/v5/broker/nd/create-subaccount", params)
             .await
     }
 
+    /// Delete a sub-account (broker).
+    /// POST /api/v5/broker/nd/delete-subaccount
+    pub async fn broker_delete_sub_account(
+        &self,
+        params: &BrokerDeleteSubAccountRequest,
+    ) -> OkxResult<Vec<BrokerDeleteResult>> {
+        self.post_signed("/api/v5/broker/nd/delete-subaccount", params)
+            .await
+    }
+
+    /// Get the sub-account list (broker).
+    /// GET /api/v5/broker/nd/subaccount-info
+    pub async fn broker_get_sub_accounts(
+        &self,
+        params: &BrokerGetSubAccountsRequest,
+    ) -> OkxResult<Vec<BrokerSubAccountPage>> {
+        self.get_signed("/api/v5/broker/nd/subaccount-info", Some(params))
+            .await
+    }
+
     /// Create API key for a sub-account (broker).
     /// POST /api/v5/broker/nd/subaccount/apikey
     pub async fn broker_create_sub_account_api_key(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &BrokerCreateApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>> {
         self.post_signed("/api/v5/broker/nd/subaccount/apikey", params)
             .await
     }
 
+    /// Query the API keys of a sub-account (broker).
+    /// GET /api/v5/broker/nd/subaccount/apikey
+    pub async fn broker_get_sub_account_api_keys(
+        &self,
+        params: &BrokerGetApiKeysRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>> {
+        self.get_signed("/api/v5/broker/nd/subaccount/apikey", Some(params))
+            .await
+    }
+
+    /// Modify an API key of a sub-account (broker).
+    /// POST /api/v5/broker/nd/subaccount/modify-apikey
+    pub async fn broker_modify_sub_account_api_key(
+        &self,
+        params: &BrokerModifyApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerApiKey>> {
+        self.post_signed("/api/v5/broker/nd/subaccount/modify-apikey", params)
+            .await
+    }
+
+    /// Delete an API key of a sub-account (broker).
+    /// POST /api/v5/broker/nd/subaccount/delete-apikey
+    pub async fn broker_delete_sub_account_api_key(
+        &self,
+        params: &BrokerDeleteApiKeyRequest,
+    ) -> OkxResult<Vec<BrokerDeleteResult>> {
+        self.post_signed("/api/v5/broker/nd/subaccount/delete-apikey", params)
+            .await
+    }
+
+    /// Set the trading fee rate of a sub-account (broker).
+    /// POST /api/v5/broker/nd/set-subaccount-fee-rate
+    pub async fn broker_set_sub_account_fee_rate(
+        &self,
+        params: &BrokerSetFeeRateRequest,
+    ) -> OkxResult<Vec<BrokerSetFeeRateResult>> {
+        self.post_signed("/api/v5/broker/nd/set-subaccount-fee-rate", params)
+            .await
+    }
+
+    /// Create a deposit address for a sub-account (broker).
+    /// POST /api/v5/asset/broker/nd/subaccount-deposit-address
+    pub async fn broker_create_sub_account_deposit_address(
+        &self,
+        params: &BrokerCreateDepositAddressRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>> {
+        self.post_signed("/api/v5/asset/broker/nd/subaccount-deposit-address", params)
+            .await
+    }
+
+    /// Modify the crediting account of a sub-account deposit address (broker).
+    /// POST /api/v5/asset/broker/nd/modify-subaccount-deposit-address
+    pub async fn broker_modify_sub_account_deposit_address(
+        &self,
+        params: &BrokerModifyDepositAddressRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>> {
+        self.post_signed(
+            "/api/v5/asset/broker/nd/modify-subaccount-deposit-address",
+            params,
+        )
+        .await
+    }
+
+    /// Get the deposit addresses of a sub-account (broker).
+    /// GET /api/v5/asset/broker/nd/subaccount-deposit-address
+    pub async fn broker_get_sub_account_deposit_addresses(
+        &self,
+        params: &BrokerGetDepositAddressesRequest,
+    ) -> OkxResult<Vec<BrokerDepositAddress>> {
+        self.get_signed(
+            "/api/v5/asset/broker/nd/subaccount-deposit-address",
+            Some(params),
+        )
+        .await
+    }
+
     /// Get sub-account deposit history (broker).
     /// GET /api/v5/broker/nd/subaccount-deposit-history
     pub async fn broker_get_sub_account_deposit_history(
@@ -52,4 +150,24 @@ impl RestClient {
         self.get_signed("/api/v5/broker/nd/rebate-daily", Some(params))
             .await
     }
+
+    /// Request generation of the rebate details file per order (broker).
+    /// POST /api/v5/broker/nd/rebate-per-orders
+    pub async fn broker_apply_rebate_per_orders(
+        &self,
+        params: &BrokerRebatePerOrdersRequest,
+    ) -> OkxResult<Vec<BrokerRebateApplyResult>> {
+        self.post_signed("/api/v5/broker/nd/rebate-per-orders", params)
+            .await
+    }
+
+    /// Get the download link of the rebate details file per order (broker).
+    /// GET /api/v5/broker/nd/rebate-per-orders
+    pub async fn broker_get_rebate_per_orders(
+        &self,
+        params: &BrokerRebatePerOrdersRequest,
+    ) -> OkxResult<Vec<BrokerRebateFile>> {
+        self.get_signed("/api/v5/broker/nd/rebate-per-orders", Some(params))
+            .await
+    }
 }
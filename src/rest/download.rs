@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::rest::RestClient;
+use crate::types::enums::Bar;
+use crate::types::request::market::{GetCandlesRequest, GetHistoricTradesRequest};
+use crate::types::response::market::{Ohlcv, Trade};
+
+/// Maximum number of candles returned by one history-candles request.
+const CANDLE_PAGE_LIMIT: i64 = 100;
+
+/// Maximum number of trades returned by one history-trades request.
+const TRADE_PAGE_LIMIT: usize = 100;
+
+/// Upper bound on the length of a month bar, used to size request windows.
+const MAX_MONTH_MILLIS: i64 = 31 * 24 * 60 * 60 * 1_000;
+
+/// Pacing for paginated history downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// Delay between consecutive requests. The default keeps within the
+    /// 20 requests per 2 seconds limit of the history endpoints.
+    pub request_interval: Duration,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            request_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Downloads an arbitrary range of historic candles.
+///
+/// The range is split into windows of at most 100 bars, each fetched from
+/// `GET /api/v5/market/history-candles`. Candles are yielded oldest first,
+/// without duplicates, and gaps between consecutive bars are logged.
+pub struct CandleDownloader<'a> {
+    client: &'a RestClient,
+    inst_id: String,
+    bar: Bar,
+    options: DownloadOptions,
+}
+
+impl RestClient {
+    /// Create a [`CandleDownloader`] for an instrument and bar size.
+    pub fn candle_downloader(&self, inst_id: &str, bar: Bar) -> CandleDownloader<'_> {
+        CandleDownloader {
+            client: self,
+            inst_id: inst_id.to_string(),
+            bar,
+            options: DownloadOptions::default(),
+        }
+    }
+
+    /// Download all trades with a timestamp in `[start, end)` (Unix
+    /// milliseconds), oldest first.
+    ///
+    /// The first page is located by timestamp; later pages continue from
+    /// the oldest trade ID seen so trades sharing a millisecond are not
+    /// skipped. Trades repeated across pages are dropped.
+    pub async fn download_trades(
+        &self,
+        inst_id: &str,
+        start: i64,
+        end: i64,
+        options: &DownloadOptions,
+    ) -> OkxResult<Vec<Trade>> {
+        let mut params = GetHistoricTradesRequest {
+            inst_id: inst_id.to_string(),
+            // 2: paginate by timestamp.
+            type_: Some("2".to_string()),
+            after: Some(end.to_string()),
+            before: None,
+            limit: Some(TRADE_PAGE_LIMIT.to_string()),
+        };
+        let mut seen = HashSet::new();
+        let mut trades = Vec::new();
+
+        loop {
+            let page = self.get_history_trades(&params).await?;
+            let Some(oldest) = page.last() else {
+                break;
+            };
+            let oldest_ts = parse_ts(&oldest.ts)?;
+            let next_after = oldest.trade_id.clone();
+            let full_page = page.len() >= TRADE_PAGE_LIMIT;
+
+            for trade in page {
+                let ts = parse_ts(&trade.ts)?;
+                if (start..end).contains(&ts) && seen.insert(trade.trade_id.clone()) {
+                    trades.push(trade);
+                }
+            }
+
+            if oldest_ts < start || !full_page {
+                break;
+            }
+            // 1: paginate by trade ID.
+            params.type_ = Some("1".to_string());
+            params.after = Some(next_after);
+            crate::time::sleep(options.request_interval).await;
+        }
+
+        trades.reverse();
+        Ok(trades)
+    }
+}
+
+/// Stream of downloaded candles. HTTP futures are not `Send` in the
+/// browser, so on wasm neither is the stream.
+#[cfg(not(target_arch = "wasm32"))]
+pub type CandleStream<'a> = futures::stream::BoxStream<'a, OkxResult<Ohlcv>>;
+#[cfg(target_arch = "wasm32")]
+pub type CandleStream<'a> = futures::stream::LocalBoxStream<'a, OkxResult<Ohlcv>>;
+
+/// Cursor state of a candle download.
+struct CandleCursor {
+    /// Start of the next window, Unix timestamp in milliseconds.
+    next: i64,
+    /// Open time of the last candle yielded.
+    last_ts: Option<i64>,
+    /// Whether a request has been made yet.
+    started: bool,
+}
+
+impl<'a> CandleDownloader<'a> {
+    /// Override the request pacing.
+    pub fn options(mut self, options: DownloadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Stream candles whose open time falls in `range` (Unix milliseconds),
+    /// oldest first.
+    pub fn stream(&self, range: Range<i64>) -> CandleStream<'_> {
+        let window = self.bar.millis().unwrap_or(MAX_MONTH_MILLIS) * CANDLE_PAGE_LIMIT;
+        let cursor = CandleCursor {
+            next: range.start,
+            last_ts: None,
+            started: false,
+        };
+
+        let candles = stream::try_unfold(cursor, move |mut cursor| async move {
+            if cursor.next >= range.end {
+                return Ok::<_, OkxError>(None);
+            }
+            if cursor.started {
+                crate::time::sleep(self.options.request_interval).await;
+            }
+            cursor.started = true;
+
+            let window_end = cursor.next.saturating_add(window).min(range.end);
+            let mut page = self.fetch_window(cursor.next, window_end).await?;
+            page.reverse();
+
+            for candle in &page {
+                let ts = parse_ts(&candle.ts)?;
+                if let (Some(last), Some(bar_millis)) = (cursor.last_ts, self.bar.millis()) {
+                    if ts - last > bar_millis {
+                        warn!(
+                            "Gap in {} {} candles between {last} and {ts}",
+                            self.inst_id,
+                            self.bar.as_str()
+                        );
+                    }
+                }
+                cursor.last_ts = Some(ts);
+            }
+
+            cursor.next = window_end;
+            Ok(Some((page, cursor)))
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let candles = candles.boxed();
+        #[cfg(target_arch = "wasm32")]
+        let candles = candles.boxed_local();
+        candles
+    }
+
+    /// Download all candles in `range` (Unix milliseconds), oldest first.
+    pub async fn collect(&self, range: Range<i64>) -> OkxResult<Vec<Ohlcv>> {
+        self.stream(range).try_collect().await
+    }
+
+    /// Fetch the candles with open time in `[start, end)`, newest first.
+    async fn fetch_window(&self, start: i64, end: i64) -> OkxResult<Vec<Ohlcv>> {
+        let params = GetCandlesRequest {
+            inst_id: self.inst_id.clone(),
+            bar: Some(self.bar.as_str().to_string()),
+            after: Some(end.to_string()),
+            before: Some((start - 1).to_string()),
+            limit: Some(CANDLE_PAGE_LIMIT.to_string()),
+        };
+        self.client
+            .get("/api/v5/market/history-candles", Some(&params))
+            .await
+    }
+}
+
+/// Find gaps between consecutive candles longer than one bar.
+///
+/// `candles` must be sorted oldest first. Each gap is returned as the range
+/// between the open times of the candles on either side. Always empty for
+/// month bars.
+pub fn candle_gaps(candles: &[Ohlcv], bar: Bar) -> OkxResult<Vec<Range<i64>>> {
+    let Some(bar_millis) = bar.millis() else {
+        return Ok(Vec::new());
+    };
+    let mut gaps = Vec::new();
+    let mut prev: Option<i64> = None;
+    for candle in candles {
+        let ts = parse_ts(&candle.ts)?;
+        if let Some(prev) = prev {
+            if ts - prev > bar_millis {
+                gaps.push(prev..ts);
+            }
+        }
+        prev = Some(ts);
+    }
+    Ok(gaps)
+}
+
+fn parse_ts(ts: &str) -> OkxResult<i64> {
+    serde_json::from_str(ts).map_err(|e| OkxError::deserialize::<i64>(e, ts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(ts: i64) -> Ohlcv {
+        let row = serde_json::json!([ts.to_string(), "1", "1", "1", "1", "1", "1", "1", "1"]);
+        serde_json::from_value(row).unwrap()
+    }
+
+    #[test]
+    fn finds_gaps_longer_than_one_bar() {
+        let minute = 60_000;
+        let candles: Vec<_> = [0, 1, 2, 5, 6].iter().map(|i| candle(i * minute)).collect();
+        assert_eq!(
+            candle_gaps(&candles, Bar::M1).unwrap(),
+            vec![2 * minute..5 * minute]
+        );
+        assert!(candle_gaps(&candles, Bar::Mo1).unwrap().is_empty());
+    }
+}
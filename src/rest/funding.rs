@@ -1,8 +1,68 @@
-use crate::error::OkxResult;
+use std::time::Duration;
+
+use crate::error::{OkxError, OkxResult};
 use crate::rest::RestClient;
+use crate::time::Instant;
+use crate::types::enums::{TransferAccount, TransferStatus, TransferType};
 use crate::types::request::funding::*;
 use crate::types::response::funding::*;
 
+/// A submitted funds transfer whose state can be polled.
+#[derive(Clone)]
+pub struct TransferPoller<'a> {
+    client: &'a RestClient,
+    trans_id: String,
+    type_: TransferType,
+}
+
+impl TransferPoller<'_> {
+    /// Transfer ID assigned by OKX.
+    pub fn trans_id(&self) -> &str {
+        &self.trans_id
+    }
+
+    /// Current state of the transfer.
+    pub async fn state(&self) -> OkxResult<FundsTransferState> {
+        self.client
+            .get_transfer_state(&GetTransferStateRequest {
+                trans_id: Some(self.trans_id.clone()),
+                client_id: None,
+                type_: Some(self.type_),
+            })
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                OkxError::UnexpectedResponse(format!("no state for transfer {}", self.trans_id))
+            })
+    }
+
+    /// Poll every `interval` until the transfer leaves the pending state.
+    ///
+    /// Returns the final state, which may be [`TransferStatus::Failed`], or
+    /// [`OkxError::Timeout`] if it is still pending after `timeout`.
+    pub async fn wait(
+        &self,
+        interval: Duration,
+        timeout: Duration,
+    ) -> OkxResult<FundsTransferState> {
+        let started = Instant::now();
+        loop {
+            let state = self.state().await?;
+            if state.state != TransferStatus::Pending {
+                return Ok(state);
+            }
+            if started.elapsed() >= timeout {
+                return Err(OkxError::Timeout(format!(
+                    "transfer {} still pending after {timeout:?}",
+                    self.trans_id
+                )));
+            }
+            crate::time::sleep(interval).await;
+        }
+    }
+}
+
 impl RestClient {
 
     /// Get list of currencies.
@@ -25,12 +85,82 @@ impl RestClient {
             .await
     }
 
+    /// Get the valuation of all assets across account types.
+    /// GET /api/v5/asset/asset-valuation
+    pub async fn get_asset_valuation(
+        &self,
+        params: &GetAssetValuationRequest,
+    ) -> OkxResult<Vec<AssetValuation>> {
+        self.get_signed("/api/v5/asset/asset-valuation", Some(params))
+            .await
+    }
+
+    /// Get non-tradable assets in the funding account.
+    /// GET /api/v5/asset/non-tradable-assets
+    pub async fn get_non_tradable_assets(
+        &self,
+        params: &GetNonTradableAssetsRequest,
+    ) -> OkxResult<Vec<NonTradableAsset>> {
+        self.get_signed("/api/v5/asset/non-tradable-assets", Some(params))
+            .await
+    }
+
+    /// Convert small balances to OKB.
+    /// POST /api/v5/asset/convert-dust-assets
+    pub async fn convert_dust_assets(
+        &self,
+        params: &ConvertDustAssetsRequest,
+    ) -> OkxResult<Vec<DustConversion>> {
+        self.post_signed("/api/v5/asset/convert-dust-assets", params)
+            .await
+    }
+
     /// Submit a withdrawal request.
     /// POST /api/v5/asset/withdrawal
     pub async fn withdraw(&self, params: &WithdrawRequest) -> OkxResult<Vec<WithdrawalResult>> {
         self.post_signed("/api/v5/asset/withdrawal", params).await
     }
 
+    /// Cancel a withdrawal that has not been sent to the chain yet.
+    /// POST /api/v5/asset/cancel-withdrawal
+    pub async fn cancel_withdrawal(
+        &self,
+        params: &CancelWithdrawalRequest,
+    ) -> OkxResult<Vec<CancelWithdrawalResult>> {
+        self.post_signed("/api/v5/asset/cancel-withdrawal", params)
+            .await
+    }
+
+    /// Create a Lightning Network deposit invoice.
+    /// GET /api/v5/asset/deposit-lightning
+    pub async fn get_lightning_deposit(
+        &self,
+        params: &LightningDepositRequest,
+    ) -> OkxResult<Vec<LightningDeposit>> {
+        self.get_signed("/api/v5/asset/deposit-lightning", Some(params))
+            .await
+    }
+
+    /// Pay a Lightning Network invoice from the funding account.
+    /// POST /api/v5/asset/withdrawal-lightning
+    pub async fn lightning_withdrawal(
+        &self,
+        params: &LightningWithdrawalRequest,
+    ) -> OkxResult<Vec<LightningWithdrawalResult>> {
+        self.post_signed("/api/v5/asset/withdrawal-lightning", params)
+            .await
+    }
+
+    /// Get the detailed status of a deposit or withdrawal.
+    /// GET /api/v5/asset/deposit-withdraw-status
+    pub async fn get_deposit_withdraw_status(
+        &self,
+        params: &GetDepositWithdrawStatusRequest,
+    ) -> OkxResult<Vec<DepositWithdrawStatus>> {
+        self.get_signed("/api/v5/asset/deposit-withdraw-status", Some(params))
+            .await
+    }
+
     /// Transfer funds between accounts.
     /// POST /api/v5/asset/transfer
     pub async fn funds_transfer(
@@ -69,4 +199,88 @@ impl RestClient {
         self.get_signed("/api/v5/asset/deposit-address", Some(params))
             .await
     }
+
+    /// Get the state of a funds transfer.
+    /// GET /api/v5/asset/transfer-state
+    pub async fn get_transfer_state(
+        &self,
+        params: &GetTransferStateRequest,
+    ) -> OkxResult<Vec<FundsTransferState>> {
+        self.get_signed("/api/v5/asset/transfer-state", Some(params))
+            .await
+    }
+
+    /// Get funding account bills, newest first.
+    /// GET /api/v5/asset/bills
+    pub async fn get_asset_bills(
+        &self,
+        params: &GetAssetBillsRequest,
+    ) -> OkxResult<Vec<AssetBill>> {
+        self.get_signed("/api/v5/asset/bills", Some(params))
+            .await
+    }
+
+    /// Move funds from this (master) account to a sub-account.
+    pub async fn transfer_to_subaccount(
+        &self,
+        sub_acct: &str,
+        ccy: &str,
+        amt: &str,
+        from: TransferAccount,
+        to: TransferAccount,
+    ) -> OkxResult<TransferPoller<'_>> {
+        let type_ = TransferType::MasterToSubAccount;
+        self.typed_transfer(type_, sub_acct, ccy, amt, from, to)
+            .await
+    }
+
+    /// Move funds from a sub-account to this (master) account.
+    pub async fn transfer_from_subaccount(
+        &self,
+        sub_acct: &str,
+        ccy: &str,
+        amt: &str,
+        from: TransferAccount,
+        to: TransferAccount,
+    ) -> OkxResult<TransferPoller<'_>> {
+        let type_ = TransferType::SubAccountToMaster;
+        self.typed_transfer(type_, sub_acct, ccy, amt, from, to)
+            .await
+    }
+
+    async fn typed_transfer(
+        &self,
+        type_: TransferType,
+        sub_acct: &str,
+        ccy: &str,
+        amt: &str,
+        from: TransferAccount,
+        to: TransferAccount,
+    ) -> OkxResult<TransferPoller<'_>> {
+        let request = FundsTransferRequest {
+            ccy: ccy.to_string(),
+            amt: amt.to_string(),
+            from: from.as_str().to_string(),
+            to: to.as_str().to_string(),
+            type_: Some(type_),
+            sub_acct: Some(sub_acct.to_string()),
+            client_id: None,
+        };
+        request.validate()?;
+        let trans_id = self
+            .funds_transfer(&request)
+            .await?
+            .into_iter()
+            .next()
+            .map(|r| r.trans_id)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| {
+                OkxError::UnexpectedResponse("transfer response has no transId".into())
+            })?;
+        Ok(TransferPoller {
+            client: self,
+            trans_id,
+            type_,
+        })
+    }
 }
@@ -0,0 +1,279 @@
+use crate::error::OkxResult;
+use crate::rest::RestClient;
+use crate::types::enums::{AlgoOrderType, MarginMode, PositionSide};
+use crate::types::request::account::GetPositionsRequest;
+use crate::types::request::trade::{
+    CancelAlgoOrderRequest, CancelOrderRequest, ClosePositionRequest, GetAlgoOrderListRequest,
+    GetOrderListRequest,
+};
+use crate::types::response::account::Position;
+use crate::types::response::trade::{AlgoOrderDetails, OrderDetails};
+
+/// Maximum number of orders returned by one orders-pending request.
+const PENDING_PAGE_LIMIT: usize = 100;
+
+/// Maximum number of orders in one cancel-batch-orders request.
+const CANCEL_BATCH_LIMIT: usize = 20;
+
+/// Maximum number of algo orders in one cancel-algos request.
+const CANCEL_ALGO_BATCH_LIMIT: usize = 10;
+
+/// Settings of [`RestClient::emergency_flatten`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlattenOptions {
+    /// Only list what would be cancelled and closed, sending nothing.
+    pub dry_run: bool,
+}
+
+/// What [`RestClient::emergency_flatten`] did, or would do in a dry run.
+#[derive(Debug, Clone, Default)]
+pub struct FlattenReport {
+    pub dry_run: bool,
+    /// Pending algo orders cancelled.
+    pub cancelled_algos: Vec<AlgoOrderDetails>,
+    /// Pending orders cancelled.
+    pub cancelled: Vec<OrderDetails>,
+    /// Positions closed.
+    pub closed: Vec<Position>,
+    /// Orders and positions OKX did not cancel or close.
+    pub failures: Vec<FlattenFailure>,
+}
+
+impl FlattenReport {
+    /// Whether every pending order and algo order was cancelled and every
+    /// position closed.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// An order or position left open by [`RestClient::emergency_flatten`].
+#[derive(Debug, Clone)]
+pub enum FlattenFailure {
+    CancelAlgo { order: AlgoOrderDetails, error: String },
+    Cancel { order: OrderDetails, error: String },
+    Close { position: Position, error: String },
+}
+
+impl RestClient {
+    /// Kill switch: cancel all pending algo orders and orders of every
+    /// instrument type, then close all open positions at market.
+    ///
+    /// Algo orders go first so none triggers while the rest is flattened.
+    /// They are cancelled in batches of 10, orders in batches of 20, and
+    /// positions closed one by one with `POST /api/v5/trade/close-position`.
+    /// Failures of single orders or positions are collected in the report
+    /// instead of stopping the run; only failing to list orders or positions
+    /// returns an error.
+    pub async fn emergency_flatten(&self, options: &FlattenOptions) -> OkxResult<FlattenReport> {
+        let mut report = FlattenReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+
+        let algos = self.all_pending_algo_orders().await?;
+        if options.dry_run {
+            report.cancelled_algos = algos;
+        } else {
+            let (cancelled, failed) = self.cancel_algos(algos).await;
+            report.cancelled_algos = cancelled;
+            report.failures.extend(
+                failed
+                    .into_iter()
+                    .map(|(order, error)| FlattenFailure::CancelAlgo { order, error }),
+            );
+        }
+
+        let orders = self.all_pending_orders().await?;
+        if options.dry_run {
+            report.cancelled = orders;
+        } else {
+            let (cancelled, failed) = self.cancel_orders(orders).await;
+            report.cancelled = cancelled;
+            report.failures.extend(
+                failed
+                    .into_iter()
+                    .map(|(order, error)| FlattenFailure::Cancel { order, error }),
+            );
+        }
+
+        let positions = self
+            .get_positions(&GetPositionsRequest::default())
+            .await?
+            .into_iter()
+            .filter(|p| p.pos.parse::<f64>().is_ok_and(|pos| pos != 0.0));
+        for position in positions {
+            if options.dry_run {
+                report.closed.push(position);
+                continue;
+            }
+            let closed = match close_request(&position) {
+                Ok(req) => self.close_position(&req).await.map_err(|e| e.to_string()),
+                Err(error) => Err(error),
+            };
+            match closed {
+                Ok(_) => report.closed.push(position),
+                Err(error) => report
+                    .failures
+                    .push(FlattenFailure::Close { position, error }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Page through the pending orders of all instrument types.
+    pub(crate) async fn all_pending_orders(&self) -> OkxResult<Vec<OrderDetails>> {
+        let mut orders = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .get_order_list(&GetOrderListRequest {
+                    after: after.take(),
+                    limit: Some(PENDING_PAGE_LIMIT.to_string()),
+                    ..Default::default()
+                })
+                .await?;
+            let done = page.len() < PENDING_PAGE_LIMIT;
+            after = page.last().map(|o| o.ord_id.clone());
+            orders.extend(page);
+            if done || after.is_none() {
+                return Ok(orders);
+            }
+        }
+    }
+
+    /// Page through the pending algo orders of every type.
+    ///
+    /// OKX only lists `conditional` and `oco` together, so the other types
+    /// are listed one at a time.
+    async fn all_pending_algo_orders(&self) -> OkxResult<Vec<AlgoOrderDetails>> {
+        let groups = [
+            vec![AlgoOrderType::Conditional, AlgoOrderType::Oco],
+            vec![AlgoOrderType::Trigger],
+            vec![AlgoOrderType::MoveOrderStop],
+            vec![AlgoOrderType::Iceberg],
+            vec![AlgoOrderType::Twap],
+            vec![AlgoOrderType::Chase],
+        ];
+        let mut orders = Vec::new();
+        for ord_type in groups {
+            let mut after = None;
+            loop {
+                let page = self
+                    .get_algo_order_list(&GetAlgoOrderListRequest {
+                        ord_type: ord_type.clone(),
+                        after: after.take(),
+                        limit: Some(PENDING_PAGE_LIMIT.to_string()),
+                        ..Default::default()
+                    })
+                    .await?;
+                let done = page.len() < PENDING_PAGE_LIMIT;
+                after = page.last().map(|o| o.algo_id.clone());
+                orders.extend(page);
+                if done || after.is_none() {
+                    break;
+                }
+            }
+        }
+        Ok(orders)
+    }
+
+    /// Cancel algo orders in batches of 10, returning the cancelled orders
+    /// and the others with the reason they were not cancelled.
+    async fn cancel_algos(
+        &self,
+        orders: Vec<AlgoOrderDetails>,
+    ) -> (Vec<AlgoOrderDetails>, Vec<(AlgoOrderDetails, String)>) {
+        let mut cancelled = Vec::new();
+        let mut failed = Vec::new();
+        for batch in orders.chunks(CANCEL_ALGO_BATCH_LIMIT) {
+            let requests = batch
+                .iter()
+                .map(|o| CancelAlgoOrderRequest {
+                    inst_id: o.inst_id.clone(),
+                    algo_id: o.algo_id.clone(),
+                })
+                .collect();
+            let resp = match self.cancel_algo_orders_with_meta(&requests).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    failed.extend(batch.iter().map(|o| (o.clone(), e.to_string())));
+                    continue;
+                }
+            };
+            for order in batch {
+                match resp.data.iter().find(|r| r.algo_id == order.algo_id) {
+                    Some(r) if r.s_code == "0" => cancelled.push(order.clone()),
+                    Some(r) => failed.push((order.clone(), format!("{}: {}", r.s_code, r.s_msg))),
+                    None => failed.push((order.clone(), "missing from cancel response".into())),
+                }
+            }
+        }
+        (cancelled, failed)
+    }
+
+    /// Cancel orders in batches of 20, returning the cancelled orders and
+    /// the others with the reason they were not cancelled.
+    pub(crate) async fn cancel_orders(
+        &self,
+        orders: Vec<OrderDetails>,
+    ) -> (Vec<OrderDetails>, Vec<(OrderDetails, String)>) {
+        let mut cancelled = Vec::new();
+        let mut failed = Vec::new();
+        for batch in orders.chunks(CANCEL_BATCH_LIMIT) {
+            let requests = batch
+                .iter()
+                .map(|o| CancelOrderRequest {
+                    inst_id: o.inst_id.clone(),
+                    ord_id: Some(o.ord_id.clone()),
+                    cl_ord_id: None,
+                })
+                .collect();
+            let resp = match self.cancel_multiple_orders_with_meta(&requests).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    failed.extend(batch.iter().map(|o| (o.clone(), e.to_string())));
+                    continue;
+                }
+            };
+            for order in batch {
+                match resp.data.iter().find(|r| r.ord_id == order.ord_id) {
+                    Some(r) if r.s_code == "0" => cancelled.push(order.clone()),
+                    Some(r) => failed.push((order.clone(), format!("{}: {}", r.s_code, r.s_msg))),
+                    None => failed.push((order.clone(), "missing from cancel response".into())),
+                }
+            }
+        }
+        (cancelled, failed)
+    }
+}
+
+/// Market close of the whole position, cancelling any orders left on the
+/// instrument. Fails with the reason if the position cannot be closed.
+fn close_request(position: &Position) -> Result<ClosePositionRequest, String> {
+    let mgn_mode = match position.mgn_mode.as_str() {
+        "cross" => MarginMode::Cross,
+        "isolated" => MarginMode::Isolated,
+        other => {
+            return Err(format!(
+                "unknown margin mode {other:?} of {} position",
+                position.inst_id
+            ))
+        }
+    };
+    let pos_side = match position.pos_side.as_str() {
+        "long" => Some(PositionSide::Long),
+        "short" => Some(PositionSide::Short),
+        _ => None,
+    };
+    Ok(ClosePositionRequest {
+        inst_id: position.inst_id.clone(),
+        mgn_mode,
+        pos_side,
+        ccy: (position.inst_type == "MARGIN" && mgn_mode == MarginMode::Cross)
+            .then(|| position.ccy.clone()),
+        auto_cxl: Some(true),
+        ..Default::default()
+    })
+}
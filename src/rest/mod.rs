@@ -1,15 +1,21 @@
+mod query;
 mod response;
 
 pub mod account;
 pub mod affiliate;
 pub mod algo;
+pub mod archive;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
 pub mod block_trading;
 pub mod broker;
 pub mod convert;
+pub mod download;
 pub mod copy_trading;
 pub mod finance;
 pub mod funding;
 pub mod grid_trading;
+pub mod kill_switch;
 pub mod market;
 pub mod public;
 pub mod signal_bot;
@@ -19,6 +25,8 @@ pub mod system;
 pub mod trade;
 pub mod trading_data;
 
+use std::sync::Arc;
+
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::policies::ExponentialBackoff;
@@ -30,19 +38,32 @@ use serde::Serialize;
 use tracing::instrument;
 
 use crate::auth;
-use crate::config::{ClientConfig, TradingMode};
+use crate::config::{ClientConfig, Credentials, TradingMode};
 use crate::constants;
-use crate::error::{OkxError, OkxResult};
+use crate::error::{OkxError, OkxResult, RequestContext};
+use crate::retry::{PolicyRetryableStrategy, RetryClass};
+use crate::throttle::OrderThrottle;
 
 use self::response::OkxResponse;
+pub use self::response::ApiResponse;
+
+/// Response bodies at least this large are parsed on a blocking thread.
+const LARGE_BODY_THRESHOLD: usize = 256 * 1024;
 
 /// HTTP REST client for the OKX API v5.
 ///
 /// Provides methods covering all OKX REST endpoints, organized by domain.
 /// Methods are defined in domain-specific files (e.g., `trade.rs`, `account.rs`).
 pub struct RestClient {
+    /// Client for idempotent requests, retried on transport failures.
     http: ClientWithMiddleware,
+    /// Client for requests that may have side effects. A POST that timed
+    /// out may still have been executed, so these are never resent blind.
+    http_once: ClientWithMiddleware,
     config: ClientConfig,
+    throttle: Option<OrderThrottle>,
+    /// Signing key parsed from the configured credentials.
+    signer: Option<Arc<auth::Signer>>,
 }
 
 impl RestClient {
@@ -51,29 +72,92 @@ impl RestClient {
         let mut default_headers = HeaderMap::new();
         default_headers.insert("Content-Type", HeaderValue::from_static("application/json"));
         default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+        default_headers.extend(config.custom_headers()?);
+
+        let builder = reqwest::Client::builder().default_headers(default_headers);
+        // In the browser, timeouts, pooling, compression and TLS are left
+        // to the fetch API.
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = crate::tls::configure_reqwest(
+            builder
+                .timeout(config.request_timeout)
+                .pool_max_idle_per_host(10)
+                .gzip(config.compression)
+                .deflate(config.compression),
+            &config.tls,
+        )?;
+        let client = builder.build().map_err(OkxError::Http)?;
+
+        let policy = &config.retry_policy;
+        let backoff = ExponentialBackoff::builder()
+            .retry_bounds(policy.min_backoff, policy.max_backoff)
+            .build_with_max_retries(policy.max_retries);
+
+        let http = ClientBuilder::new(client.clone())
+            .with(TracingMiddleware::default())
+            .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+                backoff,
+                PolicyRetryableStrategy::new(policy.clone()),
+            ))
+            .build();
+        let http_once = ClientBuilder::new(client)
+            .with(TracingMiddleware::default())
+            .build();
 
-        if config.trading_mode == TradingMode::Demo {
-            default_headers.insert(
-                constants::HEADER_SIMULATED_TRADING,
-                HeaderValue::from_static("1"),
-            );
-        }
+        let throttle = config.order_throttle.clone().map(OrderThrottle::new);
+        let signer = Self::signer(&config)?;
 
-        let client = reqwest::Client::builder()
-            .default_headers(default_headers)
-            .timeout(config.request_timeout)
-            .pool_max_idle_per_host(10)
-            .build()
-            .map_err(OkxError::Http)?;
+        Ok(Self {
+            http,
+            http_once,
+            config,
+            throttle,
+            signer,
+        })
+    }
 
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+    /// A client for the other trading mode that shares this client's
+    /// connection pool.
+    ///
+    /// The `x-simulated-trading` header is sent per request, so live and
+    /// demo clients can be used side by side. Demo trading needs API keys
+    /// created for it; pair with [`with_credentials`](Self::with_credentials)
+    /// for private endpoints. The order throttle, if any, starts empty.
+    pub fn with_trading_mode(&self, mode: TradingMode) -> Self {
+        let mut config = self.config.clone();
+        config.trading_mode = mode;
+        Self {
+            http: self.http.clone(),
+            http_once: self.http_once.clone(),
+            throttle: config.order_throttle.clone().map(OrderThrottle::new),
+            signer: self.signer.clone(),
+            config,
+        }
+    }
 
-        let http = ClientBuilder::new(client)
-            .with(TracingMiddleware::default())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
+    /// A client signing with other credentials that shares this client's
+    /// connection pool. The order throttle, if any, starts empty.
+    pub fn with_credentials(&self, credentials: Credentials) -> OkxResult<Self> {
+        let mut config = self.config.clone();
+        config.credentials = Some(credentials);
+        Ok(Self {
+            http: self.http.clone(),
+            http_once: self.http_once.clone(),
+            throttle: config.order_throttle.clone().map(OrderThrottle::new),
+            signer: Self::signer(&config)?,
+            config,
+        })
+    }
 
-        Ok(Self { http, config })
+    fn signer(config: &ClientConfig) -> OkxResult<Option<Arc<auth::Signer>>> {
+        config
+            .credentials
+            .as_ref()
+            .map(|creds| {
+                auth::Signer::new(&creds.api_secret, config.effective_signing_algorithm())
+                    .map(Arc::new)
+            })
+            .transpose()
     }
 
     /// Create a `RestClient` with default configuration (unauthenticated, global, live).
@@ -94,11 +178,16 @@ impl RestClient {
         &self.config
     }
 
+    /// Returns the local order throttle, if enabled.
+    pub(crate) fn throttle(&self) -> Option<&OrderThrottle> {
+        self.throttle.as_ref()
+    }
+
     /// Generate an ISO 8601 timestamp for REST signing.
     fn timestamp() -> OkxResult<String> {
         // Use system time to build an ISO 8601 timestamp.
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
+        let now = crate::time::SystemTime::now()
+            .duration_since(crate::time::UNIX_EPOCH)
             .map_err(|_| OkxError::Config("system time is before Unix epoch".into()))?;
         let secs = now.as_secs();
         let millis = now.subsec_millis();
@@ -132,7 +221,11 @@ impl RestClient {
             .as_ref()
             .ok_or_else(|| OkxError::Auth("Credentials required for private endpoint".into()))?;
 
-        let signature = auth::sign_rest(timestamp, method, endpoint, body, &creds.api_secret)?;
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| OkxError::Auth("Credentials required for private endpoint".into()))?;
+        let signature = signer.sign_rest(timestamp, method, endpoint, body);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -160,36 +253,18 @@ impl RestClient {
     }
 
     /// Serialize query parameters to a query string (e.g., `?key=val&key2=val2`).
+    ///
+    /// Keys keep the declaration order of the parameter type.
     fn serialize_query_string<P: Serialize>(params: &P) -> OkxResult<String> {
-        let value = serde_json::to_value(params)?;
-        if let serde_json::Value::Object(map) = value {
-            let pairs: Vec<String> = map
-                .into_iter()
-                .filter(|(_, v)| !v.is_null())
-                .map(|(k, v)| {
-                    let val = match v {
-                        serde_json::Value::String(s) => s,
-                        other => other.to_string(),
-                    };
-                    format!("{}={}", k, urlencoding::encode(&val))
-                })
-                .collect();
-            if pairs.is_empty() {
-                Ok(String::new())
-            } else {
-                Ok(format!("?{}", pairs.join("&")))
-            }
-        } else {
-            Ok(String::new())
-        }
+        Ok(query::encode(&query::to_pairs(params)?))
     }
 
 
     /// Public GET request.
-    #[instrument(skip(self, params), fields(endpoint))]
+    #[instrument(skip(self, params), fields(endpoint, request_id = tracing::field::Empty))]
     pub(crate) async fn get<T, P>(&self, endpoint: &str, params: Option<&P>) -> OkxResult<Vec<T>>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
         P: Serialize,
     {
         let url = format!("{}{}", self.base_url(), endpoint);
@@ -202,46 +277,39 @@ impl RestClient {
             }
         }
 
-        let response = request.send().await?;
-        let body = response.text().await.map_err(OkxError::Http)?;
-        let parsed: OkxResponse<Vec<T>> = serde_json::from_str(&body)?;
-        parsed.into_result()
+        self.execute("GET", endpoint, request).await
     }
 
     /// Public POST request.
-    #[instrument(skip(self, params), fields(endpoint))]
+    #[instrument(skip(self, params), fields(endpoint, request_id = tracing::field::Empty))]
     #[allow(dead_code)]
     pub(crate) async fn post<T, P>(&self, endpoint: &str, params: &P) -> OkxResult<Vec<T>>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
         P: Serialize,
     {
         let url = format!("{}{}", self.base_url(), endpoint);
         let body = serde_json::to_string(params)?;
 
-        let response = self
-            .http
+        let request = self
+            .http_once
             .post(&url)
             .header("Content-Type", "application/json")
-            .body(body)
-            .send()
-            .await?;
+            .body(body);
 
-        let resp_body = response.text().await.map_err(OkxError::Http)?;
-        let parsed: OkxResponse<Vec<T>> = serde_json::from_str(&resp_body)?;
-        parsed.into_result()
+        self.execute("POST", endpoint, request).await
     }
 
 
     /// Signed GET request (for private endpoints).
-    #[instrument(skip(self, params), fields(endpoint))]
+    #[instrument(skip(self, params), fields(endpoint, request_id = tracing::field::Empty))]
     pub(crate) async fn get_signed<T, P>(
         &self,
         endpoint: &str,
         params: Option<&P>,
     ) -> OkxResult<Vec<T>>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
         P: Serialize,
     {
         let timestamp = Self::timestamp()?;
@@ -251,78 +319,222 @@ impl RestClient {
             String::new()
         };
 
-        let auth_headers = self.auth_headers(&timestamp, "GET", endpoint, &qs)?;
-        let url = format!("{}{}{}", self.base_url(), endpoint, qs);
+        // Sign the query exactly as it appears in the parsed URL that is sent,
+        // so any normalization by the URL parser is reflected in the signature.
+        let url = reqwest::Url::parse(&format!("{}{}{}", self.base_url(), endpoint, qs))?;
+        let signed_query = url.query().map(|q| format!("?{q}")).unwrap_or_default();
+        let auth_headers = self.auth_headers(&timestamp, "GET", endpoint, &signed_query)?;
 
-        let response = self.http.get(&url).headers(auth_headers).send().await?;
+        let request = self.http.get(url).headers(auth_headers);
 
-        let body = response.text().await.map_err(OkxError::Http)?;
-        let parsed: OkxResponse<Vec<T>> = serde_json::from_str(&body)?;
-        parsed.into_result()
+        self.execute("GET", endpoint, request).await
     }
 
     /// Signed POST request (for private endpoints).
-    /// Auto-injects the program ID tag into the request body.
-    #[instrument(skip(self, params), fields(endpoint))]
+    /// Auto-injects the configured tag into the request body, see
+    /// [`ClientConfig::tag_request`]. The tag provider only applies to
+    /// [`constants::ORDER_ENDPOINTS`].
+    #[instrument(skip(self, params), fields(endpoint, request_id = tracing::field::Empty))]
     pub(crate) async fn post_signed<T, P>(
         &self,
         endpoint: &str,
         params: &P,
     ) -> OkxResult<Vec<T>>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
         P: Serialize,
     {
+        let request = self.signed_post_request(endpoint, params)?;
+        self.execute("POST", endpoint, request).await
+    }
+
+    /// Signed POST request that keeps the top-level `code` and `msg`, for
+    /// batch endpoints that report partial success.
+    #[instrument(skip(self, params), fields(endpoint, request_id = tracing::field::Empty))]
+    pub(crate) async fn post_signed_with_meta<T, P>(
+        &self,
+        endpoint: &str,
+        params: &P,
+    ) -> OkxResult<ApiResponse<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        P: Serialize,
+    {
+        let request = self.signed_post_request(endpoint, params)?;
+        self.execute_with("POST", endpoint, request, OkxResponse::into_meta)
+            .await
+    }
+
+    fn signed_post_request<P: Serialize>(
+        &self,
+        endpoint: &str,
+        params: &P,
+    ) -> OkxResult<reqwest_middleware::RequestBuilder> {
         let timestamp = Self::timestamp()?;
-        let body = inject_program_tag(&serde_json::to_value(params)?)?;
+        let mut body = serde_json::to_value(params)?;
+        if constants::ORDER_ENDPOINTS.contains(&endpoint) {
+            self.config.tag_request(&mut body)?;
+        } else {
+            self.config.tag_program(&mut body)?;
+        }
+        let body = serde_json::to_string(&body)?;
 
         let auth_headers = self.auth_headers(&timestamp, "POST", endpoint, &body)?;
         let url = format!("{}{}", self.base_url(), endpoint);
 
-        let response = self
-            .http
+        Ok(self
+            .http_once
             .post(&url)
             .headers(auth_headers)
             .header("Content-Type", "application/json")
-            .body(body)
-            .send()
-            .await?;
+            .body(body))
+    }
 
-        let resp_body = response.text().await.map_err(OkxError::Http)?;
-        let parsed: OkxResponse<Vec<T>> = serde_json::from_str(&resp_body)?;
-        parsed.into_result()
+    /// Send a request and unwrap the OKX response envelope.
+    ///
+    /// Failures are wrapped in [`OkxError::Request`] with the endpoint, HTTP
+    /// status, request ID, and body snippet. The request ID is also recorded
+    /// on the current span.
+    async fn execute<T>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request: reqwest_middleware::RequestBuilder,
+    ) -> OkxResult<Vec<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.execute_with(method, endpoint, request, OkxResponse::into_result)
+            .await
     }
-}
 
-/// Inject the OKX program ID tag into a JSON value.
-/// If the value is an object, adds `"tag": PROGRAM_ID"`.
-/// If the value is an array, injects into each element.
-fn inject_program_tag(value: &serde_json::Value) -> OkxResult<String> {
-    let mut val = value.clone();
-    match &mut val {
-        serde_json::Value::Object(map) => {
-            if !map.contains_key("tag") {
-                map.insert(
-                    "tag".to_string(),
-                    serde_json::Value::String(constants::PROGRAM_ID.to_string()),
-                );
+    /// Like [`execute`](Self::execute), unwrapping the envelope with `finish`.
+    ///
+    /// Transport failures are retried by the middleware for GETs only. Error
+    /// codes in the response body are classified here: GETs are resent for
+    /// any class the retry policy allows, other methods only for rate limit
+    /// and maintenance rejections, which OKX returns before executing.
+    async fn execute_with<T, R>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request: reqwest_middleware::RequestBuilder,
+        finish: fn(OkxResponse<Vec<T>>) -> OkxResult<R>,
+    ) -> OkxResult<R>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let policy = &self.config.retry_policy;
+        let mut request = if self.config.trading_mode == TradingMode::Demo {
+            request.header(constants::HEADER_SIMULATED_TRADING, "1")
+        } else {
+            request
+        };
+        let mut attempt = 0;
+        loop {
+            let retry = request.try_clone();
+            let result = self.send_once(method, endpoint, request, finish).await;
+            let class = match &result {
+                Err(e) => match e.root() {
+                    OkxError::Api { code, .. } => RetryClass::from_okx_code(code),
+                    _ => return result,
+                },
+                Ok(_) => return result,
+            };
+            let resendable = method == "GET"
+                || matches!(class, RetryClass::RateLimited | RetryClass::Maintenance);
+            match retry {
+                Some(next)
+                    if resendable && attempt < policy.max_retries && policy.should_retry(class) =>
+                {
+                    tracing::debug!(?class, attempt, "Retrying {method} {endpoint}");
+                    crate::time::sleep(policy.backoff(attempt)).await;
+                    attempt += 1;
+                    request = next;
+                }
+                _ => return result,
             }
         }
-        serde_json::Value::Array(arr) => {
-            for item in arr.iter_mut() {
-                if let serde_json::Value::Object(map) = item {
-                    if !map.contains_key("tag") {
-                        map.insert(
-                            "tag".to_string(),
-                            serde_json::Value::String(constants::PROGRAM_ID.to_string()),
-                        );
-                    }
-                }
+    }
+
+    /// Send a request once and unwrap the OKX response envelope.
+    async fn send_once<T, R>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request: reqwest_middleware::RequestBuilder,
+        finish: fn(OkxResponse<Vec<T>>) -> OkxResult<R>,
+    ) -> OkxResult<R>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let context = RequestContext::new(method, endpoint);
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let context = match e.status() {
+                    Some(status) => context.status(status.as_u16()),
+                    None => context,
+                };
+                return Err(context.wrap(e));
             }
+        };
+
+        let mut context = context.status(response.status().as_u16());
+        if let Some(request_id) = request_id(response.headers()) {
+            tracing::Span::current().record("request_id", request_id.as_str());
+            context = context.request_id(request_id);
         }
-        _ => {}
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(e) => return Err(context.wrap(e)),
+        };
+
+        // Large bodies (instrument lists, full books) are parsed off the
+        // async runtime so they do not stall other tasks.
+        let (parsed, body) = if body.len() >= LARGE_BODY_THRESHOLD {
+            parse_blocking::<T>(body).await
+        } else {
+            (response::parse_body::<T>(&body), body)
+        };
+
+        parsed
+            .and_then(finish)
+            .map_err(|e| context.body(&body).wrap(e))
     }
-    Ok(serde_json::to_string(&val)?)
+}
+
+/// Parse a response body on a blocking thread, handing the body back for
+/// error context.
+#[cfg(not(target_arch = "wasm32"))]
+async fn parse_blocking<T>(body: String) -> (OkxResult<OkxResponse<Vec<T>>>, String)
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || (response::parse_body::<T>(&body), body))
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
+
+/// The browser has no blocking threads, so the body is parsed in place.
+#[cfg(target_arch = "wasm32")]
+async fn parse_blocking<T>(body: String) -> (OkxResult<OkxResponse<Vec<T>>>, String)
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    (response::parse_body::<T>(&body), body)
+}
+
+/// First request identification header OKX sent back, for support
+/// escalation.
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    constants::REQUEST_ID_HEADERS.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    })
 }
 
 /// Convert days since Unix epoch to (year, month, day).
@@ -390,11 +602,21 @@ mod tests {
         assert_eq!(qs, "?instId=BTC-USDT");
     }
 
+    /// Body of a signed POST to `endpoint` as it would be sent.
+    fn signed_body(
+        builder: crate::config::ClientConfigBuilder,
+        endpoint: &str,
+        val: &serde_json::Value,
+    ) -> serde_json::Value {
+        let client = RestClient::new(builder.credentials("key", "secret", "pass").build()).unwrap();
+        let request = client.signed_post_request(endpoint, val).unwrap().build().unwrap();
+        serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap()
+    }
+
     #[test]
     fn test_inject_program_tag_object() {
         let val = serde_json::json!({"instId": "BTC-USDT", "sz": "1"});
-        let result = inject_program_tag(&val).unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let parsed = signed_body(Default::default(), "/api/v5/trade/order", &val);
         assert_eq!(
             parsed["tag"],
             serde_json::Value::String(constants::PROGRAM_ID.to_string())
@@ -404,8 +626,7 @@ mod tests {
     #[test]
     fn test_inject_program_tag_array() {
         let val = serde_json::json!([{"instId": "BTC-USDT"}, {"instId": "ETH-USDT"}]);
-        let result = inject_program_tag(&val).unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let parsed = signed_body(Default::default(), "/api/v5/trade/batch-orders", &val);
         assert_eq!(
             parsed[0]["tag"],
             serde_json::Value::String(constants::PROGRAM_ID.to_string())
@@ -419,11 +640,29 @@ mod tests {
     #[test]
     fn test_inject_program_tag_preserves_existing() {
         let val = serde_json::json!({"instId": "BTC-USDT", "tag": "custom"});
-        let result = inject_program_tag(&val).unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let parsed = signed_body(Default::default(), "/api/v5/trade/order", &val);
         assert_eq!(
             parsed["tag"],
             serde_json::Value::String("custom".to_string())
         );
     }
+
+    #[test]
+    fn test_inject_program_tag_disabled() {
+        let val = serde_json::json!({"instId": "BTC-USDT"});
+        let builder = crate::config::ClientConfigBuilder::new().disable_program_tag();
+        let parsed = signed_body(builder, "/api/v5/trade/order", &val);
+        assert_eq!(parsed, val);
+    }
+
+    #[test]
+    fn test_tag_provider_only_tags_order_endpoints() {
+        let provider =
+            || crate::config::ClientConfigBuilder::new().tag_provider(|_| Some("strategy1".into()));
+        let val = serde_json::json!({"instId": "BTC-USDT"});
+        let order = signed_body(provider(), "/api/v5/trade/order", &val);
+        assert_eq!(order["tag"], "strategy1");
+        let leverage = signed_body(provider(), "/api/v5/account/set-leverage", &val);
+        assert_eq!(leverage["tag"], constants::PROGRAM_ID);
+    }
 }
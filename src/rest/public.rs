@@ -1,5 +1,6 @@
-use crate::error::OkxResult;
+use crate::error::{OkxError, OkxResult};
 use crate::rest::RestClient;
+use crate::time::now_ms;
 use crate::types::request::public::*;
 use crate::types::response::public::*;
 
@@ -147,4 +148,17 @@ impl RestClient {
     pub async fn get_server_time(&self) -> OkxResult<Vec<ServerTime>> {
         self.get::<ServerTime, ()>("/api/v5/public/time", None).await
     }
+
+    /// Server clock minus local clock in milliseconds, measured against the
+    /// midpoint of a [`get_server_time`](Self::get_server_time) round trip.
+    pub async fn server_time_offset(&self) -> OkxResult<i64> {
+        let sent = now_ms();
+        let times = self.get_server_time().await?;
+        let received = now_ms();
+        let server: i64 = times
+            .first()
+            .and_then(|t| t.ts.parse().ok())
+            .ok_or_else(|| OkxError::UnexpectedResponse("server time response has no ts".into()))?;
+        Ok(server - (sent + received) / 2)
+    }
 }
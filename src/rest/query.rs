@@ -0,0 +1,337 @@
+//! Query string serialization for GET parameters.
+//!
+//! Parameters are emitted in declaration order (struct fields) or insertion
+//! order (maps), so the signed query string matches what the request type
+//! describes. `None` and `null` values are skipped and sequences become
+//! repeated keys (`instId=A&instId=B`).
+
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize};
+use serde_json::Value;
+
+use crate::error::{OkxError, OkxResult};
+
+/// Serialize `params` into ordered `(key, value)` pairs.
+pub(crate) fn to_pairs<P: Serialize + ?Sized>(params: &P) -> OkxResult<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    params.serialize(PairSerializer { pairs: &mut pairs })?;
+    Ok(pairs)
+}
+
+/// Encode pairs as `?key=val&key2=val2`, or an empty string if there are none.
+pub(crate) fn encode(pairs: &[(String, String)]) -> String {
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let joined: Vec<String> = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect();
+    format!("?{}", joined.join("&"))
+}
+
+fn push_value(pairs: &mut Vec<(String, String)>, key: &str, value: Value) {
+    match value {
+        Value::Null => {}
+        Value::String(s) => pairs.push((key.to_string(), s)),
+        Value::Array(items) => {
+            for item in items {
+                push_value(pairs, key, item);
+            }
+        }
+        other => pairs.push((key.to_string(), other.to_string())),
+    }
+}
+
+/// Error raised while serializing parameters, turned into
+/// [`OkxError::Config`] by [`to_pairs`].
+#[derive(Debug)]
+struct QueryError(String);
+
+type QueryResult<T> = Result<T, QueryError>;
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl ser::Error for QueryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        QueryError(format!("invalid query parameters: {msg}"))
+    }
+}
+
+impl From<serde_json::Error> for QueryError {
+    fn from(err: serde_json::Error) -> Self {
+        <QueryError as ser::Error>::custom(err)
+    }
+}
+
+impl From<QueryError> for OkxError {
+    fn from(err: QueryError) -> Self {
+        OkxError::Config(err.0)
+    }
+}
+
+fn unsupported(kind: &str) -> QueryError {
+    QueryError(format!(
+        "query parameters must be a struct or map, got {kind}"
+    ))
+}
+
+/// Top-level serializer: accepts structs and maps, plus unit and `None`
+/// for "no parameters".
+struct PairSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+}
+
+struct PairFields<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    key: Option<String>,
+}
+
+impl<'a> ser::Serializer for PairSerializer<'a> {
+    type Ok = ();
+    type Error = QueryError;
+    type SerializeSeq = Impossible<(), QueryError>;
+    type SerializeTuple = Impossible<(), QueryError>;
+    type SerializeTupleStruct = Impossible<(), QueryError>;
+    type SerializeTupleVariant = Impossible<(), QueryError>;
+    type SerializeMap = PairFields<'a>;
+    type SerializeStruct = PairFields<'a>;
+    type SerializeStructVariant = Impossible<(), QueryError>;
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> QueryResult<Self::SerializeStruct> {
+        Ok(PairFields {
+            pairs: self.pairs,
+            key: None,
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> QueryResult<Self::SerializeMap> {
+        Ok(PairFields {
+            pairs: self.pairs,
+            key: None,
+        })
+    }
+
+    fn serialize_unit(self) -> QueryResult<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> QueryResult<()> {
+        Ok(())
+    }
+
+    fn serialize_none(self) -> QueryResult<()> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> QueryResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> QueryResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _: bool) -> QueryResult<()> {
+        Err(unsupported("bool"))
+    }
+
+    fn serialize_i8(self, _: i8) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_i16(self, _: i16) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_i32(self, _: i32) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_i64(self, _: i64) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_u8(self, _: u8) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_u16(self, _: u16) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_u32(self, _: u32) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_u64(self, _: u64) -> QueryResult<()> {
+        Err(unsupported("integer"))
+    }
+
+    fn serialize_f32(self, _: f32) -> QueryResult<()> {
+        Err(unsupported("float"))
+    }
+
+    fn serialize_f64(self, _: f64) -> QueryResult<()> {
+        Err(unsupported("float"))
+    }
+
+    fn serialize_char(self, _: char) -> QueryResult<()> {
+        Err(unsupported("char"))
+    }
+
+    fn serialize_str(self, _: &str) -> QueryResult<()> {
+        Err(unsupported("string"))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> QueryResult<()> {
+        Err(unsupported("bytes"))
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> QueryResult<()> {
+        Err(unsupported("enum"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> QueryResult<()> {
+        Err(unsupported("enum"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> QueryResult<Self::SerializeSeq> {
+        Err(unsupported("sequence"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> QueryResult<Self::SerializeTuple> {
+        Err(unsupported("tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> QueryResult<Self::SerializeTupleStruct> {
+        Err(unsupported("tuple"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> QueryResult<Self::SerializeTupleVariant> {
+        Err(unsupported("enum"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> QueryResult<Self::SerializeStructVariant> {
+        Err(unsupported("enum"))
+    }
+}
+
+impl ser::SerializeStruct for PairFields<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> QueryResult<()> {
+        push_value(self.pairs, key, serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for PairFields<'_> {
+    type Ok = ();
+    type Error = QueryError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> QueryResult<()> {
+        self.key = Some(match serde_json::to_value(key)? {
+            Value::String(s) => s,
+            other => other.to_string(),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> QueryResult<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <QueryError as ser::Error>::custom("map value without key"))?;
+        push_value(self.pairs, &key, serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[test]
+    fn keeps_declaration_order_and_repeats_sequences() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            inst_type: &'static str,
+            after: Option<u64>,
+            inst_id: Vec<&'static str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            ccy: Option<String>,
+            limit: u32,
+        }
+
+        let pairs = to_pairs(&Params {
+            inst_type: "SWAP",
+            after: None,
+            inst_id: vec!["BTC-USDT-SWAP", "ETH-USDT-SWAP"],
+            ccy: None,
+            limit: 10,
+        })
+        .unwrap();
+        assert_eq!(
+            encode(&pairs),
+            "?instType=SWAP&instId=BTC-USDT-SWAP&instId=ETH-USDT-SWAP&limit=10"
+        );
+    }
+
+    #[test]
+    fn accepts_maps_and_empty_params() {
+        let pairs = to_pairs(&serde_json::json!({"ccy": "BTC,ETH", "amt": 1.5})).unwrap();
+        assert_eq!(encode(&pairs), "?amt=1.5&ccy=BTC%2CETH");
+        assert_eq!(encode(&to_pairs(&()).unwrap()), "");
+        assert!(matches!(to_pairs(&"instId"), Err(OkxError::Config(_))));
+    }
+}
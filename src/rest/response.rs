@@ -1,6 +1,7 @@
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
-use crate::error::OkxError;
+use crate::error::{OkxError, OkxResult};
 
 /// Raw OKX API response wrapper.
 ///
@@ -31,6 +32,70 @@ impl<T> OkxResponse<T> {
     }
 }
 
+/// Top-level codes of batch endpoints: "0" all items succeeded, "1" all
+/// failed, "2" some failed. Per-item results carry their own `sCode`.
+const BATCH_CODES: [&str; 3] = ["0", "1", "2"];
+
+/// Response data together with the top-level `code` and `msg`.
+///
+/// Returned by the `*_with_meta` batch methods, which succeed on partial
+/// failures so each item's `sCode` can be inspected.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ApiResponse<T> {
+    pub code: String,
+    pub msg: String,
+    pub data: Vec<T>,
+}
+
+impl<T> ApiResponse<T> {
+    /// Whether every item succeeded.
+    pub fn is_success(&self) -> bool {
+        self.code == "0"
+    }
+
+    /// Whether some, but not all, items failed.
+    pub fn is_partial(&self) -> bool {
+        self.code == "2"
+    }
+}
+
+impl<T> OkxResponse<Vec<T>> {
+    /// Keep the top-level code and message of a batch response. Codes other
+    /// than the batch codes are still errors.
+    pub fn into_meta(self) -> Result<ApiResponse<T>, OkxError> {
+        if BATCH_CODES.contains(&self.code.as_str()) {
+            Ok(ApiResponse {
+                code: self.code,
+                msg: self.msg,
+                data: self.data,
+            })
+        } else {
+            Err(OkxError::Api {
+                code: self.code,
+                msg: self.msg,
+            })
+        }
+    }
+}
+
+/// Parse a REST response body.
+///
+/// With the `simd-json` feature the body is parsed with simd-json; on
+/// failure it is re-parsed with serde_json so the error is the same either
+/// way.
+pub(crate) fn parse_body<T: DeserializeOwned>(body: &str) -> OkxResult<OkxResponse<Vec<T>>> {
+    #[cfg(feature = "simd-json")]
+    {
+        let mut bytes = body.as_bytes().to_vec();
+        if let Ok(parsed) = simd_json::serde::from_slice(&mut bytes) {
+            return Ok(parsed);
+        }
+    }
+
+    serde_json::from_str(body).map_err(|e| OkxError::deserialize::<T>(e, body))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,6 +108,33 @@ mod tests {
         assert_eq!(data.len(), 1);
     }
 
+    #[test]
+    fn parse_body_reports_type_on_failure() {
+        let json = r#"{"code":"0","msg":"","data":[{"balance":1}]}"#;
+        let resp = parse_body::<std::collections::HashMap<String, String>>(json);
+        assert!(matches!(resp, Err(OkxError::Deserialize { .. })));
+
+        let json = r#"{"code":"0","msg":"","data":[{"balance":"1"}]}"#;
+        let data = parse_body::<std::collections::HashMap<String, String>>(json)
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert_eq!(data[0]["balance"], "1");
+    }
+
+    #[test]
+    fn into_meta_keeps_batch_codes() {
+        let json = r#"{"code":"2","msg":"Partially successful","data":[{"sCode":"0"},{"sCode":"51008"}]}"#;
+        let resp: OkxResponse<Vec<serde_json::Value>> = serde_json::from_str(json).unwrap();
+        let meta = resp.into_meta().unwrap();
+        assert!(meta.is_partial() && !meta.is_success());
+        assert_eq!(meta.data.len(), 2);
+
+        let json = r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#;
+        let resp: OkxResponse<Vec<serde_json::Value>> = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.into_meta().unwrap_err().api_code(), Some("50011"));
+    }
+
     #[test]
     fn test_error_response() {
         let json = r#"{"code":"51008","msg":"Order failed. Insufficient balance.","data":[]}"#;
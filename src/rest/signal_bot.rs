@@ -1,5 +1,7 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::request::signal_bot::*;
+use crate::types::response::signal_bot::*;
 
 impl RestClient {
 
@@ -7,18 +9,15 @@ impl RestClient {
     /// POST /api/v5/tradingBot/signal/create-signal
     pub async fn create_signal(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &CreateSignalRequest,
+    ) -> OkxResult<Vec<CreatedSignal>> {
         self.post_signed("/api/v5/tradingBot/signal/create-signal", params)
             .await
     }
 
     /// Get signals.
     /// GET /api/v5/tradingBot/signal/signals
-    pub async fn get_signals(
-        &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    pub async fn get_signals(&self, params: &GetSignalsRequest) -> OkxResult<Vec<Signal>> {
         self.get_signed("/api/v5/tradingBot/signal/signals", Some(params))
             .await
     }
@@ -27,19 +26,39 @@ impl RestClient {
     /// POST /api/v5/tradingBot/signal/order-algo
     pub async fn create_signal_bot(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &CreateSignalBotRequest,
+    ) -> OkxResult<Vec<SignalBotResult>> {
         self.post_signed("/api/v5/tradingBot/signal/order-algo", params)
             .await
     }
 
-    /// Stop a signal bot order.
+    /// Stop signal bot orders. Maximum 10 bots per request.
     /// POST /api/v5/tradingBot/signal/stop-order-algo
     pub async fn stop_signal_bot(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
-        self.post_signed("/api/v5/tradingBot/signal/stop-order-algo", params)
+        params: &[SignalBotId],
+    ) -> OkxResult<Vec<SignalBotResult>> {
+        self.post_signed("/api/v5/tradingBot/signal/stop-order-algo", &params)
+            .await
+    }
+
+    /// Add or reduce the margin of a signal bot.
+    /// POST /api/v5/tradingBot/signal/margin-balance
+    pub async fn adjust_signal_bot_margin(
+        &self,
+        params: &SignalMarginBalanceRequest,
+    ) -> OkxResult<Vec<SignalBotAmendResult>> {
+        self.post_signed("/api/v5/tradingBot/signal/margin-balance", params)
+            .await
+    }
+
+    /// Amend the take-profit / stop-loss settings of a signal bot.
+    /// POST /api/v5/tradingBot/signal/amendTPSL
+    pub async fn amend_signal_bot_tp_sl(
+        &self,
+        params: &SignalAmendTpSlRequest,
+    ) -> OkxResult<Vec<SignalBotAmendResult>> {
+        self.post_signed("/api/v5/tradingBot/signal/amendTPSL", params)
             .await
     }
 
@@ -47,8 +66,8 @@ impl RestClient {
     /// GET /api/v5/tradingBot/signal/orders-algo-pending
     pub async fn get_signal_bot_order_list(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetSignalBotOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotOrder>> {
         self.get_signed(
             "/api/v5/tradingBot/signal/orders-algo-pending",
             Some(params),
@@ -60,8 +79,8 @@ impl RestClient {
     /// GET /api/v5/tradingBot/signal/orders-algo-history
     pub async fn get_signal_bot_order_history(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetSignalBotOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotOrder>> {
         self.get_signed(
             "/api/v5/tradingBot/signal/orders-algo-history",
             Some(params),
@@ -69,13 +88,56 @@ impl RestClient {
         .await
     }
 
+    /// Get the open positions of a signal bot.
+    /// GET /api/v5/tradingBot/signal/positions
+    pub async fn get_signal_bot_positions(
+        &self,
+        params: &GetSignalBotPositionsRequest,
+    ) -> OkxResult<Vec<SignalBotPosition>> {
+        self.get_signed("/api/v5/tradingBot/signal/positions", Some(params))
+            .await
+    }
+
+    /// Get the closed positions of a signal bot.
+    /// GET /api/v5/tradingBot/signal/positions-history
+    pub async fn get_signal_bot_positions_history(
+        &self,
+        params: &GetSignalBotPositionsHistoryRequest,
+    ) -> OkxResult<Vec<SignalBotPositionHistory>> {
+        self.get_signed(
+            "/api/v5/tradingBot/signal/positions-history",
+            Some(params),
+        )
+        .await
+    }
+
     /// Get signal bot sub-orders.
     /// GET /api/v5/tradingBot/signal/sub-orders
     pub async fn get_signal_bot_sub_orders(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetSignalBotSubOrdersRequest,
+    ) -> OkxResult<Vec<SignalBotSubOrder>> {
         self.get_signed("/api/v5/tradingBot/signal/sub-orders", Some(params))
             .await
     }
+
+    /// Cancel a pending sub-order of a signal bot.
+    /// POST /api/v5/tradingBot/signal/cancel-sub-order
+    pub async fn cancel_signal_bot_sub_order(
+        &self,
+        params: &CancelSignalSubOrderRequest,
+    ) -> OkxResult<Vec<CancelledSignalSubOrder>> {
+        self.post_signed("/api/v5/tradingBot/signal/cancel-sub-order", params)
+            .await
+    }
+
+    /// Get the signal event history of a signal bot.
+    /// GET /api/v5/tradingBot/signal/event-history
+    pub async fn get_signal_bot_event_history(
+        &self,
+        params: &GetSignalBotEventHistoryRequest,
+    ) -> OkxResult<Vec<SignalBotEvent>> {
+        self.get_signed("/api/v5/tradingBot/signal/event-history", Some(params))
+            .await
+    }
 }
@@ -1,5 +1,8 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::request::spread_trading::{
+    SpreadAmendRequest, SpreadCancelRequest, SpreadOrderRequest,
+};
 
 impl RestClient {
 
@@ -7,7 +10,7 @@ impl RestClient {
     /// POST /api/v5/sprd/order
     pub async fn place_spread_order(
         &self,
-        params: &serde_json::Value,
+        params: &SpreadOrderRequest,
     ) -> OkxResult<Vec<serde_json::Value>> {
         self.post_signed("/api/v5/sprd/order", params).await
     }
@@ -16,11 +19,20 @@ impl RestClient {
     /// POST /api/v5/sprd/cancel-order
     pub async fn cancel_spread_order(
         &self,
-        params: &serde_json::Value,
+        params: &SpreadCancelRequest,
     ) -> OkxResult<Vec<serde_json::Value>> {
         self.post_signed("/api/v5/sprd/cancel-order", params).await
     }
 
+    /// Amend a spread order.
+    /// POST /api/v5/sprd/amend-order
+    pub async fn amend_spread_order(
+        &self,
+        params: &SpreadAmendRequest,
+    ) -> OkxResult<Vec<serde_json::Value>> {
+        self.post_signed("/api/v5/sprd/amend-order", params).await
+    }
+
     /// Cancel all spread orders.
     /// POST /api/v5/sprd/mass-cancel
     pub async fn cancel_all_spread_orders(
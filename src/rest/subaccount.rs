@@ -1,8 +1,49 @@
-use crate::error::OkxResult;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{OkxError, OkxResult};
 use crate::rest::RestClient;
 use crate::types::request::subaccount::*;
+use crate::types::response::account::AccountBalance;
+use crate::types::response::funding::AssetBalance;
 use crate::types::response::subaccount::*;
 
+/// Page size of the sub-account list endpoint.
+const SUB_ACCOUNT_PAGE_LIMIT: usize = 100;
+
+/// Pause between balance requests. Both sub-account balance endpoints allow
+/// 6 requests per 2 seconds.
+const SUB_ACCOUNT_BALANCE_INTERVAL: Duration = Duration::from_millis(350);
+
+/// One currency summed across sub-accounts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AggregatedBalance {
+    pub ccy: String,
+    /// Equity in trading accounts.
+    pub trading: f64,
+    /// Balance in funding accounts.
+    pub funding: f64,
+    /// `(trading, funding)` per sub-account name.
+    pub by_sub_account: BTreeMap<String, (f64, f64)>,
+}
+
+impl AggregatedBalance {
+    /// Trading equity plus funding balance.
+    pub fn total(&self) -> f64 {
+        self.trading + self.funding
+    }
+
+    fn add(&mut self, sub_acct: &str, trading: f64, funding: f64) {
+        self.trading += trading;
+        self.funding += funding;
+        let entry = self.by_sub_account.entry(sub_acct.to_string()).or_default();
+        entry.0 += trading;
+        entry.1 += funding;
+    }
+}
+
 impl RestClient {
 
     /// Get sub-account list.
@@ -44,4 +85,93 @@ impl RestClient {
         self.post_signed("/api/v5/asset/subaccount/transfer", params)
             .await
     }
+
+    /// List every sub-account, following pagination.
+    pub async fn get_all_sub_accounts(&self) -> OkxResult<Vec<SubAccount>> {
+        let mut all = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .get_sub_account_list(&GetSubAccountListRequest {
+                    after: after.clone(),
+                    limit: Some(SUB_ACCOUNT_PAGE_LIMIT.to_string()),
+                    ..Default::default()
+                })
+                .await?;
+            let done = page.len() < SUB_ACCOUNT_PAGE_LIMIT;
+            // Pages go back in time by creation timestamp.
+            after = page.last().map(|s| s.ts.clone());
+            all.extend(page);
+            if done || after.as_deref().is_none_or(str::is_empty) {
+                return Ok(all);
+            }
+        }
+    }
+
+    /// Trading and funding balances of every sub-account, summed per
+    /// currency.
+    ///
+    /// Requests are sent one at a time and paced to stay within the balance
+    /// endpoints' rate limits, so this takes roughly 0.7 s per sub-account.
+    /// The master account's own balances are not included; see
+    /// [`get_balance`](Self::get_balance) and
+    /// [`get_asset_balances`](Self::get_asset_balances).
+    pub async fn get_all_subaccount_balances(
+        &self,
+    ) -> OkxResult<BTreeMap<String, AggregatedBalance>> {
+        let mut totals: BTreeMap<String, AggregatedBalance> = BTreeMap::new();
+        let mut first = true;
+        for sub in self.get_all_sub_accounts().await? {
+            if !first {
+                crate::time::sleep(SUB_ACCOUNT_BALANCE_INTERVAL).await;
+            }
+            first = false;
+
+            let trading: Vec<AccountBalance> = typed(
+                self.get_sub_account_balance(&GetSubAccountBalanceRequest {
+                    sub_acct: sub.sub_acct.clone(),
+                })
+                .await?,
+            )?;
+            crate::time::sleep(SUB_ACCOUNT_BALANCE_INTERVAL).await;
+            let funding: Vec<AssetBalance> = typed(
+                self.get_sub_account_funding_balance(&GetSubAccountFundingBalanceRequest {
+                    sub_acct: sub.sub_acct.clone(),
+                    ccy: None,
+                })
+                .await?,
+            )?;
+
+            for detail in trading.iter().flat_map(|b| &b.details) {
+                aggregate(&mut totals, &detail.ccy).add(&sub.sub_acct, parse(&detail.eq), 0.0);
+            }
+            for balance in &funding {
+                aggregate(&mut totals, &balance.ccy).add(&sub.sub_acct, 0.0, parse(&balance.bal));
+            }
+        }
+        Ok(totals)
+    }
+}
+
+fn typed<T: DeserializeOwned>(values: Vec<serde_json::Value>) -> OkxResult<Vec<T>> {
+    values
+        .into_iter()
+        .map(|v| T::deserialize(&v).map_err(|e| OkxError::deserialize::<T>(e, &v.to_string())))
+        .collect()
+}
+
+fn aggregate<'a>(
+    totals: &'a mut BTreeMap<String, AggregatedBalance>,
+    ccy: &str,
+) -> &'a mut AggregatedBalance {
+    totals
+        .entry(ccy.to_string())
+        .or_insert_with(|| AggregatedBalance {
+            ccy: ccy.to_string(),
+            ..Default::default()
+        })
+}
+
+fn parse(value: &str) -> f64 {
+    value.parse().unwrap_or_default()
 }
@@ -1,12 +1,13 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::response::system::SystemStatus;
 
 impl RestClient {
 
     /// Get system status.
     /// GET /api/v5/system/status
-    pub async fn get_system_status(&self) -> OkxResult<Vec<serde_json::Value>> {
-        self.get::<serde_json::Value, ()>("/api/v5/system/status", None)
+    pub async fn get_system_status(&self) -> OkxResult<Vec<SystemStatus>> {
+        self.get::<SystemStatus, ()>("/api/v5/system/status", None)
             .await
     }
 }
@@ -1,25 +1,78 @@
+use futures::stream::BoxStream;
+
 use crate::error::OkxResult;
-use crate::rest::RestClient;
+use crate::rest::archive::ArchiveOptions;
+use crate::rest::{ApiResponse, RestClient};
 use crate::types::request::trade::*;
 use crate::types::response::trade::*;
+use crate::types::shared::{ArchiveApplyResult, ArchiveFile};
 
 impl RestClient {
 
     /// Place a single order.
     /// POST /api/v5/trade/order
     pub async fn place_order(&self, params: &OrderRequest) -> OkxResult<Vec<OrderResult>> {
+        if let Some(guard) = &self.config().maintenance_guard {
+            guard.admit([params.inst_id.as_str()]).await?;
+        }
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check(params)?;
+        }
+        if let Some(throttle) = self.throttle() {
+            throttle.check(params)?;
+        }
         self.post_signed("/api/v5/trade/order", params).await
     }
 
+    /// SPOT market buy sized in the quote currency, e.g. spend 100 USDT on BTC.
+    /// POST /api/v5/trade/order
+    pub async fn buy_spot_with_quote(
+        &self,
+        inst_id: &str,
+        quote_amount: &str,
+    ) -> OkxResult<Vec<OrderResult>> {
+        self.place_order(&OrderRequest::buy_spot_with_quote(inst_id, quote_amount))
+            .await
+    }
+
     /// Place multiple orders (up to 20) in a single request.
     /// POST /api/v5/trade/batch-orders
     pub async fn place_multiple_orders(
         &self,
         params: &Vec<OrderRequest>,
     ) -> OkxResult<Vec<OrderResult>> {
+        if let Some(guard) = &self.config().maintenance_guard {
+            guard.admit(params.iter().map(|p| p.inst_id.as_str())).await?;
+        }
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check_all(params)?;
+        }
+        if let Some(throttle) = self.throttle() {
+            throttle.check_all(params)?;
+        }
         self.post_signed("/api/v5/trade/batch-orders", params).await
     }
 
+    /// Place multiple orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/batch-orders
+    pub async fn place_multiple_orders_with_meta(
+        &self,
+        params: &Vec<OrderRequest>,
+    ) -> OkxResult<ApiResponse<OrderResult>> {
+        if let Some(guard) = &self.config().maintenance_guard {
+            guard.admit(params.iter().map(|p| p.inst_id.as_str())).await?;
+        }
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check_all(params)?;
+        }
+        if let Some(throttle) = self.throttle() {
+            throttle.check_all(params)?;
+        }
+        self.post_signed_with_meta("/api/v5/trade/batch-orders", params)
+            .await
+    }
+
     /// Cancel a single order.
     /// POST /api/v5/trade/cancel-order
     pub async fn cancel_order(
@@ -39,9 +92,23 @@ impl RestClient {
             .await
     }
 
+    /// Cancel multiple orders, succeeding on partial failure so each
+    /// order's `sCode` can be checked.
+    /// POST /api/v5/trade/cancel-batch-orders
+    pub async fn cancel_multiple_orders_with_meta(
+        &self,
+        params: &Vec<CancelOrderRequest>,
+    ) -> OkxResult<ApiResponse<CancelledOrder>> {
+        self.post_signed_with_meta("/api/v5/trade/cancel-batch-orders", params)
+            .await
+    }
+
     /// Amend an existing order.
     /// POST /api/v5/trade/amend-order
     pub async fn amend_order(&self, params: &AmendOrderRequest) -> OkxResult<Vec<AmendedOrder>> {
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check_amend(params)?;
+        }
         self.post_signed("/api/v5/trade/amend-order", params).await
     }
 
@@ -51,16 +118,33 @@ impl RestClient {
         &self,
         params: &Vec<AmendOrderRequest>,
     ) -> OkxResult<Vec<AmendedOrder>> {
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check_amends(params)?;
+        }
         self.post_signed("/api/v5/trade/amend-batch-orders", params)
             .await
     }
 
+    /// Amend multiple orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/amend-batch-orders
+    pub async fn amend_multiple_orders_with_meta(
+        &self,
+        params: &Vec<AmendOrderRequest>,
+    ) -> OkxResult<ApiResponse<AmendedOrder>> {
+        if let Some(guard) = &self.config().risk_guard {
+            guard.check_amends(params)?;
+        }
+        self.post_signed_with_meta("/api/v5/trade/amend-batch-orders", params)
+            .await
+    }
+
     /// Close a position.
     /// POST /api/v5/trade/close-position
     pub async fn close_position(
         &self,
         params: &ClosePositionRequest,
-    ) -> OkxResult<Vec<OrderResult>> {
+    ) -> OkxResult<Vec<ClosedPosition>> {
         self.post_signed("/api/v5/trade/close-position", params)
             .await
     }
@@ -114,6 +198,37 @@ impl RestClient {
             .await
     }
 
+    /// Apply for the fills archive of a quarter (since 2022).
+    /// POST /api/v5/trade/fills-archive
+    pub async fn apply_fills_archive(
+        &self,
+        params: &FillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveApplyResult>> {
+        self.post_signed("/api/v5/trade/fills-archive", params)
+            .await
+    }
+
+    /// Get the download link of a previously applied fills archive.
+    /// GET /api/v5/trade/fills-archive
+    pub async fn get_fills_archive(
+        &self,
+        params: &FillsArchiveRequest,
+    ) -> OkxResult<Vec<ArchiveFile>> {
+        self.get_signed("/api/v5/trade/fills-archive", Some(params))
+            .await
+    }
+
+    /// Apply for the fills archive, wait until it is ready, download it, and
+    /// stream the parsed fills.
+    pub async fn download_fills_archive(
+        &self,
+        params: &FillsArchiveRequest,
+        options: &ArchiveOptions,
+    ) -> OkxResult<BoxStream<'static, OkxResult<Fill>>> {
+        self.fetch_archive("/api/v5/trade/fills-archive", params, options)
+            .await
+    }
+
     /// Mass cancel all pending orders for an instrument type.
     /// POST /api/v5/trade/mass-cancel
     pub async fn mass_cancel(&self, params: &MassCancelRequest) -> OkxResult<Vec<MassCancelResult>> {
@@ -149,6 +264,17 @@ impl RestClient {
         self.post_signed("/api/v5/trade/cancel-algos", params).await
     }
 
+    /// Cancel algo orders, succeeding on partial failure so each order's
+    /// `sCode` can be checked.
+    /// POST /api/v5/trade/cancel-algos
+    pub async fn cancel_algo_orders_with_meta(
+        &self,
+        params: &Vec<CancelAlgoOrderRequest>,
+    ) -> OkxResult<ApiResponse<AlgoOrderResult>> {
+        self.post_signed_with_meta("/api/v5/trade/cancel-algos", params)
+            .await
+    }
+
     /// Amend an algo order.
     /// POST /api/v5/trade/amend-algos
     pub async fn amend_algo_order(
@@ -190,12 +316,9 @@ impl RestClient {
 
     /// Get the list of currencies available for easy convert.
     /// GET /api/v5/trade/easy-convert-currency-list
-    pub async fn get_easy_convert_currency_list(&self) -> OkxResult<Vec<serde_json::Value>> {
-        self.get_signed::<serde_json::Value, ()>(
-            "/api/v5/trade/easy-convert-currency-list",
-            None,
-        )
-        .await
+    pub async fn get_easy_convert_currency_list(&self) -> OkxResult<Vec<EasyConvertCurrencyList>> {
+        self.get_signed::<_, ()>("/api/v5/trade/easy-convert-currency-list", None)
+            .await
     }
 
     /// Convert small balances into OKB or USDT.
@@ -203,7 +326,7 @@ impl RestClient {
     pub async fn easy_convert(
         &self,
         params: &EasyConvertRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<EasyConvertResult>> {
         self.post_signed("/api/v5/trade/easy-convert", params).await
     }
 
@@ -212,17 +335,20 @@ impl RestClient {
     pub async fn get_easy_convert_history(
         &self,
         params: &GetEasyConvertHistoryRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<EasyConvertResult>> {
         self.get_signed("/api/v5/trade/easy-convert-history", Some(params))
             .await
     }
 
-    /// Get the list of currencies available for one-click repay.
+    /// Get the list of debt and repayment currencies for one-click repay.
     /// GET /api/v5/trade/one-click-repay-currency-list
-    pub async fn get_one_click_repay_list(&self) -> OkxResult<Vec<serde_json::Value>> {
-        self.get_signed::<serde_json::Value, ()>(
+    pub async fn get_one_click_repay_currency_list(
+        &self,
+        params: &GetOneClickRepayCurrencyListRequest,
+    ) -> OkxResult<Vec<OneClickRepayCurrencyList>> {
+        self.get_signed(
             "/api/v5/trade/one-click-repay-currency-list",
-            None,
+            Some(params),
         )
         .await
     }
@@ -232,7 +358,7 @@ impl RestClient {
     pub async fn one_click_repay(
         &self,
         params: &OneClickRepayRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<OneClickRepayResult>> {
         self.post_signed("/api/v5/trade/one-click-repay", params)
             .await
     }
@@ -242,11 +368,40 @@ impl RestClient {
     pub async fn get_one_click_repay_history(
         &self,
         params: &GetOneClickRepayHistoryRequest,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+    ) -> OkxResult<Vec<OneClickRepayResult>> {
         self.get_signed(
             "/api/v5/trade/one-click-repay-history",
             Some(params),
         )
         .await
     }
+
+    /// Get the debt currencies and their repayment currencies for one-click repay v2.
+    /// GET /api/v5/trade/one-click-repay-currency-list-v2
+    pub async fn get_one_click_repay_currency_list_v2(
+        &self,
+    ) -> OkxResult<Vec<OneClickRepayCurrencyListV2>> {
+        self.get_signed::<_, ()>("/api/v5/trade/one-click-repay-currency-list-v2", None)
+            .await
+    }
+
+    /// Repay a debt with one or more currencies.
+    /// POST /api/v5/trade/one-click-repay-v2
+    pub async fn one_click_repay_v2(
+        &self,
+        params: &OneClickRepayV2Request,
+    ) -> OkxResult<Vec<OneClickRepayResultV2>> {
+        self.post_signed("/api/v5/trade/one-click-repay-v2", params)
+            .await
+    }
+
+    /// Get one-click repay v2 history.
+    /// GET /api/v5/trade/one-click-repay-history-v2
+    pub async fn get_one_click_repay_history_v2(
+        &self,
+        params: &GetOneClickRepayHistoryRequest,
+    ) -> OkxResult<Vec<OneClickRepayRecordV2>> {
+        self.get_signed("/api/v5/trade/one-click-repay-history-v2", Some(params))
+            .await
+    }
 }
@@ -1,12 +1,14 @@
 use crate::error::OkxResult;
 use crate::rest::RestClient;
+use crate::types::request::trading_data::*;
+use crate::types::response::trading_data::*;
 
 impl RestClient {
 
     /// Get support coin.
     /// GET /api/v5/rubik/stat/trading-data/support-coin
-    pub async fn get_support_coin(&self) -> OkxResult<Vec<serde_json::Value>> {
-        self.get::<serde_json::Value, ()>(
+    pub async fn get_support_coin(&self) -> OkxResult<Vec<SupportCoin>> {
+        self.get::<SupportCoin, ()>(
             "/api/v5/rubik/stat/trading-data/support-coin",
             None,
         )
@@ -17,8 +19,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/taker-volume
     pub async fn get_taker_volume(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetTakerVolumeRequest,
+    ) -> OkxResult<Vec<TakerVolume>> {
         self.get("/api/v5/rubik/stat/taker-volume", Some(params))
             .await
     }
@@ -27,8 +29,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/margin/loan-ratio
     pub async fn get_margin_lending_ratio(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>> {
         self.get("/api/v5/rubik/stat/margin/loan-ratio", Some(params))
             .await
     }
@@ -37,8 +39,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio
     pub async fn get_long_short_ratio(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>> {
         self.get(
             "/api/v5/rubik/stat/contracts/long-short-account-ratio",
             Some(params),
@@ -50,8 +52,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/contracts/open-interest-volume
     pub async fn get_open_interest_volume(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetCurrencyTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestVolume>> {
         self.get(
             "/api/v5/rubik/stat/contracts/open-interest-volume",
             Some(params),
@@ -63,8 +65,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/option/open-interest-volume-ratio
     pub async fn get_put_call_ratio(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetOptionTradingDataRequest,
+    ) -> OkxResult<Vec<PutCallRatio>> {
         self.get(
             "/api/v5/rubik/stat/option/open-interest-volume-ratio",
             Some(params),
@@ -76,8 +78,8 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/option/open-interest-volume
     pub async fn get_option_open_interest_volume(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetOptionTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestVolume>> {
         self.get(
             "/api/v5/rubik/stat/option/open-interest-volume",
             Some(params),
@@ -89,12 +91,64 @@ impl RestClient {
     /// GET /api/v5/rubik/stat/taker-volume-contract
     pub async fn get_taker_volume_contracts(
         &self,
-        params: &serde_json::Value,
-    ) -> OkxResult<Vec<serde_json::Value>> {
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<TakerVolume>> {
         self.get(
             "/api/v5/rubik/stat/taker-volume-contract",
             Some(params),
         )
         .await
     }
+
+    /// Get contract open interest history.
+    /// GET /api/v5/rubik/stat/contracts/open-interest-history
+    pub async fn get_contract_open_interest_history(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<OpenInterestHistory>> {
+        self.get(
+            "/api/v5/rubik/stat/contracts/open-interest-history",
+            Some(params),
+        )
+        .await
+    }
+
+    /// Get the long/short account ratio of a contract.
+    /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio-contract
+    pub async fn get_contract_long_short_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>> {
+        self.get(
+            "/api/v5/rubik/stat/contracts/long-short-account-ratio-contract",
+            Some(params),
+        )
+        .await
+    }
+
+    /// Get the long/short account ratio of a contract's top traders.
+    /// GET /api/v5/rubik/stat/contracts/long-short-account-ratio-contract-top-trader
+    pub async fn get_top_traders_long_short_account_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>> {
+        self.get(
+            "/api/v5/rubik/stat/contracts/long-short-account-ratio-contract-top-trader",
+            Some(params),
+        )
+        .await
+    }
+
+    /// Get the long/short position ratio of a contract's top traders.
+    /// GET /api/v5/rubik/stat/contracts/long-short-position-ratio-contract-top-trader
+    pub async fn get_top_traders_long_short_position_ratio(
+        &self,
+        params: &GetContractTradingDataRequest,
+    ) -> OkxResult<Vec<RatioPoint>> {
+        self.get(
+            "/api/v5/rubik/stat/contracts/long-short-position-ratio-contract-top-trader",
+            Some(params),
+        )
+        .await
+    }
 }
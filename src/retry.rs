@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use reqwest_retry::{default_on_request_failure, Retryable, RetryableStrategy};
+
+use crate::error::OkxError;
+
+/// Why a request failed, from the point of view of whether it is worth retrying.
+///
+/// Shared by the REST retry middleware and the WS API resend loop so both
+/// layers agree on what is transient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryClass {
+    /// Connection failure or timeout before a response was received.
+    Network,
+    /// HTTP 5xx or an OKX internal system error.
+    ServerError,
+    /// HTTP 429 or an OKX rate limit error code.
+    RateLimited,
+    /// OKX service unavailable or under maintenance.
+    Maintenance,
+    /// Anything that will fail again if retried unchanged.
+    Permanent,
+}
+
+impl RetryClass {
+    /// Classify an HTTP status. Returns `None` for successful responses.
+    pub fn from_status(status: StatusCode) -> Option<Self> {
+        if status.is_success() {
+            return None;
+        }
+        Some(match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited,
+            StatusCode::SERVICE_UNAVAILABLE => Self::Maintenance,
+            StatusCode::REQUEST_TIMEOUT => Self::Network,
+            s if s.is_server_error() => Self::ServerError,
+            _ => Self::Permanent,
+        })
+    }
+
+    /// Classify an OKX API error code.
+    pub fn from_okx_code(code: &str) -> Self {
+        match code {
+            // Rate limit reached / sub-account rate limit / too frequent operations.
+            "50011" | "50061" | "50040" => Self::RateLimited,
+            // Service temporarily unavailable.
+            "50001" => Self::Maintenance,
+            // Systems are busy / system error.
+            "50013" | "50026" => Self::ServerError,
+            _ => Self::Permanent,
+        }
+    }
+
+    /// Classify a client error.
+    ///
+    /// WS API timeouts and in-flight disconnects are treated as permanent: the
+    /// request may already have been executed, so resending it could
+    /// duplicate an order.
+    pub fn from_error(err: &OkxError) -> Self {
+        match err {
+            OkxError::Api { code, .. } => Self::from_okx_code(code),
+            OkxError::Http(e) | OkxError::Middleware(reqwest_middleware::Error::Reqwest(e)) => {
+                Self::from_reqwest(e)
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            OkxError::WebSocket(_) => Self::Network,
+            OkxError::Request { source, .. } => Self::from_error(source),
+            _ => Self::Permanent,
+        }
+    }
+
+    fn from_reqwest(e: &reqwest::Error) -> Self {
+        match e.status() {
+            Some(status) => Self::from_status(status).unwrap_or(Self::Permanent),
+            None if e.is_timeout() => Self::Network,
+            #[cfg(not(target_arch = "wasm32"))]
+            None if e.is_connect() => Self::Network,
+            None => Self::Permanent,
+        }
+    }
+
+    /// Whether this class can ever succeed on retry.
+    pub fn is_transient(self) -> bool {
+        self != Self::Permanent
+    }
+}
+
+/// Retry settings shared by REST and WS API requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each subsequent retry.
+    pub min_backoff: Duration,
+    /// Upper bound on the delay between retries.
+    pub max_backoff: Duration,
+    /// Retry on [`RetryClass::Network`].
+    pub retry_network: bool,
+    /// Retry on [`RetryClass::ServerError`].
+    pub retry_server_error: bool,
+    /// Retry on [`RetryClass::RateLimited`].
+    pub retry_rate_limited: bool,
+    /// Retry on [`RetryClass::Maintenance`].
+    pub retry_maintenance: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            retry_network: true,
+            retry_server_error: true,
+            retry_rate_limited: true,
+            retry_maintenance: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a failure of the given class should be retried.
+    pub fn should_retry(&self, class: RetryClass) -> bool {
+        match class {
+            RetryClass::Network => self.retry_network,
+            RetryClass::ServerError => self.retry_server_error,
+            RetryClass::RateLimited => self.retry_rate_limited,
+            RetryClass::Maintenance => self.retry_maintenance,
+            RetryClass::Permanent => false,
+        }
+    }
+
+    /// Delay before retry number `attempt` (zero-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.min_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// [`RetryableStrategy`] for the REST middleware backed by a [`RetryPolicy`].
+///
+/// Only installed for idempotent requests: a POST that timed out or got a
+/// 5xx may still have been executed, so resending it could duplicate an order.
+pub(crate) struct PolicyRetryableStrategy {
+    policy: RetryPolicy,
+}
+
+impl PolicyRetryableStrategy {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+
+    fn decide(&self, class: RetryClass) -> Retryable {
+        if self.policy.should_retry(class) {
+            Retryable::Transient
+        } else {
+            Retryable::Fatal
+        }
+    }
+}
+
+impl RetryableStrategy for PolicyRetryableStrategy {
+    fn handle(
+        &self,
+        res: &Result<reqwest::Response, reqwest_middleware::Error>,
+    ) -> Option<Retryable> {
+        match res {
+            Ok(response) => RetryClass::from_status(response.status()).map(|c| self.decide(c)),
+            Err(error) => match default_on_request_failure(error)? {
+                Retryable::Transient => Some(self.decide(RetryClass::Network)),
+                Retryable::Fatal => Some(Retryable::Fatal),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_http_status() {
+        assert_eq!(RetryClass::from_status(StatusCode::OK), None);
+        assert_eq!(
+            RetryClass::from_status(StatusCode::TOO_MANY_REQUESTS),
+            Some(RetryClass::RateLimited)
+        );
+        assert_eq!(
+            RetryClass::from_status(StatusCode::SERVICE_UNAVAILABLE),
+            Some(RetryClass::Maintenance)
+        );
+        assert_eq!(
+            RetryClass::from_status(StatusCode::BAD_GATEWAY),
+            Some(RetryClass::ServerError)
+        );
+        assert_eq!(
+            RetryClass::from_status(StatusCode::UNAUTHORIZED),
+            Some(RetryClass::Permanent)
+        );
+    }
+
+    #[test]
+    fn classifies_okx_errors() {
+        let err = |code: &str| OkxError::Api {
+            code: code.into(),
+            msg: String::new(),
+        };
+        assert_eq!(RetryClass::from_error(&err("50011")), RetryClass::RateLimited);
+        assert_eq!(RetryClass::from_error(&err("50001")), RetryClass::Maintenance);
+        assert_eq!(RetryClass::from_error(&err("50026")), RetryClass::ServerError);
+        assert_eq!(RetryClass::from_error(&err("51008")), RetryClass::Permanent);
+        assert_eq!(
+            RetryClass::from_error(&OkxError::WsApiTimeout {
+                id: "1".into(),
+                operation: "order".into(),
+            }),
+            RetryClass::Permanent
+        );
+        assert_eq!(
+            RetryClass::from_error(&OkxError::WsDisconnected {
+                conn_type: crate::types::ws::events::WsConnectionType::Private,
+                op: "order".into(),
+                id: "1".into(),
+            }),
+            RetryClass::Permanent
+        );
+    }
+
+    #[test]
+    fn policy_respects_class_toggles_and_caps_backoff() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(RetryClass::RateLimited));
+        assert!(!policy.should_retry(RetryClass::Maintenance));
+        assert!(!policy.should_retry(RetryClass::Permanent));
+
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(20), Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,724 @@
+//! Pre-trade risk limits checked before an order leaves the client.
+//!
+//! A [`RiskGuard`] holds the [`RiskLimits`] together with the mark prices,
+//! contract specs, positions and open orders it checks them against. Open
+//! orders come from an [`OrderTracker`] when one is attached, so limits
+//! cover the orders already working as well as the new one.
+//! Set it on [`ClientConfig`](crate::config::ClientConfig) and the REST and
+//! WS API order placement and amendment methods fail with
+//! [`OkxError::RiskRejected`] instead of sending an order that breaks a
+//! limit.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use thiserror::Error;
+
+use crate::error::{OkxError, OkxResult};
+use crate::orders::{OpenOrder, OrderTracker};
+use crate::types::enums::{OrderSide, TargetCurrency};
+use crate::types::request::trade::{AmendOrderRequest, OrderRequest};
+use crate::types::response::account::Position;
+use crate::types::response::public::Instrument;
+
+/// Limits applied to every order. Each is disabled when `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Maximum notional of a single order, in the quote currency.
+    pub max_order_notional: Option<f64>,
+    /// Maximum absolute net position of any instrument once the order and
+    /// the tracked open orders on the same side fill, in order size units:
+    /// contracts for derivatives, coins for spot. Quote-sized spot orders
+    /// are converted at their limit price, or the mark price for market
+    /// orders. Orders that shrink the position are always allowed.
+    ///
+    /// Spot holdings are balances, not positions, so they only count once
+    /// fed in with [`RiskGuard::set_position`].
+    pub max_position: Option<f64>,
+    /// Per-instrument overrides of `max_position`.
+    pub max_position_by_inst: HashMap<String, f64>,
+    /// Maximum number of open orders across all instruments, counting the
+    /// new ones.
+    pub max_open_orders: Option<usize>,
+    /// Maximum number of open orders of one instrument, counting the new
+    /// ones. Needs an [`OrderTracker`].
+    pub max_open_orders_per_inst: Option<usize>,
+    /// Maximum notional of the open orders of one instrument, counting the
+    /// new ones, in the quote currency. Needs an [`OrderTracker`].
+    pub max_open_notional: Option<f64>,
+    /// Maximum distance of a limit price from the mark price, as a fraction,
+    /// e.g. `0.05` for 5%.
+    pub price_collar: Option<f64>,
+}
+
+impl RiskLimits {
+    /// Position limit of an instrument, if any.
+    pub fn position_limit(&self, inst_id: &str) -> Option<f64> {
+        self.max_position_by_inst
+            .get(inst_id)
+            .copied()
+            .or(self.max_position)
+    }
+}
+
+/// Limit an order was rejected for.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RiskViolation {
+    #[error("{inst_id} order notional {notional} exceeds {limit}")]
+    OrderNotional {
+        inst_id: String,
+        notional: f64,
+        limit: f64,
+    },
+    #[error("{inst_id} position would reach {projected}, limit {limit}")]
+    Position {
+        inst_id: String,
+        projected: f64,
+        limit: f64,
+    },
+    #[error("{open} open orders would exceed the limit of {limit}")]
+    OpenOrders { open: usize, limit: usize },
+    #[error("{open} open {inst_id} orders would exceed the limit of {limit}")]
+    InstOpenOrders {
+        inst_id: String,
+        open: usize,
+        limit: usize,
+    },
+    #[error("{inst_id} open order notional would reach {notional}, limit {limit}")]
+    OpenNotional {
+        inst_id: String,
+        notional: f64,
+        limit: f64,
+    },
+    #[error("{inst_id} price {px} is more than {collar} away from mark price {mark_px}")]
+    PriceCollar {
+        inst_id: String,
+        px: f64,
+        mark_px: f64,
+        collar: f64,
+    },
+    /// A limit needs the mark price of an instrument that has none yet.
+    #[error("no mark price for {inst_id}")]
+    NoMarkPrice { inst_id: String },
+    #[error("{inst_id} order has an invalid size or price")]
+    InvalidOrder { inst_id: String },
+}
+
+#[derive(Debug, Default)]
+struct RiskState {
+    marks: HashMap<String, f64>,
+    instruments: HashMap<String, Instrument>,
+    /// Signed position size keyed by instrument ID and position side.
+    positions: HashMap<(String, String), f64>,
+    open_orders: usize,
+}
+
+impl RiskState {
+    fn net_position(&self, inst_id: &str) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((id, _), _)| id == inst_id)
+            .map(|(_, pos)| pos)
+            .sum()
+    }
+}
+
+/// Shared risk limits and the account state they are checked against.
+///
+/// Clones share the same state, so keep one to feed mark prices and
+/// positions after handing another to the client config.
+#[derive(Debug, Clone)]
+pub struct RiskGuard {
+    limits: Arc<RiskLimits>,
+    state: Arc<RwLock<RiskState>>,
+    tracker: Option<OrderTracker>,
+}
+
+impl RiskGuard {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits: Arc::new(limits),
+            state: Arc::default(),
+            tracker: None,
+        }
+    }
+
+    /// Take open orders from `tracker` instead of
+    /// [`set_open_orders`](Self::set_open_orders).
+    pub fn with_tracker(mut self, tracker: OrderTracker) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Returns the attached order tracker, if any.
+    pub fn tracker(&self) -> Option<&OrderTracker> {
+        self.tracker.as_ref()
+    }
+
+    /// Returns the limits.
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Record the latest mark price of an instrument.
+    pub fn set_mark_price(&self, inst_id: &str, mark_px: f64) {
+        self.write().marks.insert(inst_id.to_string(), mark_px);
+    }
+
+    /// Register the contract specs of an instrument, used to value
+    /// derivative orders. Without them, sizes are taken as coins.
+    pub fn add_instrument(&self, instrument: Instrument) {
+        self.write()
+            .instruments
+            .insert(instrument.inst_id.clone(), instrument);
+    }
+
+    /// Insert or replace a position, e.g. from a `positions` push.
+    ///
+    /// The position's mark price is used until a newer one is set.
+    pub fn apply_position(&self, position: &Position) {
+        let mut state = self.write();
+        if let Ok(mark) = position.mark_px.parse::<f64>() {
+            state.marks.insert(position.inst_id.clone(), mark);
+        }
+        let pos: f64 = position.pos.parse().unwrap_or(0.0);
+        let signed = match position.pos_side.as_str() {
+            "short" => -pos.abs(),
+            "long" => pos.abs(),
+            _ => pos,
+        };
+        state.positions.insert(
+            (position.inst_id.clone(), position.pos_side.clone()),
+            signed,
+        );
+    }
+
+    /// Set the net position of an instrument directly, e.g. a spot holding
+    /// from the account balance. Replaces any positions applied for it.
+    pub fn set_position(&self, inst_id: &str, pos: f64) {
+        let mut state = self.write();
+        state.positions.retain(|(id, _), _| id != inst_id);
+        state
+            .positions
+            .insert((inst_id.to_string(), "net".to_string()), pos);
+    }
+
+    /// Set the number of orders currently open on the account. Ignored once
+    /// an order tracker is attached.
+    pub fn set_open_orders(&self, count: usize) {
+        self.write().open_orders = count;
+    }
+
+    /// Net position of an instrument across position sides.
+    pub fn position(&self, inst_id: &str) -> f64 {
+        self.read().net_position(inst_id)
+    }
+
+    /// Check an order against the limits.
+    pub fn check(&self, req: &OrderRequest) -> OkxResult<()> {
+        self.check_all(std::slice::from_ref(req))
+    }
+
+    /// Check a batch of orders against the limits. The open order limit
+    /// counts the whole batch.
+    pub fn check_all(&self, reqs: &[OrderRequest]) -> OkxResult<()> {
+        self.violation(reqs)
+            .map_or(Ok(()), |v| Err(OkxError::RiskRejected(v)))
+    }
+
+    /// Check an amendment against the limits.
+    ///
+    /// The order is looked up in the attached tracker for its side and
+    /// unchanged size or price, and the new size is counted in full as if
+    /// nothing had filled. Orders the tracker does not know, and all orders
+    /// when no tracker is attached, are checked against the price collar
+    /// only.
+    pub fn check_amend(&self, req: &AmendOrderRequest) -> OkxResult<()> {
+        self.check_amends(std::slice::from_ref(req))
+    }
+
+    /// Check a batch of amendments against the limits.
+    pub fn check_amends(&self, reqs: &[AmendOrderRequest]) -> OkxResult<()> {
+        reqs.iter()
+            .find_map(|req| self.amend_violation(req))
+            .map_or(Ok(()), |v| Err(OkxError::RiskRejected(v)))
+    }
+
+    fn amend_violation(&self, req: &AmendOrderRequest) -> Option<RiskViolation> {
+        let open = match &self.tracker {
+            Some(tracker) => tracker.open_orders_for(&req.inst_id),
+            None => Vec::new(),
+        };
+        let state = self.read();
+        let is_target = |o: &OpenOrder| match (&req.ord_id, &req.cl_ord_id) {
+            (Some(ord_id), _) if !ord_id.is_empty() => *ord_id == o.ord_id,
+            (_, Some(cl_ord_id)) => !cl_ord_id.is_empty() && *cl_ord_id == o.cl_ord_id,
+            _ => false,
+        };
+        let Some(order) = open.iter().find(|o| is_target(o)) else {
+            let px = req.new_px.as_deref()?;
+            let Ok(px) = px.parse::<f64>() else {
+                return Some(RiskViolation::InvalidOrder {
+                    inst_id: req.inst_id.clone(),
+                });
+            };
+            return self.collar_violation(&state, &req.inst_id, px);
+        };
+        let amended = OrderRequest {
+            inst_id: req.inst_id.clone(),
+            side: if order.is_buy() {
+                OrderSide::Buy
+            } else {
+                OrderSide::Sell
+            },
+            sz: req
+                .new_sz
+                .clone()
+                .unwrap_or_else(|| order.remaining_sz.to_string()),
+            px: req
+                .new_px
+                .clone()
+                .or_else(|| (order.px > 0.0).then(|| order.px.to_string())),
+            tgt_ccy: order.quote_sized.then_some(TargetCurrency::QuoteCcy),
+            ..Default::default()
+        };
+        let others: Vec<OpenOrder> = open.iter().filter(|o| !is_target(o)).cloned().collect();
+        self.order_violation(&state, &amended, &others)
+    }
+
+    fn violation(&self, reqs: &[OrderRequest]) -> Option<RiskViolation> {
+        let state = self.read();
+        let open_orders = match &self.tracker {
+            Some(tracker) => tracker.open_orders(),
+            None => Vec::new(),
+        };
+        if let Some(limit) = self.limits.max_open_orders {
+            let current = match &self.tracker {
+                Some(_) => open_orders.len(),
+                None => state.open_orders,
+            };
+            let open = current + reqs.len();
+            if open > limit {
+                return Some(RiskViolation::OpenOrders { open, limit });
+            }
+        }
+        reqs.iter().enumerate().find_map(|(i, req)| {
+            // Earlier orders of the batch count as open for later ones.
+            let pending = reqs[..i].iter().filter_map(|r| pending_order(r).ok());
+            let open: Vec<OpenOrder> = open_orders
+                .iter()
+                .filter(|o| o.inst_id == req.inst_id)
+                .cloned()
+                .chain(pending.filter(|o| o.inst_id == req.inst_id))
+                .collect();
+            self.order_violation(&state, req, &open)
+        })
+    }
+
+    /// Check one order; `open` are the open orders of its instrument.
+    fn order_violation(
+        &self,
+        state: &RiskState,
+        req: &OrderRequest,
+        open: &[OpenOrder],
+    ) -> Option<RiskViolation> {
+        let inst_id = &req.inst_id;
+        let invalid = || RiskViolation::InvalidOrder {
+            inst_id: inst_id.clone(),
+        };
+        let Ok(order) = pending_order(req) else {
+            return Some(invalid());
+        };
+        let px = (order.px > 0.0).then_some(order.px);
+        let mark = state.marks.get(inst_id).copied();
+        let instrument = state.instruments.get(inst_id);
+        let no_mark = || RiskViolation::NoMarkPrice {
+            inst_id: inst_id.clone(),
+        };
+
+        if let Some(px) = px {
+            if let Some(violation) = self.collar_violation(state, inst_id, px) {
+                return Some(violation);
+            }
+        }
+
+        if let Some(limit) = self.limits.max_order_notional {
+            let Some(price) = px.or(mark) else {
+                return Some(no_mark());
+            };
+            let notional = quote_notional(instrument, &order, price);
+            if notional > limit {
+                return Some(RiskViolation::OrderNotional {
+                    inst_id: inst_id.clone(),
+                    notional,
+                    limit,
+                });
+            }
+        }
+
+        if let Some(limit) = self.limits.max_open_orders_per_inst {
+            let count = open.len() + 1;
+            if self.tracker.is_some() && count > limit {
+                return Some(RiskViolation::InstOpenOrders {
+                    inst_id: inst_id.clone(),
+                    open: count,
+                    limit,
+                });
+            }
+        }
+
+        if let Some(limit) = self.limits.max_open_notional {
+            if self.tracker.is_some() {
+                let Some(price) = px.or(mark) else {
+                    return Some(no_mark());
+                };
+                let resting: f64 = open
+                    .iter()
+                    .map(|o| quote_notional(instrument, o, open_price(o, mark)))
+                    .sum();
+                let notional = resting + quote_notional(instrument, &order, price);
+                if notional > limit {
+                    return Some(RiskViolation::OpenNotional {
+                        inst_id: inst_id.clone(),
+                        notional,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        if let Some(limit) = self.limits.position_limit(inst_id) {
+            let current = state.net_position(inst_id);
+            // Quote-sized orders only have a base size once priced.
+            let Some(sz) = base_size(&order, mark) else {
+                return Some(no_mark());
+            };
+            let Some(same_side) = open
+                .iter()
+                .filter(|o| o.is_buy() == order.is_buy())
+                .map(|o| base_size(o, mark))
+                .sum::<Option<f64>>()
+            else {
+                return Some(no_mark());
+            };
+            let delta = match req.side {
+                OrderSide::Buy => sz + same_side,
+                OrderSide::Sell => -(sz + same_side),
+            };
+            let projected = current + delta;
+            if projected.abs() > limit && projected.abs() > current.abs() {
+                return Some(RiskViolation::Position {
+                    inst_id: inst_id.clone(),
+                    projected,
+                    limit,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Check a limit price against the price collar.
+    fn collar_violation(&self, state: &RiskState, inst_id: &str, px: f64) -> Option<RiskViolation> {
+        let collar = self.limits.price_collar?;
+        let Some(mark_px) = state.marks.get(inst_id).copied().filter(|m| *m > 0.0) else {
+            return Some(RiskViolation::NoMarkPrice {
+                inst_id: inst_id.to_string(),
+            });
+        };
+        ((px - mark_px).abs() / mark_px > collar).then(|| RiskViolation::PriceCollar {
+            inst_id: inst_id.to_string(),
+            px,
+            mark_px,
+            collar,
+        })
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, RiskState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, RiskState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A new order as it will rest on the book, or an error if its size or
+/// price does not parse.
+fn pending_order(req: &OrderRequest) -> Result<OpenOrder, std::num::ParseFloatError> {
+    Ok(OpenOrder {
+        inst_id: req.inst_id.clone(),
+        ord_id: String::new(),
+        cl_ord_id: req.cl_ord_id.clone().unwrap_or_default(),
+        side: match req.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        }
+        .to_string(),
+        px: req
+            .px
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(0.0),
+        remaining_sz: req.sz.parse()?,
+        quote_sized: req.tgt_ccy == Some(TargetCurrency::QuoteCcy),
+        u_time: 0,
+    })
+}
+
+/// Size of an order in base units: its size, or for quote-sized orders the
+/// quote amount over [`open_price`]. `None` if that price is unknown.
+fn base_size(order: &OpenOrder, mark: Option<f64>) -> Option<f64> {
+    if !order.quote_sized {
+        return Some(order.remaining_sz);
+    }
+    let price = open_price(order, mark);
+    (price > 0.0).then(|| order.remaining_sz / price)
+}
+
+/// Price to value a resting order at: its limit price, or the mark price
+/// for market orders.
+fn open_price(order: &OpenOrder, mark: Option<f64>) -> f64 {
+    if order.px > 0.0 {
+        order.px
+    } else {
+        mark.unwrap_or(0.0)
+    }
+}
+
+/// Remaining value of an order in the quote currency at `px`.
+fn quote_notional(instrument: Option<&Instrument>, order: &OpenOrder, px: f64) -> f64 {
+    let sz = order.remaining_sz;
+    if order.quote_sized {
+        return sz;
+    }
+    match instrument.and_then(|i| i.contracts_to_coin(sz, px)) {
+        Some(coins) => coins * px,
+        None => sz * px,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{OrderType, TradeMode};
+
+    fn limit_buy(inst_id: &str, sz: &str, px: &str) -> OrderRequest {
+        OrderRequest {
+            inst_id: inst_id.into(),
+            td_mode: TradeMode::Cross,
+            side: OrderSide::Buy,
+            ord_type: OrderType::Limit,
+            sz: sz.into(),
+            px: Some(px.into()),
+            ..Default::default()
+        }
+    }
+
+    fn violation(guard: &RiskGuard, req: &OrderRequest) -> Option<RiskViolation> {
+        match guard.check(req) {
+            Ok(()) => None,
+            Err(OkxError::RiskRejected(v)) => Some(v),
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn price_collar_needs_a_mark_price_within_range() {
+        let guard = RiskGuard::new(RiskLimits {
+            price_collar: Some(0.05),
+            ..Default::default()
+        });
+        let req = limit_buy("BTC-USDT", "1", "110");
+        assert!(matches!(
+            violation(&guard, &req),
+            Some(RiskViolation::NoMarkPrice { .. })
+        ));
+
+        guard.set_mark_price("BTC-USDT", 100.0);
+        assert!(matches!(
+            violation(&guard, &req),
+            Some(RiskViolation::PriceCollar { .. })
+        ));
+        assert_eq!(violation(&guard, &limit_buy("BTC-USDT", "1", "104")), None);
+    }
+
+    #[test]
+    fn notional_uses_contract_specs() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_order_notional: Some(1_000.0),
+            ..Default::default()
+        });
+        let instrument: Instrument = serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": "BTC-USDT-SWAP", "instFamily": "BTC-USDT",
+            "ctVal": "0.01", "ctMult": "1", "ctValCcy": "BTC"
+        }))
+        .unwrap();
+        guard.add_instrument(instrument);
+
+        // 5 contracts of 0.01 BTC at 10000 = 500 USDT.
+        assert_eq!(
+            violation(&guard, &limit_buy("BTC-USDT-SWAP", "5", "10000")),
+            None
+        );
+        assert!(matches!(
+            violation(&guard, &limit_buy("BTC-USDT-SWAP", "20", "10000")),
+            Some(RiskViolation::OrderNotional { notional, .. }) if notional == 2_000.0
+        ));
+    }
+
+    #[test]
+    fn position_limit_allows_reducing_orders() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_position: Some(10.0),
+            ..Default::default()
+        });
+        let position: Position = serde_json::from_value(serde_json::json!({
+            "instId": "ETH-USDT-SWAP", "posSide": "net", "pos": "8", "markPx": "2000"
+        }))
+        .unwrap();
+        guard.apply_position(&position);
+
+        assert!(matches!(
+            violation(&guard, &limit_buy("ETH-USDT-SWAP", "3", "2000")),
+            Some(RiskViolation::Position { projected, .. }) if projected == 11.0
+        ));
+        let mut sell = limit_buy("ETH-USDT-SWAP", "3", "2000");
+        sell.side = OrderSide::Sell;
+        assert_eq!(violation(&guard, &sell), None);
+    }
+
+    #[test]
+    fn open_order_limit_counts_the_batch() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_open_orders: Some(3),
+            ..Default::default()
+        });
+        guard.set_open_orders(2);
+        let batch = vec![limit_buy("BTC-USDT", "1", "1"); 2];
+        assert!(matches!(
+            guard.check_all(&batch),
+            Err(OkxError::RiskRejected(RiskViolation::OpenOrders {
+                open: 4,
+                limit: 3
+            }))
+        ));
+        assert!(guard.check(&batch[0]).is_ok());
+    }
+
+    #[test]
+    fn tracked_open_orders_count_towards_limits() {
+        let tracker = OrderTracker::new();
+        let resting: crate::orders::OrderUpdate = serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT", "ordId": "1", "side": "buy", "px": "100", "sz": "6",
+            "accFillSz": "1", "state": "partially_filled"
+        }))
+        .unwrap();
+        tracker.apply(&resting);
+        let guard = RiskGuard::new(RiskLimits {
+            max_open_notional: Some(1_000.0),
+            max_position: Some(8.0),
+            max_open_orders_per_inst: Some(2),
+            ..Default::default()
+        })
+        .with_tracker(tracker.clone());
+
+        // 500 resting plus 400 new.
+        assert_eq!(violation(&guard, &limit_buy("BTC-USDT", "2", "200")), None);
+        assert!(matches!(
+            violation(&guard, &limit_buy("BTC-USDT", "3", "200")),
+            Some(RiskViolation::OpenNotional { notional, .. }) if notional == 1_100.0
+        ));
+        // 5 resting plus 4 new buys would exceed the position limit.
+        assert!(matches!(
+            violation(&guard, &limit_buy("BTC-USDT", "4", "10")),
+            Some(RiskViolation::Position { projected, .. }) if projected == 9.0
+        ));
+        assert!(matches!(
+            guard.check_all(&[
+                limit_buy("BTC-USDT", "1", "1"),
+                limit_buy("BTC-USDT", "1", "1")
+            ]),
+            Err(OkxError::RiskRejected(RiskViolation::InstOpenOrders {
+                open: 3,
+                ..
+            }))
+        ));
+        assert_eq!(violation(&guard, &limit_buy("ETH-USDT", "4", "10")), None);
+    }
+
+    #[test]
+    fn amendments_are_checked_against_the_tracked_order() {
+        let tracker = OrderTracker::new();
+        let resting: crate::orders::OrderUpdate = serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT", "ordId": "1", "side": "buy", "px": "100", "sz": "5",
+            "accFillSz": "0", "state": "live"
+        }))
+        .unwrap();
+        tracker.apply(&resting);
+        let guard = RiskGuard::new(RiskLimits {
+            max_open_notional: Some(1_000.0),
+            price_collar: Some(0.05),
+            ..Default::default()
+        })
+        .with_tracker(tracker);
+        guard.set_mark_price("BTC-USDT", 100.0);
+        let amend = |ord_id: &str, sz: Option<&str>, px: Option<&str>| AmendOrderRequest {
+            inst_id: "BTC-USDT".into(),
+            ord_id: Some(ord_id.into()),
+            new_sz: sz.map(Into::into),
+            new_px: px.map(Into::into),
+            ..Default::default()
+        };
+
+        // The amended order replaces the resting one rather than adding to it.
+        assert!(guard.check_amend(&amend("1", Some("9"), None)).is_ok());
+        assert!(matches!(
+            guard.check_amend(&amend("1", Some("11"), None)),
+            Err(OkxError::RiskRejected(RiskViolation::OpenNotional { notional, .. }))
+                if notional == 1_100.0
+        ));
+        // Unknown orders still get the price collar.
+        assert!(matches!(
+            guard.check_amend(&amend("2", None, Some("120"))),
+            Err(OkxError::RiskRejected(RiskViolation::PriceCollar { .. }))
+        ));
+    }
+
+    #[test]
+    fn set_position_seeds_spot_holdings() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_position: Some(1.0),
+            ..Default::default()
+        });
+        assert_eq!(
+            violation(&guard, &limit_buy("BTC-USDT", "0.5", "100")),
+            None
+        );
+        guard.set_position("BTC-USDT", 0.8);
+        assert!(matches!(
+            violation(&guard, &limit_buy("BTC-USDT", "0.5", "100")),
+            Some(RiskViolation::Position { .. })
+        ));
+    }
+
+    #[test]
+    fn quote_sized_orders_count_in_base_units() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_position: Some(1.0),
+            ..Default::default()
+        });
+        let buy = OrderRequest::buy_spot_with_quote("BTC-USDT", "100");
+        assert!(matches!(
+            violation(&guard, &buy),
+            Some(RiskViolation::NoMarkPrice { .. })
+        ));
+        guard.set_mark_price("BTC-USDT", 50_000.0);
+        assert_eq!(violation(&guard, &buy), None);
+        assert!(matches!(
+            violation(&guard, &OrderRequest::buy_spot_with_quote("BTC-USDT", "60000")),
+            Some(RiskViolation::Position { projected, .. }) if projected == 1.2
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::time::now_ms;
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::{WsDataEvent, WsMessage};
+use crate::ws::WebsocketClient;
+
+/// Settings of a [`StalenessMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StalenessConfig {
+    /// Pushes whose `ts` is older than this are stale, and channels silent
+    /// for this long are reported (default: 5 seconds).
+    pub max_age: Duration,
+    /// How often silent channels are checked for (default: 1 second).
+    pub check_interval: Duration,
+    /// Server clock minus local clock in milliseconds, e.g. from
+    /// [`RestClient::server_time_offset`](crate::RestClient::server_time_offset)
+    /// (default: 0).
+    pub server_offset_ms: i64,
+}
+
+impl Default for StalenessConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(5),
+            check_interval: Duration::from_secs(1),
+            server_offset_ms: 0,
+        }
+    }
+}
+
+/// Problem found on a channel by a [`StalenessMonitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StalenessEvent {
+    /// A push arrived with a `ts` older than the allowed age.
+    Stale {
+        arg: WsSubscriptionArg,
+        age: Duration,
+    },
+    /// A push's `ts` is earlier than the previous push on the same
+    /// subscription.
+    OutOfOrder {
+        arg: WsSubscriptionArg,
+        ts: i64,
+        previous_ts: i64,
+    },
+    /// A channel that was pushing has been silent for the allowed age.
+    /// Reported once until the next push.
+    Silent {
+        arg: WsSubscriptionArg,
+        since: Duration,
+    },
+}
+
+#[derive(Debug)]
+struct ChannelState {
+    last_ts: i64,
+    last_seen: Instant,
+    silent_reported: bool,
+}
+
+#[derive(Debug)]
+struct Checker {
+    config: StalenessConfig,
+    channels: HashMap<WsSubscriptionArg, ChannelState>,
+}
+
+/// Watches every data push of a client for stale timestamps, timestamps
+/// going backwards, and channels that stop pushing.
+///
+/// Only pushes whose items carry a `ts` are checked. Each problem is logged
+/// and sent to [`events`](Self::events). Channels are forgotten once
+/// unsubscribed. The background task stops when the monitor is dropped.
+pub struct StalenessMonitor {
+    events: broadcast::Sender<StalenessEvent>,
+    task: JoinHandle<()>,
+}
+
+impl StalenessMonitor {
+    pub fn start(ws: &WebsocketClient, config: StalenessConfig) -> Self {
+        let (events, _) = broadcast::channel(256);
+        let mut rx = ws.event_receiver();
+        let task = {
+            let events = events.clone();
+            let ws = ws.clone();
+            tokio::spawn(async move {
+                let mut checker = Checker {
+                    config,
+                    channels: HashMap::new(),
+                };
+                let mut ticker = tokio::time::interval(config.check_interval);
+                loop {
+                    let found = tokio::select! {
+                        msg = rx.recv() => match msg {
+                            Ok(msg) => match msg.as_ref() {
+                                WsMessage::Data(evt) => {
+                                    checker.on_push(evt, now_ms(), Instant::now())
+                                }
+                                _ => continue,
+                            },
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("Staleness monitor lagged, {n} events dropped");
+                                continue;
+                            }
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        },
+                        _ = ticker.tick() => {
+                            let subscribed = ws.subscriptions().await.into_iter().collect();
+                            checker.forget_unsubscribed(&subscribed);
+                            checker.silent(Instant::now())
+                        }
+                    };
+                    for event in found {
+                        warn!("Market data problem: {event:?}");
+                        let _ = events.send(event);
+                    }
+                }
+            })
+        };
+        Self { events, task }
+    }
+
+    /// Receive every problem found from now on.
+    pub fn events(&self) -> broadcast::Receiver<StalenessEvent> {
+        self.events.subscribe()
+    }
+}
+
+impl Drop for StalenessMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Checker {
+    fn on_push(&mut self, evt: &WsDataEvent, now_ms: i64, now: Instant) -> Vec<StalenessEvent> {
+        let Some(ts) = evt
+            .data
+            .iter()
+            .filter_map(|item| item.get("ts")?.as_str()?.parse::<i64>().ok())
+            .max()
+        else {
+            return Vec::new();
+        };
+
+        let mut found = Vec::new();
+        let age_ms = now_ms + self.config.server_offset_ms - ts;
+        if age_ms > self.config.max_age.as_millis() as i64 {
+            found.push(StalenessEvent::Stale {
+                arg: evt.arg.clone(),
+                age: Duration::from_millis(age_ms as u64),
+            });
+        }
+
+        let state = self
+            .channels
+            .entry(evt.arg.clone())
+            .or_insert(ChannelState {
+                last_ts: ts,
+                last_seen: now,
+                silent_reported: false,
+            });
+        if ts < state.last_ts {
+            found.push(StalenessEvent::OutOfOrder {
+                arg: evt.arg.clone(),
+                ts,
+                previous_ts: state.last_ts,
+            });
+        } else {
+            state.last_ts = ts;
+        }
+        state.last_seen = now;
+        state.silent_reported = false;
+        found
+    }
+
+    /// Drop channels no longer subscribed, so they are not reported silent
+    /// and do not accumulate.
+    fn forget_unsubscribed(&mut self, subscribed: &HashSet<WsSubscriptionArg>) {
+        self.channels.retain(|arg, _| subscribed.contains(arg));
+    }
+
+    fn silent(&mut self, now: Instant) -> Vec<StalenessEvent> {
+        let max_age = self.config.max_age;
+        self.channels
+            .iter_mut()
+            .filter(|(_, state)| !state.silent_reported)
+            .filter_map(|(arg, state)| {
+                let since = now.saturating_duration_since(state.last_seen);
+                (since >= max_age).then(|| {
+                    state.silent_reported = true;
+                    StalenessEvent::Silent {
+                        arg: arg.clone(),
+                        since,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(ts: i64) -> WsDataEvent {
+        WsDataEvent {
+            arg: WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT"),
+            data: vec![serde_json::json!({"instId": "BTC-USDT", "ts": ts.to_string()})],
+            action: None,
+        }
+    }
+
+    #[test]
+    fn stale_and_out_of_order_pushes_are_reported() {
+        let mut checker = Checker {
+            config: StalenessConfig {
+                server_offset_ms: 500,
+                ..Default::default()
+            },
+            channels: HashMap::new(),
+        };
+        let now = Instant::now();
+
+        // 4.5 s old by the local clock, 5 s by the server's.
+        assert!(checker.on_push(&push(10_000), 14_500, now).is_empty());
+        let found = checker.on_push(&push(10_000), 15_001, now);
+        assert!(matches!(&found[..], [StalenessEvent::Stale { age, .. }]
+            if *age == Duration::from_millis(5_501)));
+
+        let found = checker.on_push(&push(9_000), 10_000, now);
+        assert!(matches!(
+            &found[..],
+            [StalenessEvent::OutOfOrder {
+                ts: 9_000,
+                previous_ts: 10_000,
+                ..
+            }]
+        ));
+        assert!(checker.on_push(&push(11_000), 11_000, now).is_empty());
+    }
+
+    #[test]
+    fn silent_channels_are_reported_once() {
+        let mut checker = Checker {
+            config: StalenessConfig::default(),
+            channels: HashMap::new(),
+        };
+        let start = Instant::now();
+        checker.on_push(&push(1_000), 1_000, start);
+
+        assert!(checker.silent(start + Duration::from_secs(4)).is_empty());
+        assert_eq!(checker.silent(start + Duration::from_secs(5)).len(), 1);
+        assert!(checker.silent(start + Duration::from_secs(6)).is_empty());
+
+        checker.on_push(&push(7_000), 7_000, start + Duration::from_secs(7));
+        assert_eq!(checker.silent(start + Duration::from_secs(12)).len(), 1);
+    }
+
+    #[test]
+    fn unsubscribed_channels_are_forgotten() {
+        let mut checker = Checker {
+            config: StalenessConfig::default(),
+            channels: HashMap::new(),
+        };
+        let start = Instant::now();
+        checker.on_push(&push(1_000), 1_000, start);
+
+        checker.forget_unsubscribed(&HashSet::from([push(0).arg]));
+        assert_eq!(checker.channels.len(), 1);
+        checker.forget_unsubscribed(&HashSet::new());
+        assert!(checker.channels.is_empty());
+        assert!(checker.silent(start + Duration::from_secs(6)).is_empty());
+    }
+}
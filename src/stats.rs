@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::types::response::market::{Ticker, Trade};
+use crate::types::ws::data::{BboUpdate, DepthLevel, DepthSnapshot};
+
+/// Statistics of the trades and quotes inside a [`RollingStats`] window.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSnapshot {
+    /// Volume-weighted average trade price; `None` without trades.
+    pub vwap: Option<f64>,
+    /// Traded size.
+    pub volume: f64,
+    /// Number of trades.
+    pub trades: usize,
+    /// Latest mid price.
+    pub mid: Option<f64>,
+    /// Latest ask minus bid.
+    pub spread: Option<f64>,
+    /// Mean of the quoted spreads.
+    pub avg_spread: Option<f64>,
+    /// Standard deviation of the log returns between consecutive trades,
+    /// not annualized; `None` with fewer than three trades.
+    pub realized_vol: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TradeSample {
+    ts: i64,
+    px: f64,
+    sz: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct QuoteSample {
+    ts: i64,
+    bid: f64,
+    ask: f64,
+}
+
+/// Rolling VWAP, mid, spread and realized volatility of one instrument.
+///
+/// Feed it the items of typed `trades`, `bbo-tbt`, `books5` or `tickers`
+/// subscriptions. The window is measured in exchange time: samples older
+/// than the newest timestamp seen minus the window are dropped, so replays
+/// give the same results as live data. Items with unparseable prices are
+/// ignored.
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    window_ms: i64,
+    latest_ts: i64,
+    trades: VecDeque<TradeSample>,
+    quotes: VecDeque<QuoteSample>,
+    /// Running sums over `trades`.
+    volume: f64,
+    notional: f64,
+}
+
+impl RollingStats {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window_ms: window.as_millis() as i64,
+            latest_ts: i64::MIN,
+            trades: VecDeque::new(),
+            quotes: VecDeque::new(),
+            volume: 0.0,
+            notional: 0.0,
+        }
+    }
+
+    /// Add a public trade.
+    pub fn on_trade(&mut self, trade: &Trade) {
+        if let (Ok(px), Ok(sz), Ok(ts)) = (
+            trade.px.parse::<f64>(),
+            trade.sz.parse::<f64>(),
+            trade.ts.parse::<i64>(),
+        ) {
+            self.add_trade(ts, px, sz);
+        }
+    }
+
+    /// Add a `bbo-tbt` push.
+    pub fn on_bbo(&mut self, bbo: &BboUpdate) {
+        if let (Ok(bid), Ok(ask), Ok(ts)) = (
+            bbo.bid_px.parse::<f64>(),
+            bbo.ask_px.parse::<f64>(),
+            bbo.ts.parse::<i64>(),
+        ) {
+            self.add_quote(ts, bid, ask);
+        }
+    }
+
+    /// Add the best bid and ask of a `books5` push.
+    pub fn on_depth(&mut self, book: &DepthSnapshot) {
+        let best = |level: Option<&DepthLevel>| level?.px.parse::<f64>().ok();
+        if let (Some(bid), Some(ask), Ok(ts)) = (
+            best(book.best_bid()),
+            best(book.best_ask()),
+            book.ts.parse::<i64>(),
+        ) {
+            self.add_quote(ts, bid, ask);
+        }
+    }
+
+    /// Add the best bid and ask of a ticker.
+    pub fn on_ticker(&mut self, ticker: &Ticker) {
+        if let (Ok(bid), Ok(ask), Ok(ts)) = (
+            ticker.bid_px.parse::<f64>(),
+            ticker.ask_px.parse::<f64>(),
+            ticker.ts.parse::<i64>(),
+        ) {
+            self.add_quote(ts, bid, ask);
+        }
+    }
+
+    /// Add a trade from its timestamp, price and size.
+    pub fn add_trade(&mut self, ts: i64, px: f64, sz: f64) {
+        self.trades.push_back(TradeSample { ts, px, sz });
+        self.volume += sz;
+        self.notional += px * sz;
+        self.advance(ts);
+    }
+
+    /// Add a best bid and ask quoted at `ts`.
+    pub fn add_quote(&mut self, ts: i64, bid: f64, ask: f64) {
+        self.quotes.push_back(QuoteSample { ts, bid, ask });
+        self.advance(ts);
+    }
+
+    /// Statistics of the current window.
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let latest_quote = self.quotes.back();
+        StatsSnapshot {
+            vwap: (self.volume > 0.0).then(|| self.notional / self.volume),
+            volume: self.volume,
+            trades: self.trades.len(),
+            mid: latest_quote.map(|q| (q.bid + q.ask) / 2.0),
+            spread: latest_quote.map(|q| q.ask - q.bid),
+            avg_spread: (!self.quotes.is_empty()).then(|| {
+                self.quotes.iter().map(|q| q.ask - q.bid).sum::<f64>() / self.quotes.len() as f64
+            }),
+            realized_vol: self.realized_vol(),
+        }
+    }
+
+    /// Move the window end to `ts` if it is newer and drop expired samples.
+    fn advance(&mut self, ts: i64) {
+        self.latest_ts = self.latest_ts.max(ts);
+        let cutoff = self.latest_ts.saturating_sub(self.window_ms);
+        while self.trades.front().is_some_and(|t| t.ts <= cutoff) {
+            let expired = self.trades.pop_front().expect("front exists");
+            self.volume -= expired.sz;
+            self.notional -= expired.px * expired.sz;
+        }
+        while self.quotes.front().is_some_and(|q| q.ts <= cutoff) {
+            self.quotes.pop_front();
+        }
+        if self.trades.is_empty() {
+            // Clear rounding residue of the running sums.
+            self.volume = 0.0;
+            self.notional = 0.0;
+        }
+    }
+
+    fn realized_vol(&self) -> Option<f64> {
+        let returns: Vec<f64> = self
+            .trades
+            .iter()
+            .zip(self.trades.iter().skip(1))
+            .filter(|(a, b)| a.px > 0.0 && b.px > 0.0)
+            .map(|(a, b)| (b.px / a.px).ln())
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Some(variance.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_drops_old_trades_and_quotes() {
+        let mut stats = RollingStats::new(Duration::from_secs(10));
+        stats.add_trade(1_000, 100.0, 1.0);
+        stats.add_trade(5_000, 110.0, 3.0);
+        stats.add_quote(5_000, 109.0, 111.0);
+        assert_eq!(stats.snapshot().vwap, Some(107.5));
+        assert_eq!(stats.snapshot().mid, Some(110.0));
+
+        stats.add_quote(11_000, 99.0, 100.0);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.trades, 1);
+        assert_eq!(snapshot.vwap, Some(110.0));
+        assert_eq!(snapshot.spread, Some(1.0));
+        assert_eq!(snapshot.avg_spread, Some(1.5));
+
+        stats.add_quote(15_001, 99.0, 100.0);
+        assert_eq!(stats.snapshot().vwap, None);
+        assert_eq!(stats.snapshot().volume, 0.0);
+    }
+
+    #[test]
+    fn realized_vol_of_alternating_returns() {
+        let mut stats = RollingStats::new(Duration::from_secs(60));
+        assert_eq!(stats.snapshot().realized_vol, None);
+        let up = 1.01_f64;
+        for (i, px) in [100.0, 100.0 * up, 100.0, 100.0 * up]
+            .into_iter()
+            .enumerate()
+        {
+            stats.add_trade(i as i64, px, 1.0);
+        }
+        // Returns +r, -r, +r: sample standard deviation is r * sqrt(4/3).
+        let r = up.ln();
+        let vol = stats.snapshot().realized_vol.unwrap();
+        assert!((vol - r * (4.0_f64 / 3.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn typed_items_feed_the_window() {
+        let mut stats = RollingStats::new(Duration::from_secs(60));
+        let trade: Trade = serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT", "px": "40000", "sz": "0.5", "ts": "1000",
+        }))
+        .unwrap();
+        let bbo: BboUpdate = serde_json::from_value(serde_json::json!({
+            "bids": [["39999", "1", "0", "1"]], "asks": [["40001", "2", "0", "1"]], "ts": "1000",
+        }))
+        .unwrap();
+        stats.on_trade(&trade);
+        stats.on_bbo(&bbo);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.vwap, Some(40_000.0));
+        assert_eq!(snapshot.mid, Some(40_000.0));
+        assert_eq!(snapshot.spread, Some(2.0));
+    }
+}
@@ -0,0 +1,13 @@
+//! Test doubles for code built on this crate.
+//!
+//! Enabled with the `test-util` feature. Nothing here talks to OKX.
+
+mod rest;
+mod ws;
+
+pub use rest::{
+    mock_balance, mock_cancelled_order, mock_order_rejected, mock_order_result,
+    mock_pending_algo_order, mock_pending_order, mock_position, mock_ticker, okx_error, okx_ok,
+    MockOkxRest, ValidSignature, MOCK_API_KEY, MOCK_API_SECRET, MOCK_PASSPHRASE,
+};
+pub use ws::MockOkxWs;
@@ -0,0 +1,360 @@
+use secrecy::SecretString;
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+use crate::auth;
+use crate::config::{ClientConfig, ClientConfigBuilder};
+use crate::rest::RestClient;
+
+/// API key of the credentials [`MockOkxRest::config`] signs with.
+pub const MOCK_API_KEY: &str = "mock-api-key";
+/// API secret of the credentials [`MockOkxRest::config`] signs with.
+pub const MOCK_API_SECRET: &str = "mock-api-secret";
+/// Passphrase of the credentials [`MockOkxRest::config`] signs with.
+pub const MOCK_PASSPHRASE: &str = "mock-passphrase";
+
+/// Successful OKX response envelope around `data`.
+pub fn okx_ok(data: Vec<Value>) -> Value {
+    json!({"code": "0", "msg": "", "data": data})
+}
+
+/// OKX error envelope with an empty `data` array.
+pub fn okx_error(code: &str, msg: &str) -> Value {
+    json!({"code": code, "msg": msg, "data": []})
+}
+
+/// `GET /api/v5/account/balance` entry holding one currency with `eq` equity,
+/// all of it available.
+pub fn mock_balance(ccy: &str, eq: &str) -> Value {
+    json!({
+        "uTime": "1700000000000",
+        "totalEq": eq,
+        "isoEq": "0",
+        "adjEq": eq,
+        "ordFroz": "0",
+        "imr": "0",
+        "mmr": "0",
+        "notionalUsd": "0",
+        "details": [{
+            "ccy": ccy,
+            "eq": eq,
+            "cashBal": eq,
+            "uTime": "1700000000000",
+            "isoEq": "0",
+            "availEq": eq,
+            "disEq": eq,
+            "availBal": eq,
+            "frozenBal": "0",
+            "ordFrozen": "0",
+            "eqUsd": eq,
+        }],
+    })
+}
+
+/// Accepted order entry, as returned by order placement and amendment.
+pub fn mock_order_result(ord_id: &str, cl_ord_id: &str) -> Value {
+    json!({
+        "ordId": ord_id,
+        "clOrdId": cl_ord_id,
+        "tag": "",
+        "ts": "1700000000000",
+        "sCode": "0",
+        "sMsg": "",
+    })
+}
+
+/// Rejected order entry with the given `sCode` and `sMsg`.
+pub fn mock_order_rejected(cl_ord_id: &str, s_code: &str, s_msg: &str) -> Value {
+    json!({
+        "ordId": "",
+        "clOrdId": cl_ord_id,
+        "tag": "",
+        "ts": "1700000000000",
+        "sCode": s_code,
+        "sMsg": s_msg,
+    })
+}
+
+/// Successful cancellation entry.
+pub fn mock_cancelled_order(ord_id: &str, cl_ord_id: &str) -> Value {
+    json!({
+        "ordId": ord_id,
+        "clOrdId": cl_ord_id,
+        "ts": "1700000000000",
+        "sCode": "0",
+        "sMsg": "",
+    })
+}
+
+/// `GET /api/v5/trade/orders-pending` entry: a live limit buy of `sz` at `px`.
+pub fn mock_pending_order(inst_id: &str, ord_id: &str, px: &str, sz: &str) -> Value {
+    json!({
+        "instType": if inst_id.ends_with("-SWAP") { "SWAP" } else { "SPOT" },
+        "instId": inst_id,
+        "ccy": "",
+        "ordId": ord_id,
+        "clOrdId": "",
+        "tag": "",
+        "px": px,
+        "sz": sz,
+        "pnl": "0",
+        "ordType": "limit",
+        "side": "buy",
+        "posSide": "",
+        "tdMode": "cross",
+        "accFillSz": "0",
+        "fillPx": "",
+        "tradeId": "",
+        "fillSz": "0",
+        "fillTime": "",
+        "state": "live",
+        "avgPx": "",
+        "lever": "",
+        "feeCcy": "",
+        "fee": "0",
+        "rebateCcy": "",
+        "rebate": "0",
+        "source": "",
+        "category": "normal",
+        "uTime": "1700000000000",
+        "cTime": "1700000000000",
+        "cancelSource": "",
+        "tpTriggerPx": "",
+        "tpTriggerPxType": "",
+        "tpOrdPx": "",
+        "slTriggerPx": "",
+        "slTriggerPxType": "",
+        "slOrdPx": "",
+        "stpId": "",
+        "stpMode": "",
+        "reduceOnly": "false",
+    })
+}
+
+/// `GET /api/v5/trade/orders-algo-pending` entry: a live sell algo order
+/// of `ord_type` for one contract.
+pub fn mock_pending_algo_order(inst_id: &str, algo_id: &str, ord_type: &str) -> Value {
+    json!({
+        "instType": if inst_id.ends_with("-SWAP") { "SWAP" } else { "SPOT" },
+        "instId": inst_id,
+        "ordId": "",
+        "algoId": algo_id,
+        "clOrdId": "",
+        "ccy": "",
+        "sz": "1",
+        "ordType": ord_type,
+        "side": "sell",
+        "posSide": "net",
+        "tdMode": "cross",
+        "state": "live",
+        "lever": "",
+        "tpTriggerPx": "",
+        "tpOrdPx": "",
+        "slTriggerPx": "",
+        "slOrdPx": "",
+        "triggerPx": "",
+        "ordPx": "",
+        "actualSz": "",
+        "actualPx": "",
+        "actualSide": "",
+        "triggerTime": "",
+        "cTime": "1700000000000",
+    })
+}
+
+/// `GET /api/v5/account/positions` entry: a cross-margin position of `pos`
+/// contracts on `pos_side`.
+pub fn mock_position(inst_id: &str, pos_side: &str, pos: &str) -> Value {
+    json!({
+        "instType": if inst_id.ends_with("-SWAP") { "SWAP" } else { "MARGIN" },
+        "instId": inst_id,
+        "mgnMode": "cross",
+        "posSide": pos_side,
+        "pos": pos,
+        "availPos": pos,
+        "avgPx": "100",
+        "markPx": "100",
+        "cTime": "1700000000000",
+        "uTime": "1700000000000",
+    })
+}
+
+/// `GET /api/v5/market/ticker` entry with a one-tick spread around `last`.
+pub fn mock_ticker(inst_id: &str, last: &str) -> Value {
+    json!({
+        "instType": "SPOT",
+        "instId": inst_id,
+        "last": last,
+        "lastSz": "1",
+        "askPx": last,
+        "askSz": "1",
+        "bidPx": last,
+        "bidSz": "1",
+        "open24h": last,
+        "high24h": last,
+        "low24h": last,
+        "volCcy24h": "0",
+        "vol24h": "0",
+        "sodUtc0": last,
+        "sodUtc8": last,
+        "ts": "1700000000000",
+    })
+}
+
+/// Matches requests signed with the given credentials.
+///
+/// Checks `OK-ACCESS-KEY` and `OK-ACCESS-PASSPHRASE`, and recomputes
+/// `OK-ACCESS-SIGN` from `OK-ACCESS-TIMESTAMP`, the method, the path with its
+/// query string and the body.
+pub struct ValidSignature {
+    api_key: String,
+    passphrase: String,
+    secret: SecretString,
+}
+
+impl ValidSignature {
+    pub fn new(api_key: &str, api_secret: &str, passphrase: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            passphrase: passphrase.to_string(),
+            secret: SecretString::from(api_secret.to_string()),
+        }
+    }
+}
+
+impl Match for ValidSignature {
+    fn matches(&self, request: &Request) -> bool {
+        let header = |name: &str| {
+            request
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+        };
+        if header("ok-access-key") != self.api_key
+            || header("ok-access-passphrase") != self.passphrase
+        {
+            return false;
+        }
+
+        let endpoint = match request.url.query() {
+            Some(query) => format!("{}?{query}", request.url.path()),
+            None => request.url.path().to_string(),
+        };
+        let Ok(body) = std::str::from_utf8(&request.body) else {
+            return false;
+        };
+        auth::sign_rest(
+            header("ok-access-timestamp"),
+            request.method.as_str(),
+            &endpoint,
+            body,
+            &self.secret,
+            None,
+        )
+        .is_ok_and(|expected| expected == header("ok-access-sign"))
+    }
+}
+
+/// Local HTTP server that answers OKX REST endpoints with canned responses.
+///
+/// Endpoints mounted with [`respond_signed`](Self::respond_signed) only
+/// answer requests signed with [`MOCK_API_KEY`], [`MOCK_API_SECRET`] and
+/// [`MOCK_PASSPHRASE`]; anything else gets OKX error `50113`. Unmounted
+/// paths return 404.
+///
+/// ```no_run
+/// # async fn example() {
+/// use okx_client::test_util::{mock_balance, MockOkxRest};
+/// use okx_client::types::request::account::GetBalanceRequest;
+///
+/// let mock = MockOkxRest::start().await;
+/// mock.respond_signed("GET", "/api/v5/account/balance", vec![mock_balance("USDT", "1000")])
+///     .await;
+///
+/// let balance = mock
+///     .client()
+///     .get_balance(&GetBalanceRequest::default())
+///     .await
+///     .unwrap();
+/// assert_eq!(balance[0].details[0].eq, "1000");
+/// # }
+/// ```
+pub struct MockOkxRest {
+    server: MockServer,
+}
+
+impl MockOkxRest {
+    /// Start the server on a free local port.
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:40000`.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The underlying wiremock server, for mounting custom mocks.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Client configuration pointed at this server with the mock credentials.
+    pub fn config(&self) -> ClientConfig {
+        ClientConfigBuilder::new()
+            .base_url(&self.server.uri())
+            .credentials(MOCK_API_KEY, MOCK_API_SECRET, MOCK_PASSPHRASE)
+            .build()
+    }
+
+    /// REST client built from [`config`](Self::config).
+    pub fn client(&self) -> RestClient {
+        RestClient::new(self.config()).expect("build mock REST client")
+    }
+
+    /// Answer every `http_method` request to `endpoint` with `data`.
+    pub async fn respond(&self, http_method: &str, endpoint: &str, data: Vec<Value>) {
+        self.respond_json(http_method, endpoint, okx_ok(data)).await;
+    }
+
+    /// Answer every `http_method` request to `endpoint` with a raw body.
+    pub async fn respond_json(&self, http_method: &str, endpoint: &str, body: Value) {
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer correctly signed `http_method` requests to `endpoint` with
+    /// `data`, and reject the rest as OKX does.
+    pub async fn respond_signed(&self, http_method: &str, endpoint: &str, data: Vec<Value>) {
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .and(ValidSignature::new(
+                MOCK_API_KEY,
+                MOCK_API_SECRET,
+                MOCK_PASSPHRASE,
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(okx_ok(data)))
+            .mount(&self.server)
+            .await;
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .respond_with(
+                ResponseTemplate::new(401).set_body_json(okx_error("50113", "Invalid Sign")),
+            )
+            .with_priority(u8::MAX)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Every request received so far.
+    pub async fn received_requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::http::HeaderMap;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::WsConnectionType;
+use crate::ws::types::WsConfig;
+
+/// Canned reply to a WS API operation.
+#[derive(Debug, Clone)]
+struct OpReply {
+    code: String,
+    msg: String,
+    data: Vec<Value>,
+}
+
+struct Connection {
+    tx: mpsc::UnboundedSender<Message>,
+    subscriptions: HashSet<WsSubscriptionArg>,
+}
+
+#[derive(Default)]
+struct Shared {
+    connections: HashMap<usize, Connection>,
+    accepted: usize,
+    handshakes: HashMap<usize, HeaderMap>,
+    replies: HashMap<String, OpReply>,
+    reject_logins: bool,
+    ignore_pings: bool,
+}
+
+/// Local WebSocket server that speaks the OKX v5 protocol.
+///
+/// Answers `ping`, acknowledges `login`, `subscribe` and `unsubscribe`, and
+/// replies to WS API operations with `code` `"0"` and empty data unless a
+/// reply was set with [`reply`](Self::reply). Every JSON frame the client
+/// sends is recorded and can be read with [`next_frame`](Self::next_frame).
+///
+/// ```no_run
+/// # async fn example() {
+/// use okx_client::test_util::MockOkxWs;
+/// use okx_client::types::ws::channels::WsSubscriptionArg;
+/// use okx_client::ws::WebsocketClient;
+///
+/// let mut mock = MockOkxWs::start().await;
+/// let client = WebsocketClient::new(mock.ws_config());
+/// let arg = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
+/// let mut rx = client.subscribe(vec![arg.clone()]).await.unwrap();
+///
+/// mock.wait_for_subscription(&arg).await;
+/// mock.push(&arg, vec![serde_json::json!({"instId": "BTC-USDT", "last": "1"})]);
+/// # }
+/// ```
+pub struct MockOkxWs {
+    url: String,
+    shared: Arc<Mutex<Shared>>,
+    frames: mpsc::UnboundedReceiver<(usize, Value)>,
+    task: JoinHandle<()>,
+}
+
+impl MockOkxWs {
+    /// Start the server on a free local port.
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock WS server");
+        let url = format!("ws://{}", listener.local_addr().expect("local address"));
+        let shared: Arc<Mutex<Shared>> = Arc::default();
+        let (frames_tx, frames) = mpsc::unbounded_channel();
+
+        let task = {
+            let shared = shared.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let id = {
+                        let mut s = lock(&shared);
+                        s.accepted += 1;
+                        s.accepted
+                    };
+                    tokio::spawn(serve(stream, id, shared.clone(), frames_tx.clone()));
+                }
+            })
+        };
+
+        Self {
+            url,
+            shared,
+            frames,
+            task,
+        }
+    }
+
+    /// URL of the server, e.g. `ws://127.0.0.1:40000`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Client configuration with every connection type pointed at this server.
+    pub fn ws_config(&self) -> WsConfig {
+        WsConfig {
+            url_overrides: [
+                WsConnectionType::Public,
+                WsConnectionType::Private,
+                WsConnectionType::Business,
+            ]
+            .into_iter()
+            .map(|conn_type| (conn_type, self.url.clone()))
+            .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Reply to every request of `op` with the given code, message and data.
+    pub fn reply(&self, op: &str, code: &str, msg: &str, data: Vec<Value>) {
+        lock(&self.shared).replies.insert(
+            op.to_string(),
+            OpReply {
+                code: code.to_string(),
+                msg: msg.to_string(),
+                data,
+            },
+        );
+    }
+
+    /// Answer logins with error `60009` instead of success.
+    pub fn reject_logins(&self, reject: bool) {
+        lock(&self.shared).reject_logins = reject;
+    }
+
+    /// Leave text `ping`s unanswered, like a half-open connection.
+    pub fn ignore_pings(&self, ignore: bool) {
+        lock(&self.shared).ignore_pings = ignore;
+    }
+
+    /// Push `data` on `arg` to every connection subscribed to it.
+    ///
+    /// Returns the number of connections the push was sent to.
+    pub fn push(&self, arg: &WsSubscriptionArg, data: Vec<Value>) -> usize {
+        let frame = json!({"arg": arg, "data": data}).to_string();
+        let s = lock(&self.shared);
+        s.connections
+            .values()
+            .filter(|c| c.subscriptions.iter().any(|sub| sub.matches(arg)))
+            .filter(|c| c.tx.send(Message::Text(frame.clone().into())).is_ok())
+            .count()
+    }
+
+    /// Close every open connection with a close frame.
+    pub fn disconnect_all(&self) {
+        let mut s = lock(&self.shared);
+        for (_, conn) in s.connections.drain() {
+            let _ = conn.tx.send(Message::Close(None));
+        }
+    }
+
+    /// Number of connections accepted so far, including closed ones.
+    pub fn connections_accepted(&self) -> usize {
+        lock(&self.shared).accepted
+    }
+
+    /// Request headers of the WebSocket handshake of a connection, numbered
+    /// as in [`next_frame`](Self::next_frame).
+    pub fn handshake_headers(&self, connection: usize) -> Option<HeaderMap> {
+        lock(&self.shared).handshakes.get(&connection).cloned()
+    }
+
+    /// Topics currently subscribed across all open connections.
+    pub fn subscriptions(&self) -> Vec<WsSubscriptionArg> {
+        let s = lock(&self.shared);
+        s.connections
+            .values()
+            .flat_map(|c| c.subscriptions.iter().cloned())
+            .collect()
+    }
+
+    /// Wait until some open connection is subscribed to `arg`.
+    ///
+    /// Frames received while waiting are discarded.
+    pub async fn wait_for_subscription(&mut self, arg: &WsSubscriptionArg) {
+        while !self.subscriptions().contains(arg) {
+            if self.frames.recv().await.is_none() {
+                return;
+            }
+        }
+    }
+
+    /// Next JSON frame received from a client, with the number of the
+    /// connection it arrived on (starting at 1).
+    pub async fn next_frame(&mut self) -> Option<(usize, Value)> {
+        self.frames.recv().await
+    }
+}
+
+impl Drop for MockOkxWs {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.disconnect_all();
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> std::sync::MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+async fn serve(
+    stream: TcpStream,
+    id: usize,
+    shared: Arc<Mutex<Shared>>,
+    frames: mpsc::UnboundedSender<(usize, Value)>,
+) {
+    // The error type is set by tungstenite's callback signature.
+    #[allow(clippy::result_large_err)]
+    let record_headers = |request: &Request, response: Response| {
+        lock(&shared)
+            .handshakes
+            .insert(id, request.headers().clone());
+        Ok(response)
+    };
+    let Ok(ws) = tokio_tungstenite::accept_hdr_async(stream, record_headers).await else {
+        return;
+    };
+    let (mut write, mut read) = ws.split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+    lock(&shared).connections.insert(
+        id,
+        Connection {
+            tx: tx.clone(),
+            subscriptions: HashSet::new(),
+        },
+    );
+
+    let writer = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            let close = matches!(msg, Message::Close(_));
+            if write.send(msg).await.is_err() || close {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(msg)) = read.next().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        if text.as_str() == "ping" {
+            if !lock(&shared).ignore_pings {
+                let _ = tx.send(Message::Text("pong".into()));
+            }
+            continue;
+        }
+        let Ok(frame) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        for reply in respond(&shared, id, &frame) {
+            let _ = tx.send(Message::Text(reply.to_string().into()));
+        }
+        // Record after replying so waiting on a frame implies it was handled.
+        let _ = frames.send((id, frame));
+    }
+
+    lock(&shared).connections.remove(&id);
+    writer.abort();
+}
+
+/// Build the replies to one client frame and update the connection state.
+fn respond(shared: &Mutex<Shared>, id: usize, frame: &Value) -> Vec<Value> {
+    let mut s = lock(shared);
+    let op = frame["op"].as_str().unwrap_or_default();
+    let args: Vec<Value> = frame["args"].as_array().cloned().unwrap_or_default();
+
+    match op {
+        "login" if s.reject_logins => vec![json!({
+            "event": "error", "code": "60009", "msg": "Login failed.", "connId": id.to_string()
+        })],
+        "login" => vec![json!({
+            "event": "login", "code": "0", "msg": "", "connId": id.to_string()
+        })],
+        "subscribe" | "unsubscribe" => {
+            let Some(conn) = s.connections.get_mut(&id) else {
+                return Vec::new();
+            };
+            args.into_iter()
+                .filter_map(|arg| {
+                    let parsed: WsSubscriptionArg = serde_json::from_value(arg.clone()).ok()?;
+                    if op == "subscribe" {
+                        conn.subscriptions.insert(parsed);
+                    } else {
+                        conn.subscriptions.remove(&parsed);
+                    }
+                    Some(json!({"event": op, "arg": arg, "connId": id.to_string()}))
+                })
+                .collect()
+        }
+        _ => {
+            let Some(req_id) = frame["id"].as_str() else {
+                return Vec::new();
+            };
+            let reply = s.replies.get(op).cloned().unwrap_or(OpReply {
+                code: "0".into(),
+                msg: String::new(),
+                data: Vec::new(),
+            });
+            vec![json!({
+                "id": req_id,
+                "op": op,
+                "code": reply.code,
+                "msg": reply.msg,
+                "data": reply.data,
+                "inTime": "0",
+                "outTime": "0",
+            })]
+        }
+    }
+}
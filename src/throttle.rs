@@ -0,0 +1,237 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::{OkxError, OkxResult};
+use crate::time::Instant;
+use crate::types::enums::OrderSide;
+use crate::types::request::trade::OrderRequest;
+
+/// Local order submission throttle settings.
+///
+/// Guards against runaway strategy loops by rejecting orders before they
+/// reach OKX. Disabled unless set on [`ClientConfig`](crate::config::ClientConfig).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderThrottleConfig {
+    /// Maximum number of orders per instrument within `window`.
+    pub max_orders_per_window: usize,
+    /// Sliding window for `max_orders_per_window`.
+    pub window: Duration,
+    /// Reject an order identical (same side/px/sz) to one submitted for the
+    /// same instrument within this cooldown. `Duration::ZERO` disables the check.
+    pub duplicate_cooldown: Duration,
+}
+
+impl Default for OrderThrottleConfig {
+    fn default() -> Self {
+        Self {
+            max_orders_per_window: 10,
+            window: Duration::from_secs(1),
+            duplicate_cooldown: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Identity of an order for duplicate detection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OrderFingerprint {
+    side: OrderSide,
+    px: Option<String>,
+    sz: String,
+}
+
+impl OrderFingerprint {
+    fn of(req: &OrderRequest) -> Self {
+        Self {
+            side: req.side,
+            px: req.px.clone(),
+            sz: req.sz.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct InstrumentHistory {
+    submissions: VecDeque<(Instant, OrderFingerprint)>,
+}
+
+/// Per-instrument order rate and duplicate tracker.
+#[derive(Debug)]
+pub struct OrderThrottle {
+    config: OrderThrottleConfig,
+    history: Mutex<HashMap<String, InstrumentHistory>>,
+}
+
+impl OrderThrottle {
+    pub fn new(config: OrderThrottleConfig) -> Self {
+        Self {
+            config,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the throttle configuration.
+    pub fn config(&self) -> &OrderThrottleConfig {
+        &self.config
+    }
+
+    /// Check an order against the policy and record it if accepted.
+    pub fn check(&self, req: &OrderRequest) -> OkxResult<()> {
+        self.check_at(req, Instant::now())
+    }
+
+    /// Check a batch of orders. Either all orders are recorded or none are.
+    pub fn check_all(&self, reqs: &[OrderRequest]) -> OkxResult<()> {
+        let now = Instant::now();
+        let mut history = self.lock();
+        let snapshot: Vec<_> = reqs
+            .iter()
+            .map(|r| {
+                (
+                    r.inst_id.clone(),
+                    history.get(&r.inst_id).map(|h| h.submissions.clone()),
+                )
+            })
+            .collect();
+
+        for req in reqs {
+            if let Err(e) = self.check_locked(&mut history, req, now) {
+                for (inst_id, prev) in snapshot {
+                    match prev {
+                        Some(submissions) => {
+                            history.entry(inst_id).or_default().submissions = submissions;
+                        }
+                        None => {
+                            history.remove(&inst_id);
+                        }
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_at(&self, req: &OrderRequest, now: Instant) -> OkxResult<()> {
+        let mut history = self.lock();
+        self.check_locked(&mut history, req, now)
+    }
+
+    fn check_locked(
+        &self,
+        history: &mut HashMap<String, InstrumentHistory>,
+        req: &OrderRequest,
+        now: Instant,
+    ) -> OkxResult<()> {
+        let entry = history.entry(req.inst_id.clone()).or_default();
+        let horizon = self.config.window.max(self.config.duplicate_cooldown);
+        while let Some((ts, _)) = entry.submissions.front() {
+            if now.duration_since(*ts) > horizon {
+                entry.submissions.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let fingerprint = OrderFingerprint::of(req);
+        if !self.config.duplicate_cooldown.is_zero()
+            && entry.submissions.iter().any(|(ts, fp)| {
+                now.duration_since(*ts) <= self.config.duplicate_cooldown && *fp == fingerprint
+            })
+        {
+            return Err(OkxError::Throttled(format!(
+                "duplicate order for {} within {:?}",
+                req.inst_id, self.config.duplicate_cooldown
+            )));
+        }
+
+        let in_window = entry
+            .submissions
+            .iter()
+            .filter(|(ts, _)| now.duration_since(*ts) <= self.config.window)
+            .count();
+        if in_window >= self.config.max_orders_per_window {
+            return Err(OkxError::Throttled(format!(
+                "more than {} orders for {} within {:?}",
+                self.config.max_orders_per_window, req.inst_id, self.config.window
+            )));
+        }
+
+        entry.submissions.push_back((now, fingerprint));
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, InstrumentHistory>> {
+        self.history.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(inst_id: &str, px: &str) -> OrderRequest {
+        OrderRequest {
+            inst_id: inst_id.into(),
+            sz: "1".into(),
+            px: Some(px.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_within_cooldown() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            max_orders_per_window: 100,
+            window: Duration::from_secs(1),
+            duplicate_cooldown: Duration::from_secs(1),
+        });
+        let now = Instant::now();
+        throttle.check_at(&order("BTC-USDT", "100"), now).unwrap();
+        let err = throttle
+            .check_at(&order("BTC-USDT", "100"), now)
+            .unwrap_err();
+        assert!(matches!(err, OkxError::Throttled(_)));
+
+        // Different price or instrument is not a duplicate.
+        throttle.check_at(&order("BTC-USDT", "101"), now).unwrap();
+        throttle.check_at(&order("ETH-USDT", "100"), now).unwrap();
+
+        // Same order is accepted again once the cooldown has elapsed.
+        let later = now + Duration::from_millis(1500);
+        throttle.check_at(&order("BTC-USDT", "100"), later).unwrap();
+    }
+
+    #[test]
+    fn rejects_orders_over_window_limit() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            max_orders_per_window: 2,
+            window: Duration::from_secs(1),
+            duplicate_cooldown: Duration::ZERO,
+        });
+        let now = Instant::now();
+        throttle.check_at(&order("BTC-USDT", "1"), now).unwrap();
+        throttle.check_at(&order("BTC-USDT", "2"), now).unwrap();
+        assert!(throttle.check_at(&order("BTC-USDT", "3"), now).is_err());
+        throttle.check_at(&order("ETH-USDT", "3"), now).unwrap();
+
+        let later = now + Duration::from_millis(1100);
+        throttle.check_at(&order("BTC-USDT", "3"), later).unwrap();
+    }
+
+    #[test]
+    fn batch_check_is_all_or_nothing() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            max_orders_per_window: 2,
+            window: Duration::from_secs(60),
+            duplicate_cooldown: Duration::ZERO,
+        });
+        let batch = vec![
+            order("BTC-USDT", "1"),
+            order("BTC-USDT", "2"),
+            order("BTC-USDT", "3"),
+        ];
+        assert!(throttle.check_all(&batch).is_err());
+        throttle.check_all(&batch[..2]).unwrap();
+    }
+}
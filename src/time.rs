@@ -0,0 +1,22 @@
+//! Clock and timer used across the crate.
+//!
+//! `std::time` clocks panic on `wasm32-unknown-unknown` and tokio's timer
+//! needs a tokio runtime, so browser builds use `web-time` and `wasmtimer`.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::sleep;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasmtimer::tokio::sleep;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Current Unix time in milliseconds.
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
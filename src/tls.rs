@@ -0,0 +1,166 @@
+//! TLS setup shared by the REST and WebSocket clients.
+//!
+//! The backend is chosen at compile time with the `rustls-tls` (default) or
+//! `native-tls` feature. When both are enabled, rustls is used.
+
+use tokio_tungstenite::Connector;
+
+use crate::config::TlsConfig;
+use crate::error::{OkxError, OkxResult};
+
+/// Add the configured root certificates to a reqwest client.
+pub(crate) fn configure_reqwest(
+    builder: reqwest::ClientBuilder,
+    tls: &TlsConfig,
+) -> OkxResult<reqwest::ClientBuilder> {
+    if tls.extra_root_certificates.is_empty() {
+        return Ok(builder);
+    }
+
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    {
+        let mut certs = Vec::new();
+        for pem in &tls.extra_root_certificates {
+            certs.extend(
+                reqwest::Certificate::from_pem_bundle(pem)
+                    .map_err(|e| OkxError::Config(format!("invalid root certificate: {e}")))?,
+            );
+        }
+        Ok(builder.tls_certs_merge(certs))
+    }
+
+    #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+    Err(no_tls_backend())
+}
+
+/// TLS connector for a WebSocket connection.
+#[cfg(feature = "rustls-tls")]
+pub(crate) fn ws_connector(tls: &TlsConfig) -> OkxResult<Option<Connector>> {
+    use std::sync::Arc;
+
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for pem in &tls.extra_root_certificates {
+        for cert in CertificateDer::pem_slice_iter(pem) {
+            let cert =
+                cert.map_err(|e| OkxError::Config(format!("invalid root certificate: {e}")))?;
+            roots
+                .add(cert)
+                .map_err(|e| OkxError::Config(format!("invalid root certificate: {e}")))?;
+        }
+    }
+
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| OkxError::Config(format!("invalid TLS configuration: {e}")))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Some(Connector::Rustls(Arc::new(config))))
+}
+
+/// TLS connector for a WebSocket connection.
+#[cfg(all(feature = "native-tls", not(feature = "rustls-tls")))]
+pub(crate) fn ws_connector(tls: &TlsConfig) -> OkxResult<Option<Connector>> {
+    if tls.extra_root_certificates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = native_tls::TlsConnector::builder();
+    for pem in &tls.extra_root_certificates {
+        let blocks = pem_blocks(pem);
+        if blocks.is_empty() {
+            return Err(OkxError::Config(
+                "invalid root certificate: no PEM certificate found".into(),
+            ));
+        }
+        for block in blocks {
+            let cert = native_tls::Certificate::from_pem(block)
+                .map_err(|e| OkxError::Config(format!("invalid root certificate: {e}")))?;
+            builder.add_root_certificate(cert);
+        }
+    }
+    let connector = builder
+        .build()
+        .map_err(|e| OkxError::Config(format!("invalid TLS configuration: {e}")))?;
+    Ok(Some(Connector::NativeTls(connector)))
+}
+
+/// Split a PEM bundle into its certificates. `native_tls::Certificate::from_pem`
+/// reads only the first one.
+#[cfg(all(feature = "native-tls", not(feature = "rustls-tls")))]
+fn pem_blocks(pem: &[u8]) -> Vec<&[u8]> {
+    const BEGIN: &[u8] = b"-----BEGIN CERTIFICATE-----";
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+    let find = |haystack: &[u8], needle: &[u8]| {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    };
+
+    let mut blocks = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = find(rest, BEGIN) {
+        let Some(len) = find(&rest[start..], END) else {
+            break;
+        };
+        let end = start + len + END.len();
+        blocks.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// Without a TLS backend only plain `ws://` URLs can be used.
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+pub(crate) fn ws_connector(tls: &TlsConfig) -> OkxResult<Option<Connector>> {
+    if tls.extra_root_certificates.is_empty() {
+        Ok(None)
+    } else {
+        Err(no_tls_backend())
+    }
+}
+
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+fn no_tls_backend() -> OkxError {
+    OkxError::Config("TLS is disabled; enable the rustls-tls or native-tls feature".into())
+}
+
+#[cfg(all(test, feature = "rustls-tls"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_root_certificate() {
+        let tls = TlsConfig {
+            extra_root_certificates: vec![
+                b"-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n".to_vec(),
+            ],
+        };
+        assert!(matches!(ws_connector(&tls), Err(OkxError::Config(_))));
+        assert!(ws_connector(&TlsConfig::default()).unwrap().is_some());
+    }
+}
+
+#[cfg(all(test, feature = "native-tls", not(feature = "rustls-tls")))]
+mod native_tests {
+    use super::*;
+
+    #[test]
+    fn splits_pem_bundles() {
+        let bundle = b"# first\n-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n\
+            -----BEGIN CERTIFICATE-----\nBBBB\n-----END CERTIFICATE-----\n";
+        let blocks = pem_blocks(bundle);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[1].starts_with(b"-----BEGIN CERTIFICATE-----\nBBBB"));
+        assert!(blocks[1].ends_with(b"-----END CERTIFICATE-----"));
+
+        let tls = TlsConfig {
+            extra_root_certificates: vec![b"not a certificate".to_vec()],
+        };
+        assert!(matches!(ws_connector(&tls), Err(OkxError::Config(_))));
+    }
+}
@@ -12,6 +12,19 @@ pub enum InstrumentType {
     Option,
 }
 
+impl InstrumentType {
+    /// OKX string value, e.g. "SPOT".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spot => "SPOT",
+            Self::Margin => "MARGIN",
+            Self::Swap => "SWAP",
+            Self::Futures => "FUTURES",
+            Self::Option => "OPTION",
+        }
+    }
+}
+
 /// "ANY" or a specific instrument type, used in some subscription args.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -100,9 +113,13 @@ pub enum PosMode {
 }
 
 /// Algo order type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+///
+/// Unknown types are preserved in `Other` so new OKX types can be sent and
+/// received before they are added here.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(from = "String", into = "String")]
 pub enum AlgoOrderType {
+    #[default]
     Conditional,
     Oco,
     Trigger,
@@ -110,6 +127,53 @@ pub enum AlgoOrderType {
     Iceberg,
     Twap,
     Chase,
+    Other(String),
+}
+
+impl AlgoOrderType {
+    /// OKX name of this variant.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Conditional => "conditional",
+            Self::Oco => "oco",
+            Self::Trigger => "trigger",
+            Self::MoveOrderStop => "move_order_stop",
+            Self::Iceberg => "iceberg",
+            Self::Twap => "twap",
+            Self::Chase => "chase",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for AlgoOrderType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "conditional" => Self::Conditional,
+            "oco" => Self::Oco,
+            "trigger" => Self::Trigger,
+            "move_order_stop" => Self::MoveOrderStop,
+            "iceberg" => Self::Iceberg,
+            "twap" => Self::Twap,
+            "chase" => Self::Chase,
+            _ => Self::Other(name),
+        }
+    }
+}
+
+impl From<AlgoOrderType> for String {
+    fn from(value: AlgoOrderType) -> Self {
+        match value {
+            AlgoOrderType::Other(name) => name,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
+impl AsRef<str> for AlgoOrderType {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
 }
 
 /// Algo order state.
@@ -172,6 +236,80 @@ pub enum TransferType {
     SubAccountToSubAccount,
 }
 
+impl TransferType {
+    /// Whether OKX requires `subAcct` for this transfer type. Types that do
+    /// not require it do not accept it either.
+    pub fn requires_sub_account(&self) -> bool {
+        matches!(
+            self,
+            Self::MasterToSubAccount | Self::SubAccountToMaster | Self::SubAccountToSubAccount
+        )
+    }
+}
+
+/// Account a funds transfer debits or credits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransferAccount {
+    #[serde(rename = "6")]
+    Funding,
+    #[serde(rename = "18")]
+    Trading,
+}
+
+impl TransferAccount {
+    /// OKX string value, e.g. "6" for the funding account.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Funding => "6",
+            Self::Trading => "18",
+        }
+    }
+}
+
+/// State of a funds transfer.
+///
+/// Unknown states are preserved in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum TransferStatus {
+    Success,
+    Pending,
+    Failed,
+    Other(String),
+}
+
+impl TransferStatus {
+    /// OKX name of this variant.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Success => "success",
+            Self::Pending => "pending",
+            Self::Failed => "failed",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for TransferStatus {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "success" => Self::Success,
+            "pending" => Self::Pending,
+            "failed" => Self::Failed,
+            _ => Self::Other(name),
+        }
+    }
+}
+
+impl From<TransferStatus> for String {
+    fn from(value: TransferStatus) -> Self {
+        match value {
+            TransferStatus::Other(name) => name,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
 /// Candle bar size / period.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Bar {
@@ -211,6 +349,122 @@ pub enum Bar {
     Mo3,
 }
 
+impl Bar {
+    /// All bar sizes, shortest first.
+    pub const ALL: [Bar; 17] = [
+        Self::S1,
+        Self::M1,
+        Self::M3,
+        Self::M5,
+        Self::M15,
+        Self::M30,
+        Self::H1,
+        Self::H2,
+        Self::H4,
+        Self::H6,
+        Self::H12,
+        Self::D1,
+        Self::D2,
+        Self::D3,
+        Self::W1,
+        Self::Mo1,
+        Self::Mo3,
+    ];
+
+    /// OKX string value, e.g. "1m" or "1H".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::S1 => "1s",
+            Self::M1 => "1m",
+            Self::M3 => "3m",
+            Self::M5 => "5m",
+            Self::M15 => "15m",
+            Self::M30 => "30m",
+            Self::H1 => "1H",
+            Self::H2 => "2H",
+            Self::H4 => "4H",
+            Self::H6 => "6H",
+            Self::H12 => "12H",
+            Self::D1 => "1D",
+            Self::D2 => "2D",
+            Self::D3 => "3D",
+            Self::W1 => "1W",
+            Self::Mo1 => "1M",
+            Self::Mo3 => "3M",
+        }
+    }
+
+    /// Parse an OKX bar string. Returns `None` for unknown values.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|bar| bar.as_str() == s)
+    }
+
+    /// Bar length in milliseconds. `None` for month bars, whose length varies.
+    pub fn millis(&self) -> Option<i64> {
+        const SECOND: i64 = 1_000;
+        const MINUTE: i64 = 60 * SECOND;
+        const HOUR: i64 = 60 * MINUTE;
+        const DAY: i64 = 24 * HOUR;
+        Some(match self {
+            Self::S1 => SECOND,
+            Self::M1 => MINUTE,
+            Self::M3 => 3 * MINUTE,
+            Self::M5 => 5 * MINUTE,
+            Self::M15 => 15 * MINUTE,
+            Self::M30 => 30 * MINUTE,
+            Self::H1 => HOUR,
+            Self::H2 => 2 * HOUR,
+            Self::H4 => 4 * HOUR,
+            Self::H6 => 6 * HOUR,
+            Self::H12 => 12 * HOUR,
+            Self::D1 => DAY,
+            Self::D2 => 2 * DAY,
+            Self::D3 => 3 * DAY,
+            Self::W1 => 7 * DAY,
+            Self::Mo1 | Self::Mo3 => return None,
+        })
+    }
+}
+
+/// Aggregation period of trading statistics (Rubik) endpoints.
+///
+/// Each endpoint supports a subset of these periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TradingDataPeriod {
+    #[serde(rename = "5m")]
+    M5,
+    #[serde(rename = "15m")]
+    M15,
+    #[serde(rename = "30m")]
+    M30,
+    #[serde(rename = "1H")]
+    H1,
+    #[serde(rename = "2H")]
+    H2,
+    #[serde(rename = "4H")]
+    H4,
+    #[serde(rename = "6H")]
+    H6,
+    #[serde(rename = "8H")]
+    H8,
+    #[serde(rename = "12H")]
+    H12,
+    #[serde(rename = "1D")]
+    D1,
+    #[serde(rename = "2D")]
+    D2,
+    #[serde(rename = "3D")]
+    D3,
+    #[serde(rename = "5D")]
+    D5,
+    #[serde(rename = "1W")]
+    W1,
+    #[serde(rename = "1M")]
+    Mo1,
+    #[serde(rename = "3M")]
+    Mo3,
+}
+
 /// Withdrawal destination.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum WithdrawDest {
@@ -221,6 +475,16 @@ pub enum WithdrawDest {
     OnChain,
 }
 
+/// Unit of the order quantity for SPOT market orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetCurrency {
+    /// `sz` is denominated in the base currency (default for sells).
+    BaseCcy,
+    /// `sz` is denominated in the quote currency (default for buys).
+    QuoteCcy,
+}
+
 /// Self-trade prevention mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -238,3 +502,450 @@ pub enum GridAlgoOrderType {
     ContractGrid,
     MoonGrid,
 }
+
+/// Direction of a manual borrow or repay operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BorrowRepaySide {
+    #[default]
+    Borrow,
+    Repay,
+}
+
+/// Currency spot trading fees are charged in.
+///
+/// Unknown values are preserved in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(from = "String", into = "String")]
+pub enum FeeType {
+    /// The currency received from the trade.
+    #[default]
+    ReceivedCurrency,
+    /// Always the quote currency of the pair.
+    QuoteCurrency,
+    Other(String),
+}
+
+impl FeeType {
+    /// OKX value of this variant.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::ReceivedCurrency => "0",
+            Self::QuoteCurrency => "1",
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for FeeType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "0" => Self::ReceivedCurrency,
+            "1" => Self::QuoteCurrency,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<FeeType> for String {
+    fn from(value: FeeType) -> Self {
+        match value {
+            FeeType::Other(value) => value,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
+/// Auto earn product.
+///
+/// Unknown values are preserved in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(from = "String", into = "String")]
+pub enum EarnType {
+    #[default]
+    AutoEarn,
+    /// Auto earn in USDG.
+    AutoEarnUsdg,
+    Other(String),
+}
+
+impl EarnType {
+    /// OKX value of this variant.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AutoEarn => "0",
+            Self::AutoEarnUsdg => "1",
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for EarnType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "0" => Self::AutoEarn,
+            "1" => Self::AutoEarnUsdg,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<EarnType> for String {
+    fn from(value: EarnType) -> Self {
+        match value {
+            EarnType::Other(value) => value,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
+/// Whether to turn auto earn on or off.
+///
+/// Unknown values are preserved in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum AutoEarnAction {
+    TurnOn,
+    TurnOff,
+    Other(String),
+}
+
+impl AutoEarnAction {
+    /// OKX value of this variant.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::TurnOn => "turn_on",
+            Self::TurnOff => "turn_off",
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for AutoEarnAction {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "turn_on" => Self::TurnOn,
+            "turn_off" => Self::TurnOff,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<AutoEarnAction> for String {
+    fn from(value: AutoEarnAction) -> Self {
+        match value {
+            AutoEarnAction::Other(value) => value,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
+/// Calendar quarter, used by archive download endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Quarter {
+    Q1,
+    Q2,
+    Q3,
+    Q4,
+}
+
+/// Preparation state of a downloadable archive file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveState {
+    Finished,
+    Ongoing,
+    Failed,
+}
+
+/// Account bill type.
+///
+/// Unknown codes are preserved in `Other` so new OKX codes never fail
+/// deserialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum BillType {
+    Transfer,
+    Trade,
+    Delivery,
+    AutoTokenConversion,
+    Liquidation,
+    MarginTransfer,
+    InterestDeduction,
+    FundingFee,
+    Adl,
+    Clawback,
+    SystemTokenConversion,
+    StrategyTransfer,
+    Ddh,
+    BlockTrade,
+    QuickMargin,
+    Borrowing,
+    Repay,
+    SpreadTrading,
+    StructuredProducts,
+    Convert,
+    EasyConvert,
+    OneClickRepay,
+    SimpleTrade,
+    MovePosition,
+    Loans,
+    Settlement,
+    ProfitSharingExpense,
+    ProfitSharingRefund,
+    ProfitSharingIncome,
+    Other(String),
+}
+
+impl BillType {
+    /// Numeric OKX code of this variant.
+    pub fn as_code(&self) -> &str {
+        match self {
+            Self::Transfer => "1",
+            Self::Trade => "2",
+            Self::Delivery => "3",
+            Self::AutoTokenConversion => "4",
+            Self::Liquidation => "5",
+            Self::MarginTransfer => "6",
+            Self::InterestDeduction => "7",
+            Self::FundingFee => "8",
+            Self::Adl => "9",
+            Self::Clawback => "10",
+            Self::SystemTokenConversion => "11",
+            Self::StrategyTransfer => "12",
+            Self::Ddh => "13",
+            Self::BlockTrade => "14",
+            Self::QuickMargin => "15",
+            Self::Borrowing => "16",
+            Self::Repay => "22",
+            Self::SpreadTrading => "24",
+            Self::StructuredProducts => "26",
+            Self::Convert => "27",
+            Self::EasyConvert => "28",
+            Self::OneClickRepay => "29",
+            Self::SimpleTrade => "30",
+            Self::MovePosition => "32",
+            Self::Loans => "33",
+            Self::Settlement => "34",
+            Self::ProfitSharingExpense => "250",
+            Self::ProfitSharingRefund => "251",
+            Self::ProfitSharingIncome => "252",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for BillType {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "1" => Self::Transfer,
+            "2" => Self::Trade,
+            "3" => Self::Delivery,
+            "4" => Self::AutoTokenConversion,
+            "5" => Self::Liquidation,
+            "6" => Self::MarginTransfer,
+            "7" => Self::InterestDeduction,
+            "8" => Self::FundingFee,
+            "9" => Self::Adl,
+            "10" => Self::Clawback,
+            "11" => Self::SystemTokenConversion,
+            "12" => Self::StrategyTransfer,
+            "13" => Self::Ddh,
+            "14" => Self::BlockTrade,
+            "15" => Self::QuickMargin,
+            "16" => Self::Borrowing,
+            "22" => Self::Repay,
+            "24" => Self::SpreadTrading,
+            "26" => Self::StructuredProducts,
+            "27" => Self::Convert,
+            "28" => Self::EasyConvert,
+            "29" => Self::OneClickRepay,
+            "30" => Self::SimpleTrade,
+            "32" => Self::MovePosition,
+            "33" => Self::Loans,
+            "34" => Self::Settlement,
+            "250" => Self::ProfitSharingExpense,
+            "251" => Self::ProfitSharingRefund,
+            "252" => Self::ProfitSharingIncome,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl From<BillType> for String {
+    fn from(value: BillType) -> Self {
+        match value {
+            BillType::Other(code) => code,
+            other => other.as_code().to_string(),
+        }
+    }
+}
+
+/// Account bill sub-type.
+///
+/// Unknown codes are preserved in `Other` so new OKX codes never fail
+/// deserialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum BillSubType {
+    Buy,
+    Sell,
+    OpenLong,
+    OpenShort,
+    CloseLong,
+    CloseShort,
+    MarketLoanInterest,
+    TransferIn,
+    TransferOut,
+    VipLoanInterest,
+    PartialLiquidationCloseLong,
+    PartialLiquidationCloseShort,
+    PartialLiquidationBuy,
+    PartialLiquidationSell,
+    LiquidationLong,
+    LiquidationShort,
+    LiquidationBuy,
+    LiquidationSell,
+    LiquidationTransferIn,
+    LiquidationTransferOut,
+    DeliveryLong,
+    DeliveryShort,
+    AdlCloseLong,
+    AdlCloseShort,
+    AdlBuy,
+    AdlSell,
+    ManualMarginIncrease,
+    ManualMarginDecrease,
+    AutoMarginIncrease,
+    FundingFeeExpense,
+    FundingFeeIncome,
+    Other(String),
+}
+
+impl BillSubType {
+    /// Numeric OKX code of this variant.
+    pub fn as_code(&self) -> &str {
+        match self {
+            Self::Buy => "1",
+            Self::Sell => "2",
+            Self::OpenLong => "3",
+            Self::OpenShort => "4",
+            Self::CloseLong => "5",
+            Self::CloseShort => "6",
+            Self::MarketLoanInterest => "9",
+            Self::TransferIn => "11",
+            Self::TransferOut => "12",
+            Self::VipLoanInterest => "14",
+            Self::PartialLiquidationCloseLong => "100",
+            Self::PartialLiquidationCloseShort => "101",
+            Self::PartialLiquidationBuy => "102",
+            Self::PartialLiquidationSell => "103",
+            Self::LiquidationLong => "104",
+            Self::LiquidationShort => "105",
+            Self::LiquidationBuy => "106",
+            Self::LiquidationSell => "107",
+            Self::LiquidationTransferIn => "110",
+            Self::LiquidationTransferOut => "111",
+            Self::DeliveryLong => "112",
+            Self::DeliveryShort => "113",
+            Self::AdlCloseLong => "125",
+            Self::AdlCloseShort => "126",
+            Self::AdlBuy => "127",
+            Self::AdlSell => "128",
+            Self::ManualMarginIncrease => "160",
+            Self::ManualMarginDecrease => "161",
+            Self::AutoMarginIncrease => "162",
+            Self::FundingFeeExpense => "173",
+            Self::FundingFeeIncome => "174",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for BillSubType {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "1" => Self::Buy,
+            "2" => Self::Sell,
+            "3" => Self::OpenLong,
+            "4" => Self::OpenShort,
+            "5" => Self::CloseLong,
+            "6" => Self::CloseShort,
+            "9" => Self::MarketLoanInterest,
+            "11" => Self::TransferIn,
+            "12" => Self::TransferOut,
+            "14" => Self::VipLoanInterest,
+            "100" => Self::PartialLiquidationCloseLong,
+            "101" => Self::PartialLiquidationCloseShort,
+            "102" => Self::PartialLiquidationBuy,
+            "103" => Self::PartialLiquidationSell,
+            "104" => Self::LiquidationLong,
+            "105" => Self::LiquidationShort,
+            "106" => Self::LiquidationBuy,
+            "107" => Self::LiquidationSell,
+            "110" => Self::LiquidationTransferIn,
+            "111" => Self::LiquidationTransferOut,
+            "112" => Self::DeliveryLong,
+            "113" => Self::DeliveryShort,
+            "125" => Self::AdlCloseLong,
+            "126" => Self::AdlCloseShort,
+            "127" => Self::AdlBuy,
+            "128" => Self::AdlSell,
+            "160" => Self::ManualMarginIncrease,
+            "161" => Self::ManualMarginDecrease,
+            "162" => Self::AutoMarginIncrease,
+            "173" => Self::FundingFeeExpense,
+            "174" => Self::FundingFeeIncome,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl From<BillSubType> for String {
+    fn from(value: BillSubType) -> Self {
+        match value {
+            BillSubType::Other(code) => code,
+            other => other.as_code().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bill_type_round_trips_known_and_unknown_codes() {
+        let known: BillType = serde_json::from_str("\"8\"").unwrap();
+        assert_eq!(known, BillType::FundingFee);
+        assert_eq!(serde_json::to_string(&known).unwrap(), "\"8\"");
+
+        let unknown: BillSubType = serde_json::from_str("\"9999\"").unwrap();
+        assert_eq!(unknown, BillSubType::Other("9999".into()));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"9999\"");
+    }
+
+    #[test]
+    fn algo_order_type_round_trips_known_and_unknown_names() {
+        let known: AlgoOrderType = serde_json::from_str("\"move_order_stop\"").unwrap();
+        assert_eq!(known, AlgoOrderType::MoveOrderStop);
+        assert_eq!(
+            serde_json::to_string(&known).unwrap(),
+            "\"move_order_stop\""
+        );
+
+        let unknown: AlgoOrderType = serde_json::from_str("\"new_type\"").unwrap();
+        assert_eq!(unknown, AlgoOrderType::Other("new_type".into()));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"new_type\"");
+    }
+
+    #[test]
+    fn transfer_status_keeps_unknown_states() {
+        let known: TransferStatus = serde_json::from_str("\"pending\"").unwrap();
+        assert_eq!(known, TransferStatus::Pending);
+
+        let unknown: TransferStatus = serde_json::from_str("\"processing\"").unwrap();
+        assert_eq!(unknown, TransferStatus::Other("processing".into()));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"processing\"");
+    }
+}
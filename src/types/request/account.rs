@@ -100,10 +100,10 @@ pub struct GetBillsRequest {
     /// Bill type.
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub type_: Option<String>,
+    pub type_: Option<BillType>,
     /// Bill sub-type.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sub_type: Option<String>,
+    pub sub_type: Option<BillSubType>,
     /// Pagination of data to return records earlier than the requested `billId`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
@@ -336,6 +336,19 @@ pub struct GetMmpConfigRequest {
     pub inst_family: Option<String>,
 }
 
+/// Reset MMP request.
+///
+/// Unfreeze an instrument family after MMP has been triggered.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MmpResetRequest {
+    /// Instrument type. Only `OPTION` is supported; defaults to `OPTION`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_type: Option<InstrumentType>,
+    /// Instrument family, e.g. "BTC-USD".
+    pub inst_family: String,
+}
+
 /// Set account level request.
 ///
 /// Set the account level.
@@ -373,14 +386,14 @@ pub struct ChangePositionMarginRequest {
     pub loan_trans: Option<bool>,
 }
 
-/// Borrow or repay in quick margin mode.
+/// Borrow or repay a loan in multi-currency margin or portfolio margin mode.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BorrowRepayRequest {
     /// Currency, e.g. "BTC".
     pub ccy: String,
-    /// Direction: "borrow" or "repay".
-    pub side: String,
+    /// Direction: borrow or repay.
+    pub side: BorrowRepaySide,
     /// Amount to borrow or repay.
     pub amt: String,
     /// Order ID for repayment. Required when `side` is "repay".
@@ -406,6 +419,62 @@ pub struct GetBorrowRepayHistoryRequest {
     pub limit: Option<String>,
 }
 
+/// Borrow or repay in quick margin mode.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickMarginBorrowRepayRequest {
+    /// Instrument ID, e.g. "BTC-USDT".
+    pub inst_id: String,
+    /// Loan currency, e.g. "BTC".
+    pub ccy: String,
+    /// Direction: borrow or repay.
+    pub side: BorrowRepaySide,
+    /// Amount to borrow or repay.
+    pub amt: String,
+}
+
+/// Get quick margin borrow/repay history request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetQuickMarginBorrowRepayHistoryRequest {
+    /// Instrument ID, e.g. "BTC-USDT".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_id: Option<String>,
+    /// Loan currency, e.g. "BTC".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    /// Direction: borrow or repay.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<BorrowRepaySide>,
+    /// Pagination of data to return records earlier than the requested `refId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `refId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Filter with a begin timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<String>,
+    /// Filter with an end timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Get borrow interest and limit request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetInterestLimitsRequest {
+    /// Loan type: "2" for market loans (default).
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Loan currency, e.g. "BTC".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+}
+
 /// Get greeks request.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -415,6 +484,47 @@ pub struct GetGreeksRequest {
     pub ccy: Option<String>,
 }
 
+/// Get account position tiers request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAccountPositionTiersRequest {
+    /// Instrument type: `SWAP`, `FUTURES` or `OPTION`.
+    pub inst_type: InstrumentType,
+    /// Underlying, e.g. "BTC-USD". Either `uly` or `inst_family` is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uly: Option<String>,
+    /// Instrument family, e.g. "BTC-USD". Either `uly` or `inst_family` is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_family: Option<String>,
+}
+
+/// Virtual position used by the portfolio margin simulator.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedPosition {
+    /// Instrument ID, e.g. "BTC-USDT-SWAP".
+    pub inst_id: String,
+    /// Position quantity; negative for short positions.
+    pub pos: String,
+}
+
+/// Portfolio margin simulated margin (position builder) request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedMarginRequest {
+    /// Instrument type: `SWAP`, `FUTURES` or `OPTION`. All types if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_type: Option<InstrumentType>,
+    /// Whether to include the account's real positions. Default `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incl_real_pos: Option<bool>,
+    /// Spot-derivatives offset mode: "1" spot-derivatives, "2" derivatives only, "3" none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot_offset_type: Option<String>,
+    /// Virtual positions to add to the simulation.
+    pub sim_pos: Vec<SimulatedPosition>,
+}
+
 /// Set auto loan request.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -422,3 +532,39 @@ pub struct SetAutoLoanRequest {
     /// Whether to enable automatic borrowing.
     pub auto_loan: bool,
 }
+
+/// Set fee type request.
+///
+/// Choose the currency spot trading fees are charged in.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeeTypeRequest {
+    pub fee_type: FeeType,
+}
+
+/// Set auto earn request.
+///
+/// Turn auto earn on or off for a currency.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAutoEarnRequest {
+    /// Auto earn product (OKX default: [`EarnType::AutoEarn`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub earn_type: Option<EarnType>,
+    /// Currency, e.g. "USDT".
+    pub ccy: String,
+    pub action: AutoEarnAction,
+}
+
+/// Bills history archive request.
+///
+/// Apply for, or query the download link of, the bills archive of a given
+/// quarter. Used for both `POST` and `GET /api/v5/account/bills-history-archive`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillsArchiveRequest {
+    /// Four-digit year, e.g. "2024".
+    pub year: String,
+    /// Quarter of the year.
+    pub quarter: Quarter,
+}
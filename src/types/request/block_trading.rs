@@ -0,0 +1,78 @@
+use serde::Serialize;
+
+use crate::types::enums::{OrderSide, PositionSide, TradeMode};
+
+/// Move positions between accounts of the same master account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePositionsRequest {
+    /// Client-supplied ID, up to 32 alphanumeric characters.
+    pub client_id: String,
+    /// Source account name. Use "0" for the master account.
+    pub from_acct: String,
+    /// Destination account name. Use "0" for the master account.
+    pub to_acct: String,
+    /// Positions to move.
+    pub legs: Vec<MovePositionLeg>,
+}
+
+/// A single position moved by [`MovePositionsRequest`].
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePositionLeg {
+    /// Position in the source account.
+    pub from: MovePositionFrom,
+    /// How the position is opened in the destination account.
+    pub to: MovePositionTo,
+}
+
+/// Source side of a moved position.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePositionFrom {
+    /// Position ID in the source account.
+    pub pos_id: String,
+    /// Quantity to move.
+    pub sz: String,
+    /// Side of the trade executed in the source account.
+    pub side: OrderSide,
+}
+
+/// Destination side of a moved position.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePositionTo {
+    /// Trade mode in the destination account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub td_mode: Option<TradeMode>,
+    /// Position side in the destination account. Required in long/short mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pos_side: Option<PositionSide>,
+    /// Margin currency. Only applicable to cross `MARGIN` positions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+}
+
+/// Get move positions history request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMovePositionsHistoryRequest {
+    /// Block trade ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_td_id: Option<String>,
+    /// Client-supplied ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Filter with a begin timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin_ts: Option<String>,
+    /// Filter with an end timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_ts: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    /// Move state: "filled" or "pending".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
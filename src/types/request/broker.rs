@@ -0,0 +1,180 @@
+use serde::Serialize;
+
+use crate::types::enums::InstrumentType;
+
+/// Create a sub-account under a non-disclosed broker.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerCreateSubAccountRequest {
+    /// Sub-account name, 6-20 alphanumeric characters.
+    pub sub_acct: String,
+    /// Sub-account notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// IP address of the sub-account's end user.
+    #[serde(rename = "clientIP", skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+    /// Main account name of the secondary sub-account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_acct: Option<String>,
+}
+
+/// Delete a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerDeleteSubAccountRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+}
+
+/// Get broker sub-account list.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerGetSubAccountsRequest {
+    /// Sub-account name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_acct: Option<String>,
+    /// Sub-account UID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    /// Page number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Create an API key for a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerCreateApiKeyRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// API key note.
+    pub label: String,
+    /// API key passphrase.
+    pub passphrase: String,
+    /// Comma-separated list of up to 20 bound IP addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    /// Comma-separated permissions: "read_only", "trade", "withdraw".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perm: Option<String>,
+}
+
+/// Query API keys of a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerGetApiKeysRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// API key. Returns all keys of the sub-account if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Modify an API key of a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerModifyApiKeyRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// API key to modify.
+    pub api_key: String,
+    /// New API key note.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// New comma-separated permissions. Replaces the existing permissions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perm: Option<String>,
+    /// New comma-separated list of bound IP addresses. Replaces the existing list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+}
+
+/// Delete an API key of a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerDeleteApiKeyRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// API key to delete.
+    pub api_key: String,
+}
+
+/// Set the trading fee rate of a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerSetFeeRateRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// Instrument type: `SPOT`, `MARGIN`, `SWAP`, `FUTURES` or `OPTION`.
+    pub inst_type: InstrumentType,
+    /// Fee rate type for `SWAP` and `FUTURES`: "1" crypto-margined, "2" USDT-margined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mgn_type: Option<String>,
+    /// How the fee rate is adjusted: "absolute" or "percentage".
+    pub chg_type: String,
+    /// Taker fee rate adjustment.
+    pub chg_taker: String,
+    /// Maker fee rate adjustment.
+    pub chg_maker: String,
+    /// Effective date in UTC, format "YYYYMMDD".
+    pub eff_date: String,
+}
+
+/// Generate or query the rebate details file per order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerRebatePerOrdersRequest {
+    /// Begin date in UTC, format "YYYYMMDD".
+    pub begin: String,
+    /// End date in UTC, format "YYYYMMDD".
+    pub end: String,
+}
+
+/// Create a deposit address for a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerCreateDepositAddressRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// Currency, e.g. "USDT".
+    pub ccy: String,
+    /// Chain, e.g. "USDT-TRC20".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Address type: "1" regular, "2" contract address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr_type: Option<String>,
+    /// Account deposits are credited to: "6" funding, "18" trading.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+/// Modify the crediting account of a broker sub-account deposit address.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerModifyDepositAddressRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// Currency, e.g. "USDT".
+    pub ccy: String,
+    /// Chain, e.g. "USDT-TRC20".
+    pub chain: String,
+    /// Deposit address.
+    pub addr: String,
+    /// Account deposits are credited to: "6" funding, "18" trading.
+    pub to: String,
+}
+
+/// Get the deposit addresses of a broker sub-account.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerGetDepositAddressesRequest {
+    /// Sub-account name.
+    pub sub_acct: String,
+    /// Currency, e.g. "USDT".
+    pub ccy: String,
+}
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+
 use serde::Serialize;
 
+use crate::error::{OkxError, OkxResult};
 use crate::types::enums::*;
+use crate::types::response::funding::Currency;
 
 /// Get currencies list.
 #[derive(Debug, Clone, Serialize, Default)]
@@ -18,6 +22,32 @@ pub struct GetAssetBalancesRequest {
     pub ccy: Option<String>,
 }
 
+/// Get asset valuation.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAssetValuationRequest {
+    /// Valuation currency, e.g. "USDT" (default: "BTC").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+}
+
+/// Get non-tradable assets.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNonTradableAssetsRequest {
+    /// Comma-separated currencies, up to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+}
+
+/// Convert small balances to OKB.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertDustAssetsRequest {
+    /// Currencies to convert, e.g. `["BTC", "USDC"]`.
+    pub ccy: Vec<String>,
+}
+
 /// Submit a withdrawal.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -33,6 +63,223 @@ pub struct WithdrawRequest {
     pub client_id: Option<String>,
 }
 
+impl WithdrawRequest {
+    /// Start a validated withdrawal of `amt` `ccy` to `to_addr`.
+    pub fn builder(ccy: &str, amt: &str, to_addr: &str) -> WithdrawRequestBuilder {
+        WithdrawRequestBuilder {
+            request: Self {
+                ccy: ccy.to_string(),
+                amt: amt.to_string(),
+                to_addr: to_addr.to_string(),
+                ..Default::default()
+            },
+            currency: None,
+            allow_list: None,
+        }
+    }
+}
+
+/// Builds a [`WithdrawRequest`] and checks it locally before anything is
+/// sent.
+///
+/// [`build`](Self::build) always checks the chain format. With
+/// [`currency`](Self::currency) it also checks the amount against the chain's
+/// withdrawal limits and precision, and with [`allow_list`](Self::allow_list)
+/// it rejects destinations that are not listed.
+#[derive(Debug, Clone)]
+pub struct WithdrawRequestBuilder {
+    request: WithdrawRequest,
+    currency: Option<Currency>,
+    allow_list: Option<HashSet<String>>,
+}
+
+impl WithdrawRequestBuilder {
+    pub fn dest(mut self, dest: WithdrawDest) -> Self {
+        self.request.dest = dest;
+        self
+    }
+
+    pub fn fee(mut self, fee: &str) -> Self {
+        self.request.fee = fee.to_string();
+        self
+    }
+
+    /// Chain as `<ccy>-<network>`, e.g. "USDT-TRC20".
+    pub fn chain(mut self, chain: &str) -> Self {
+        self.request.chain = Some(chain.to_string());
+        self
+    }
+
+    pub fn client_id(mut self, client_id: &str) -> Self {
+        self.request.client_id = Some(client_id.to_string());
+        self
+    }
+
+    /// Currency entry from [`get_currencies`](crate::RestClient::get_currencies)
+    /// for the target chain, used to check limits and precision.
+    pub fn currency(mut self, currency: &Currency) -> Self {
+        self.currency = Some(currency.clone());
+        self
+    }
+
+    /// Only allow withdrawals to these addresses (or, for internal
+    /// transfers, these recipients).
+    pub fn allow_list<I, S>(mut self, addresses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allow_list = Some(addresses.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Validate and return the request.
+    pub fn build(self) -> OkxResult<WithdrawRequest> {
+        let request = self.request;
+        if request.to_addr.is_empty() {
+            return Err(OkxError::Config("withdrawal address is empty".into()));
+        }
+        let amt: f64 = request
+            .amt
+            .parse()
+            .ok()
+            .filter(|amt: &f64| *amt > 0.0)
+            .ok_or_else(|| {
+                OkxError::Config(format!(
+                    "withdrawal amount must be positive, got {:?}",
+                    request.amt
+                ))
+            })?;
+
+        if let Some(chain) = &request.chain {
+            let network = chain
+                .strip_prefix(request.ccy.as_str())
+                .and_then(|rest| rest.strip_prefix('-'));
+            if network.is_none_or(str::is_empty) {
+                return Err(OkxError::Config(format!(
+                    "chain {chain:?} is not of the form {}-<network>",
+                    request.ccy
+                )));
+            }
+        }
+
+        if let Some(currency) = &self.currency {
+            check_currency(&request, amt, currency)?;
+        }
+
+        if let Some(allowed) = &self.allow_list {
+            if !allowed.contains(&request.to_addr) {
+                return Err(OkxError::Config(format!(
+                    "withdrawal address {} is not on the allow-list",
+                    request.to_addr
+                )));
+            }
+        }
+        Ok(request)
+    }
+}
+
+fn check_currency(request: &WithdrawRequest, amt: f64, currency: &Currency) -> OkxResult<()> {
+    if currency.ccy != request.ccy {
+        return Err(OkxError::Config(format!(
+            "currency info is for {}, not {}",
+            currency.ccy, request.ccy
+        )));
+    }
+    if let Some(chain) = &request.chain {
+        if !currency.chain.is_empty() && currency.chain != *chain {
+            return Err(OkxError::Config(format!(
+                "currency info is for chain {}, not {chain}",
+                currency.chain
+            )));
+        }
+    }
+    if !currency.can_wd {
+        return Err(OkxError::Config(format!(
+            "withdrawals of {} are disabled",
+            currency.chain
+        )));
+    }
+    // wdTickSz is the number of decimal places allowed.
+    if let Ok(max_decimals) = currency.wd_tick_sz.parse::<usize>() {
+        let decimals = request
+            .amt
+            .split_once('.')
+            .map_or(0, |(_, frac)| frac.trim_end_matches('0').len());
+        if decimals > max_decimals {
+            return Err(OkxError::Config(format!(
+                "withdrawal amount {} has more than {max_decimals} decimal places",
+                request.amt
+            )));
+        }
+    }
+    if let Ok(min) = currency.min_wd.parse::<f64>() {
+        if amt < min {
+            return Err(OkxError::Config(format!(
+                "withdrawal amount {} is below the minimum {}",
+                request.amt, currency.min_wd
+            )));
+        }
+    }
+    if let Ok(max) = currency.max_wd.parse::<f64>() {
+        if amt > max {
+            return Err(OkxError::Config(format!(
+                "withdrawal amount {} is above the maximum {}",
+                request.amt, currency.max_wd
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Cancel a pending withdrawal.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelWithdrawalRequest {
+    pub wd_id: String,
+}
+
+/// Create a Lightning Network deposit invoice.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningDepositRequest {
+    /// Only "BTC" is supported.
+    pub ccy: String,
+    pub amt: String,
+    /// Account to credit: "6" funding or "18" trading (default: funding).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+/// Withdraw over the Lightning Network.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningWithdrawalRequest {
+    /// Only "BTC" is supported.
+    pub ccy: String,
+    pub invoice: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// Get the detailed status of a deposit or withdrawal. Set `wd_id` for a
+/// withdrawal, or `tx_id`, `ccy`, `to` and `chain` for a deposit.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDepositWithdrawStatusRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wd_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    /// Deposit address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+}
+
 /// Funds transfer between accounts.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -50,6 +297,66 @@ pub struct FundsTransferRequest {
     pub client_id: Option<String>,
 }
 
+impl FundsTransferRequest {
+    /// Check that `subAcct` is set exactly when the transfer type needs it
+    /// and that an amount and currency are given.
+    pub fn validate(&self) -> OkxResult<()> {
+        if self.ccy.is_empty() {
+            return Err(OkxError::Config("transfer currency is empty".into()));
+        }
+        if !self.amt.parse::<f64>().is_ok_and(|amt| amt > 0.0) {
+            return Err(OkxError::Config(format!(
+                "transfer amount must be positive, got {:?}",
+                self.amt
+            )));
+        }
+        let type_ = self.type_.unwrap_or(TransferType::WithinAccount);
+        let has_sub_acct = self.sub_acct.as_deref().is_some_and(|s| !s.is_empty());
+        match (type_.requires_sub_account(), has_sub_acct) {
+            (true, false) => Err(OkxError::Config(format!(
+                "transfer type {type_:?} requires subAcct"
+            ))),
+            (false, true) => Err(OkxError::Config(format!(
+                "transfer type {type_:?} does not take subAcct"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Get funds transfer state. Set either `trans_id` or `client_id`.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransferStateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trans_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<TransferType>,
+}
+
+/// Get funding account bills.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAssetBillsRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    /// Bill type code, e.g. "1" for deposit.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
 /// Get deposit history.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -98,3 +405,77 @@ pub struct GetWithdrawalHistoryRequest {
 pub struct GetDepositAddressRequest {
     pub ccy: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(type_: Option<TransferType>, sub_acct: Option<&str>) -> FundsTransferRequest {
+        FundsTransferRequest {
+            ccy: "USDT".into(),
+            amt: "10".into(),
+            from: "6".into(),
+            to: "6".into(),
+            type_,
+            sub_acct: sub_acct.map(String::from),
+            client_id: None,
+        }
+    }
+
+    #[test]
+    fn validate_checks_sub_account_against_type() {
+        assert!(transfer(None, None).validate().is_ok());
+        assert!(transfer(None, Some("sub1")).validate().is_err());
+        assert!(
+            transfer(Some(TransferType::MasterToSubAccount), Some("sub1"))
+                .validate()
+                .is_ok()
+        );
+        assert!(transfer(Some(TransferType::SubAccountToMaster), None)
+            .validate()
+            .is_err());
+        assert!(
+            transfer(Some(TransferType::SubAccountToMasterManaged), Some("sub1"))
+                .validate()
+                .is_err()
+        );
+
+        let mut zero = transfer(None, None);
+        zero.amt = "0".into();
+        assert!(zero.validate().is_err());
+    }
+
+    fn usdt_trc20() -> Currency {
+        serde_json::from_value(serde_json::json!({
+            "ccy": "USDT", "chain": "USDT-TRC20", "canWd": true,
+            "minWd": "2", "maxWd": "1000000", "wdTickSz": "6",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn withdraw_builder_checks_chain_precision_and_allow_list() {
+        let builder = |amt: &str| {
+            WithdrawRequest::builder("USDT", amt, "TXyz")
+                .chain("USDT-TRC20")
+                .fee("1")
+                .currency(&usdt_trc20())
+        };
+
+        let request = builder("10.5").build().unwrap();
+        assert_eq!(request.chain.as_deref(), Some("USDT-TRC20"));
+        assert_eq!(request.dest, WithdrawDest::OnChain);
+
+        assert!(builder("10.1234567").build().is_err());
+        assert!(builder("10.1234560").build().is_ok());
+        assert!(builder("1").build().is_err());
+        assert!(builder("0").build().is_err());
+
+        assert!(builder("10").chain("TRC20").build().is_err());
+        assert!(builder("10").chain("USDT-").build().is_err());
+        assert!(builder("10").chain("USDT-ERC20").build().is_err());
+
+        assert!(builder("10").allow_list(["TXyz"]).build().is_ok());
+        assert!(builder("10").allow_list(["Tabc"]).build().is_err());
+    }
+}
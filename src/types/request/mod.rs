@@ -1,5 +1,6 @@
 pub mod account;
 pub mod block_trading;
+pub mod broker;
 pub mod convert;
 pub mod copy_trading;
 pub mod finance;
@@ -0,0 +1,249 @@
+use serde::Serialize;
+
+use crate::types::enums::OrderState;
+
+/// Create a signal channel.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSignalRequest {
+    /// Signal channel name.
+    pub signal_chan_name: String,
+    /// Signal channel description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_chan_desc: Option<String>,
+}
+
+/// Get signal channels.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalsRequest {
+    /// Signal source: "1" created by you, "2" subscribed, "3" free signals.
+    pub signal_source_type: String,
+    /// Signal channel ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_chan_id: Option<String>,
+    /// Pagination of data to return records earlier than the requested `signalChanId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `signalChanId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Entry settings of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalEntrySetting {
+    /// Whether to allow multiple entries in the same direction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_multiple_entry: Option<bool>,
+    /// Entry type: "1" from the signal, "2" fixed margin, "3" contracts,
+    /// "4" percentage of available balance, "5" percentage of initial investment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<String>,
+    /// Amount per order, used with entry type "2".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amt: Option<String>,
+    /// Ratios per order, used with entry types "4" and "5".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<Vec<SignalEntryRatio>>,
+}
+
+/// Entry ratio of a signal bot order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalEntryRatio {
+    /// Ratio of the order amount.
+    pub ratio: String,
+}
+
+/// Exit (take-profit / stop-loss) settings of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalExitSetting {
+    /// TP/SL type: "pnl" based on PnL percentage, "price" based on price change.
+    pub tp_sl_type: String,
+    /// Take-profit percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_pct: Option<String>,
+    /// Stop-loss percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_pct: Option<String>,
+}
+
+/// Create a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSignalBotRequest {
+    /// Signal channel ID.
+    pub signal_chan_id: String,
+    /// Leverage.
+    pub lever: String,
+    /// Investment amount.
+    pub invest_amt: String,
+    /// Sub-order type: "1" limit, "2" market, "9" TradingView signal.
+    pub sub_ord_type: String,
+    /// Whether to trade all instruments supported by the signal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
+    /// Instrument IDs to trade. Ignored when `include_all` is `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_ids: Option<Vec<String>>,
+    /// Price offset ratio for limit sub-orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<String>,
+    /// Entry settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_setting_param: Option<SignalEntrySetting>,
+    /// Exit settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_setting_param: Option<SignalExitSetting>,
+}
+
+/// Identifies a signal bot, used to stop bots in batch.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalBotId {
+    /// Algo ID.
+    pub algo_id: String,
+}
+
+/// Add or reduce the margin of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalMarginBalanceRequest {
+    /// Algo ID.
+    pub algo_id: String,
+    /// Operation: "add" or "reduce".
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Amount to add or reduce.
+    pub amt: String,
+    /// Whether to reinvest the added margin. Only applicable to "add".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_reinvest: Option<bool>,
+}
+
+/// Amend the take-profit / stop-loss settings of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalAmendTpSlRequest {
+    /// Algo ID.
+    pub algo_id: String,
+    /// New exit settings.
+    pub exit_setting_param: SignalExitSetting,
+}
+
+/// Get signal bot orders (pending or history).
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalBotOrdersRequest {
+    /// Algo order type. Only "contract" is supported.
+    pub algo_ord_type: String,
+    /// Algo ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algo_id: Option<String>,
+    /// Pagination of data to return records earlier than the requested `algoId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `algoId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Get the open positions of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalBotPositionsRequest {
+    /// Algo order type. Only "contract" is supported.
+    pub algo_ord_type: String,
+    /// Algo ID.
+    pub algo_id: String,
+}
+
+/// Get the closed positions of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalBotPositionsHistoryRequest {
+    /// Algo ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algo_id: Option<String>,
+    /// Instrument ID, e.g. "BTC-USDT-SWAP".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inst_id: Option<String>,
+    /// Pagination of data to return records earlier than the requested `uTime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `uTime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Get the sub-orders of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalBotSubOrdersRequest {
+    /// Algo ID.
+    pub algo_id: String,
+    /// Algo order type. Only "contract" is supported.
+    pub algo_ord_type: String,
+    /// Sub-order state. Either `state` or `signal_ord_id` is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<OrderState>,
+    /// Sub-order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_ord_id: Option<String>,
+    /// Pagination of data to return records earlier than the requested `ordId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `ordId`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Filter with a begin timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<String>,
+    /// Filter with an end timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
+
+/// Cancel a pending sub-order of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelSignalSubOrderRequest {
+    /// Algo ID.
+    pub algo_id: String,
+    /// Instrument ID, e.g. "BTC-USDT-SWAP".
+    pub inst_id: String,
+    /// Sub-order ID.
+    pub signal_ord_id: String,
+}
+
+/// Get the event history of a signal bot.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSignalBotEventHistoryRequest {
+    /// Algo ID.
+    pub algo_id: String,
+    /// Pagination of data to return records earlier than the requested `eventCtime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Pagination of data to return records newer than the requested `eventCtime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+use crate::types::enums::{OrderSide, OrderType};
+
+/// Place a spread order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadOrderRequest {
+    /// Spread ID, e.g. "BTC-USDT_BTC-USDT-SWAP".
+    pub sprd_id: String,
+    /// Client Order ID as assigned by the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    /// Order tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Order side: buy or sell.
+    pub side: OrderSide,
+    /// Order type: limit, post_only, ioc or market.
+    pub ord_type: OrderType,
+    /// Quantity to buy or sell, in the spread's size unit.
+    pub sz: String,
+    /// Order price. Not applicable to market orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub px: Option<String>,
+}
+
+/// Cancel a spread order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadCancelRequest {
+    /// Order ID. Either ordId or clOrdId is required; if both are passed, ordId will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    /// Client Order ID as assigned by the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+}
+
+/// Amend a pending spread order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadAmendRequest {
+    /// Order ID. Either ordId or clOrdId is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    /// Client Order ID as assigned by the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    /// Client Request ID as assigned by the client for order amendment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub req_id: Option<String>,
+    /// New quantity after amendment. Either newSz or newPx is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_sz: Option<String>,
+    /// New price after amendment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_px: Option<String>,
+}
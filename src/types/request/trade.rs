@@ -2,10 +2,12 @@ use serde::Serialize;
 
 use crate::types::enums::*;
 
-fn serialize_csv<S>(values: &[String], serializer: S) -> Result<S::Ok, S::Error>
+fn serialize_csv<T, S>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
 where
+    T: AsRef<str>,
     S: serde::Serializer,
 {
+    let values: Vec<&str> = values.iter().map(AsRef::as_ref).collect();
     serializer.serialize_str(&values.join(","))
 }
 
@@ -43,7 +45,7 @@ pub struct OrderRequest {
     pub reduce_only: Option<bool>,
     /// Target currency for the quantity: base_ccy or quote_ccy.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tgt_ccy: Option<String>,
+    pub tgt_ccy: Option<TargetCurrency>,
     /// Whether to disallow the system from amending the size of the SPOT Market Order.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ban_amend: Option<bool>,
@@ -71,11 +73,92 @@ pub struct OrderRequest {
     /// Stop-loss trigger price type: last, index, mark.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sl_trigger_px_type: Option<String>,
+    /// Take-profit and stop-loss legs placed with the order. Several legs
+    /// with their own `sz` split the take-profit. Use instead of the flat
+    /// TP/SL fields above.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attach_algo_ords: Vec<AttachedAlgoOrder>,
     /// Expiration time, a Unix timestamp in milliseconds. Order will be cancelled after expiry.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exp_time: Option<String>,
 }
 
+impl OrderRequest {
+    /// SPOT market buy that spends exactly `quote_amount` of the quote currency,
+    /// e.g. 100 USDT on "BTC-USDT".
+    pub fn buy_spot_with_quote(inst_id: &str, quote_amount: &str) -> Self {
+        Self {
+            inst_id: inst_id.to_string(),
+            td_mode: TradeMode::Cash,
+            side: OrderSide::Buy,
+            ord_type: OrderType::Market,
+            sz: quote_amount.to_string(),
+            tgt_ccy: Some(TargetCurrency::QuoteCcy),
+            ..Default::default()
+        }
+    }
+}
+
+/// A take-profit and/or stop-loss leg attached to an order, an element of
+/// `attachAlgoOrds`.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachedAlgoOrder {
+    /// Client-supplied algo ID of the leg.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach_algo_cl_ord_id: Option<String>,
+    /// Take-profit trigger price. For `tp_ord_kind` `limit`, the limit price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_px: Option<String>,
+    /// Take-profit order price. -1 for market price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_ord_px: Option<String>,
+    /// Take-profit order kind: condition (default) or limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_ord_kind: Option<String>,
+    /// Take-profit trigger price type (OKX default: last).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_px_type: Option<PriceTriggerType>,
+    /// Stop-loss trigger price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_px: Option<String>,
+    /// Stop-loss order price. -1 for market price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_ord_px: Option<String>,
+    /// Stop-loss trigger price type (OKX default: last).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_px_type: Option<PriceTriggerType>,
+    /// Size of this leg. Required when splitting the take-profit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sz: Option<String>,
+    /// "1" moves the stop-loss to the cost price once the first split
+    /// take-profit fills, "0" leaves it (default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amend_px_on_trigger_type: Option<String>,
+}
+
+impl AttachedAlgoOrder {
+    /// Take-profit triggered at `trigger_px` that places an order at
+    /// `ord_px`, or at market if it is "-1".
+    pub fn take_profit(trigger_px: &str, ord_px: &str) -> Self {
+        Self {
+            tp_trigger_px: Some(trigger_px.to_string()),
+            tp_ord_px: Some(ord_px.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Stop-loss triggered at `trigger_px` that places an order at
+    /// `ord_px`, or at market if it is "-1".
+    pub fn stop_loss(trigger_px: &str, ord_px: &str) -> Self {
+        Self {
+            sl_trigger_px: Some(trigger_px.to_string()),
+            sl_ord_px: Some(ord_px.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
 /// Cancel a single order.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -299,8 +382,8 @@ pub struct AlgoOrderRequest {
     /// Position side: net, long, or short.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pos_side: Option<PositionSide>,
-    /// Algo order type: conditional, oco, trigger, move_order_stop, iceberg, twap, chase.
-    pub ord_type: String,
+    /// Algo order type.
+    pub ord_type: AlgoOrderType,
     /// Quantity to buy or sell.
     pub sz: String,
     /// Order tag.
@@ -308,7 +391,7 @@ pub struct AlgoOrderRequest {
     pub tag: Option<String>,
     /// Target currency for the quantity.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tgt_ccy: Option<String>,
+    pub tgt_ccy: Option<TargetCurrency>,
     /// Whether orders can only reduce position size.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reduce_only: Option<bool>,
@@ -419,8 +502,10 @@ pub struct GetAlgoOrderRequest {
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAlgoOrderListRequest {
-    /// Algo order type: conditional, oco, trigger, move_order_stop, iceberg, twap, chase.
-    pub ord_type: String,
+    /// Algo order types, sent comma-separated. Only `conditional` and `oco`
+    /// can be combined.
+    #[serde(serialize_with = "serialize_csv")]
+    pub ord_type: Vec<AlgoOrderType>,
     /// Algo order ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub algo_id: Option<String>,
@@ -519,10 +604,79 @@ pub struct GetOneClickRepayHistoryRequest {
     pub limit: Option<String>,
 }
 
+/// Get one-click repay currency list request.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOneClickRepayCurrencyListRequest {
+    /// Debt type: cross, isolated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debt_type: Option<String>,
+}
+
+/// One-click repay v2 request.
+///
+/// Repay a debt currency using one or more currencies, in the given order.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OneClickRepayV2Request {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Currencies to repay with, in order of use.
+    pub repay_ccy_list: Vec<String>,
+}
+
+/// Apply for or query the fills archive of a given quarter.
+///
+/// Used for both `POST` and `GET /api/v5/trade/fills-archive`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillsArchiveRequest {
+    /// Four-digit year, e.g. "2024".
+    pub year: String,
+    /// Quarter of the year.
+    pub quarter: Quarter,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn buy_spot_with_quote_sets_market_quote_ccy() {
+        let req = OrderRequest::buy_spot_with_quote("BTC-USDT", "100");
+
+        let value = serde_json::to_value(req).unwrap();
+        assert_eq!(value["ordType"], "market");
+        assert_eq!(value["side"], "buy");
+        assert_eq!(value["tdMode"], "cash");
+        assert_eq!(value["tgtCcy"], "quote_ccy");
+        assert_eq!(value["sz"], "100");
+    }
+
+    #[test]
+    fn attached_algo_orders_serialize_as_list_only_when_set() {
+        let mut req = OrderRequest::buy_spot_with_quote("BTC-USDT", "100");
+        let value = serde_json::to_value(&req).unwrap();
+        assert!(value.get("attachAlgoOrds").is_none());
+
+        req.attach_algo_ords = vec![
+            AttachedAlgoOrder {
+                sz: Some("0.5".into()),
+                tp_trigger_px_type: Some(PriceTriggerType::Mark),
+                ..AttachedAlgoOrder::take_profit("110", "-1")
+            },
+            AttachedAlgoOrder::stop_loss("90", "-1"),
+        ];
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            value["attachAlgoOrds"],
+            serde_json::json!([
+                {"tpTriggerPx": "110", "tpOrdPx": "-1", "tpTriggerPxType": "mark", "sz": "0.5"},
+                {"slTriggerPx": "90", "slOrdPx": "-1"},
+            ])
+        );
+    }
+
     #[test]
     fn easy_convert_serializes_currency_list_as_csv() {
         let req = EasyConvertRequest {
@@ -544,4 +698,15 @@ mod tests {
         let value = serde_json::to_value(req).unwrap();
         assert_eq!(value["debtCcy"], "BTC,ETH");
     }
+
+    #[test]
+    fn algo_order_list_serializes_order_types_as_csv() {
+        let req = GetAlgoOrderListRequest {
+            ord_type: vec![AlgoOrderType::Conditional, AlgoOrderType::Oco],
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(req).unwrap();
+        assert_eq!(value["ordType"], "conditional,oco");
+    }
 }
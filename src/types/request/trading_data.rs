@@ -0,0 +1,76 @@
+use serde::Serialize;
+
+use crate::types::enums::TradingDataPeriod;
+
+/// Get taker buy/sell volume for a currency.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTakerVolumeRequest {
+    /// Currency, e.g. "BTC".
+    pub ccy: String,
+    /// Instrument type: "SPOT" or "CONTRACTS".
+    pub inst_type: String,
+    /// Begin time, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<String>,
+    /// End time, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Period: 5m, 1H or 1D. Default 5m.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<TradingDataPeriod>,
+}
+
+/// Get a currency-level statistic (margin lending ratio, long/short ratio,
+/// contracts open interest and volume).
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCurrencyTradingDataRequest {
+    /// Currency, e.g. "BTC".
+    pub ccy: String,
+    /// Begin time, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<String>,
+    /// End time, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Period: 5m, 1H or 1D. Default 5m.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<TradingDataPeriod>,
+}
+
+/// Get an options statistic (put/call ratio, open interest and volume).
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOptionTradingDataRequest {
+    /// Currency, e.g. "BTC".
+    pub ccy: String,
+    /// Period: 8H or 1D. Default 8H.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<TradingDataPeriod>,
+}
+
+/// Get a contract-level statistic (taker volume, long/short ratios, open
+/// interest history).
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetContractTradingDataRequest {
+    /// Instrument ID, e.g. "BTC-USDT-SWAP".
+    pub inst_id: String,
+    /// Period, from 5m to 3M. Default 5m.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<TradingDataPeriod>,
+    /// Volume unit: "0" coin, "1" contracts, "2" USD. Only applicable to
+    /// contract taker volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Return records newer than this timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<String>,
+    /// Return records earlier than this timestamp, Unix timestamp in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    /// Number of results per request. Maximum 100; default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+}
@@ -1,5 +1,9 @@
 use serde::Deserialize;
 
+use crate::types::enums::{
+    AutoEarnAction, BillSubType, BillType, BorrowRepaySide, EarnType, FeeType,
+};
+
 /// Full account balance information.
 ///
 /// Contains overall account equity, margin, and per-currency balance details.
@@ -224,6 +228,9 @@ pub struct Position {
     /// Realized profit and loss.
     #[serde(default)]
     pub real_pnl: String,
+    /// Option delta in coins. Only applicable to OPTION.
+    #[serde(default, rename = "deltaPA")]
+    pub delta_pa: String,
 }
 
 /// Account configuration.
@@ -392,6 +399,22 @@ pub struct MmpConfig {
     /// Quantity limit.
     #[serde(default)]
     pub qty_limit: String,
+    /// Whether MMP is currently triggered and the instrument family is frozen.
+    #[serde(default)]
+    pub mmp_frozen: bool,
+    /// Time until which MMP stays frozen, Unix timestamp in milliseconds.
+    /// Empty when not frozen.
+    #[serde(default)]
+    pub mmp_frozen_until: String,
+}
+
+/// MMP reset result.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct MmpResetResult {
+    /// Whether the reset succeeded.
+    #[serde(default)]
+    pub result: bool,
 }
 
 /// Account risk state.
@@ -427,3 +450,432 @@ pub struct SetResult {
     #[serde(flatten)]
     pub data: serde_json::Value,
 }
+
+/// Account bill (balance change record).
+///
+/// Returned by the bills endpoints and the bills history archive.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Bill {
+    /// Bill ID.
+    #[serde(default)]
+    pub bill_id: String,
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Account balance currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Margin mode: isolated, cross, cash.
+    #[serde(default)]
+    pub mgn_mode: String,
+    /// Bill type.
+    #[serde(rename = "type")]
+    pub type_: BillType,
+    /// Bill sub-type.
+    pub sub_type: BillSubType,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+    /// Balance at the account level.
+    #[serde(default)]
+    pub bal: String,
+    /// Change in balance amount at the account level.
+    #[serde(default)]
+    pub bal_chg: String,
+    /// Balance at the position level.
+    #[serde(default)]
+    pub pos_bal: String,
+    /// Change in balance amount at the position level.
+    #[serde(default)]
+    pub pos_bal_chg: String,
+    /// Quantity.
+    #[serde(default)]
+    pub sz: String,
+    /// Price related to the bill type.
+    #[serde(default)]
+    pub px: String,
+    /// Profit and loss.
+    #[serde(default)]
+    pub pnl: String,
+    /// Fee. Negative means fee charged; positive means rebate.
+    #[serde(default)]
+    pub fee: String,
+    /// Interest.
+    #[serde(default)]
+    pub interest: String,
+    /// Liquidity taker or maker: T or M.
+    #[serde(default)]
+    pub exec_type: String,
+    /// Order ID.
+    #[serde(default)]
+    pub ord_id: String,
+    /// Client Order ID as assigned by the client.
+    #[serde(default)]
+    pub cl_ord_id: String,
+    /// Last traded ID.
+    #[serde(default)]
+    pub trade_id: String,
+    /// Order tag.
+    #[serde(default)]
+    pub tag: String,
+    /// Last filled time.
+    #[serde(default)]
+    pub fill_time: String,
+    /// Last filled price.
+    #[serde(default)]
+    pub fill_px: String,
+    /// Index price at the moment of trade execution.
+    #[serde(default)]
+    pub fill_idx_px: String,
+    /// Mark price when filled.
+    #[serde(default)]
+    pub fill_mark_px: String,
+    /// Remitting account, 6: Funding account, 18: Trading account.
+    #[serde(default, rename = "from")]
+    pub from_: String,
+    /// Beneficiary account, 6: Funding account, 18: Trading account.
+    #[serde(default)]
+    pub to: String,
+    /// Notes.
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Result of a manual borrow or repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BorrowRepayResult {
+    /// Loan currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Direction: borrow or repay.
+    pub side: BorrowRepaySide,
+    /// Amount borrowed or repaid.
+    #[serde(default)]
+    pub amt: String,
+    /// Borrow order ID.
+    #[serde(default)]
+    pub ord_id: String,
+    /// Order state.
+    #[serde(default)]
+    pub state: String,
+}
+
+/// Borrow or repay history record.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BorrowRepayRecord {
+    /// Loan currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Record type, e.g. "borrow", "repay", "auto_borrow", "auto_repay".
+    #[serde(default, rename = "type")]
+    pub type_: String,
+    /// Amount of the traded loan.
+    #[serde(default)]
+    pub traded_loan: String,
+    /// Amount of the loan currently in use.
+    #[serde(default)]
+    pub used_loan: String,
+    /// Record time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Result of a quick margin borrow or repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct QuickMarginBorrowRepayResult {
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Loan currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Direction: borrow or repay.
+    pub side: BorrowRepaySide,
+    /// Amount borrowed or repaid.
+    #[serde(default)]
+    pub amt: String,
+}
+
+/// Quick margin borrow or repay history record.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct QuickMarginBorrowRepayRecord {
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Loan currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Direction: borrow or repay.
+    pub side: BorrowRepaySide,
+    /// Amount borrowed or repaid.
+    #[serde(default)]
+    pub amt: String,
+    /// Accumulated borrowed amount.
+    #[serde(default)]
+    pub acc_borrowed: String,
+    /// Record ID, used for pagination.
+    #[serde(default)]
+    pub ref_id: String,
+    /// Record time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Borrow interest and limit summary.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct InterestLimits {
+    /// Current debt in USD.
+    #[serde(default)]
+    pub debt: String,
+    /// Current interest in USD.
+    #[serde(default)]
+    pub interest: String,
+    /// Next deduct time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub next_discount_time: String,
+    /// Next accrual time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub next_interest_time: String,
+    /// VIP loan allocation ratio of the current trading account.
+    #[serde(default)]
+    pub loan_alloc: String,
+    /// Per-currency details.
+    #[serde(default)]
+    pub records: Vec<InterestLimitRecord>,
+}
+
+/// Per-currency borrow interest and limit details.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct InterestLimitRecord {
+    /// Loan currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Current daily interest rate.
+    #[serde(default)]
+    pub rate: String,
+    /// Borrow limit of the master account.
+    #[serde(default)]
+    pub loan_quota: String,
+    /// Available amount across all sub-accounts.
+    #[serde(default)]
+    pub surplus_lmt: String,
+    /// Borrowed amount of the current account.
+    #[serde(default)]
+    pub used_lmt: String,
+    /// Interest to be deducted.
+    #[serde(default)]
+    pub interest: String,
+    /// Frozen amount for open orders.
+    #[serde(default)]
+    pub pos_loan: String,
+    /// Available amount to borrow.
+    #[serde(default)]
+    pub avail_loan: String,
+    /// Borrowed amount.
+    #[serde(default)]
+    pub used_loan: String,
+    /// Average hourly interest rate of the borrowed amount.
+    #[serde(default)]
+    pub avg_rate: String,
+}
+
+/// Greeks of the account's assets for one currency.
+///
+/// `BS` values are in USD (Black-Scholes); `PA` values are coin-denominated.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct AccountGreeks {
+    /// Currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Delta in USD.
+    #[serde(default, rename = "deltaBS")]
+    pub delta_bs: String,
+    /// Delta in coins.
+    #[serde(default, rename = "deltaPA")]
+    pub delta_pa: String,
+    /// Gamma in USD.
+    #[serde(default, rename = "gammaBS")]
+    pub gamma_bs: String,
+    /// Gamma in coins.
+    #[serde(default, rename = "gammaPA")]
+    pub gamma_pa: String,
+    /// Theta in USD.
+    #[serde(default, rename = "thetaBS")]
+    pub theta_bs: String,
+    /// Theta in coins.
+    #[serde(default, rename = "thetaPA")]
+    pub theta_pa: String,
+    /// Vega in USD.
+    #[serde(default, rename = "vegaBS")]
+    pub vega_bs: String,
+    /// Vega in coins.
+    #[serde(default, rename = "vegaPA")]
+    pub vega_pa: String,
+    /// Update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Position tier applied to the account in portfolio margin mode.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AccountPositionTier {
+    /// Underlying.
+    #[serde(default)]
+    pub uly: String,
+    /// Instrument family.
+    #[serde(default)]
+    pub inst_family: String,
+    /// Maximum number of positions.
+    #[serde(default)]
+    pub max_sz: String,
+    /// Limitation of position type. Only applicable to cross `OPTION`.
+    #[serde(default)]
+    pub pos_type: String,
+}
+
+/// Projected margin requirement from the portfolio margin simulator.
+///
+/// `mr1` to `mr8` are the stress-test components that make up the MMR.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SimulatedMargin {
+    /// Initial margin requirement in USD.
+    #[serde(default)]
+    pub imr: String,
+    /// Maintenance margin requirement in USD.
+    #[serde(default)]
+    pub mmr: String,
+    /// Spot and volatility shock.
+    #[serde(default)]
+    pub mr1: String,
+    /// Time value decay.
+    #[serde(default)]
+    pub mr2: String,
+    /// Volatility tightening.
+    #[serde(default)]
+    pub mr3: String,
+    /// Basis risk.
+    #[serde(default)]
+    pub mr4: String,
+    /// Interest rate risk.
+    #[serde(default)]
+    pub mr5: String,
+    /// Extreme market move.
+    #[serde(default)]
+    pub mr6: String,
+    /// Position reduction cost.
+    #[serde(default)]
+    pub mr7: String,
+    /// Borrowing margin.
+    #[serde(default)]
+    pub mr8: String,
+    /// Risk unit, e.g. "BTC-USD".
+    #[serde(default)]
+    pub risk_unit: String,
+    /// Positions included in the simulation.
+    #[serde(default)]
+    pub pos_data: Vec<SimulatedPositionData>,
+    /// Calculation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Position included in a portfolio margin simulation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SimulatedPositionData {
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Margin mode.
+    #[serde(default)]
+    pub mgn_mode: String,
+    /// Position quantity.
+    #[serde(default)]
+    pub pos: String,
+    /// Notional value in USD.
+    #[serde(default)]
+    pub notional_usd: String,
+}
+
+/// Result of setting the fee type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FeeTypeResult {
+    #[serde(default)]
+    pub fee_type: FeeType,
+}
+
+/// Result of turning auto earn on or off.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AutoEarnResult {
+    #[serde(default)]
+    pub earn_type: EarnType,
+    /// Currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Action applied, if OKX echoed it.
+    #[serde(default)]
+    pub action: Option<AutoEarnAction>,
+    /// Minimum annual rate accepted for lending, if set.
+    #[serde(default)]
+    pub apr: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_greeks_maps_bs_and_pa_fields() {
+        let greeks: AccountGreeks = serde_json::from_str(
+            r#"{"ccy":"BTC","deltaBS":"1.2","deltaPA":"0.5","gammaBS":"0.01","gammaPA":"0.002",
+                "thetaBS":"-3","thetaPA":"-0.0001","vegaBS":"7","vegaPA":"0.0002","ts":"1620282889345"}"#,
+        )
+        .unwrap();
+        assert_eq!(greeks.ccy, "BTC");
+        assert_eq!(greeks.delta_bs, "1.2");
+        assert_eq!(greeks.gamma_pa, "0.002");
+        assert_eq!(greeks.theta_bs, "-3");
+        assert_eq!(greeks.vega_pa, "0.0002");
+    }
+
+    #[test]
+    fn auto_earn_result_tolerates_missing_and_unknown_fields() {
+        let result: AutoEarnResult =
+            serde_json::from_str(r#"{"earnType":"2","ccy":"USDT"}"#).unwrap();
+        assert_eq!(result.earn_type, EarnType::Other("2".into()));
+        assert_eq!(result.action, None);
+
+        let fee: FeeTypeResult = serde_json::from_str("{}").unwrap();
+        assert_eq!(fee.fee_type, FeeType::ReceivedCurrency);
+    }
+}
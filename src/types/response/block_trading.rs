@@ -0,0 +1,95 @@
+use serde::Deserialize;
+
+/// Result of a move positions request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MovePositionsResult {
+    /// Client-supplied ID.
+    #[serde(default)]
+    pub client_id: String,
+    /// Block trade ID.
+    #[serde(default)]
+    pub block_td_id: String,
+    /// Move state: "filled", "pending" or "failed".
+    #[serde(default)]
+    pub state: String,
+    /// Source account name.
+    #[serde(default)]
+    pub from_acct: String,
+    /// Destination account name.
+    #[serde(default)]
+    pub to_acct: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Move positions history record.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MovePositionsRecord {
+    /// Client-supplied ID.
+    #[serde(default)]
+    pub client_id: String,
+    /// Block trade ID.
+    #[serde(default)]
+    pub block_td_id: String,
+    /// Move state: "filled", "pending" or "failed".
+    #[serde(default)]
+    pub state: String,
+    /// Source account name.
+    #[serde(default)]
+    pub from_acct: String,
+    /// Destination account name.
+    #[serde(default)]
+    pub to_acct: String,
+    /// Moved positions.
+    #[serde(default)]
+    pub legs: Vec<MovedPositionLeg>,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// A single moved position.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct MovedPositionLeg {
+    /// Execution in the source account.
+    pub from: MovedPositionFill,
+    /// Execution in the destination account.
+    pub to: MovedPositionFill,
+}
+
+/// One side of a moved position.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MovedPositionFill {
+    /// Position ID. Only present on the source side.
+    #[serde(default)]
+    pub pos_id: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Execution price.
+    #[serde(default)]
+    pub px: String,
+    /// Side of the trade.
+    #[serde(default)]
+    pub side: String,
+    /// Quantity moved.
+    #[serde(default)]
+    pub sz: String,
+    /// Trade mode. Only present on the destination side.
+    #[serde(default)]
+    pub td_mode: String,
+    /// Position side. Only present on the destination side.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Margin currency. Only present on the destination side.
+    #[serde(default)]
+    pub ccy: String,
+}
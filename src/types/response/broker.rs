@@ -0,0 +1,175 @@
+use serde::Deserialize;
+
+/// Non-disclosed broker account information.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerInfo {
+    /// Broker rebate level.
+    #[serde(default)]
+    pub level: String,
+    /// Maximum number of sub-accounts.
+    #[serde(default)]
+    pub max_sub_acct_qty: String,
+    /// Number of sub-accounts created.
+    #[serde(default)]
+    pub sub_acct_qty: String,
+    /// Query time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Broker sub-account.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerSubAccount {
+    /// Sub-account name.
+    #[serde(default)]
+    pub sub_acct: String,
+    /// Sub-account UID.
+    #[serde(default)]
+    pub uid: String,
+    /// Sub-account notes.
+    #[serde(default)]
+    pub label: String,
+    /// Account level.
+    #[serde(default)]
+    pub acct_lv: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Page of broker sub-accounts.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerSubAccountPage {
+    /// Total number of pages.
+    #[serde(default)]
+    pub total_page: String,
+    /// Current page number.
+    #[serde(default)]
+    pub page: String,
+    /// Sub-accounts on this page.
+    #[serde(default)]
+    pub details: Vec<BrokerSubAccount>,
+}
+
+/// Result of deleting a broker sub-account or one of its API keys.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerDeleteResult {
+    /// Sub-account name.
+    #[serde(default)]
+    pub sub_acct: String,
+}
+
+/// API key of a broker sub-account.
+///
+/// `secret_key` and `passphrase` are only returned when the key is created.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerApiKey {
+    /// Sub-account name.
+    #[serde(default)]
+    pub sub_acct: String,
+    /// API key note.
+    #[serde(default)]
+    pub label: String,
+    /// API key.
+    #[serde(default)]
+    pub api_key: String,
+    /// API secret key.
+    #[serde(default)]
+    pub secret_key: String,
+    /// API key passphrase.
+    #[serde(default)]
+    pub passphrase: String,
+    /// Comma-separated permissions.
+    #[serde(default)]
+    pub perm: String,
+    /// Comma-separated list of bound IP addresses.
+    #[serde(default)]
+    pub ip: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Result of setting a sub-account fee rate.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerSetFeeRateResult {
+    /// Sub-account name.
+    #[serde(default)]
+    pub sub_acct: String,
+}
+
+/// Deposit address of a broker sub-account.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerDepositAddress {
+    /// Deposit address.
+    #[serde(default)]
+    pub addr: String,
+    /// Deposit tag, for currencies that require one.
+    #[serde(default)]
+    pub tag: String,
+    /// Deposit memo, for currencies that require one.
+    #[serde(default)]
+    pub memo: String,
+    /// Deposit payment ID, for currencies that require one.
+    #[serde(default)]
+    pub pmt_id: String,
+    /// Currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Chain.
+    #[serde(default)]
+    pub chain: String,
+    /// Account deposits are credited to: "6" funding, "18" trading.
+    #[serde(default)]
+    pub to: String,
+    /// Whether this is the default address of the chain.
+    #[serde(default)]
+    pub selected: bool,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Result of requesting the rebate details file per order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerRebateApplyResult {
+    /// Whether the file already exists: "true" if it can be fetched now,
+    /// "false" if it is being generated.
+    #[serde(default, rename = "type")]
+    pub type_: String,
+    /// Request time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Download link of the rebate details file per order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BrokerRebateFile {
+    /// Download link. Empty until the file is ready.
+    #[serde(default)]
+    pub file_href: String,
+    /// Link state: "1" generating, "2" ready, "3" failed.
+    #[serde(default, rename = "type")]
+    pub type_: String,
+    /// Request time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
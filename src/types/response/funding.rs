@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::types::enums::TransferStatus;
+
 /// Currency information.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +50,103 @@ pub struct AssetBalance {
     pub avail_bal: String,
 }
 
+/// Total asset valuation across account types.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AssetValuation {
+    /// Total valuation in the requested currency.
+    #[serde(default)]
+    pub total_bal: String,
+    /// Valuation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+    #[serde(default)]
+    pub details: AssetValuationDetails,
+}
+
+/// Valuation per account type.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AssetValuationDetails {
+    #[serde(default)]
+    pub funding: String,
+    #[serde(default)]
+    pub trading: String,
+    #[serde(default)]
+    pub classic: String,
+    #[serde(default)]
+    pub earn: String,
+}
+
+/// Asset held in the funding account that cannot be traded, e.g. a delisted
+/// token.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct NonTradableAsset {
+    #[serde(default)]
+    pub ccy: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub logo_link: String,
+    #[serde(default)]
+    pub bal: String,
+    #[serde(default)]
+    pub can_wd: bool,
+    #[serde(default)]
+    pub chain: String,
+    #[serde(default)]
+    pub min_wd: String,
+    /// Whether the full balance must be withdrawn at once.
+    #[serde(default)]
+    pub wd_all: bool,
+    #[serde(default)]
+    pub fee: String,
+    #[serde(default)]
+    pub fee_ccy: String,
+    #[serde(default)]
+    pub burning_fee_rate: String,
+    #[serde(default)]
+    pub ct_addr: String,
+    #[serde(default)]
+    pub wd_tick_sz: String,
+    #[serde(default)]
+    pub need_tag: bool,
+}
+
+/// Result of a dust conversion.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DustConversion {
+    /// Total OKB received.
+    #[serde(default)]
+    pub total_cnv_amt: String,
+    #[serde(default)]
+    pub details: Vec<DustConversionDetail>,
+}
+
+/// One converted currency.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DustConversionDetail {
+    #[serde(default)]
+    pub ccy: String,
+    /// Amount converted.
+    #[serde(default)]
+    pub amt: String,
+    /// OKB received.
+    #[serde(default)]
+    pub cnv_amt: String,
+    /// Fee in OKB.
+    #[serde(default)]
+    pub fee: String,
+}
+
 /// Withdrawal result.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -65,6 +164,57 @@ pub struct WithdrawalResult {
     pub client_id: String,
 }
 
+/// Cancelled withdrawal.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CancelWithdrawalResult {
+    #[serde(default)]
+    pub wd_id: String,
+}
+
+/// Lightning Network deposit invoice.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LightningDeposit {
+    #[serde(default)]
+    pub invoice: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+}
+
+/// Lightning Network withdrawal result.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LightningWithdrawalResult {
+    #[serde(default)]
+    pub wd_id: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+}
+
+/// Detailed status of a deposit or withdrawal.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DepositWithdrawStatus {
+    #[serde(default)]
+    pub wd_id: String,
+    #[serde(default)]
+    pub tx_id: String,
+    /// Human-readable step, e.g. "Pending withdrawal: Wallet is under
+    /// maintenance".
+    #[serde(default)]
+    pub state: String,
+    /// Estimated completion time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub est_complete_time: String,
+}
+
 /// Transfer result.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -84,6 +234,58 @@ pub struct TransferResult {
     pub client_id: String,
 }
 
+/// Funds transfer state.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FundsTransferState {
+    #[serde(default)]
+    pub trans_id: String,
+    #[serde(default)]
+    pub client_id: String,
+    #[serde(default)]
+    pub ccy: String,
+    #[serde(default)]
+    pub amt: String,
+    /// Transfer type, e.g. "1" for master to sub-account.
+    #[serde(default, rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+    #[serde(default)]
+    pub sub_acct: String,
+    pub state: TransferStatus,
+}
+
+/// Funding account bill.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AssetBill {
+    #[serde(default)]
+    pub bill_id: String,
+    #[serde(default)]
+    pub ccy: String,
+    #[serde(default)]
+    pub client_id: String,
+    /// Change in balance.
+    #[serde(default)]
+    pub bal_chg: String,
+    /// Balance after the change.
+    #[serde(default)]
+    pub bal: String,
+    /// Bill type code, e.g. "1" for deposit.
+    #[serde(default, rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub notes: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
 /// Deposit record.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
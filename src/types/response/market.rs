@@ -52,6 +52,78 @@ pub struct OrderBook {
 
 pub type Candle = Vec<String>;
 
+/// Typed candlestick.
+///
+/// OKX returns candles as arrays of strings. Trade candles have nine fields
+/// `[ts, o, h, l, c, vol, volCcy, volCcyQuote, confirm]`; index and mark
+/// price candles have six, `[ts, o, h, l, c, confirm]`, and leave the
+/// volume fields empty.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "Vec<String>")]
+#[non_exhaustive]
+pub struct Ohlcv {
+    /// Bar open time, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Open price.
+    pub o: String,
+    /// Highest price.
+    pub h: String,
+    /// Lowest price.
+    pub l: String,
+    /// Close price.
+    pub c: String,
+    /// Volume in contracts (derivatives) or base currency (spot).
+    pub vol: String,
+    /// Volume in base currency (derivatives) or quote currency (spot).
+    pub vol_ccy: String,
+    /// Volume in quote currency.
+    pub vol_ccy_quote: String,
+    /// "0" while the bar is still forming, "1" once it is complete.
+    pub confirm: String,
+}
+
+impl TryFrom<Vec<String>> for Ohlcv {
+    type Error = String;
+
+    fn try_from(row: Vec<String>) -> Result<Self, Self::Error> {
+        let len = row.len();
+        let mut fields = row.into_iter();
+        let mut next = || fields.next().unwrap_or_default();
+        match len {
+            9 => Ok(Self {
+                ts: next(),
+                o: next(),
+                h: next(),
+                l: next(),
+                c: next(),
+                vol: next(),
+                vol_ccy: next(),
+                vol_ccy_quote: next(),
+                confirm: next(),
+            }),
+            6 => Ok(Self {
+                ts: next(),
+                o: next(),
+                h: next(),
+                l: next(),
+                c: next(),
+                vol: String::new(),
+                vol_ccy: String::new(),
+                vol_ccy_quote: String::new(),
+                confirm: next(),
+            }),
+            n => Err(format!("candle row must have 6 or 9 fields, got {n}")),
+        }
+    }
+}
+
+impl Ohlcv {
+    /// Whether the bar is complete.
+    pub fn is_confirmed(&self) -> bool {
+        self.confirm == "1"
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -101,3 +173,31 @@ pub struct IndexTicker {
     #[serde(default)]
     pub ts: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ohlcv_deserializes_from_array_row() {
+        let rows: Vec<Ohlcv> = serde_json::from_str(
+            r#"[["1700000000000","1","3","0.5","2","10","20","40","1"]]"#,
+        )
+        .unwrap();
+        assert_eq!(rows[0].ts, "1700000000000");
+        assert_eq!(rows[0].c, "2");
+        assert_eq!(rows[0].vol_ccy_quote, "40");
+        assert!(rows[0].is_confirmed());
+    }
+
+    #[test]
+    fn ohlcv_deserializes_index_candle_row() {
+        let rows: Vec<Ohlcv> =
+            serde_json::from_str(r#"[["1700000000000","1","3","0.5","2","0"]]"#).unwrap();
+        assert_eq!(rows[0].c, "2");
+        assert!(rows[0].vol.is_empty());
+        assert!(!rows[0].is_confirmed());
+
+        assert!(serde_json::from_str::<Ohlcv>(r#"["1","2","3"]"#).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod account;
 pub mod block_trading;
+pub mod broker;
 pub mod convert;
 pub mod copy_trading;
 pub mod finance;
@@ -10,5 +11,6 @@ pub mod public;
 pub mod signal_bot;
 pub mod spread_trading;
 pub mod subaccount;
+pub mod system;
 pub mod trade;
 pub mod trading_data;
@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::types::enums::OrderSide;
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -62,6 +64,67 @@ pub struct Instrument {
     pub max_stop_sz: String,
 }
 
+impl Instrument {
+    /// Value of one contract, `ctVal * ctMult`, in [`ct_val_ccy`](Self::ct_val_ccy).
+    ///
+    /// `None` for instruments that are not traded in contracts, e.g. SPOT.
+    pub fn contract_size(&self) -> Option<f64> {
+        let ct_val: f64 = self.ct_val.parse().ok()?;
+        let ct_mult = self.ct_mult.parse().ok().filter(|m: &f64| *m != 0.0);
+        Some(ct_val * ct_mult.unwrap_or(1.0))
+    }
+
+    /// Whether contracts are valued in the coin itself, e.g. 0.01 BTC for
+    /// `BTC-USDT-SWAP`, rather than in the quote currency, e.g. 100 USD for
+    /// `BTC-USD-SWAP`.
+    fn is_coin_valued(&self) -> bool {
+        let uly = if self.uly.is_empty() {
+            &self.inst_family
+        } else {
+            &self.uly
+        };
+        uly.split('-').next() == Some(self.ct_val_ccy.as_str())
+    }
+
+    /// Convert a number of contracts to coins at price `px`.
+    ///
+    /// `px` is only used by contracts valued in the quote currency.
+    pub fn contracts_to_coin(&self, contracts: f64, px: f64) -> Option<f64> {
+        let value = contracts * self.contract_size()?;
+        if self.is_coin_valued() {
+            Some(value)
+        } else if px > 0.0 {
+            Some(value / px)
+        } else {
+            None
+        }
+    }
+
+    /// Convert coins to a (fractional) number of contracts at price `px`.
+    ///
+    /// Round to [`lot_sz`](Self::lot_sz) before placing an order. `None` if
+    /// contracts are valued in the quote currency and `px` is not positive.
+    pub fn coin_to_contracts(&self, coin: f64, px: f64) -> Option<f64> {
+        let size = self.contract_size().filter(|s| *s != 0.0)?;
+        if self.is_coin_valued() {
+            Some(coin / size)
+        } else {
+            (px > 0.0).then(|| coin * px / size)
+        }
+    }
+
+    /// Notional value in USD of a number of contracts at price `px`, taking
+    /// the quote currency as USD.
+    pub fn notional_usd(&self, contracts: f64, px: f64) -> Option<f64> {
+        let value = contracts * self.contract_size()?;
+        if self.is_coin_valued() {
+            Some(value * px)
+        } else {
+            Some(value)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -90,6 +153,8 @@ pub struct FundingRate {
     pub premium: String,
     #[serde(default)]
     pub settle_state: String,
+    #[serde(default)]
+    pub ts: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -106,6 +171,70 @@ pub struct MarkPrice {
     pub ts: String,
 }
 
+/// Highest buy and lowest sell price currently accepted for an instrument.
+///
+/// Push data of the `price-limit` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PriceLimit {
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    /// Highest buy limit price.
+    #[serde(default)]
+    pub buy_lmt: String,
+    /// Lowest sell limit price.
+    #[serde(default)]
+    pub sell_lmt: String,
+    #[serde(default)]
+    pub ts: String,
+    /// Whether the limit is in effect. When false the prices are empty.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl PriceLimit {
+    /// Whether an order at `px` falls inside the band.
+    ///
+    /// Always true when the limit is disabled or its prices are not known.
+    pub fn allows(&self, side: OrderSide, px: f64) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        match side {
+            OrderSide::Buy => self.buy_lmt.parse().map_or(true, |lmt: f64| px <= lmt),
+            OrderSide::Sell => self.sell_lmt.parse().map_or(true, |lmt: f64| px >= lmt),
+        }
+    }
+}
+
+/// Estimated delivery, exercise or settlement price.
+///
+/// Push data of the `estimated-price` channel, which is only pushed in the
+/// hour before delivery, exercise or settlement.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EstimatedPrice {
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    /// "settlement", "delivery" or "exercise".
+    #[serde(default)]
+    pub settle_type: String,
+    #[serde(default)]
+    pub settle_px: String,
+    #[serde(default)]
+    pub ts: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -245,3 +374,52 @@ pub struct DiscountInfo {
     #[serde(default)]
     pub min_amt: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument(inst_id: &str, uly: &str, ct_val: &str, ct_val_ccy: &str) -> Instrument {
+        serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": inst_id, "uly": uly,
+            "ctVal": ct_val, "ctMult": "1", "ctValCcy": ct_val_ccy,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_linear_and_inverse_contracts() {
+        let linear = instrument("BTC-USDT-SWAP", "BTC-USDT", "0.01", "BTC");
+        assert_eq!(linear.contracts_to_coin(250.0, 0.0), Some(2.5));
+        assert_eq!(linear.coin_to_contracts(2.5, 40_000.0), Some(250.0));
+        assert_eq!(linear.notional_usd(250.0, 40_000.0), Some(100_000.0));
+
+        let inverse = instrument("BTC-USD-SWAP", "BTC-USD", "100", "USD");
+        assert_eq!(inverse.contracts_to_coin(10.0, 40_000.0), Some(0.025));
+        assert_eq!(inverse.contracts_to_coin(10.0, 0.0), None);
+        assert_eq!(inverse.coin_to_contracts(0.025, 40_000.0), Some(10.0));
+        assert_eq!(inverse.coin_to_contracts(0.025, 0.0), None);
+        assert_eq!(inverse.notional_usd(10.0, 40_000.0), Some(1_000.0));
+
+        let spot = instrument("BTC-USDT", "", "", "");
+        assert_eq!(spot.contract_size(), None);
+    }
+
+    #[test]
+    fn price_limit_checks_band() {
+        let limit: PriceLimit = serde_json::from_str(
+            r#"{"instId":"BTC-USDT-SWAP","buyLmt":"105","sellLmt":"95","ts":"1","enabled":true}"#,
+        )
+        .unwrap();
+        assert!(limit.allows(OrderSide::Buy, 105.0));
+        assert!(!limit.allows(OrderSide::Buy, 105.5));
+        assert!(limit.allows(OrderSide::Sell, 95.0));
+        assert!(!limit.allows(OrderSide::Sell, 94.0));
+
+        let off: PriceLimit = serde_json::from_str(
+            r#"{"instId":"BTC-USDT-SWAP","buyLmt":"","sellLmt":"","enabled":false}"#,
+        )
+        .unwrap();
+        assert!(off.allows(OrderSide::Buy, 1e9));
+    }
+}
@@ -0,0 +1,366 @@
+use serde::Deserialize;
+
+/// Newly created signal channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CreatedSignal {
+    /// Signal channel ID.
+    #[serde(default)]
+    pub signal_chan_id: String,
+    /// Token used to send signals to the channel.
+    #[serde(default)]
+    pub signal_chan_token: String,
+}
+
+/// Signal channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Signal {
+    /// Signal channel ID.
+    #[serde(default)]
+    pub signal_chan_id: String,
+    /// Signal channel name.
+    #[serde(default)]
+    pub signal_chan_name: String,
+    /// Signal channel description.
+    #[serde(default)]
+    pub signal_chan_desc: String,
+    /// Token used to send signals to the channel.
+    #[serde(default)]
+    pub signal_chan_token: String,
+    /// Signal source: "1" created by you, "2" subscribed, "3" free signals.
+    #[serde(default)]
+    pub signal_source_type: String,
+}
+
+/// Result of a signal bot operation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotResult {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Client-supplied algo ID.
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    /// The code of the event execution result, 0 means success.
+    #[serde(default)]
+    pub s_code: String,
+    /// Rejection message if the request is unsuccessful.
+    #[serde(default)]
+    pub s_msg: String,
+}
+
+/// Signal bot order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotOrder {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Client-supplied algo ID.
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Instrument IDs traded by the bot.
+    #[serde(default)]
+    pub inst_ids: Vec<String>,
+    /// Bot state: "starting", "running", "stopping", "stopped".
+    #[serde(default)]
+    pub state: String,
+    /// Reason the bot stopped.
+    #[serde(default)]
+    pub cancel_type: String,
+    /// Signal channel ID.
+    #[serde(default)]
+    pub signal_chan_id: String,
+    /// Signal channel name.
+    #[serde(default)]
+    pub signal_chan_name: String,
+    /// Signal source type.
+    #[serde(default)]
+    pub signal_source_type: String,
+    /// Leverage.
+    #[serde(default)]
+    pub lever: String,
+    /// Investment amount.
+    #[serde(default)]
+    pub invest_amt: String,
+    /// Sub-order type.
+    #[serde(default)]
+    pub sub_ord_type: String,
+    /// Price offset ratio for limit sub-orders.
+    #[serde(default)]
+    pub ratio: String,
+    /// Available balance.
+    #[serde(default)]
+    pub avail_bal: String,
+    /// Frozen balance.
+    #[serde(default)]
+    pub frozen_bal: String,
+    /// Total PnL.
+    #[serde(default)]
+    pub total_pnl: String,
+    /// Total PnL ratio.
+    #[serde(default)]
+    pub total_pnl_ratio: String,
+    /// Realized PnL.
+    #[serde(default)]
+    pub realized_pnl: String,
+    /// Floating PnL.
+    #[serde(default)]
+    pub float_pnl: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+    /// Update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub u_time: String,
+}
+
+/// Result of a signal bot margin or TP/SL amendment.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotAmendResult {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+}
+
+/// Open position of a signal bot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotPosition {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Margin mode.
+    #[serde(default)]
+    pub mgn_mode: String,
+    /// Position side.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Quantity of positions.
+    #[serde(default)]
+    pub pos: String,
+    /// Average open price.
+    #[serde(default)]
+    pub avg_px: String,
+    /// Leverage.
+    #[serde(default)]
+    pub lever: String,
+    /// Estimated liquidation price.
+    #[serde(default)]
+    pub liq_px: String,
+    /// Mark price.
+    #[serde(default)]
+    pub mark_px: String,
+    /// Margin ratio.
+    #[serde(default)]
+    pub mgn_ratio: String,
+    /// Notional value in USD.
+    #[serde(default)]
+    pub notional_usd: String,
+    /// Unrealized PnL.
+    #[serde(default)]
+    pub upl: String,
+    /// Unrealized PnL ratio.
+    #[serde(default)]
+    pub upl_ratio: String,
+    /// Realized PnL.
+    #[serde(default)]
+    pub realized_pnl: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+    /// Update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub u_time: String,
+}
+
+/// Closed position of a signal bot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotPositionHistory {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Margin mode.
+    #[serde(default)]
+    pub mgn_mode: String,
+    /// Position direction: "long" or "short".
+    #[serde(default)]
+    pub direction: String,
+    /// Leverage.
+    #[serde(default)]
+    pub lever: String,
+    /// Average open price.
+    #[serde(default)]
+    pub open_avg_px: String,
+    /// Average close price.
+    #[serde(default)]
+    pub close_avg_px: String,
+    /// Realized PnL.
+    #[serde(default)]
+    pub pnl: String,
+    /// Realized PnL ratio.
+    #[serde(default)]
+    pub pnl_ratio: String,
+    /// Underlying.
+    #[serde(default)]
+    pub uly: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+    /// Update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub u_time: String,
+}
+
+/// Sub-order placed by a signal bot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotSubOrder {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Client-supplied algo ID.
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Sub-order ID.
+    #[serde(default)]
+    pub ord_id: String,
+    /// Client-supplied sub-order ID.
+    #[serde(default)]
+    pub cl_ord_id: String,
+    /// Order side.
+    #[serde(default)]
+    pub side: String,
+    /// Position side.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Order type.
+    #[serde(default)]
+    pub ord_type: String,
+    /// Trade mode.
+    #[serde(default)]
+    pub td_mode: String,
+    /// Order price.
+    #[serde(default)]
+    pub px: String,
+    /// Order quantity.
+    #[serde(default)]
+    pub sz: String,
+    /// Accumulated fill quantity.
+    #[serde(default)]
+    pub acc_fill_sz: String,
+    /// Average filled price.
+    #[serde(default)]
+    pub avg_px: String,
+    /// Order state.
+    #[serde(default)]
+    pub state: String,
+    /// Leverage.
+    #[serde(default)]
+    pub lever: String,
+    /// Fee.
+    #[serde(default)]
+    pub fee: String,
+    /// Fee currency.
+    #[serde(default)]
+    pub fee_ccy: String,
+    /// Profit and loss.
+    #[serde(default)]
+    pub pnl: String,
+    /// Contract value.
+    #[serde(default)]
+    pub ct_val: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub c_time: String,
+    /// Update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub u_time: String,
+}
+
+/// Result of cancelling a signal bot sub-order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CancelledSignalSubOrder {
+    /// Sub-order ID.
+    #[serde(default)]
+    pub signal_ord_id: String,
+    /// The code of the event execution result, 0 means success.
+    #[serde(default)]
+    pub s_code: String,
+    /// Rejection message if the request is unsuccessful.
+    #[serde(default)]
+    pub s_msg: String,
+}
+
+/// Signal event received by a signal bot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalBotEvent {
+    /// Algo ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Raw alert message sent to the signal channel.
+    #[serde(default)]
+    pub alert_msg: String,
+    /// Event type, e.g. "signal_processing".
+    #[serde(default)]
+    pub event_type: String,
+    /// Event status: "success" or "failure".
+    #[serde(default)]
+    pub event_status: String,
+    /// Processing result message.
+    #[serde(default)]
+    pub event_process_msg: String,
+    /// Orders triggered by the event.
+    #[serde(default)]
+    pub triggered_ord_data: Vec<SignalTriggeredOrder>,
+    /// Event creation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub event_ctime: String,
+    /// Event update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub event_utime: String,
+}
+
+/// Order triggered by a signal event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SignalTriggeredOrder {
+    /// Client-supplied order ID.
+    #[serde(default)]
+    pub cl_ord_id: String,
+}
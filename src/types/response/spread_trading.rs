@@ -0,0 +1,98 @@
+use serde::Deserialize;
+
+/// Ticker of a spread.
+///
+/// Push data of the `sprd-tickers` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SpreadTicker {
+    #[serde(default)]
+    pub sprd_id: String,
+    #[serde(default)]
+    pub last: String,
+    #[serde(default)]
+    pub last_sz: String,
+    #[serde(default)]
+    pub ask_px: String,
+    #[serde(default)]
+    pub ask_sz: String,
+    #[serde(default)]
+    pub bid_px: String,
+    #[serde(default)]
+    pub bid_sz: String,
+    #[serde(default)]
+    pub open24h: String,
+    #[serde(default)]
+    pub high24h: String,
+    #[serde(default)]
+    pub low24h: String,
+    #[serde(default)]
+    pub vol24h: String,
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Public trade of a spread.
+///
+/// Push data of the `sprd-public-trades` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SpreadPublicTrade {
+    #[serde(default)]
+    pub sprd_id: String,
+    #[serde(default)]
+    pub trade_id: String,
+    #[serde(default)]
+    pub px: String,
+    #[serde(default)]
+    pub sz: String,
+    /// Taker side: "buy" or "sell".
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Order book of a spread.
+///
+/// Push data of the `sprd-books5` and `sprd-bbo-tbt` channels; the latter
+/// carries a single level per side.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SpreadOrderBook {
+    /// Ask levels, best first.
+    #[serde(default)]
+    pub asks: Vec<SpreadBookLevel>,
+    /// Bid levels, best first.
+    #[serde(default)]
+    pub bids: Vec<SpreadBookLevel>,
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// One spread order book level: `[price, size, order count]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct SpreadBookLevel {
+    pub px: String,
+    pub sz: String,
+    pub num_orders: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spread_book_levels_have_three_fields() {
+        let book: SpreadOrderBook = serde_json::from_str(
+            r#"{"asks":[["111.06","55154","2"]],"bids":[["111.05","57745","1"]],"ts":"1670324386802"}"#,
+        )
+        .unwrap();
+        assert_eq!(book.asks[0].px, "111.06");
+        assert_eq!(book.bids[0].num_orders, "1");
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+
+/// A maintenance or incident notice from the system status endpoint or the
+/// `status` WebSocket channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SystemStatus {
+    #[serde(default)]
+    pub title: String,
+    /// `scheduled`, `ongoing`, `pre_open`, `completed` or `canceled`.
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub begin: String,
+    #[serde(default)]
+    pub end: String,
+    #[serde(default)]
+    pub pre_open_begin: String,
+    #[serde(default)]
+    pub href: String,
+    /// Affected service, e.g. `5` (trading service) or `8`/`9` (trading
+    /// service in batches of accounts).
+    #[serde(default)]
+    pub service_type: String,
+    #[serde(default)]
+    pub system: String,
+    #[serde(default)]
+    pub sche_desc: String,
+    #[serde(default)]
+    pub maint_type: String,
+    /// `1` for production, `2` for demo trading.
+    #[serde(default)]
+    pub env: String,
+    #[serde(default)]
+    pub ts: String,
+}
+
+impl SystemStatus {
+    /// Start of the window in milliseconds since the epoch.
+    pub fn begin_ms(&self) -> Option<i64> {
+        self.begin.parse().ok()
+    }
+
+    /// End of the window in milliseconds since the epoch.
+    pub fn end_ms(&self) -> Option<i64> {
+        self.end.parse().ok()
+    }
+
+    /// Whether the notice is finished, i.e. `completed` or `canceled`.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state.as_str(), "completed" | "canceled")
+    }
+}
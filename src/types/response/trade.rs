@@ -19,6 +19,25 @@ pub struct OrderResult {
     pub s_msg: String,
 }
 
+/// Result from closing a position.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ClosedPosition {
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Position side.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Client Order ID as assigned by the client.
+    #[serde(default)]
+    pub cl_ord_id: String,
+    /// Order tag.
+    #[serde(default)]
+    pub tag: String,
+}
+
 /// Result from cancelling a single order.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -265,3 +284,172 @@ pub struct MassCancelResult {
     /// Whether the mass cancel was successful. "true" or "false".
     pub result: String,
 }
+
+/// Currencies available for easy convert.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EasyConvertCurrencyList {
+    /// Currencies that can be converted, with their balances.
+    pub from_data: Vec<EasyConvertFromCurrency>,
+    /// Currencies that can be converted into, e.g. "USDT", "OKB".
+    pub to_ccy: Vec<String>,
+}
+
+/// Small balance eligible for easy convert.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EasyConvertFromCurrency {
+    /// Currency.
+    pub from_ccy: String,
+    /// Amount available to convert.
+    pub from_amt: String,
+}
+
+/// Result or history record of an easy convert.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EasyConvertResult {
+    /// Source currency.
+    pub from_ccy: String,
+    /// Filled amount of the source currency.
+    pub fill_from_sz: String,
+    /// Target currency.
+    pub to_ccy: String,
+    /// Filled amount of the target currency.
+    pub fill_to_sz: String,
+    /// Status: running, filled, failed.
+    pub status: String,
+    /// Account the converted balance is credited to. History only.
+    #[serde(default)]
+    pub acct: String,
+    /// Update time, Unix timestamp in milliseconds.
+    pub u_time: String,
+}
+
+/// Debt and repayment currencies available for one-click repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayCurrencyList {
+    /// Debt type: cross, isolated.
+    pub debt_type: String,
+    /// Currencies in debt.
+    pub debt_data: Vec<OneClickRepayDebt>,
+    /// Currencies that can be used for repayment.
+    pub repay_data: Vec<OneClickRepayAsset>,
+}
+
+/// Debt eligible for one-click repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayDebt {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Debt amount.
+    pub debt_amt: String,
+}
+
+/// Asset that can be used for one-click repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayAsset {
+    /// Repayment currency.
+    pub repay_ccy: String,
+    /// Available amount.
+    pub repay_amt: String,
+}
+
+/// Result or history record of a one-click repay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayResult {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Amount of debt repaid.
+    pub fill_debt_sz: String,
+    /// Repayment currency.
+    pub repay_ccy: String,
+    /// Amount of the repayment currency used.
+    pub fill_repay_sz: String,
+    /// Status: running, filled, failed.
+    pub status: String,
+    /// Update time, Unix timestamp in milliseconds.
+    pub u_time: String,
+}
+
+/// Debt currency and its repayment currencies for one-click repay v2.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayCurrencyListV2 {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Currencies that can repay the debt, in order of use.
+    pub repay_ccy_list: Vec<String>,
+}
+
+/// Result of a one-click repay v2 request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayResultV2 {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Repayment currencies, in order of use.
+    pub repay_ccy_list: Vec<String>,
+    /// Request time, Unix timestamp in milliseconds.
+    pub ts: String,
+}
+
+/// History record of a one-click repay v2.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayRecordV2 {
+    /// Debt currency.
+    pub debt_ccy: String,
+    /// Amount of debt repaid.
+    pub fill_debt_sz: String,
+    /// Repayment currencies, in order of use.
+    pub repay_ccy_list: Vec<String>,
+    /// Status: running, filled, failed.
+    pub status: String,
+    /// Orders placed to buy the debt currency.
+    #[serde(default)]
+    pub ord_id_info: Vec<OneClickRepayOrder>,
+    /// Request time, Unix timestamp in milliseconds.
+    pub ts: String,
+}
+
+/// Order placed by a one-click repay v2.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OneClickRepayOrder {
+    /// Order ID.
+    pub ord_id: String,
+    /// Instrument ID.
+    pub inst_id: String,
+    /// Order type.
+    pub ord_type: String,
+    /// Order side: buy, sell.
+    pub side: String,
+    /// Order price.
+    pub px: String,
+    /// Order size.
+    pub sz: String,
+    /// Average filled price.
+    pub fill_px: String,
+    /// Filled size.
+    pub fill_sz: String,
+    /// Order state.
+    pub state: String,
+    /// Creation time, Unix timestamp in milliseconds.
+    pub c_time: String,
+}
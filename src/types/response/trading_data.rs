@@ -0,0 +1,97 @@
+use serde::Deserialize;
+
+// Apart from the supported coin list, trading statistics are returned as
+// arrays of strings rather than objects. Serde maps each array element to the
+// struct fields in declaration order, so field order below must match the API.
+
+/// Currencies supported by the trading statistics endpoints.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct SupportCoin {
+    /// Currencies supported by contract statistics.
+    #[serde(default)]
+    pub contract: Vec<String>,
+    /// Currencies supported by option statistics.
+    #[serde(default)]
+    pub option: Vec<String>,
+    /// Currencies supported by spot statistics.
+    #[serde(default)]
+    pub spot: Vec<String>,
+}
+
+/// Taker buy and sell volume for a period.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct TakerVolume {
+    /// Period start, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Taker sell volume.
+    pub sell_vol: String,
+    /// Taker buy volume.
+    pub buy_vol: String,
+}
+
+/// A ratio for a period, e.g. margin lending or long/short account ratio.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct RatioPoint {
+    /// Period start, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Ratio value.
+    pub ratio: String,
+}
+
+/// Open interest and trading volume for a period.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenInterestVolume {
+    /// Period start, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Open interest in USD.
+    pub oi: String,
+    /// Trading volume in USD.
+    pub vol: String,
+}
+
+/// Options put/call ratios for a period.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct PutCallRatio {
+    /// Period start, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Put/call open interest ratio.
+    pub oi_ratio: String,
+    /// Put/call trading volume ratio.
+    pub vol_ratio: String,
+}
+
+/// Contract open interest for a period.
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct OpenInterestHistory {
+    /// Period start, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Open interest in contracts.
+    pub oi: String,
+    /// Open interest in coin.
+    pub oi_ccy: String,
+    /// Open interest in USD.
+    pub oi_usd: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_array_rows() {
+        let rows: Vec<OpenInterestHistory> =
+            serde_json::from_str(r#"[["1701417600000","1","2","3"]]"#).unwrap();
+        assert_eq!(rows[0].ts, "1701417600000");
+        assert_eq!(rows[0].oi_usd, "3");
+
+        let rows: Vec<RatioPoint> =
+            serde_json::from_str(r#"[["1701417600000","1.25"]]"#).unwrap();
+        assert_eq!(rows[0].ratio, "1.25");
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::types::enums::ArchiveState;
+
 /// Pagination parameters for cursor-based pagination.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,3 +20,28 @@ pub struct Pagination {
 /// Empty request parameters (for endpoints with no params).
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
 pub struct Empty {}
+
+/// Result of applying for an archive file (fills or bills).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ArchiveApplyResult {
+    /// Whether the application was accepted: "true" or "false".
+    pub result: String,
+    /// Application time, Unix timestamp in milliseconds.
+    pub ts: String,
+}
+
+/// Download link and preparation state of an archive file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ArchiveFile {
+    /// Download link. Empty until the file is ready.
+    #[serde(default)]
+    pub file_href: String,
+    /// Preparation state: finished, ongoing, failed.
+    pub state: ArchiveState,
+    /// Application time, Unix timestamp in milliseconds.
+    pub ts: String,
+}
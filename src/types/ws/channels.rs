@@ -1,8 +1,16 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
+use super::events::WsConnectionType;
+use crate::types::enums::{Bar, GridAlgoOrderType, InstrumentType};
+
 /// WebSocket channel names.
+///
+/// Serializes to the OKX channel string. Unknown channels deserialize to
+/// [`WsChannel::Other`] so new channels can be used before they are added here.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(from = "String", into = "String")]
 pub enum WsChannel {
     // Public channels
     Instruments,
@@ -21,11 +29,8 @@ pub enum WsChannel {
     // Order book channels
     Books,
     Books5,
-    #[serde(rename = "bbo-tbt")]
     BboTbt,
-    #[serde(rename = "books-l2-tbt")]
     BooksL2Tbt,
-    #[serde(rename = "books50-l2-tpt")]
     Books50L2Tpt,
 
     // Private channels
@@ -36,18 +41,214 @@ pub enum WsChannel {
     OrdersAlgo,
     AlgoAdvance,
     LiquidationWarning,
+    AdlWarning,
     AccountGreeks,
 
-    // Grid channels (private)
+    // Grid channels (business)
     GridOrdersSpot,
     GridOrdersContract,
     GridOrdersMoon,
     GridPositions,
     GridSubOrders,
 
-    // Candle channels (use string for dynamic bar sizes)
-    #[serde(untagged)]
-    Candle(String),
+    // Funding channels (business)
+    DepositInfo,
+    WithdrawalInfo,
+
+    // Spread trading channels (business)
+    SprdPublicTrades,
+    SprdBboTbt,
+    SprdBooks5,
+    SprdTickers,
+
+    // Candle channels (business)
+    Candle(Bar),
+    MarkPriceCandle(Bar),
+    IndexCandle(Bar),
+
+    /// Any other channel, by its raw OKX name.
+    Other(String),
+}
+
+impl WsChannel {
+    /// OKX channel name, e.g. "tickers" or "candle1m".
+    pub fn name(&self) -> Cow<'static, str> {
+        let name = match self {
+            Self::Instruments => "instruments",
+            Self::Tickers => "tickers",
+            Self::OpenInterest => "open-interest",
+            Self::Trades => "trades",
+            Self::EstimatedPrice => "estimated-price",
+            Self::MarkPrice => "mark-price",
+            Self::PriceLimit => "price-limit",
+            Self::OptSummary => "opt-summary",
+            Self::FundingRate => "funding-rate",
+            Self::IndexTickers => "index-tickers",
+            Self::Status => "status",
+            Self::LiquidationOrders => "liquidation-orders",
+            Self::Books => "books",
+            Self::Books5 => "books5",
+            Self::BboTbt => "bbo-tbt",
+            Self::BooksL2Tbt => "books-l2-tbt",
+            Self::Books50L2Tpt => "books50-l2-tpt",
+            Self::Account => "account",
+            Self::Positions => "positions",
+            Self::BalanceAndPosition => "balance_and_position",
+            Self::Orders => "orders",
+            Self::OrdersAlgo => "orders-algo",
+            Self::AlgoAdvance => "algo-advance",
+            Self::LiquidationWarning => "liquidation-warning",
+            Self::AdlWarning => "adl-warning",
+            Self::AccountGreeks => "account-greeks",
+            Self::GridOrdersSpot => "grid-orders-spot",
+            Self::GridOrdersContract => "grid-orders-contract",
+            Self::GridOrdersMoon => "grid-orders-moon",
+            Self::GridPositions => "grid-positions",
+            Self::GridSubOrders => "grid-sub-orders",
+            Self::DepositInfo => "deposit-info",
+            Self::WithdrawalInfo => "withdrawal-info",
+            Self::SprdPublicTrades => "sprd-public-trades",
+            Self::SprdBboTbt => "sprd-bbo-tbt",
+            Self::SprdBooks5 => "sprd-books5",
+            Self::SprdTickers => "sprd-tickers",
+            Self::Candle(bar) => return format!("candle{}", bar.as_str()).into(),
+            Self::MarkPriceCandle(bar) => {
+                return format!("mark-price-candle{}", bar.as_str()).into();
+            }
+            Self::IndexCandle(bar) => return format!("index-candle{}", bar.as_str()).into(),
+            Self::Other(name) => return name.clone().into(),
+        };
+        name.into()
+    }
+
+    /// Whether subscribing requires a logged-in connection.
+    ///
+    /// True for every private channel and for the business channels that
+    /// carry account data.
+    pub fn requires_auth(&self) -> bool {
+        match self {
+            Self::DepositInfo
+            | Self::WithdrawalInfo
+            | Self::GridOrdersSpot
+            | Self::GridOrdersContract
+            | Self::GridOrdersMoon
+            | Self::GridPositions
+            | Self::GridSubOrders => true,
+            other => other.connection_type() == WsConnectionType::Private,
+        }
+    }
+
+    /// Connection this channel is served on.
+    pub fn connection_type(&self) -> WsConnectionType {
+        match self {
+            Self::Account
+            | Self::Positions
+            | Self::BalanceAndPosition
+            | Self::Orders
+            | Self::OrdersAlgo
+            | Self::AlgoAdvance
+            | Self::LiquidationWarning
+            | Self::AdlWarning
+            | Self::AccountGreeks => WsConnectionType::Private,
+            Self::GridOrdersSpot
+            | Self::GridOrdersContract
+            | Self::GridOrdersMoon
+            | Self::GridPositions
+            | Self::GridSubOrders
+            | Self::DepositInfo
+            | Self::WithdrawalInfo
+            | Self::SprdPublicTrades
+            | Self::SprdBboTbt
+            | Self::SprdBooks5
+            | Self::SprdTickers
+            | Self::Candle(_)
+            | Self::MarkPriceCandle(_)
+            | Self::IndexCandle(_) => WsConnectionType::Business,
+            // Candle channels with bar sizes not known to `Bar`.
+            Self::Other(name)
+                if name.starts_with("candle")
+                    || name.starts_with("mark-price-candle")
+                    || name.starts_with("index-candle") =>
+            {
+                WsConnectionType::Business
+            }
+            _ => WsConnectionType::Public,
+        }
+    }
+}
+
+impl From<&str> for WsChannel {
+    fn from(name: &str) -> Self {
+        match name {
+            "instruments" => Self::Instruments,
+            "tickers" => Self::Tickers,
+            "open-interest" => Self::OpenInterest,
+            "trades" => Self::Trades,
+            "estimated-price" => Self::EstimatedPrice,
+            "mark-price" => Self::MarkPrice,
+            "price-limit" => Self::PriceLimit,
+            "opt-summary" => Self::OptSummary,
+            "funding-rate" => Self::FundingRate,
+            "index-tickers" => Self::IndexTickers,
+            "status" => Self::Status,
+            "liquidation-orders" => Self::LiquidationOrders,
+            "books" => Self::Books,
+            "books5" => Self::Books5,
+            "bbo-tbt" => Self::BboTbt,
+            "books-l2-tbt" => Self::BooksL2Tbt,
+            "books50-l2-tpt" => Self::Books50L2Tpt,
+            "account" => Self::Account,
+            "positions" => Self::Positions,
+            "balance_and_position" => Self::BalanceAndPosition,
+            "orders" => Self::Orders,
+            "orders-algo" => Self::OrdersAlgo,
+            "algo-advance" => Self::AlgoAdvance,
+            "liquidation-warning" => Self::LiquidationWarning,
+            "adl-warning" => Self::AdlWarning,
+            "account-greeks" => Self::AccountGreeks,
+            "grid-orders-spot" => Self::GridOrdersSpot,
+            "grid-orders-contract" => Self::GridOrdersContract,
+            "grid-orders-moon" => Self::GridOrdersMoon,
+            "grid-positions" => Self::GridPositions,
+            "grid-sub-orders" => Self::GridSubOrders,
+            "deposit-info" => Self::DepositInfo,
+            "withdrawal-info" => Self::WithdrawalInfo,
+            "sprd-public-trades" => Self::SprdPublicTrades,
+            "sprd-bbo-tbt" => Self::SprdBboTbt,
+            "sprd-books5" => Self::SprdBooks5,
+            "sprd-tickers" => Self::SprdTickers,
+            _ => {
+                let candle = |prefix: &str| name.strip_prefix(prefix).and_then(Bar::parse);
+                if let Some(bar) = candle("mark-price-candle") {
+                    Self::MarkPriceCandle(bar)
+                } else if let Some(bar) = candle("index-candle") {
+                    Self::IndexCandle(bar)
+                } else if let Some(bar) = candle("candle") {
+                    Self::Candle(bar)
+                } else {
+                    Self::Other(name.to_string())
+                }
+            }
+        }
+    }
+}
+
+impl From<String> for WsChannel {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl From<WsChannel> for String {
+    fn from(channel: WsChannel) -> Self {
+        channel.name().into_owned()
+    }
+}
+
+impl std::fmt::Display for WsChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name())
+    }
 }
 
 /// Subscription argument sent to OKX WebSocket.
@@ -67,9 +268,17 @@ pub struct WsSubscriptionArg {
     pub uid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub algo_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprd_id: Option<String>,
 }
 
 impl WsSubscriptionArg {
+    /// Create a subscription arg for a typed channel. Add parameters with the
+    /// builder methods, e.g. `WsSubscriptionArg::new(WsChannel::Tickers).inst_id("BTC-USDT")`.
+    pub fn new(channel: WsChannel) -> Self {
+        Self::channel_only(&channel.name())
+    }
+
     /// Create a simple subscription arg with just a channel and instrument ID.
     pub fn with_inst_id(channel: &str, inst_id: &str) -> Self {
         Self {
@@ -80,6 +289,7 @@ impl WsSubscriptionArg {
             ccy: None,
             uid: None,
             algo_id: None,
+            sprd_id: None,
         }
     }
 
@@ -93,6 +303,7 @@ impl WsSubscriptionArg {
             ccy: None,
             uid: None,
             algo_id: None,
+            sprd_id: None,
         }
     }
 
@@ -106,39 +317,246 @@ impl WsSubscriptionArg {
             ccy: None,
             uid: None,
             algo_id: None,
+            sprd_id: None,
+        }
+    }
+
+    /// Subscribe to public liquidation orders of an instrument type
+    /// (`SWAP`, `FUTURES`, `MARGIN` or `OPTION`).
+    pub fn liquidation_orders(inst_type: &str) -> Self {
+        Self::with_inst_type("liquidation-orders", inst_type)
+    }
+
+    /// Subscribe to liquidation warnings for the account's positions of an
+    /// instrument type. Requires login.
+    pub fn liquidation_warning(inst_type: &str) -> Self {
+        Self::with_inst_type("liquidation-warning", inst_type)
+    }
+
+    /// Subscribe to auto-deleveraging warnings for an instrument family, or
+    /// for all families of the instrument type if `inst_family` is `None`.
+    /// Requires login.
+    pub fn adl_warning(inst_type: &str, inst_family: Option<&str>) -> Self {
+        Self {
+            inst_family: inst_family.map(str::to_string),
+            ..Self::with_inst_type("adl-warning", inst_type)
+        }
+    }
+
+    /// Subscribe to listings, delistings and parameter changes of all
+    /// instruments of a type.
+    pub fn instruments(inst_type: InstrumentType) -> Self {
+        Self::new(WsChannel::Instruments).inst_type(inst_type.as_str())
+    }
+
+    /// Subscribe to the best bid and ask of an instrument, pushed tick by tick.
+    pub fn bbo_tbt(inst_id: &str) -> Self {
+        Self::new(WsChannel::BboTbt).inst_id(inst_id)
+    }
+
+    /// Subscribe to five-level order book snapshots of an instrument.
+    pub fn books5(inst_id: &str) -> Self {
+        Self::new(WsChannel::Books5).inst_id(inst_id)
+    }
+
+    /// Subscribe to public trades of a spread.
+    pub fn sprd_public_trades(sprd_id: &str) -> Self {
+        Self::new(WsChannel::SprdPublicTrades).sprd_id(sprd_id)
+    }
+
+    /// Subscribe to the best bid and ask of a spread, pushed tick by tick.
+    pub fn sprd_bbo_tbt(sprd_id: &str) -> Self {
+        Self::new(WsChannel::SprdBboTbt).sprd_id(sprd_id)
+    }
+
+    /// Subscribe to five-level order book snapshots of a spread.
+    pub fn sprd_books5(sprd_id: &str) -> Self {
+        Self::new(WsChannel::SprdBooks5).sprd_id(sprd_id)
+    }
+
+    /// Subscribe to the ticker of a spread.
+    pub fn sprd_tickers(sprd_id: &str) -> Self {
+        Self::new(WsChannel::SprdTickers).sprd_id(sprd_id)
+    }
+
+    /// Subscribe to trade candles of an instrument.
+    pub fn candle(inst_id: &str, bar: Bar) -> Self {
+        Self::new(WsChannel::Candle(bar)).inst_id(inst_id)
+    }
+
+    /// Subscribe to index price candles of an index, e.g. `BTC-USDT`.
+    pub fn index_candle(inst_id: &str, bar: Bar) -> Self {
+        Self::new(WsChannel::IndexCandle(bar)).inst_id(inst_id)
+    }
+
+    /// Subscribe to mark price candles of an instrument.
+    pub fn mark_price_candle(inst_id: &str, bar: Bar) -> Self {
+        Self::new(WsChannel::MarkPriceCandle(bar)).inst_id(inst_id)
+    }
+
+    /// Subscribe to the buy and sell limit prices of an instrument.
+    pub fn price_limit(inst_id: &str) -> Self {
+        Self::new(WsChannel::PriceLimit).inst_id(inst_id)
+    }
+
+    /// Subscribe to estimated delivery or exercise prices of an instrument
+    /// family, e.g. `BTC-USD` with `inst_type` `FUTURES`.
+    pub fn estimated_price(inst_type: &str, inst_family: &str) -> Self {
+        Self::new(WsChannel::EstimatedPrice)
+            .inst_type(inst_type)
+            .inst_family(inst_family)
+    }
+
+    /// Subscribe to funding rate updates of a perpetual swap.
+    pub fn funding_rate(inst_id: &str) -> Self {
+        Self::new(WsChannel::FundingRate).inst_id(inst_id)
+    }
+
+    /// Subscribe to deposit updates of the account, optionally for one
+    /// currency. Requires login.
+    pub fn deposit_info(ccy: Option<&str>) -> Self {
+        Self {
+            ccy: ccy.map(str::to_string),
+            ..Self::new(WsChannel::DepositInfo)
         }
     }
 
+    /// Subscribe to withdrawal updates of the account, optionally for one
+    /// currency. Requires login.
+    pub fn withdrawal_info(ccy: Option<&str>) -> Self {
+        Self {
+            ccy: ccy.map(str::to_string),
+            ..Self::new(WsChannel::WithdrawalInfo)
+        }
+    }
+
+    /// Subscribe to grid bot updates of one grid type, optionally narrowed to
+    /// an instrument or a single bot. Requires login.
+    pub fn grid_orders(
+        grid_type: GridAlgoOrderType,
+        inst_type: &str,
+        inst_id: Option<&str>,
+        algo_id: Option<&str>,
+    ) -> Self {
+        let channel = match grid_type {
+            GridAlgoOrderType::SpotGrid => WsChannel::GridOrdersSpot,
+            GridAlgoOrderType::ContractGrid => WsChannel::GridOrdersContract,
+            GridAlgoOrderType::MoonGrid => WsChannel::GridOrdersMoon,
+        };
+        Self {
+            inst_id: inst_id.map(str::to_string),
+            algo_id: algo_id.map(str::to_string),
+            ..Self::new(channel).inst_type(inst_type)
+        }
+    }
+
+    /// Subscribe to positions of a contract grid bot. Requires login.
+    pub fn grid_positions(algo_id: &str) -> Self {
+        Self::new(WsChannel::GridPositions).algo_id(algo_id)
+    }
+
+    /// Subscribe to sub-orders placed by a grid bot. Requires login.
+    pub fn grid_sub_orders(algo_id: &str) -> Self {
+        Self::new(WsChannel::GridSubOrders).algo_id(algo_id)
+    }
+
+    /// Subscribe to the Greeks of the account, optionally for one currency.
+    /// Requires login.
+    pub fn account_greeks(ccy: Option<&str>) -> Self {
+        Self {
+            ccy: ccy.map(str::to_string),
+            ..Self::new(WsChannel::AccountGreeks)
+        }
+    }
+
+    /// Whether a pushed event's arg belongs to this subscription.
+    ///
+    /// The channel must be equal. Each parameter set on `self` must either
+    /// be equal in `push` or absent from it; `uid` is ignored since OKX adds
+    /// it to pushes of account channels.
+    pub fn matches(&self, push: &WsSubscriptionArg) -> bool {
+        fn field(sub: &Option<String>, push: &Option<String>) -> bool {
+            match (sub, push) {
+                (Some(sub), Some(push)) => sub == push,
+                _ => true,
+            }
+        }
+
+        self.channel == push.channel
+            && field(&self.inst_type, &push.inst_type)
+            && field(&self.inst_id, &push.inst_id)
+            && field(&self.inst_family, &push.inst_family)
+            && field(&self.ccy, &push.ccy)
+            && field(&self.algo_id, &push.algo_id)
+            && field(&self.sprd_id, &push.sprd_id)
+    }
+
+    /// Typed channel of this subscription.
+    pub fn ws_channel(&self) -> WsChannel {
+        WsChannel::from(self.channel.as_str())
+    }
+
     /// Determine if this is a private channel subscription.
     pub fn is_private(&self) -> bool {
-        matches!(
-            self.channel.as_str(),
-            "account"
-                | "positions"
-                | "balance_and_position"
-                | "orders"
-                | "orders-algo"
-                | "algo-advance"
-                | "liquidation-warning"
-                | "account-greeks"
-                | "grid-orders-spot"
-                | "grid-orders-contract"
-                | "grid-orders-moon"
-                | "grid-positions"
-                | "grid-sub-orders"
-        )
+        self.ws_channel().connection_type() == WsConnectionType::Private
+    }
+
+    /// Whether this subscription needs a logged-in connection.
+    pub fn requires_auth(&self) -> bool {
+        self.ws_channel().requires_auth()
     }
 
     /// Determine if this is a business channel subscription.
     pub fn is_business(&self) -> bool {
-        let ch = self.channel.as_str();
-        ch.starts_with("candle")
-            || ch.starts_with("mark-price-candle")
-            || ch.starts_with("index-candle")
-            || matches!(
-                ch,
-                "deposit-info" | "withdrawal-info" | "grid-orders-spot" | "grid-orders-contract"
-            )
+        self.ws_channel().connection_type() == WsConnectionType::Business
+    }
+
+    /// Set the instrument ID.
+    pub fn inst_id(mut self, inst_id: &str) -> Self {
+        self.inst_id = Some(inst_id.to_string());
+        self
+    }
+
+    /// Set the instrument type.
+    pub fn inst_type(mut self, inst_type: &str) -> Self {
+        self.inst_type = Some(inst_type.to_string());
+        self
+    }
+
+    /// Set the instrument family.
+    pub fn inst_family(mut self, inst_family: &str) -> Self {
+        self.inst_family = Some(inst_family.to_string());
+        self
+    }
+
+    /// Set the currency.
+    pub fn ccy(mut self, ccy: &str) -> Self {
+        self.ccy = Some(ccy.to_string());
+        self
+    }
+
+    /// Set the user ID.
+    pub fn uid(mut self, uid: &str) -> Self {
+        self.uid = Some(uid.to_string());
+        self
+    }
+
+    /// Set the algo order ID.
+    pub fn algo_id(mut self, algo_id: &str) -> Self {
+        self.algo_id = Some(algo_id.to_string());
+        self
+    }
+
+    /// Set the spread ID.
+    pub fn sprd_id(mut self, sprd_id: &str) -> Self {
+        self.sprd_id = Some(sprd_id.to_string());
+        self
+    }
+}
+
+impl From<WsChannel> for WsSubscriptionArg {
+    fn from(channel: WsChannel) -> Self {
+        Self::new(channel)
     }
 }
 
@@ -162,6 +580,26 @@ mod tests {
         assert!(arg.inst_id.is_none());
     }
 
+    #[test]
+    fn matches_push_args() {
+        let sub = WsSubscriptionArg::deposit_info(Some("BTC"));
+        assert!(sub.matches(&WsSubscriptionArg::deposit_info(Some("BTC")).uid("42")));
+        assert!(!sub.matches(&WsSubscriptionArg::deposit_info(Some("ETH"))));
+        assert!(!sub.matches(&WsSubscriptionArg::withdrawal_info(Some("BTC"))));
+        assert!(WsSubscriptionArg::deposit_info(None)
+            .matches(&WsSubscriptionArg::deposit_info(Some("ETH"))));
+    }
+
+    #[test]
+    fn sprd_args_serialize_sprd_id() {
+        let arg = WsSubscriptionArg::sprd_tickers("BTC-USDT_BTC-USDT-SWAP");
+        assert_eq!(
+            serde_json::to_value(&arg).unwrap(),
+            serde_json::json!({"channel": "sprd-tickers", "sprdId": "BTC-USDT_BTC-USDT-SWAP"})
+        );
+        assert!(!arg.matches(&WsSubscriptionArg::sprd_tickers("ETH-USDT_ETH-USDT-SWAP")));
+    }
+
     #[test]
     fn test_channel_only() {
         let arg = WsSubscriptionArg::channel_only("account");
@@ -179,6 +617,52 @@ mod tests {
         assert!(!WsSubscriptionArg::channel_only("trades").is_private());
     }
 
+    #[test]
+    fn test_risk_channel_helpers() {
+        let arg = WsSubscriptionArg::liquidation_orders("SWAP");
+        assert_eq!(arg.channel, "liquidation-orders");
+        assert!(!arg.is_private());
+
+        let arg = WsSubscriptionArg::adl_warning("SWAP", Some("BTC-USDT"));
+        assert_eq!(arg.channel, "adl-warning");
+        assert_eq!(arg.inst_family.as_deref(), Some("BTC-USDT"));
+        assert!(arg.is_private());
+
+        assert!(WsSubscriptionArg::liquidation_warning("SWAP").is_private());
+    }
+
+    #[test]
+    fn test_channel_names_round_trip() {
+        for channel in [
+            WsChannel::Tickers,
+            WsChannel::BalanceAndPosition,
+            WsChannel::Books50L2Tpt,
+            WsChannel::SprdBooks5,
+            WsChannel::Candle(Bar::H1),
+            WsChannel::MarkPriceCandle(Bar::M1),
+            WsChannel::IndexCandle(Bar::D1),
+            WsChannel::Other("economic-calendar".into()),
+        ] {
+            assert_eq!(WsChannel::from(channel.name().as_ref()), channel);
+            let json = serde_json::to_string(&channel).unwrap();
+            assert_eq!(json, format!("\"{}\"", channel.name()));
+        }
+        assert_eq!(WsChannel::Candle(Bar::H1).name(), "candle1H");
+        assert_eq!(WsChannel::BalanceAndPosition.name(), "balance_and_position");
+    }
+
+    #[test]
+    fn test_typed_builder() {
+        let arg = WsSubscriptionArg::new(WsChannel::Candle(Bar::M5)).inst_id("BTC-USDT");
+        assert_eq!(arg.channel, "candle5m");
+        assert_eq!(arg.inst_id.as_deref(), Some("BTC-USDT"));
+        assert!(arg.is_business());
+        assert_eq!(arg.ws_channel(), WsChannel::Candle(Bar::M5));
+
+        let arg: WsSubscriptionArg = WsChannel::Orders.into();
+        assert!(arg.inst_type("ANY").is_private());
+    }
+
     #[test]
     fn test_is_business() {
         assert!(WsSubscriptionArg::channel_only("candle1m").is_business());
@@ -190,6 +674,15 @@ mod tests {
         assert!(!WsSubscriptionArg::channel_only("account").is_business());
     }
 
+    #[test]
+    fn test_requires_auth() {
+        assert!(WsSubscriptionArg::channel_only("account").requires_auth());
+        assert!(WsSubscriptionArg::channel_only("deposit-info").requires_auth());
+        assert!(WsSubscriptionArg::channel_only("grid-positions").requires_auth());
+        assert!(!WsSubscriptionArg::channel_only("candle1m").requires_auth());
+        assert!(!WsSubscriptionArg::channel_only("tickers").requires_auth());
+    }
+
     #[test]
     fn test_serialize_subscription_arg() {
         let arg = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
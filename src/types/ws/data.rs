@@ -0,0 +1,507 @@
+use serde::Deserialize;
+
+use crate::types::response::funding::{DepositRecord, WithdrawalRecord};
+
+/// Push data of the public `liquidation-orders` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LiquidationOrder {
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Instrument family.
+    #[serde(default)]
+    pub inst_family: String,
+    /// Underlying.
+    #[serde(default)]
+    pub uly: String,
+    /// Liquidation details.
+    #[serde(default)]
+    pub details: Vec<LiquidationDetail>,
+}
+
+/// A single liquidation in a [`LiquidationOrder`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LiquidationDetail {
+    /// Order side: buy or sell.
+    #[serde(default)]
+    pub side: String,
+    /// Position side: long, short or net.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Bankruptcy price.
+    #[serde(default)]
+    pub bk_px: String,
+    /// Quantity of the liquidation.
+    #[serde(default)]
+    pub sz: String,
+    /// Bankruptcy loss.
+    #[serde(default)]
+    pub bk_loss: String,
+    /// Liquidation currency. Only applicable to `MARGIN`.
+    #[serde(default)]
+    pub ccy: String,
+    /// Liquidation time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Push data of the private `liquidation-warning` channel.
+///
+/// Pushed for positions that are close to liquidation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LiquidationWarning {
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Instrument ID.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Position ID.
+    #[serde(default)]
+    pub pos_id: String,
+    /// Margin mode: cross or isolated.
+    #[serde(default)]
+    pub mgn_mode: String,
+    /// Position side: long, short or net.
+    #[serde(default)]
+    pub pos_side: String,
+    /// Quantity of positions.
+    #[serde(default)]
+    pub pos: String,
+    /// Position currency. Only applicable to `MARGIN`.
+    #[serde(default)]
+    pub pos_ccy: String,
+    /// Margin currency.
+    #[serde(default)]
+    pub ccy: String,
+    /// Average open price.
+    #[serde(default)]
+    pub avg_px: String,
+    /// Estimated liquidation price.
+    #[serde(default)]
+    pub liq_px: String,
+    /// Latest mark price.
+    #[serde(default)]
+    pub mark_px: String,
+    /// Margin ratio.
+    #[serde(default)]
+    pub mgn_ratio: String,
+    /// Leverage.
+    #[serde(default)]
+    pub lever: String,
+    /// Latest update time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub u_time: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+}
+
+/// Push data of the private `adl-warning` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AdlWarning {
+    /// Instrument type.
+    #[serde(default)]
+    pub inst_type: String,
+    /// Instrument family.
+    #[serde(default)]
+    pub inst_family: String,
+    /// ADL state: "normal", "warning", "adl".
+    #[serde(default)]
+    pub state: String,
+    /// Real-time insurance fund balance.
+    #[serde(default)]
+    pub bal: String,
+    /// Maximum insurance fund balance in the past eight hours.
+    #[serde(default)]
+    pub max_bal: String,
+    /// Time of `max_bal`, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub max_bal_ts: String,
+    /// ADL related event type.
+    #[serde(default)]
+    pub adl_type: String,
+    /// Insurance fund balance that triggers ADL.
+    #[serde(default)]
+    pub adl_bal: String,
+    /// Insurance fund balance that stops ADL.
+    #[serde(default)]
+    pub adl_rec_bal: String,
+    /// Insurance fund decline rate over the window.
+    #[serde(default)]
+    pub dec_rate: String,
+    /// Decline rate that triggers ADL.
+    #[serde(default)]
+    pub adl_rate: String,
+    /// Decline rate that stops ADL.
+    #[serde(default)]
+    pub adl_rec_rate: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Push data of the `deposit-info` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DepositInfo {
+    /// Fields shared with the REST deposit history.
+    #[serde(flatten)]
+    pub record: DepositRecord,
+    /// User ID of the account the deposit belongs to.
+    #[serde(default)]
+    pub uid: String,
+    /// Sub-account name. Empty for the master account.
+    #[serde(default)]
+    pub sub_acct: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+    /// Deposit account, e.g. an email or phone number for internal transfers.
+    #[serde(default)]
+    pub from: String,
+    /// Withdrawal ID of the sender, for internal transfers between OKX accounts.
+    #[serde(default)]
+    pub from_wd_id: String,
+    /// Number of block confirmations so far.
+    #[serde(default)]
+    pub actual_dep_blk_confirm: String,
+}
+
+/// Push data of the `withdrawal-info` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct WithdrawalInfo {
+    /// Fields shared with the REST withdrawal history.
+    #[serde(flatten)]
+    pub record: WithdrawalRecord,
+    /// User ID of the account the withdrawal belongs to.
+    #[serde(default)]
+    pub uid: String,
+    /// Sub-account name. Empty for the master account.
+    #[serde(default)]
+    pub sub_acct: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+    /// Withdrawal account, e.g. an email or phone number for internal transfers.
+    #[serde(default)]
+    pub from: String,
+    /// Currency of the withdrawal fee.
+    #[serde(default)]
+    pub fee_ccy: String,
+    /// Tag or memo of the receiving address, for currencies that need one.
+    #[serde(default)]
+    pub tag: String,
+    #[serde(default)]
+    pub memo: String,
+}
+
+/// Push data of the `grid-orders-spot`, `grid-orders-contract` and
+/// `grid-orders-moon` channels.
+///
+/// Contract-only fields are empty for spot and moon grids.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct GridOrder {
+    /// Grid bot ID.
+    #[serde(default)]
+    pub algo_id: String,
+    /// Client-supplied grid bot ID.
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    /// "grid", "contract_grid" or "moon_grid".
+    #[serde(default)]
+    pub algo_ord_type: String,
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    /// Bot state, e.g. "starting", "running", "stopping", "stopped".
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub max_px: String,
+    #[serde(default)]
+    pub min_px: String,
+    #[serde(default)]
+    pub grid_num: String,
+    /// "1": arithmetic, "2": geometric.
+    #[serde(default)]
+    pub run_type: String,
+    /// Contract grid direction: "long", "short" or "neutral".
+    #[serde(default)]
+    pub direction: String,
+    #[serde(default)]
+    pub lever: String,
+    #[serde(default)]
+    pub investment: String,
+    #[serde(default)]
+    pub total_pnl: String,
+    #[serde(default)]
+    pub grid_profit: String,
+    #[serde(default)]
+    pub float_profit: String,
+    #[serde(default)]
+    pub pnl_ratio: String,
+    #[serde(default)]
+    pub annualized_rate: String,
+    #[serde(default)]
+    pub arbitrage_num: String,
+    /// Liquidation price of a contract grid.
+    #[serde(default)]
+    pub liq_px: String,
+    #[serde(default)]
+    pub tag: String,
+    #[serde(default)]
+    pub c_time: String,
+    #[serde(default)]
+    pub u_time: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+}
+
+/// Push data of the `grid-positions` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct GridPosition {
+    #[serde(default)]
+    pub algo_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    #[serde(default)]
+    pub pos_side: String,
+    #[serde(default)]
+    pub pos: String,
+    #[serde(default)]
+    pub avg_px: String,
+    #[serde(default)]
+    pub mgn_mode: String,
+    #[serde(default)]
+    pub mgn_ratio: String,
+    #[serde(default)]
+    pub lever: String,
+    #[serde(default)]
+    pub liq_px: String,
+    #[serde(default)]
+    pub mark_px: String,
+    #[serde(default)]
+    pub upl: String,
+    #[serde(default)]
+    pub upl_ratio: String,
+    #[serde(default)]
+    pub notional_usd: String,
+    #[serde(default)]
+    pub c_time: String,
+    #[serde(default)]
+    pub u_time: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+}
+
+/// Push data of the `grid-sub-orders` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct GridSubOrder {
+    #[serde(default)]
+    pub algo_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    #[serde(default)]
+    pub algo_ord_type: String,
+    #[serde(default)]
+    pub inst_type: String,
+    #[serde(default)]
+    pub inst_id: String,
+    /// Grid level the order belongs to.
+    #[serde(default)]
+    pub group_id: String,
+    #[serde(default)]
+    pub ord_id: String,
+    #[serde(default)]
+    pub ord_type: String,
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub pos_side: String,
+    #[serde(default)]
+    pub td_mode: String,
+    #[serde(default)]
+    pub px: String,
+    #[serde(default)]
+    pub sz: String,
+    #[serde(default)]
+    pub avg_px: String,
+    #[serde(default)]
+    pub acc_fill_sz: String,
+    /// Order state, e.g. "live", "partially_filled", "filled", "canceled".
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub fee: String,
+    #[serde(default)]
+    pub fee_ccy: String,
+    #[serde(default)]
+    pub pnl: String,
+    #[serde(default)]
+    pub c_time: String,
+    #[serde(default)]
+    pub u_time: String,
+    /// Push time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub p_time: String,
+}
+
+/// One price level of a [`DepthSnapshot`]: `[price, size, deprecated, order count]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct DepthLevel {
+    pub px: String,
+    pub sz: String,
+    pub liq: String,
+    pub num_orders: String,
+}
+
+/// Push data of the `books5` channel: the top five levels of each side.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DepthSnapshot {
+    /// Instrument ID, only set on `books5` pushes.
+    #[serde(default)]
+    pub inst_id: String,
+    /// Ask levels, best first.
+    #[serde(default)]
+    pub asks: Vec<DepthLevel>,
+    /// Bid levels, best first.
+    #[serde(default)]
+    pub bids: Vec<DepthLevel>,
+    /// Book time, Unix timestamp in milliseconds.
+    #[serde(default)]
+    pub ts: String,
+    /// Sequence ID of the update.
+    #[serde(default)]
+    pub seq_id: Option<i64>,
+}
+
+impl DepthSnapshot {
+    /// Best (lowest) ask level.
+    pub fn best_ask(&self) -> Option<&DepthLevel> {
+        self.asks.first()
+    }
+
+    /// Best (highest) bid level.
+    pub fn best_bid(&self) -> Option<&DepthLevel> {
+        self.bids.first()
+    }
+}
+
+/// Push data of the `bbo-tbt` channel, flattened to the best bid and ask.
+///
+/// Price and size fields are empty when that side of the book is empty.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "DepthSnapshot")]
+#[non_exhaustive]
+pub struct BboUpdate {
+    pub bid_px: String,
+    pub bid_sz: String,
+    pub ask_px: String,
+    pub ask_sz: String,
+    /// Book time, Unix timestamp in milliseconds.
+    pub ts: String,
+    /// Sequence ID of the update.
+    pub seq_id: Option<i64>,
+}
+
+impl From<DepthSnapshot> for BboUpdate {
+    fn from(book: DepthSnapshot) -> Self {
+        let side = |levels: Vec<DepthLevel>| {
+            levels
+                .into_iter()
+                .next()
+                .map(|l| (l.px, l.sz))
+                .unwrap_or_default()
+        };
+        let (bid_px, bid_sz) = side(book.bids);
+        let (ask_px, ask_sz) = side(book.asks);
+        Self {
+            bid_px,
+            bid_sz,
+            ask_px,
+            ask_sz,
+            ts: book.ts,
+            seq_id: book.seq_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bbo_update_takes_top_of_book() {
+        let bbo: BboUpdate = serde_json::from_str(
+            r#"{"asks":[["8446","95","0","3"]],"bids":[["8445","1","0","1"]],"ts":"1597026383085","seqId":123}"#,
+        )
+        .unwrap();
+        assert_eq!(bbo.bid_px, "8445");
+        assert_eq!(bbo.ask_sz, "95");
+        assert_eq!(bbo.seq_id, Some(123));
+
+        let empty: BboUpdate = serde_json::from_str(r#"{"asks":[],"bids":[],"ts":"1"}"#).unwrap();
+        assert!(empty.bid_px.is_empty() && empty.ask_px.is_empty());
+    }
+
+    #[test]
+    fn grid_pushes_parse() {
+        let order: GridOrder = serde_json::from_str(
+            r#"{"algoId":"1","algoOrdType":"contract_grid","instType":"SWAP","instId":"BTC-USDT-SWAP",
+                "state":"running","direction":"long","gridNum":"10","totalPnl":"1.5","pTime":"2"}"#,
+        )
+        .unwrap();
+        assert_eq!(order.direction, "long");
+        assert_eq!(order.total_pnl, "1.5");
+
+        let sub: GridSubOrder = serde_json::from_str(
+            r#"{"algoId":"1","groupId":"3","ordId":"9","side":"buy","px":"100","state":"filled"}"#,
+        )
+        .unwrap();
+        assert_eq!(sub.group_id, "3");
+        assert_eq!(sub.state, "filled");
+    }
+
+    #[test]
+    fn depth_snapshot_parses_levels() {
+        let book: DepthSnapshot = serde_json::from_str(
+            r#"{"asks":[["2","1","0","1"],["3","4","0","2"]],"bids":[["1","5","0","6"]],"instId":"BTC-USDT","ts":"1","seqId":7}"#,
+        )
+        .unwrap();
+        assert_eq!(book.asks.len(), 2);
+        assert_eq!(book.best_ask().unwrap().px, "2");
+        assert_eq!(book.best_bid().unwrap().num_orders, "6");
+        assert_eq!(book.inst_id, "BTC-USDT");
+    }
+}
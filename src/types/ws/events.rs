@@ -1,15 +1,42 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use super::channels::WsSubscriptionArg;
+use crate::error::{OkxError, OkxResult};
 
 /// A WebSocket data event (pushed data from subscriptions).
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WsDataEvent {
     /// The subscription arg that identifies the channel and parameters.
     pub arg: WsSubscriptionArg,
     /// The actual data payload. Structure depends on the channel.
     pub data: Vec<serde_json::Value>,
     /// Action type for order book updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+impl WsDataEvent {
+    /// Deserialize the payload into typed channel data, e.g.
+    /// [`LiquidationOrder`](super::data::LiquidationOrder).
+    pub fn parse_data<T: DeserializeOwned>(&self) -> OkxResult<Vec<T>> {
+        self.data
+            .iter()
+            .map(|item| {
+                T::deserialize(item).map_err(|e| OkxError::deserialize::<T>(e, &item.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// A data event whose payload was deserialized straight into `T`.
+///
+/// Produced by [`parse_typed_data`](crate::ws::connection::parse_typed_data),
+/// which skips the intermediate [`serde_json::Value`] tree of [`WsDataEvent`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WsTypedDataEvent<T> {
+    pub arg: WsSubscriptionArg,
+    pub data: Vec<T>,
     #[serde(default)]
     pub action: Option<String>,
 }
@@ -43,9 +70,94 @@ pub enum WsMessage {
     /// WS API response.
     ApiResponse(WsApiResponse),
     /// Connection opened.
-    Connected(WsConnectionType),
+    Connected(ConnectInfo),
+    /// Login on a private or business connection was confirmed.
+    Authenticated(WsConnectionType),
     /// Connection closed.
-    Disconnected(WsConnectionType),
+    Disconnected(DisconnectInfo),
+    /// Number of connections subscribed to a channel, sent after each
+    /// subscribe to a private channel.
+    ChannelConnCount(ChannelConnCount),
+    /// A subscription was refused because the channel's connection limit
+    /// was reached.
+    ChannelConnCountError(ChannelConnCount),
+    /// Service notice, e.g. code `64008` ahead of a restart for an upgrade.
+    Notice(WsNotice),
+    /// Pushes were missed on an order book or trades channel. The channel is
+    /// resubscribed automatically.
+    DataGap(DataGap),
+    /// A background task panicked or exited unexpectedly.
+    InternalError {
+        conn_type: WsConnectionType,
+        task: WsTaskKind,
+        message: String,
+    },
+}
+
+/// A `channel-conn-count` or `channel-conn-count-error` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelConnCount {
+    pub channel: String,
+    /// Connections subscribed to the channel, this one included.
+    pub conn_count: u32,
+    pub conn_id: Option<String>,
+}
+
+/// A `notice` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WsNotice {
+    pub code: String,
+    pub msg: String,
+    pub conn_id: Option<String>,
+}
+
+/// Details of an opened connection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectInfo {
+    pub conn_type: WsConnectionType,
+    /// Reconnect attempt that opened the connection, or 0 for the first
+    /// connect.
+    pub attempt: u32,
+}
+
+/// Details of a closed connection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisconnectInfo {
+    pub conn_type: WsConnectionType,
+    /// Close code of the server's close frame, if one was received.
+    pub close_code: Option<u16>,
+    /// Close reason, read error or failed task message, if any.
+    pub error: Option<String>,
+    /// Whether the client will reconnect.
+    pub will_reconnect: bool,
+    /// Number of the upcoming reconnect attempt, or 0 if none.
+    pub attempt: u32,
+}
+
+impl DisconnectInfo {
+    /// Cause of a disconnect as seen by the read loop, before the reconnect
+    /// decision is made.
+    pub fn new(conn_type: WsConnectionType) -> Self {
+        Self {
+            conn_type,
+            close_code: None,
+            error: None,
+            will_reconnect: false,
+            attempt: 0,
+        }
+    }
+}
+
+/// A break in the sequence IDs of a channel's pushes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataGap {
+    pub conn_type: WsConnectionType,
+    /// Subscription the gap was seen on.
+    pub arg: WsSubscriptionArg,
+    /// Sequence ID the push should have continued from.
+    pub expected: i64,
+    /// Sequence ID the push continued from instead.
+    pub received: i64,
 }
 
 /// WS API response (for order management via WebSocket).
@@ -65,7 +177,8 @@ pub struct WsApiResponse {
 }
 
 /// Type of WebSocket connection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum WsConnectionType {
     Public,
     Private,
@@ -81,3 +194,33 @@ impl std::fmt::Display for WsConnectionType {
         }
     }
 }
+
+/// Kind of background task run by the WebSocket client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WsTaskKind {
+    /// Socket read loop.
+    Read,
+    /// Socket write loop.
+    Write,
+    /// Ping sender.
+    Heartbeat,
+    /// Inbound message dispatcher.
+    Dispatch,
+    /// Reconnect attempt.
+    Reconnect,
+    /// Re-login after the session was rejected.
+    Relogin,
+}
+
+impl std::fmt::Display for WsTaskKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+            Self::Heartbeat => write!(f, "heartbeat"),
+            Self::Dispatch => write!(f, "dispatch"),
+            Self::Reconnect => write!(f, "reconnect"),
+            Self::Relogin => write!(f, "relogin"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod channels;
+pub mod data;
 pub mod events;
 pub mod requests;
 pub mod responses;
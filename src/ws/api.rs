@@ -3,7 +3,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use tokio::sync::oneshot;
 
-use crate::types::ws::events::WsApiResponse;
+use crate::error::{OkxError, OkxResult};
+use crate::types::ws::events::{WsApiResponse, WsConnectionType};
 use crate::types::ws::requests::WsApiRequest;
 
 /// Counter for generating unique request IDs.
@@ -23,10 +24,20 @@ pub fn build_api_request(op: &str, args: Vec<serde_json::Value>) -> WsApiRequest
     }
 }
 
+/// Outcome delivered to a waiting WS API caller.
+pub type ApiResult = OkxResult<WsApiResponse>;
+
+#[derive(Debug)]
+struct PendingRequest {
+    op: String,
+    conn_type: WsConnectionType,
+    tx: oneshot::Sender<ApiResult>,
+}
+
 /// Pending WS API request tracker. Maps request ID to oneshot sender.
 #[derive(Debug, Default)]
 pub struct PendingRequests {
-    inner: HashMap<String, oneshot::Sender<WsApiResponse>>,
+    inner: HashMap<String, PendingRequest>,
 }
 
 impl PendingRequests {
@@ -35,25 +46,57 @@ impl PendingRequests {
     }
 
     /// Register a pending request and return a receiver for the response.
-    pub fn register(&mut self, id: String) -> oneshot::Receiver<WsApiResponse> {
+    pub fn register(
+        &mut self,
+        id: String,
+        op: &str,
+        conn_type: WsConnectionType,
+    ) -> oneshot::Receiver<ApiResult> {
         let (tx, rx) = oneshot::channel();
-        self.inner.insert(id, tx);
+        self.inner.insert(
+            id,
+            PendingRequest {
+                op: op.to_string(),
+                conn_type,
+                tx,
+            },
+        );
         rx
     }
 
     /// Resolve a pending request with a response.
     pub fn resolve(&mut self, id: &str, response: WsApiResponse) -> bool {
-        if let Some(tx) = self.inner.remove(id) {
-            let _ = tx.send(response);
+        if let Some(pending) = self.inner.remove(id) {
+            let _ = pending.tx.send(Ok(response));
             true
         } else {
             false
         }
     }
 
-    /// Reject all pending requests (e.g., on disconnect).
-    pub fn reject_all(&mut self) {
-        self.inner.clear();
+    /// Forget a pending request (e.g., after it timed out).
+    pub fn remove(&mut self, id: &str) {
+        self.inner.remove(id);
+    }
+
+    /// Fail all requests in flight on a connection with
+    /// [`OkxError::WsDisconnected`].
+    pub fn reject_connection(&mut self, conn_type: WsConnectionType) {
+        let ids: Vec<String> = self
+            .inner
+            .iter()
+            .filter(|(_, p)| p.conn_type == conn_type)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in ids {
+            if let Some(pending) = self.inner.remove(&id) {
+                let _ = pending.tx.send(Err(OkxError::WsDisconnected {
+                    conn_type,
+                    op: pending.op,
+                    id,
+                }));
+            }
+        }
     }
 }
 
@@ -79,7 +122,7 @@ mod tests {
     #[test]
     fn test_pending_requests_resolve() {
         let mut pending = PendingRequests::new();
-        let mut rx = pending.register("test-1".to_string());
+        let mut rx = pending.register("test-1".to_string(), "order", WsConnectionType::Private);
 
         let response = WsApiResponse {
             id: "test-1".to_string(),
@@ -93,7 +136,7 @@ mod tests {
 
         assert!(pending.resolve("test-1", response));
 
-        let result = rx.try_recv().unwrap();
+        let result = rx.try_recv().unwrap().unwrap();
         assert_eq!(result.id, "test-1");
         assert_eq!(result.code, "0");
     }
@@ -116,15 +159,34 @@ mod tests {
     }
 
     #[test]
-    fn test_pending_requests_reject_all() {
+    fn test_pending_requests_remove() {
         let mut pending = PendingRequests::new();
-        let mut rx1 = pending.register("1".to_string());
-        let mut rx2 = pending.register("2".to_string());
+        let mut rx = pending.register("1".to_string(), "order", WsConnectionType::Private);
 
-        pending.reject_all();
+        pending.remove("1");
 
-        // Receivers should get an error because senders were dropped.
-        assert!(rx1.try_recv().is_err());
+        assert!(rx.try_recv().is_err());
+        assert!(pending.inner.is_empty());
+    }
+
+    #[test]
+    fn test_pending_requests_reject_connection() {
+        let mut pending = PendingRequests::new();
+        let mut rx1 = pending.register("1".to_string(), "order", WsConnectionType::Private);
+        let mut rx2 = pending.register("2".to_string(), "sprd-order", WsConnectionType::Business);
+
+        pending.reject_connection(WsConnectionType::Private);
+
+        match rx1.try_recv().unwrap() {
+            Err(OkxError::WsDisconnected { conn_type, op, id }) => {
+                assert_eq!(conn_type, WsConnectionType::Private);
+                assert_eq!(op, "order");
+                assert_eq!(id, "1");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        // Requests on other connections are unaffected.
         assert!(rx2.try_recv().is_err());
+        assert!(pending.inner.contains_key("2"));
     }
 }
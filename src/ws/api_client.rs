@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use crate::constants::PROGRAM_ID;
 use crate::error::{OkxError, OkxResult};
+use crate::throttle::OrderThrottle;
+use crate::types::request::spread_trading::{
+    SpreadAmendRequest, SpreadCancelRequest, SpreadOrderRequest,
+};
 use crate::types::request::trade::{
     AmendOrderRequest, CancelOrderRequest, MassCancelRequest, OrderRequest,
 };
@@ -47,21 +52,29 @@ use crate::ws::WebsocketClient;
 /// ```
 pub struct WsApiClient {
     inner: WebsocketClient,
+    throttle: Option<OrderThrottle>,
 }
 
 impl WsApiClient {
     /// Create a new `WsApiClient` with the given configuration.
     pub fn new(config: WsConfig) -> Self {
-        Self {
-            inner: WebsocketClient::new(config),
-        }
+        Self::from_client(WebsocketClient::new(config))
     }
 
     /// Create a `WsApiClient` from an existing [`WebsocketClient`].
     ///
     /// The two clients share the same underlying connections.
     pub fn from_client(client: WebsocketClient) -> Self {
-        Self { inner: client }
+        let throttle = client
+            .config()
+            .client_config
+            .order_throttle
+            .clone()
+            .map(OrderThrottle::new);
+        Self {
+            inner: client,
+            throttle,
+        }
     }
 
     /// Access the underlying [`WebsocketClient`].
@@ -69,20 +82,64 @@ impl WsApiClient {
         &self.inner
     }
 
+    /// Serialize a request and tag it, see
+    /// [`ClientConfig::tag_request`](crate::config::ClientConfig::tag_request).
+    fn tagged(&self, req: &impl Serialize) -> OkxResult<serde_json::Value> {
+        let mut value = serde_json::to_value(req)?;
+        self.inner.config().client_config.tag_request(&mut value)?;
+        Ok(value)
+    }
+
+    fn default_timeout(&self) -> Duration {
+        self.inner.config().api_timeout
+    }
+
     /// Place a single order.
     /// WS operation: `order`
     pub async fn place_order(&self, req: OrderRequest) -> OkxResult<OrderResult> {
-        let arg = to_tagged_value(&req)?;
-        let resp = self.inner.send_api_request("order", vec![arg]).await?;
+        self.place_order_with_timeout(req, self.default_timeout())
+            .await
+    }
+
+    /// Place a single order, overriding the configured WS API timeout.
+    /// WS operation: `order`
+    pub async fn place_order_with_timeout(
+        &self,
+        req: OrderRequest,
+        timeout: Duration,
+    ) -> OkxResult<OrderResult> {
+        if let Some(guard) = &self.inner.config().client_config.maintenance_guard {
+            guard.admit([req.inst_id.as_str()]).await?;
+        }
+        if let Some(guard) = &self.inner.config().client_config.risk_guard {
+            guard.check(&req)?;
+        }
+        if let Some(throttle) = &self.throttle {
+            throttle.check(&req)?;
+        }
+        let arg = self.tagged(&req)?;
+        let resp = self
+            .inner
+            .send_api_request_with_timeout("order", vec![arg], timeout)
+            .await?;
         deserialize_first(&resp.data)
     }
 
     /// Place multiple orders (up to 20).
     /// WS operation: `batch-orders`
     pub async fn place_orders(&self, reqs: Vec<OrderRequest>) -> OkxResult<Vec<OrderResult>> {
+        if let Some(guard) = &self.inner.config().client_config.maintenance_guard {
+            guard.admit(reqs.iter().map(|r| r.inst_id.as_str())).await?;
+        }
+        if let Some(guard) = &self.inner.config().client_config.risk_guard {
+            guard.check_all(&reqs)?;
+        }
+        if let Some(throttle) = &self.throttle {
+            throttle.check_all(&reqs)?;
+        }
         let args = reqs
             .iter()
-            .map(to_tagged_value)
+            .map(|req| self.tagged(req))
             .collect::<OkxResult<Vec<_>>>()?;
         let resp = self.inner.send_api_request("batch-orders", args).await?;
         deserialize_all(&resp.data)
@@ -91,10 +148,21 @@ impl WsApiClient {
     /// Cancel a single order.
     /// WS operation: `cancel-order`
     pub async fn cancel_order(&self, req: CancelOrderRequest) -> OkxResult<CancelledOrder> {
+        self.cancel_order_with_timeout(req, self.default_timeout())
+            .await
+    }
+
+    /// Cancel a single order, overriding the configured WS API timeout.
+    /// WS operation: `cancel-order`
+    pub async fn cancel_order_with_timeout(
+        &self,
+        req: CancelOrderRequest,
+        timeout: Duration,
+    ) -> OkxResult<CancelledOrder> {
         let arg = serde_json::to_value(&req)?;
         let resp = self
             .inner
-            .send_api_request("cancel-order", vec![arg])
+            .send_api_request_with_timeout("cancel-order", vec![arg], timeout)
             .await?;
         deserialize_first(&resp.data)
     }
@@ -119,10 +187,24 @@ impl WsApiClient {
     /// Amend a single order.
     /// WS operation: `amend-order`
     pub async fn amend_order(&self, req: AmendOrderRequest) -> OkxResult<AmendedOrder> {
+        self.amend_order_with_timeout(req, self.default_timeout())
+            .await
+    }
+
+    /// Amend a single order, overriding the configured WS API timeout.
+    /// WS operation: `amend-order`
+    pub async fn amend_order_with_timeout(
+        &self,
+        req: AmendOrderRequest,
+        timeout: Duration,
+    ) -> OkxResult<AmendedOrder> {
+        if let Some(guard) = &self.inner.config().client_config.risk_guard {
+            guard.check_amend(&req)?;
+        }
         let arg = serde_json::to_value(&req)?;
         let resp = self
             .inner
-            .send_api_request("amend-order", vec![arg])
+            .send_api_request_with_timeout("amend-order", vec![arg], timeout)
             .await?;
         deserialize_first(&resp.data)
     }
@@ -133,6 +215,9 @@ impl WsApiClient {
         &self,
         reqs: Vec<AmendOrderRequest>,
     ) -> OkxResult<Vec<AmendedOrder>> {
+        if let Some(guard) = &self.inner.config().client_config.risk_guard {
+            guard.check_amends(&reqs)?;
+        }
         let args = reqs
             .iter()
             .map(|r| serde_json::to_value(r).map_err(OkxError::Serialization))
@@ -159,9 +244,9 @@ impl WsApiClient {
     /// WS operation: `sprd-order`
     pub async fn place_spread_order(
         &self,
-        req: serde_json::Value,
+        req: SpreadOrderRequest,
     ) -> OkxResult<WsSpreadOrderResult> {
-        let arg = to_tagged_value_raw(req)?;
+        let arg = self.tagged(&req)?;
         let resp = self.inner.send_api_request("sprd-order", vec![arg]).await?;
         deserialize_first(&resp.data)
     }
@@ -170,11 +255,12 @@ impl WsApiClient {
     /// WS operation: `sprd-cancel-order`
     pub async fn cancel_spread_order(
         &self,
-        req: serde_json::Value,
+        req: SpreadCancelRequest,
     ) -> OkxResult<WsSpreadCancelResult> {
+        let arg = serde_json::to_value(&req)?;
         let resp = self
             .inner
-            .send_api_request("sprd-cancel-order", vec![req])
+            .send_api_request("sprd-cancel-order", vec![arg])
             .await?;
         deserialize_first(&resp.data)
     }
@@ -183,11 +269,12 @@ impl WsApiClient {
     /// WS operation: `sprd-amend-order`
     pub async fn amend_spread_order(
         &self,
-        req: serde_json::Value,
+        req: SpreadAmendRequest,
     ) -> OkxResult<WsSpreadAmendResult> {
+        let arg = serde_json::to_value(&req)?;
         let resp = self
             .inner
-            .send_api_request("sprd-amend-order", vec![req])
+            .send_api_request("sprd-amend-order", vec![arg])
             .await?;
         deserialize_first(&resp.data)
     }
@@ -206,67 +293,114 @@ impl WsApiClient {
     }
 }
 
-/// Serialize a value and inject the OKX program tag if not already present.
-fn to_tagged_value(v: &impl Serialize) -> OkxResult<serde_json::Value> {
-    let mut value = serde_json::to_value(v)?;
-    inject_tag(&mut value);
-    Ok(value)
-}
-
-/// Inject the OKX program tag into a raw JSON value if not already present.
-fn to_tagged_value_raw(mut value: serde_json::Value) -> OkxResult<serde_json::Value> {
-    inject_tag(&mut value);
-    Ok(value)
-}
-
-/// Add `tag: PROGRAM_ID` to a JSON object if the key is absent.
-fn inject_tag(value: &mut serde_json::Value) {
-    if let serde_json::Value::Object(map) = value {
-        map.entry("tag")
-            .or_insert_with(|| serde_json::json!(PROGRAM_ID));
-    }
-}
-
 /// Deserialize the first element of a WS API response data array.
 fn deserialize_first<T: DeserializeOwned>(data: &[serde_json::Value]) -> OkxResult<T> {
     let v = data
         .first()
         .ok_or_else(|| OkxError::Ws("empty response data".into()))?;
-    serde_json::from_value(v.clone()).map_err(OkxError::Serialization)
+    deserialize_value(v)
 }
 
 /// Deserialize all elements of a WS API response data array.
 fn deserialize_all<T: DeserializeOwned>(data: &[serde_json::Value]) -> OkxResult<Vec<T>> {
-    data.iter()
-        .map(|v| serde_json::from_value(v.clone()).map_err(OkxError::Serialization))
-        .collect()
+    data.iter().map(deserialize_value).collect()
+}
+
+/// Deserialize from a borrowed `Value` so the tree is not cloned first;
+/// `v` stays available for the error payload.
+fn deserialize_value<T: DeserializeOwned>(v: &serde_json::Value) -> OkxResult<T> {
+    T::deserialize(v).map_err(|e| OkxError::deserialize::<T>(e, &v.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::PROGRAM_ID;
+    use crate::types::enums::{OrderSide, OrderType};
+    use crate::types::request::trade::AttachedAlgoOrder;
+
+    fn client() -> WsApiClient {
+        WsApiClient::new(WsConfig::new(crate::config::ClientConfig::default()))
+    }
 
     #[test]
     fn inject_tag_adds_when_absent() {
         let mut v = serde_json::json!({"instId": "BTC-USDT"});
-        inject_tag(&mut v);
+        crate::config::ClientConfig::default()
+            .tag_request(&mut v)
+            .unwrap();
         assert_eq!(v["tag"], serde_json::json!(PROGRAM_ID));
     }
 
     #[test]
     fn inject_tag_does_not_overwrite() {
         let mut v = serde_json::json!({"tag": "custom"});
-        inject_tag(&mut v);
+        crate::config::ClientConfig::default()
+            .tag_request(&mut v)
+            .unwrap();
         assert_eq!(v["tag"], serde_json::json!("custom"));
     }
 
-    #[test]
-    fn to_tagged_value_injects_tag() {
+    #[tokio::test]
+    async fn tagged_injects_program_tag() {
+        let req = OrderRequest {
+            inst_id: "BTC-USDT".into(),
+            ..Default::default()
+        };
+        let v = client().tagged(&req).unwrap();
+        assert_eq!(v["tag"], serde_json::json!(PROGRAM_ID));
+    }
+
+    #[tokio::test]
+    async fn attached_algo_orders_are_not_tagged() {
         let req = OrderRequest {
             inst_id: "BTC-USDT".into(),
+            attach_algo_ords: vec![AttachedAlgoOrder::stop_loss("90", "-1")],
             ..Default::default()
         };
-        let v = to_tagged_value(&req).unwrap();
+        let v = client().tagged(&req).unwrap();
         assert_eq!(v["tag"], serde_json::json!(PROGRAM_ID));
+        assert_eq!(
+            v["attachAlgoOrds"],
+            serde_json::json!([{"slTriggerPx": "90", "slOrdPx": "-1"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn spread_order_serializes_with_tag() {
+        let req = SpreadOrderRequest {
+            sprd_id: "BTC-USDT_BTC-USDT-SWAP".into(),
+            side: OrderSide::Buy,
+            ord_type: OrderType::Limit,
+            sz: "2".into(),
+            px: Some("-10".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            client().tagged(&req).unwrap(),
+            serde_json::json!({
+                "sprdId": "BTC-USDT_BTC-USDT-SWAP",
+                "tag": PROGRAM_ID,
+                "side": "buy",
+                "ordType": "limit",
+                "sz": "2",
+                "px": "-10",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_failure_keeps_payload_and_type() {
+        let data = vec![serde_json::json!({"ordId": 12345})];
+        let err = deserialize_first::<OrderResult>(&data).unwrap_err();
+        match err {
+            OkxError::Deserialize {
+                type_name, payload, ..
+            } => {
+                assert!(type_name.ends_with("OrderResult"));
+                assert!(payload.contains("12345"));
+            }
+            other => panic!("expected deserialize error, got: {other:?}"),
+        }
     }
 }
@@ -1,15 +1,21 @@
 use secrecy::ExposeSecret;
 
-use crate::auth;
+use crate::auth::{self, SigningAlgorithm};
 use crate::config::Credentials;
 use crate::error::{OkxError, OkxResult};
 use crate::types::ws::requests::{WsLoginArg, WsLoginRequest};
 
 /// Build a WebSocket login request from credentials.
-pub fn build_login_request(creds: &Credentials) -> OkxResult<WsLoginRequest> {
+///
+/// `algorithm` forces the signing algorithm; see
+/// [`ClientConfig::effective_signing_algorithm`](crate::config::ClientConfig::effective_signing_algorithm).
+pub fn build_login_request(
+    creds: &Credentials,
+    algorithm: Option<SigningAlgorithm>,
+) -> OkxResult<WsLoginRequest> {
     let timestamp = ws_timestamp()?;
 
-    let signature = auth::sign_ws(&timestamp.to_string(), &creds.api_secret)?;
+    let signature = auth::sign_ws(&timestamp.to_string(), &creds.api_secret, algorithm)?;
 
     Ok(WsLoginRequest {
         op: "login".to_string(),
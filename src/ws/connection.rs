@@ -1,22 +1,52 @@
 use futures_util::{SinkExt, StreamExt};
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::Message;
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+use tokio_tungstenite::connect_async;
+#[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+use tokio_tungstenite::connect_async_tls_with_config;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
+use crate::config::TlsConfig;
 use crate::error::{OkxError, OkxResult};
-use crate::types::ws::events::{WsApiResponse, WsConnectionType, WsDataEvent, WsEvent, WsMessage};
+use crate::types::ws::events::{
+    ChannelConnCount, DisconnectInfo, WsApiResponse, WsConnectionType, WsDataEvent, WsEvent,
+    WsMessage, WsNotice, WsTaskKind, WsTypedDataEvent,
+};
+use crate::ws::replay::WsRecorder;
+use crate::ws::supervisor::{FailureHandler, Supervisor};
+use crate::ws::types::WriteOverflowPolicy;
+use crate::ws::write_queue::{write_queue, WriteFrame, WriteSender};
 
 type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
-/// Establish a WebSocket connection to the given URL.
-pub async fn connect(url: &str) -> OkxResult<WsStream> {
+/// Establish a WebSocket connection to the given URL, sending `headers` with
+/// the handshake.
+pub async fn connect(url: &str, tls: &TlsConfig, headers: HeaderMap) -> OkxResult<WsStream> {
     let url = url::Url::parse(url).map_err(|e| OkxError::Ws(format!("Invalid WS URL: {e}")))?;
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| OkxError::Ws(format!("Invalid WS URL: {e}")))?;
+    request.headers_mut().extend(headers);
+    let connector = crate::tls::ws_connector(tls)?;
 
-    let (ws_stream, _response) = connect_async(url.as_str())
-        .await
-        .map_err(|e| OkxError::Ws(format!("WS connection failed: {e}")))?;
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    let result = connect_async_tls_with_config(request, None, false, connector).await;
+    #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+    let result = {
+        debug_assert!(connector.is_none());
+        connect_async(request).await
+    };
+
+    let (ws_stream, _response) =
+        result.map_err(|e| OkxError::Ws(format!("WS connection failed: {e}")))?;
 
     Ok(ws_stream)
 }
@@ -42,14 +72,37 @@ pub async fn send_text(ws: &mut WsStream, text: &str) -> OkxResult<()> {
     Ok(())
 }
 
+/// Every top-level field an inbound frame may carry. Which fields are
+/// present decides the message kind.
+///
+/// Deserializing into this directly from the text parses each frame once;
+/// the fields are then moved into the typed message.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Frame {
+    id: Option<String>,
+    op: Option<String>,
+    event: Option<String>,
+    code: Option<String>,
+    msg: Option<String>,
+    arg: Option<serde_json::Value>,
+    data: Option<serde_json::Value>,
+    action: Option<String>,
+    in_time: Option<String>,
+    out_time: Option<String>,
+    conn_count: Option<String>,
+    channel: Option<String>,
+    conn_id: Option<String>,
+}
+
 /// Parse an incoming WebSocket text message into a WsMessage.
 pub fn parse_ws_message(text: &str) -> Option<WsMessage> {
     if text == "pong" {
         return Some(WsMessage::Pong);
     }
 
-    let value: serde_json::Value = match serde_json::from_str(text) {
-        Ok(v) => v,
+    let frame: Frame = match serde_json::from_str(text) {
+        Ok(frame) => frame,
         Err(e) => {
             warn!("Failed to parse WS message as JSON: {e}");
             return None;
@@ -57,93 +110,172 @@ pub fn parse_ws_message(text: &str) -> Option<WsMessage> {
     };
 
     // WS API responses include both `id` and `op`.
-    if value.get("id").is_some() && value.get("op").is_some() {
-        if let Ok(resp) = serde_json::from_value::<WsApiResponse>(value) {
-            return Some(WsMessage::ApiResponse(resp));
-        } else {
-            return None;
-        }
+    if let (Some(id), Some(op)) = (frame.id, frame.op) {
+        return Some(WsMessage::ApiResponse(WsApiResponse {
+            id,
+            op,
+            code: frame.code?,
+            msg: frame.msg.unwrap_or_default(),
+            data: match frame.data {
+                Some(serde_json::Value::Array(items)) => items,
+                Some(_) => return None,
+                None => Vec::new(),
+            },
+            in_time: frame.in_time,
+            out_time: frame.out_time,
+        }));
     }
 
     // Data events include `arg` and `data`.
-    if value.get("arg").is_some() && value.get("data").is_some() {
-        if let Ok(evt) = serde_json::from_value::<WsDataEvent>(value) {
-            return Some(WsMessage::Data(evt));
-        } else {
+    if frame.arg.is_some() && frame.data.is_some() {
+        let (Some(arg), Some(serde_json::Value::Array(data))) = (frame.arg, frame.data) else {
             return None;
-        }
+        };
+        return Some(WsMessage::Data(WsDataEvent {
+            arg: serde_json::from_value(arg).ok()?,
+            data,
+            action: frame.action,
+        }));
     }
 
     // Control events include `event`.
-    if value.get("event").is_some() {
-        if let Ok(evt) = serde_json::from_value::<WsEvent>(value) {
-            return Some(WsMessage::Event(evt));
-        } else {
-            return None;
+    if let Some(event) = frame.event {
+        match event.as_str() {
+            "channel-conn-count" | "channel-conn-count-error" => {
+                let count = ChannelConnCount {
+                    channel: frame.channel?,
+                    conn_count: frame.conn_count?.parse().ok()?,
+                    conn_id: frame.conn_id,
+                };
+                return Some(if event == "channel-conn-count" {
+                    WsMessage::ChannelConnCount(count)
+                } else {
+                    WsMessage::ChannelConnCountError(count)
+                });
+            }
+            "notice" => {
+                return Some(WsMessage::Notice(WsNotice {
+                    code: frame.code.unwrap_or_default(),
+                    msg: frame.msg.unwrap_or_default(),
+                    conn_id: frame.conn_id,
+                }));
+            }
+            _ => {}
         }
+        return Some(WsMessage::Event(WsEvent {
+            event,
+            code: frame.code,
+            msg: frame.msg,
+            arg: frame.arg,
+            data: frame.data,
+            conn_count: frame.conn_count,
+        }));
     }
 
     warn!("Unknown WS message format: {text}");
     None
 }
 
+/// Parse a data push straight into typed channel data in one pass.
+///
+/// For consumers that read the raw socket text of a known channel and want
+/// to skip [`parse_ws_message`] followed by [`WsDataEvent::parse_data`].
+pub fn parse_typed_data<T: DeserializeOwned>(text: &str) -> OkxResult<WsTypedDataEvent<T>> {
+    serde_json::from_str(text).map_err(|e| OkxError::deserialize::<WsTypedDataEvent<T>>(e, text))
+}
+
 /// Splits a WebSocket stream and spawns write and read I/O tasks.
 ///
 /// This is a synchronous function so callers can avoid holding
 /// non-`Send` stream halves across `.await` points in their own
 /// async state machines.
 ///
-/// Returns `(write_tx, msg_rx)`: a channel for sending outbound
-/// messages and a channel for receiving parsed inbound messages.
+/// Both tasks run under `supervisor`; a write error is reported to
+/// `on_failure` as an unexpected exit. Inbound text frames are written to
+/// `recorder`, if any, before they are parsed.
+///
+/// Returns `(write_tx, msg_rx)`: a queue for sending outbound messages,
+/// bounded by `write_buffer` with `overflow` applied when full, and a
+/// channel for receiving parsed inbound messages.
 pub fn spawn_io_tasks(
     ws: WsStream,
     conn_type: WsConnectionType,
-) -> (
-    mpsc::UnboundedSender<String>,
-    mpsc::UnboundedReceiver<WsMessage>,
-) {
+    supervisor: &Supervisor,
+    on_failure: FailureHandler,
+    recorder: Option<WsRecorder>,
+    write_buffer: usize,
+    overflow: WriteOverflowPolicy,
+) -> (WriteSender, mpsc::UnboundedReceiver<WsMessage>) {
     let (mut write_half, read_half) = ws.split();
-    let (write_tx, mut write_rx) = mpsc::unbounded_channel::<String>();
+    let (write_tx, mut write_rx) = write_queue(conn_type, write_buffer, overflow);
     let (msg_tx, msg_rx) = mpsc::unbounded_channel::<WsMessage>();
     let msg_tx_for_read = msg_tx.clone();
 
-    tokio::spawn(async move {
-        while let Some(msg) = write_rx.recv().await {
-            if let Err(e) = write_half
-                .send(Message::Text(msg.into()))
-                .await
-            {
-                error!("WS {conn_type} write error: {e}");
-                break;
+    supervisor.spawn(
+        conn_type,
+        WsTaskKind::Write,
+        async move {
+            while let Some(frame) = write_rx.recv().await {
+                let msg = match frame {
+                    WriteFrame::Text(text) => Message::Text(text.into()),
+                    WriteFrame::Ping => Message::Ping(Default::default()),
+                };
+                if let Err(e) = write_half.send(msg).await {
+                    return Err(format!("write error: {e}"));
+                }
             }
-        }
-        debug!("WS {conn_type} write loop ended");
-    });
-
-    tokio::spawn(async move {
-        let mut read = read_half;
-        while let Some(result) = read.next().await {
-            match result {
-                Ok(Message::Text(text)) => {
-                    if let Some(parsed) = parse_ws_message(&text) {
-                        if msg_tx_for_read.send(parsed).is_err() {
-                            break;
+            // Every sender is gone: the client is closing this connection.
+            let _ = write_half.close().await;
+            debug!("WS {conn_type} write loop ended");
+            Ok(())
+        },
+        on_failure.clone(),
+    );
+
+    supervisor.spawn(
+        conn_type,
+        WsTaskKind::Read,
+        async move {
+            let mut read = read_half;
+            let mut disconnect = DisconnectInfo::new(conn_type);
+            while let Some(result) = read.next().await {
+                match result {
+                    Ok(Message::Text(text)) => {
+                        if let Some(recorder) = &recorder {
+                            recorder.record(conn_type, &text);
+                        }
+                        if let Some(parsed) = parse_ws_message(&text) {
+                            if msg_tx_for_read.send(parsed).is_err() {
+                                break;
+                            }
                         }
                     }
+                    // Answers a Ping frame from the heartbeat.
+                    Ok(Message::Pong(_)) => {
+                        let _ = msg_tx_for_read.send(WsMessage::Pong);
+                    }
+                    Ok(Message::Close(frame)) => {
+                        if let Some(frame) = frame {
+                            disconnect.close_code = Some(frame.code.into());
+                            disconnect.error =
+                                Some(frame.reason.to_string()).filter(|r| !r.is_empty());
+                        }
+                        break;
+                    }
+                    Err(e) => {
+                        error!("WS {conn_type} read error: {e}");
+                        disconnect.error = Some(e.to_string());
+                        break;
+                    }
+                    _ => {}
                 }
-                Ok(Message::Close(_)) => {
-                    let _ = msg_tx_for_read.send(WsMessage::Disconnected(conn_type));
-                    break;
-                }
-                Err(e) => {
-                    error!("WS {conn_type} read error: {e}");
-                    let _ = msg_tx_for_read.send(WsMessage::Disconnected(conn_type));
-                    break;
-                }
-                _ => {}
             }
-        }
-    });
+            // Reached on a close frame, a read error or the end of the stream.
+            let _ = msg_tx_for_read.send(WsMessage::Disconnected(disconnect));
+            Ok(())
+        },
+        on_failure,
+    );
 
     (write_tx, msg_rx)
 }
@@ -167,9 +299,11 @@ pub async fn read_loop(
                     }
                 }
             }
-            Ok(Message::Close(_)) => {
+            Ok(Message::Close(frame)) => {
                 info!("WS {conn_type} received close frame");
-                let _ = tx.send(WsMessage::Disconnected(conn_type));
+                let mut disconnect = DisconnectInfo::new(conn_type);
+                disconnect.close_code = frame.map(|f| f.code.into());
+                let _ = tx.send(WsMessage::Disconnected(disconnect));
                 break;
             }
             Ok(Message::Ping(data)) => {
@@ -177,10 +311,15 @@ pub async fn read_loop(
                 // Tungstenite auto-responds with `pong`.
                 let _ = data;
             }
+            Ok(Message::Pong(_)) => {
+                let _ = tx.send(WsMessage::Pong);
+            }
             Ok(_) => {}
             Err(e) => {
                 error!("WS {conn_type} read error: {e}");
-                let _ = tx.send(WsMessage::Disconnected(conn_type));
+                let mut disconnect = DisconnectInfo::new(conn_type);
+                disconnect.error = Some(e.to_string());
+                let _ = tx.send(WsMessage::Disconnected(disconnect));
                 break;
             }
         }
@@ -220,6 +359,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_conn_count_and_notice_events() {
+        let json = r#"{"event":"channel-conn-count","channel":"orders","connCount":"2","connId":"abcd1234"}"#;
+        let Some(WsMessage::ChannelConnCount(count)) = parse_ws_message(json) else {
+            panic!("expected ChannelConnCount");
+        };
+        assert_eq!(count.channel, "orders");
+        assert_eq!(count.conn_count, 2);
+
+        let json = r#"{"event":"channel-conn-count-error","channel":"orders","connCount":"30","connId":"a4d3ae55"}"#;
+        assert!(matches!(
+            parse_ws_message(json),
+            Some(WsMessage::ChannelConnCountError(ChannelConnCount { conn_count: 30, .. }))
+        ));
+
+        let json = r#"{"event":"notice","code":"64008","msg":"The connection will soon be closed for a service upgrade. Please reconnect.","connId":"a4d3ae55"}"#;
+        let Some(WsMessage::Notice(notice)) = parse_ws_message(json) else {
+            panic!("expected Notice");
+        };
+        assert_eq!(notice.code, "64008");
+        assert_eq!(notice.conn_id.as_deref(), Some("a4d3ae55"));
+    }
+
     #[test]
     fn test_parse_login_event() {
         let json = r#"{"event":"login","code":"0","msg":""}"#;
@@ -243,6 +405,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_typed_data() {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Ticker {
+            inst_id: String,
+            last: String,
+        }
+
+        let json = r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","last":"50000","ts":"1"}]}"#;
+        let evt = parse_typed_data::<Ticker>(json).unwrap();
+        assert_eq!(evt.arg.channel, "tickers");
+        assert_eq!(evt.data[0].inst_id, "BTC-USDT");
+        assert_eq!(evt.data[0].last, "50000");
+
+        let msg = parse_ws_message(json);
+        let Some(WsMessage::Data(evt)) = msg else {
+            panic!("expected data event");
+        };
+        let parsed: Vec<Ticker> = evt.parse_data().unwrap();
+        assert_eq!(parsed[0].last, "50000");
+    }
+
     #[test]
     fn test_parse_invalid_json() {
         let msg = parse_ws_message("not json");
@@ -1,15 +1,25 @@
 use std::time::Duration;
 
-use tokio::sync::mpsc;
 use tracing::debug;
 
-/// Heartbeat ping sender. Sends "ping" at the configured interval.
+use super::store::LastMessageAt;
+use super::types::HeartbeatMode;
+use super::write_queue::{WriteFrame, WriteSender};
+
+/// Heartbeat ping sender. Sends "ping", a Ping frame or both at the
+/// configured interval, depending on `mode`.
 /// Stops when the stop_rx receives a signal or the sender is dropped.
+///
+/// Fails once nothing has been received for `idle_timeout`, so the
+/// connection is torn down and reconnected.
 pub async fn heartbeat_loop(
-    tx: mpsc::UnboundedSender<String>,
+    tx: WriteSender,
     interval: Duration,
+    mode: HeartbeatMode,
+    last_message_at: LastMessageAt,
+    idle_timeout: Option<Duration>,
     mut stop_rx: tokio::sync::oneshot::Receiver<()>,
-) {
+) -> Result<(), String> {
     let mut ticker = tokio::time::interval(interval);
     // Skip the first immediate tick.
     ticker.tick().await;
@@ -17,14 +27,25 @@ pub async fn heartbeat_loop(
     loop {
         tokio::select! {
             _ = ticker.tick() => {
+                let idle = last_message_at.elapsed();
+                if idle_timeout.is_some_and(|timeout| idle >= timeout) {
+                    return Err(format!("no message received for {idle:?}"));
+                }
                 debug!("Sending WS ping");
-                if tx.send("ping".to_string()).is_err() {
-                    break;
+                let text = mode.sends_text().then(|| WriteFrame::Text("ping".to_string()));
+                let frame = mode.sends_frame().then_some(WriteFrame::Ping);
+                for ping in text.into_iter().chain(frame) {
+                    if let Err(e) = tx.send_frame(ping).await {
+                        if tx.is_closed() {
+                            return Ok(());
+                        }
+                        debug!("WS ping not queued: {e}");
+                    }
                 }
             }
             _ = &mut stop_rx => {
                 debug!("Heartbeat stopped");
-                break;
+                return Ok(());
             }
         }
     }
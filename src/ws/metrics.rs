@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::types::ws::events::WsApiResponse;
+
+/// Latency breakdown of a single WS API request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySample {
+    /// Time from sending the request to receiving the response, measured locally.
+    pub round_trip: Duration,
+    /// Time spent inside OKX (`outTime - inTime`), if reported.
+    pub exchange: Option<Duration>,
+    /// Round trip minus exchange time: time spent on the network and in the
+    /// client, if the exchange time is known.
+    pub network: Option<Duration>,
+}
+
+impl LatencySample {
+    /// Build a sample from the measured round trip and the gateway
+    /// timestamps (in microseconds) of the response.
+    pub fn from_response(round_trip: Duration, response: &WsApiResponse) -> Self {
+        let micros = |t: &Option<String>| t.as_deref().and_then(|s| s.parse::<u64>().ok());
+        let exchange = match (micros(&response.in_time), micros(&response.out_time)) {
+            (Some(i), Some(o)) if o >= i => Some(Duration::from_micros(o - i)),
+            _ => None,
+        };
+        Self {
+            round_trip,
+            exchange,
+            network: exchange.map(|e| round_trip.saturating_sub(e)),
+        }
+    }
+}
+
+/// Running statistics of one latency measure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub last: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub total: Duration,
+}
+
+impl LatencyStats {
+    fn record(&mut self, value: Duration) {
+        self.min = if self.count == 0 { value } else { self.min.min(value) };
+        self.max = self.max.max(value);
+        self.last = value;
+        self.total += value;
+        self.count += 1;
+    }
+
+    /// Mean latency, or zero if nothing was recorded.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.count as u32
+        }
+    }
+}
+
+/// Latency statistics of one WS API operation (e.g. `order`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpLatency {
+    pub round_trip: LatencyStats,
+    pub exchange: LatencyStats,
+    pub network: LatencyStats,
+}
+
+/// Per-operation WS API latency recorder shared by all clones of a client.
+#[derive(Debug, Clone, Default)]
+pub struct WsMetrics {
+    inner: Arc<Mutex<HashMap<String, OpLatency>>>,
+}
+
+impl WsMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a sample for an operation.
+    pub fn record(&self, op: &str, sample: LatencySample) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = inner.entry(op.to_string()).or_default();
+        entry.round_trip.record(sample.round_trip);
+        if let Some(exchange) = sample.exchange {
+            entry.exchange.record(exchange);
+        }
+        if let Some(network) = sample.network {
+            entry.network.record(network);
+        }
+    }
+
+    /// Snapshot of the statistics recorded so far, keyed by operation.
+    pub fn snapshot(&self) -> HashMap<String, OpLatency> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Clear all recorded statistics.
+    pub fn reset(&self) {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(in_time: &str, out_time: &str) -> WsApiResponse {
+        WsApiResponse {
+            id: "1".into(),
+            op: "order".into(),
+            code: "0".into(),
+            msg: String::new(),
+            data: vec![],
+            in_time: Some(in_time.into()),
+            out_time: Some(out_time.into()),
+        }
+    }
+
+    #[test]
+    fn splits_round_trip_into_exchange_and_network() {
+        let sample = LatencySample::from_response(
+            Duration::from_millis(10),
+            &response("1700000000000000", "1700000000002000"),
+        );
+        assert_eq!(sample.exchange, Some(Duration::from_millis(2)));
+        assert_eq!(sample.network, Some(Duration::from_millis(8)));
+
+        let sample = LatencySample::from_response(Duration::from_millis(10), &response("", ""));
+        assert_eq!(sample.exchange, None);
+        assert_eq!(sample.network, None);
+    }
+
+    #[test]
+    fn aggregates_per_operation() {
+        let metrics = WsMetrics::new();
+        for ms in [4, 2, 6] {
+            metrics.record(
+                "order",
+                LatencySample {
+                    round_trip: Duration::from_millis(ms),
+                    exchange: None,
+                    network: None,
+                },
+            );
+        }
+
+        let snapshot = metrics.snapshot();
+        let rt = snapshot["order"].round_trip;
+        assert_eq!(rt.count, 3);
+        assert_eq!(rt.min, Duration::from_millis(2));
+        assert_eq!(rt.max, Duration::from_millis(6));
+        assert_eq!(rt.last, Duration::from_millis(6));
+        assert_eq!(rt.mean(), Duration::from_millis(4));
+        assert_eq!(snapshot["order"].exchange.count, 0);
+    }
+}
@@ -3,24 +3,53 @@ pub mod api_client;
 pub mod auth;
 pub mod connection;
 pub mod heartbeat;
+pub mod metrics;
+pub mod replay;
 pub mod router;
+pub mod sequence;
 pub mod store;
+pub mod supervisor;
+pub mod typed;
 pub mod types;
+pub mod write_queue;
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures_util::future::BoxFuture;
-use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
-use tracing::{error, info, warn};
+use serde::de::DeserializeOwned;
+use tokio::sync::{broadcast, Mutex, RwLock, Semaphore};
+use tokio::time::Instant;
+use tracing::{debug, error, info, warn};
 
+use crate::constants;
 use crate::error::{OkxError, OkxResult};
+use crate::persist;
+use crate::retry::{RetryClass, RetryPolicy};
+use crate::types::enums::{Bar, GridAlgoOrderType, InstrumentType};
+use crate::types::response::account::AccountGreeks;
+use crate::types::response::market::Ohlcv;
+use crate::types::response::public::{EstimatedPrice, Instrument, PriceLimit};
+use crate::types::response::spread_trading::{SpreadOrderBook, SpreadPublicTrade, SpreadTicker};
 use crate::types::ws::channels::WsSubscriptionArg;
-use crate::types::ws::events::{WsConnectionType, WsMessage};
+use crate::types::ws::data::{
+    BboUpdate, DepositInfo, DepthSnapshot, GridOrder, GridPosition, GridSubOrder, WithdrawalInfo,
+};
+use crate::types::ws::events::{
+    ConnectInfo, DisconnectInfo, WsConnectionType, WsMessage, WsTaskKind,
+};
 use crate::types::ws::requests::WsSubRequest;
 
 use self::api::PendingRequests;
+use self::metrics::{LatencySample, OpLatency, WsMetrics};
+use self::sequence::SequenceTracker;
 use self::store::{ConnectionState, WsStore};
-use self::types::WsConfig;
+use self::supervisor::{FailureHandler, Supervisor, TaskHealth};
+use self::typed::TypedSubscription;
+use self::types::{RouteOverride, WsConfig};
+use self::write_queue::WriteSender;
 
 /// WebSocket client for OKX real-time data and order management.
 ///
@@ -51,21 +80,29 @@ use self::types::WsConfig;
 pub struct WebsocketClient {
     config: WsConfig,
     store: Arc<RwLock<WsStore>>,
-    event_tx: broadcast::Sender<WsMessage>,
+    /// Event bus. Messages are shared behind an `Arc` so fan-out to many
+    /// receivers does not clone the payload.
+    event_tx: broadcast::Sender<Arc<WsMessage>>,
     pending_requests: Arc<Mutex<PendingRequests>>,
     /// Channels for sending raw text to the per-connection write loops.
     write_txs: Arc<RwLock<WriteChannels>>,
+    supervisor: Supervisor,
+    /// In-flight cap for WS API requests, if configured.
+    api_permits: Option<Arc<Semaphore>>,
+    metrics: WsMetrics,
+    /// Set by [`WebsocketClient::shutdown`]; no connection is opened afterwards.
+    shut_down: Arc<AtomicBool>,
 }
 
 #[derive(Default, Clone)]
 struct WriteChannels {
-    public: Option<mpsc::UnboundedSender<String>>,
-    private: Option<mpsc::UnboundedSender<String>>,
-    business: Option<mpsc::UnboundedSender<String>>,
+    public: Option<WriteSender>,
+    private: Option<WriteSender>,
+    business: Option<WriteSender>,
 }
 
 impl WriteChannels {
-    fn get(&self, conn_type: WsConnectionType) -> Option<&mpsc::UnboundedSender<String>> {
+    fn get(&self, conn_type: WsConnectionType) -> Option<&WriteSender> {
         match conn_type {
             WsConnectionType::Public => self.public.as_ref(),
             WsConnectionType::Private => self.private.as_ref(),
@@ -73,7 +110,7 @@ impl WriteChannels {
         }
     }
 
-    fn set(&mut self, conn_type: WsConnectionType, tx: mpsc::UnboundedSender<String>) {
+    fn set(&mut self, conn_type: WsConnectionType, tx: WriteSender) {
         match conn_type {
             WsConnectionType::Public => self.public = Some(tx),
             WsConnectionType::Private => self.private = Some(tx),
@@ -90,9 +127,29 @@ impl WriteChannels {
     }
 }
 
+/// Consecutive re-login attempts before giving up on a rejected session.
+const MAX_RELOGIN_ATTEMPTS: u32 = 3;
+
+/// Cap on the reconnect backoff, as a multiple of [`WsConfig::reconnect_delay`].
+const MAX_RECONNECT_BACKOFF: u32 = 10;
+
+/// OKX error codes meaning the session is not (or no longer) logged in.
+fn is_session_error(code: &str) -> bool {
+    // Login failed, not logged in, or session invalidated.
+    matches!(code, "60009" | "60011" | "60026")
+}
+
+/// Whether a failed WS API request may be sent again. OKX may already have
+/// executed it unless it was rejected for the rate limit or maintenance.
+fn resendable(policy: &RetryPolicy, err: &OkxError) -> bool {
+    let class = RetryClass::from_error(err);
+    matches!(class, RetryClass::RateLimited | RetryClass::Maintenance) && policy.should_retry(class)
+}
+
 /// Partition subscription args by their target connection type.
 fn partition_args(
     args: Vec<WsSubscriptionArg>,
+    route_override: Option<RouteOverride>,
 ) -> (
     Vec<WsSubscriptionArg>,
     Vec<WsSubscriptionArg>,
@@ -102,7 +159,7 @@ fn partition_args(
     let mut private = Vec::new();
     let mut business = Vec::new();
     for arg in args {
-        match router::route_subscription(&arg) {
+        match router::resolve_route(&arg, route_override) {
             WsConnectionType::Public => public.push(arg),
             WsConnectionType::Private => private.push(arg),
             WsConnectionType::Business => business.push(arg),
@@ -115,74 +172,474 @@ impl WebsocketClient {
     /// Create a new WebSocket client with the given configuration.
     pub fn new(config: WsConfig) -> Self {
         let (event_tx, _) = broadcast::channel(1024);
+        let api_permits = config
+            .max_in_flight_api_requests
+            .map(|n| Arc::new(Semaphore::new(n)));
         Self {
             config,
             store: Arc::new(RwLock::new(WsStore::new())),
             event_tx,
             pending_requests: Arc::new(Mutex::new(PendingRequests::new())),
             write_txs: Arc::new(RwLock::new(WriteChannels::default())),
+            supervisor: Supervisor::new(),
+            api_permits,
+            metrics: WsMetrics::new(),
+            shut_down: Arc::default(),
         }
     }
 
+    /// Returns a reference to the WebSocket configuration.
+    pub fn config(&self) -> &WsConfig {
+        &self.config
+    }
+
+    /// Per-operation WS API latency statistics, computed from the local
+    /// round trip and the `inTime`/`outTime` gateway timestamps.
+    pub fn ws_metrics(&self) -> HashMap<String, OpLatency> {
+        self.metrics.snapshot()
+    }
+
     /// Get a broadcast receiver for all WebSocket events.
-    pub fn event_receiver(&self) -> broadcast::Receiver<WsMessage> {
+    pub fn event_receiver(&self) -> broadcast::Receiver<Arc<WsMessage>> {
         self.event_tx.subscribe()
     }
 
     /// Subscribe to one or more channels.
     ///
     /// Automatically connects if needed and routes to the correct connection.
+    /// Subscriptions are reference-counted across all clones of the client:
+    /// a subscribe frame is only sent for topics that are not already
+    /// subscribed, and each call should be balanced by an [`unsubscribe`](Self::unsubscribe).
     pub async fn subscribe(
         &self,
         args: Vec<WsSubscriptionArg>,
-    ) -> OkxResult<broadcast::Receiver<WsMessage>> {
-        let (public_args, private_args, business_args) = partition_args(args);
+    ) -> OkxResult<broadcast::Receiver<Arc<WsMessage>>> {
+        let (public_args, private_args, business_args) =
+            partition_args(args, self.config.route_override);
+        let mut changed = false;
+        // References taken so far, released again if a later connection fails.
+        let mut acquired = Vec::new();
 
-        if !public_args.is_empty() {
-            self.ensure_connected(WsConnectionType::Public).await?;
-            self.send_subscribe(WsConnectionType::Public, public_args)
-                .await?;
+        for (conn_type, args) in [
+            (WsConnectionType::Public, public_args),
+            (WsConnectionType::Private, private_args),
+            (WsConnectionType::Business, business_args),
+        ] {
+            if args.is_empty() {
+                continue;
+            }
+            let new_topics = {
+                let mut store = self.store.write().await;
+                store.get_or_create(conn_type).acquire_topics(args.clone())
+            };
+            acquired.push((conn_type, args));
+            if new_topics.is_empty() {
+                continue;
+            }
+            let result = match self.ensure_connected(conn_type).await {
+                Ok(()) => self.send_subscribe(conn_type, new_topics).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                self.roll_back_subscribe(acquired).await;
+                return Err(e);
+            }
+            changed = true;
         }
-        if !private_args.is_empty() {
-            self.ensure_connected(WsConnectionType::Private).await?;
-            self.send_subscribe(WsConnectionType::Private, private_args)
-                .await?;
+
+        if changed {
+            self.save_subscriptions().await;
         }
-        if !business_args.is_empty() {
-            self.ensure_connected(WsConnectionType::Business).await?;
-            self.send_subscribe(WsConnectionType::Business, business_args)
-                .await?;
+        Ok(self.event_tx.subscribe())
+    }
+
+    /// Release the references a failed [`subscribe`](Self::subscribe) took,
+    /// unsubscribing topics that no other caller holds. The last entry is
+    /// the connection that failed, so nothing is sent for it.
+    async fn roll_back_subscribe(&self, acquired: Vec<(WsConnectionType, Vec<WsSubscriptionArg>)>) {
+        let failed = acquired.len().saturating_sub(1);
+        for (i, (conn_type, args)) in acquired.into_iter().enumerate() {
+            let released = {
+                let mut store = self.store.write().await;
+                store.get_or_create(conn_type).release_topics(args)
+            };
+            if i < failed && !released.is_empty() {
+                let _ = self.send_unsubscribe(conn_type, released).await;
+            }
         }
+        self.save_subscriptions().await;
+    }
 
-        Ok(self.event_tx.subscribe())
+    /// Subscribe to a single channel and receive its pushes as `T`.
+    pub async fn subscribe_typed<T: DeserializeOwned>(
+        &self,
+        arg: WsSubscriptionArg,
+    ) -> OkxResult<TypedSubscription<T>> {
+        // Take the receiver before subscribing so no early push is missed.
+        let rx = self.event_tx.subscribe();
+        self.subscribe(vec![arg.clone()]).await?;
+        Ok(TypedSubscription::new(self.clone(), arg, rx))
+    }
+
+    /// Subscribe to instrument changes of one instrument type.
+    ///
+    /// The first push is the full list; later pushes only carry the
+    /// instruments that changed.
+    pub async fn subscribe_instruments(
+        &self,
+        inst_type: InstrumentType,
+    ) -> OkxResult<TypedSubscription<Instrument>> {
+        self.subscribe_typed(WsSubscriptionArg::instruments(inst_type))
+            .await
+    }
+
+    /// Subscribe to tick-by-tick best bid and ask updates of an instrument.
+    pub async fn subscribe_bbo(&self, inst_id: &str) -> OkxResult<TypedSubscription<BboUpdate>> {
+        self.subscribe_typed(WsSubscriptionArg::bbo_tbt(inst_id))
+            .await
+    }
+
+    /// Subscribe to five-level order book snapshots of an instrument.
+    pub async fn subscribe_books5(
+        &self,
+        inst_id: &str,
+    ) -> OkxResult<TypedSubscription<DepthSnapshot>> {
+        self.subscribe_typed(WsSubscriptionArg::books5(inst_id))
+            .await
+    }
+
+    /// Subscribe to public trades of a spread.
+    pub async fn subscribe_sprd_public_trades(
+        &self,
+        sprd_id: &str,
+    ) -> OkxResult<TypedSubscription<SpreadPublicTrade>> {
+        self.subscribe_typed(WsSubscriptionArg::sprd_public_trades(sprd_id))
+            .await
+    }
+
+    /// Subscribe to tick-by-tick best bid and ask updates of a spread.
+    pub async fn subscribe_sprd_bbo(
+        &self,
+        sprd_id: &str,
+    ) -> OkxResult<TypedSubscription<SpreadOrderBook>> {
+        self.subscribe_typed(WsSubscriptionArg::sprd_bbo_tbt(sprd_id))
+            .await
+    }
+
+    /// Subscribe to five-level order book snapshots of a spread.
+    pub async fn subscribe_sprd_books5(
+        &self,
+        sprd_id: &str,
+    ) -> OkxResult<TypedSubscription<SpreadOrderBook>> {
+        self.subscribe_typed(WsSubscriptionArg::sprd_books5(sprd_id))
+            .await
+    }
+
+    /// Subscribe to the ticker of a spread.
+    pub async fn subscribe_sprd_tickers(
+        &self,
+        sprd_id: &str,
+    ) -> OkxResult<TypedSubscription<SpreadTicker>> {
+        self.subscribe_typed(WsSubscriptionArg::sprd_tickers(sprd_id))
+            .await
+    }
+
+    /// Subscribe to trade candles of an instrument.
+    pub async fn subscribe_candles(
+        &self,
+        inst_id: &str,
+        bar: Bar,
+    ) -> OkxResult<TypedSubscription<Ohlcv>> {
+        self.subscribe_typed(WsSubscriptionArg::candle(inst_id, bar))
+            .await
+    }
+
+    /// Subscribe to index price candles of an index, e.g. `BTC-USDT`.
+    pub async fn subscribe_index_candles(
+        &self,
+        inst_id: &str,
+        bar: Bar,
+    ) -> OkxResult<TypedSubscription<Ohlcv>> {
+        self.subscribe_typed(WsSubscriptionArg::index_candle(inst_id, bar))
+            .await
+    }
+
+    /// Subscribe to mark price candles of an instrument.
+    pub async fn subscribe_mark_price_candles(
+        &self,
+        inst_id: &str,
+        bar: Bar,
+    ) -> OkxResult<TypedSubscription<Ohlcv>> {
+        self.subscribe_typed(WsSubscriptionArg::mark_price_candle(inst_id, bar))
+            .await
+    }
+
+    /// Subscribe to the buy and sell limit prices of an instrument.
+    pub async fn subscribe_price_limit(
+        &self,
+        inst_id: &str,
+    ) -> OkxResult<TypedSubscription<PriceLimit>> {
+        self.subscribe_typed(WsSubscriptionArg::price_limit(inst_id))
+            .await
+    }
+
+    /// Subscribe to estimated delivery or exercise prices of an instrument
+    /// family. See [`WsSubscriptionArg::estimated_price`].
+    pub async fn subscribe_estimated_price(
+        &self,
+        inst_type: &str,
+        inst_family: &str,
+    ) -> OkxResult<TypedSubscription<EstimatedPrice>> {
+        self.subscribe_typed(WsSubscriptionArg::estimated_price(inst_type, inst_family))
+            .await
+    }
+
+    /// Subscribe to deposit updates, optionally for one currency.
+    pub async fn subscribe_deposit_info(
+        &self,
+        ccy: Option<&str>,
+    ) -> OkxResult<TypedSubscription<DepositInfo>> {
+        self.subscribe_typed(WsSubscriptionArg::deposit_info(ccy))
+            .await
+    }
+
+    /// Subscribe to withdrawal updates, optionally for one currency.
+    pub async fn subscribe_withdrawal_info(
+        &self,
+        ccy: Option<&str>,
+    ) -> OkxResult<TypedSubscription<WithdrawalInfo>> {
+        self.subscribe_typed(WsSubscriptionArg::withdrawal_info(ccy))
+            .await
+    }
+
+    /// Subscribe to grid bot updates. See [`WsSubscriptionArg::grid_orders`].
+    pub async fn subscribe_grid_orders(
+        &self,
+        grid_type: GridAlgoOrderType,
+        inst_type: &str,
+        inst_id: Option<&str>,
+        algo_id: Option<&str>,
+    ) -> OkxResult<TypedSubscription<GridOrder>> {
+        self.subscribe_typed(WsSubscriptionArg::grid_orders(
+            grid_type, inst_type, inst_id, algo_id,
+        ))
+        .await
+    }
+
+    /// Subscribe to positions of a contract grid bot.
+    pub async fn subscribe_grid_positions(
+        &self,
+        algo_id: &str,
+    ) -> OkxResult<TypedSubscription<GridPosition>> {
+        self.subscribe_typed(WsSubscriptionArg::grid_positions(algo_id))
+            .await
+    }
+
+    /// Subscribe to sub-orders placed by a grid bot.
+    pub async fn subscribe_grid_sub_orders(
+        &self,
+        algo_id: &str,
+    ) -> OkxResult<TypedSubscription<GridSubOrder>> {
+        self.subscribe_typed(WsSubscriptionArg::grid_sub_orders(algo_id))
+            .await
+    }
+
+    /// Subscribe to the Greeks of the account, optionally for one currency.
+    pub async fn subscribe_account_greeks(
+        &self,
+        ccy: Option<&str>,
+    ) -> OkxResult<TypedSubscription<AccountGreeks>> {
+        self.subscribe_typed(WsSubscriptionArg::account_greeks(ccy))
+            .await
     }
 
     /// Unsubscribe from one or more channels.
+    ///
+    /// The unsubscribe frame is only sent once the last subscriber of a
+    /// topic has unsubscribed.
     pub async fn unsubscribe(&self, args: Vec<WsSubscriptionArg>) -> OkxResult<()> {
-        let (public_args, private_args, business_args) = partition_args(args);
+        let (public_args, private_args, business_args) =
+            partition_args(args, self.config.route_override);
 
-        if !public_args.is_empty() {
-            self.send_unsubscribe(WsConnectionType::Public, public_args)
-                .await?;
+        for (conn_type, args) in [
+            (WsConnectionType::Public, public_args),
+            (WsConnectionType::Private, private_args),
+            (WsConnectionType::Business, business_args),
+        ] {
+            if args.is_empty() {
+                continue;
+            }
+            let released = {
+                let mut store = self.store.write().await;
+                store.get_or_create(conn_type).release_topics(args)
+            };
+            if !released.is_empty() {
+                self.save_subscriptions().await;
+                self.send_unsubscribe(conn_type, released).await?;
+            }
         }
-        if !private_args.is_empty() {
-            self.send_unsubscribe(WsConnectionType::Private, private_args)
-                .await?;
+
+        Ok(())
+    }
+
+    /// Topics currently subscribed on any connection.
+    pub async fn subscriptions(&self) -> Vec<WsSubscriptionArg> {
+        let store = self.store.read().await;
+        [
+            WsConnectionType::Public,
+            WsConnectionType::Private,
+            WsConnectionType::Business,
+        ]
+        .into_iter()
+        .filter_map(|conn_type| store.get(conn_type))
+        .flat_map(|conn| conn.topic_refs.keys().cloned())
+        .collect()
+    }
+
+    /// Subscribe to the topics saved in [`WsConfig::state_storage`] by a
+    /// previous process, returning them.
+    ///
+    /// Each restored topic holds one subscription reference, released with
+    /// [`unsubscribe`](Self::unsubscribe).
+    pub async fn restore_subscriptions(&self) -> OkxResult<Vec<WsSubscriptionArg>> {
+        let Some(storage) = &self.config.state_storage else {
+            return Err(OkxError::Config("no state storage configured".into()));
+        };
+        let topics: Vec<WsSubscriptionArg> =
+            persist::load_json(storage.as_ref(), persist::SUBSCRIPTIONS_KEY)?.unwrap_or_default();
+        if !topics.is_empty() {
+            self.subscribe(topics.clone()).await?;
         }
-        if !business_args.is_empty() {
-            self.send_unsubscribe(WsConnectionType::Business, business_args)
-                .await?;
+        Ok(topics)
+    }
+
+    /// Save the subscribed topics to [`WsConfig::state_storage`], if set.
+    /// The save runs inline on this task; see [`StateStorage`](crate::persist::StateStorage).
+    async fn save_subscriptions(&self) {
+        let Some(storage) = &self.config.state_storage else {
+            return;
+        };
+        let topics = self.subscriptions().await;
+        if let Err(e) = persist::save_json(storage.as_ref(), persist::SUBSCRIPTIONS_KEY, &topics) {
+            warn!("Failed to save subscriptions: {e}");
         }
+    }
 
-        Ok(())
+    /// Connect and, for private and business connections, wait until the
+    /// login is confirmed.
+    ///
+    /// Use this to authenticate ahead of time rather than on the first
+    /// subscription or WS API request. Waits at most [`WsConfig::api_timeout`]
+    /// for the login response.
+    pub async fn connect_and_login(&self, conn_type: WsConnectionType) -> OkxResult<()> {
+        if conn_type == WsConnectionType::Public {
+            return self.ensure_connected(conn_type).await;
+        }
+        if self.config.client_config.credentials.is_none() {
+            return Err(OkxError::Auth(format!(
+                "Credentials required to log in to the {conn_type} connection"
+            )));
+        }
+
+        self.ensure_connected(conn_type).await?;
+        self.wait_authenticated(conn_type, Instant::now() + self.config.api_timeout)
+            .await
+    }
+
+    /// Wait until the connection's login is confirmed or `deadline` passes.
+    async fn wait_authenticated(
+        &self,
+        conn_type: WsConnectionType,
+        deadline: Instant,
+    ) -> OkxResult<()> {
+        let mut events = self.event_tx.subscribe();
+        loop {
+            let authenticated = {
+                let store = self.store.read().await;
+                store.get(conn_type).is_some_and(|c| c.is_authenticated)
+            };
+            if authenticated {
+                return Ok(());
+            }
+            match tokio::time::timeout_at(deadline, events.recv()).await {
+                Ok(Ok(msg)) if matches!(*msg, WsMessage::Authenticated(ct) if ct == conn_type) => {
+                    return Ok(());
+                }
+                Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) => {}
+                Ok(Err(broadcast::error::RecvError::Closed)) => {
+                    return Err(OkxError::Ws("event channel closed".into()));
+                }
+                Err(_) => {
+                    return Err(OkxError::Auth(format!(
+                        "WS {conn_type} login not confirmed in time"
+                    )));
+                }
+            }
+        }
     }
 
     /// Send a WS API request and wait for the response.
+    ///
+    /// WS API operations change orders, so like non-GET REST requests they
+    /// are resent with backoff only after rate limit and maintenance
+    /// rejections, which OKX returns before executing, and only if the
+    /// configured [`RetryPolicy`](crate::retry::RetryPolicy) allows it.
+    /// Requests OKX rejects because the session is not logged in are resent
+    /// after the re-login. Each attempt waits at most [`WsConfig::api_timeout`].
     pub async fn send_api_request(
         &self,
         op: &str,
         args: Vec<serde_json::Value>,
     ) -> OkxResult<crate::types::ws::events::WsApiResponse> {
+        self.send_api_request_with_timeout(op, args, self.config.api_timeout)
+            .await
+    }
+
+    /// Send a WS API request with a per-attempt timeout overriding
+    /// [`WsConfig::api_timeout`].
+    ///
+    /// The timeout includes any time spent waiting for an in-flight slot when
+    /// [`WsConfig::max_in_flight_api_requests`] is set.
+    pub async fn send_api_request_with_timeout(
+        &self,
+        op: &str,
+        args: Vec<serde_json::Value>,
+        timeout: Duration,
+    ) -> OkxResult<crate::types::ws::events::WsApiResponse> {
+        let policy = &self.config.client_config.retry_policy;
+        let mut attempt = 0;
+        let mut relogins = 0;
+        loop {
+            match self.send_api_request_once(op, args.clone(), timeout).await {
+                // OKX rejected the session without executing the request, so
+                // it is resent once the re-login it triggered completes.
+                Err(OkxError::Api { code, msg })
+                    if is_session_error(&code)
+                        && relogins < MAX_RELOGIN_ATTEMPTS
+                        && self.config.client_config.credentials.is_some() =>
+                {
+                    warn!("WS API {op} session rejected ({code}: {msg}), resending after re-login");
+                    relogins += 1;
+                }
+                Err(e) if attempt < policy.max_retries && resendable(policy, &e) => {
+                    let delay = policy.backoff(attempt);
+                    warn!("WS API {op} failed ({e}), resending in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Send a single WS API request attempt and wait for the response.
+    async fn send_api_request_once(
+        &self,
+        op: &str,
+        args: Vec<serde_json::Value>,
+        timeout: Duration,
+    ) -> OkxResult<crate::types::ws::events::WsApiResponse> {
+        let deadline = Instant::now() + timeout;
         let conn_type = if op.starts_with("sprd-") {
             WsConnectionType::Business
         } else {
@@ -190,26 +647,67 @@ impl WebsocketClient {
         };
 
         self.ensure_connected(conn_type).await?;
+        if self.config.client_config.credentials.is_some() {
+            // Hold the request while a login or re-login is in progress.
+            self.wait_authenticated(conn_type, deadline).await?;
+        }
 
         let request = api::build_api_request(op, args);
         let json = serde_json::to_string(&request)?;
+        let timed_out = || OkxError::WsApiTimeout {
+            id: request.id.clone(),
+            operation: op.to_string(),
+        };
+
+        let _permit = match &self.api_permits {
+            Some(permits) => Some(
+                tokio::time::timeout_at(deadline, permits.clone().acquire_owned())
+                    .await
+                    .map_err(|_| timed_out())?
+                    .map_err(|_| OkxError::Ws("WS API request limiter closed".into()))?,
+            ),
+            None => None,
+        };
 
         let rx = {
             let mut pending = self.pending_requests.lock().await;
-            pending.register(request.id)
+            pending.register(request.id.clone(), op, conn_type)
         };
-        let write_txs = self.write_txs.read().await;
-        if let Some(tx) = write_txs.get(conn_type) {
-            tx.send(json)
-                .map_err(|_| OkxError::Ws("write channel closed".into()))?;
-        } else {
+        let sent_at = Instant::now();
+        let Some(tx) = self.writer(conn_type).await else {
+            self.pending_requests.lock().await.remove(&request.id);
             return Err(OkxError::Ws(format!("no {conn_type} connection")));
+        };
+        // A full queue under the blocking policy counts against the timeout.
+        let sent = tokio::time::timeout_at(deadline, tx.send(json))
+            .await
+            .unwrap_or_else(|_| Err(timed_out()));
+        if let Err(e) = sent {
+            self.pending_requests.lock().await.remove(&request.id);
+            return Err(e);
         }
 
-        let response = tokio::time::timeout(std::time::Duration::from_secs(10), rx)
-            .await
-            .map_err(|_| OkxError::Ws("WS API request timed out".into()))?
-            .map_err(|_| OkxError::Ws("WS API request cancelled".into()))?;
+        let response = match tokio::time::timeout_at(deadline, rx).await {
+            Ok(Ok(result)) => {
+                let response = result?;
+                let sample = LatencySample::from_response(sent_at.elapsed(), &response);
+                debug!(
+                    op,
+                    id = %response.id,
+                    round_trip_us = sample.round_trip.as_micros() as u64,
+                    exchange_us = sample.exchange.map(|d| d.as_micros() as u64),
+                    network_us = sample.network.map(|d| d.as_micros() as u64),
+                    "WS API latency"
+                );
+                self.metrics.record(op, sample);
+                response
+            }
+            Ok(Err(_)) => return Err(OkxError::Ws("WS API request cancelled".into())),
+            Err(_) => {
+                self.pending_requests.lock().await.remove(&request.id);
+                return Err(timed_out());
+            }
+        };
 
         if response.code == "0" {
             Ok(response)
@@ -221,8 +719,39 @@ impl WebsocketClient {
         }
     }
 
+    /// Unsubscribe and subscribe `arg` again to restart its data, without
+    /// touching the subscription bookkeeping. Returns whether both frames
+    /// were queued.
+    async fn resubscribe_raw(&self, conn_type: WsConnectionType, arg: WsSubscriptionArg) -> bool {
+        let Some(tx) = self.writer(conn_type).await else {
+            return false;
+        };
+        for req in [
+            WsSubRequest::unsubscribe(vec![arg.clone()]),
+            WsSubRequest::subscribe(vec![arg]),
+        ] {
+            let sent = match serde_json::to_string(&req) {
+                Ok(json) => tx.send(json).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = sent {
+                warn!("WS {conn_type} resubscribe failed: {e}");
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Outbound queue of a connection, if it is open.
+    async fn writer(&self, conn_type: WsConnectionType) -> Option<WriteSender> {
+        self.write_txs.read().await.get(conn_type).cloned()
+    }
+
     /// Ensure a connection of the given type is established.
     async fn ensure_connected(&self, conn_type: WsConnectionType) -> OkxResult<()> {
+        if self.is_shut_down() {
+            return Err(OkxError::Ws("client has been shut down".into()));
+        }
         {
             let store = self.store.read().await;
             if let Some(conn) = store.get(conn_type) {
@@ -248,28 +777,29 @@ impl WebsocketClient {
         conn_type: WsConnectionType,
         args: Vec<WsSubscriptionArg>,
     ) -> OkxResult<()> {
+        // Topics that need a login are queued until the connection is
+        // authenticated; the login handler subscribes them.
+        let mut args = args;
         if conn_type != WsConnectionType::Public {
-            let store = self.store.read().await;
-            if let Some(conn) = store.get(conn_type) {
+            let mut store = self.store.write().await;
+            if let Some(conn) = store.get_mut(conn_type) {
                 if !conn.is_authenticated {
-                    drop(store);
-                    let mut store = self.store.write().await;
-                    let conn = store.get_or_create(conn_type);
-                    for arg in args {
-                        conn.pending_topics.insert(arg);
-                    }
-                    return Ok(());
+                    let (queued, ready): (Vec<_>, Vec<_>) =
+                        args.into_iter().partition(|arg| arg.requires_auth());
+                    conn.pending_topics.extend(queued);
+                    args = ready;
                 }
             }
         }
+        if args.is_empty() {
+            return Ok(());
+        }
 
         let req = WsSubRequest::subscribe(args);
         let json = serde_json::to_string(&req)?;
 
-        let write_txs = self.write_txs.read().await;
-        if let Some(tx) = write_txs.get(conn_type) {
-            tx.send(json)
-                .map_err(|_| OkxError::Ws("write channel closed".into()))?;
+        if let Some(tx) = self.writer(conn_type).await {
+            tx.send(json).await?;
         }
 
         let mut store = self.store.write().await;
@@ -282,18 +812,28 @@ impl WebsocketClient {
     }
 
     /// Send an unsubscribe message on a specific connection.
+    ///
+    /// Topics still waiting for a login are dropped from the queue instead,
+    /// so the login handler does not subscribe them again.
     async fn send_unsubscribe(
         &self,
         conn_type: WsConnectionType,
-        args: Vec<WsSubscriptionArg>,
+        mut args: Vec<WsSubscriptionArg>,
     ) -> OkxResult<()> {
+        {
+            let mut store = self.store.write().await;
+            let conn = store.get_or_create(conn_type);
+            args.retain(|arg| !conn.pending_topics.remove(arg));
+        }
+        if args.is_empty() {
+            return Ok(());
+        }
+
         let req = WsSubRequest::unsubscribe(args);
         let json = serde_json::to_string(&req)?;
 
-        let write_txs = self.write_txs.read().await;
-        if let Some(tx) = write_txs.get(conn_type) {
-            tx.send(json)
-                .map_err(|_| OkxError::Ws("write channel closed".into()))?;
+        if let Some(tx) = self.writer(conn_type).await {
+            tx.send(json).await?;
         }
 
         let mut store = self.store.write().await;
@@ -312,177 +852,461 @@ impl WebsocketClient {
     /// `tokio::spawn` task (e.g. the auto-reconnect path).
     fn connect_inner(self, conn_type: WsConnectionType) -> BoxFuture<'static, OkxResult<()>> {
         Box::pin(async move {
-        let url = self.config.ws_url(conn_type).to_owned();
-        info!("Connecting WS {conn_type} to {url}");
+            let url = self.config.ws_url(conn_type);
+            info!("Connecting WS {conn_type} to {url}");
 
-        {
-            let mut store = self.store.write().await;
-            store.get_or_create(conn_type).state = ConnectionState::Connecting;
-        }
+            let last_message_at = {
+                let mut store = self.store.write().await;
+                let conn = store.get_or_create(conn_type);
+                conn.state = ConnectionState::Connecting;
+                conn.last_message_at.clone()
+            };
 
-        let ws = connection::connect(&url).await?;
-        let (write_tx, mut msg_rx) = connection::spawn_io_tasks(ws, conn_type);
+            let headers = self.config.client_config.custom_headers()?;
+            let ws = connection::connect(&url, self.config.tls(conn_type), headers).await?;
+            last_message_at.touch();
+            let on_failure = self.failure_handler(conn_type);
+            let (write_tx, mut msg_rx) = connection::spawn_io_tasks(
+                ws,
+                conn_type,
+                &self.supervisor,
+                on_failure.clone(),
+                self.config.recorder.clone(),
+                self.config.write_buffer,
+                self.config.write_overflow,
+            );
 
-        let (hb_stop_tx, hb_stop_rx) = tokio::sync::oneshot::channel::<()>();
-        let hb_tx = write_tx.clone();
-        let ping_interval = self.config.ping_interval;
-        tokio::spawn(async move {
-            heartbeat::heartbeat_loop(hb_tx, ping_interval, hb_stop_rx).await;
-        });
-
-        {
-            let mut write_txs = self.write_txs.write().await;
-            write_txs.set(conn_type, write_tx.clone());
-        }
-
-        let event_tx = self.event_tx.clone();
-        let client_for_reconnect = self.clone();
-        let store = self.store.clone();
-        let pending_requests = self.pending_requests.clone();
-        let write_txs = self.write_txs.clone();
-
-        tokio::spawn(async move {
-            while let Some(msg) = msg_rx.recv().await {
-                match &msg {
-                    WsMessage::Event(evt) if evt.event == "login" => {
-                        if evt.code.as_deref() == Some("0") {
-                            info!("WS {conn_type} authenticated");
-                            let mut s = store.write().await;
-                            let conn = s.get_or_create(conn_type);
-                            conn.is_authenticated = true;
-                            conn.state = ConnectionState::Authenticated;
-
-                            let pending: Vec<_> = conn.pending_topics.drain().collect();
-                            if !pending.is_empty() {
-                                let req = WsSubRequest::subscribe(pending);
-                                if let Ok(json) = serde_json::to_string(&req) {
-                                    let wt = write_txs.read().await;
-                                    if let Some(tx) = wt.get(conn_type) {
-                                        let _ = tx.send(json);
-                                    }
-                                }
-                                let conn = s.get_or_create(conn_type);
-                                for topic in req.args {
-                                    conn.subscribed_topics.insert(topic);
-                                }
-                            }
-                        } else {
-                            error!("WS {conn_type} login failed: {:?}", evt.msg);
-                        }
-                    }
-                    WsMessage::ApiResponse(resp) => {
-                        let mut pending = pending_requests.lock().await;
-                        pending.resolve(&resp.id, resp.clone());
-                    }
-                    WsMessage::Disconnected(_) => {
-                        warn!("WS {conn_type} disconnected");
-                        {
-                            let mut s = store.write().await;
-                            let conn = s.get_or_create(conn_type);
-                            conn.state = ConnectionState::Disconnected;
-                            conn.is_authenticated = false;
-                        }
+            let (hb_stop_tx, hb_stop_rx) = tokio::sync::oneshot::channel::<()>();
+            let hb_tx = write_tx.clone();
+            let ping_interval = self.config.ping_interval;
+            let heartbeat = self.config.heartbeat;
+            let idle_timeout = self.config.idle_timeout;
+            let hb_last_message_at = last_message_at.clone();
+            self.supervisor.spawn(
+                conn_type,
+                WsTaskKind::Heartbeat,
+                heartbeat::heartbeat_loop(
+                    hb_tx,
+                    ping_interval,
+                    heartbeat,
+                    hb_last_message_at,
+                    idle_timeout,
+                    hb_stop_rx,
+                ),
+                on_failure.clone(),
+            );
 
-                        {
-                            let mut pending = pending_requests.lock().await;
-                            pending.reject_all();
-                        }
+            {
+                let mut write_txs = self.write_txs.write().await;
+                write_txs.set(conn_type, write_tx.clone());
+            }
 
-                        {
-                            let mut wt = write_txs.write().await;
-                            wt.remove(conn_type);
-                        }
+            let event_tx = self.event_tx.clone();
+            let client = self.clone();
+            let store = self.store.clone();
+            let pending_requests = self.pending_requests.clone();
+            let write_txs = self.write_txs.clone();
 
-                        if client_for_reconnect.config.auto_reconnect {
-                            let delay = client_for_reconnect.config.reconnect_delay;
-                            let client = client_for_reconnect.clone();
-                            tokio::spawn(async move {
-                                info!("WS {conn_type} reconnecting in {delay:?}");
-                                tokio::time::sleep(delay).await;
-
-                                // For authenticated connections, move subscribed topics into
-                                // pending so the login handler resubscribes them after auth.
-                                // For public connections, capture them for direct resubscription.
-                                let public_topics =
-                                    if conn_type == WsConnectionType::Public {
-                                        let s = client.store.read().await;
-                                        s.get(conn_type)
-                                            .map(|c| {
-                                                c.subscribed_topics
-                                                    .iter()
-                                                    .cloned()
-                                                    .collect::<Vec<_>>()
-                                            })
-                                            .unwrap_or_default()
-                                    } else {
-                                        let mut s = client.store.write().await;
+            self.supervisor.spawn(
+                conn_type,
+                WsTaskKind::Dispatch,
+                async move {
+                    let mut sequences = SequenceTracker::new(conn_type);
+                    while let Some(msg) = msg_rx.recv().await {
+                        last_message_at.touch();
+                        let gap = match &msg {
+                            WsMessage::Data(evt) => sequences.check(evt),
+                            _ => None,
+                        };
+                        match &msg {
+                            WsMessage::Event(evt) if evt.event == "login" => {
+                                if evt.code.as_deref() == Some("0") {
+                                    info!("WS {conn_type} authenticated");
+                                    // Update the store before announcing, so waiters
+                                    // woken by the broadcast see the new state.
+                                    let pending: Vec<_> = {
+                                        let mut s = store.write().await;
                                         let conn = s.get_or_create(conn_type);
-                                        let topics: Vec<_> =
-                                            conn.subscribed_topics.drain().collect();
-                                        for topic in &topics {
-                                            conn.pending_topics.insert(topic.clone());
-                                        }
-                                        Vec::new()
+                                        conn.is_authenticated = true;
+                                        conn.relogin_attempts = 0;
+                                        conn.state = ConnectionState::Authenticated;
+                                        let pending: Vec<_> = conn.pending_topics.drain().collect();
+                                        conn.subscribed_topics.extend(pending.iter().cloned());
+                                        pending
                                     };
+                                    let _ = event_tx
+                                        .send(Arc::new(WsMessage::Authenticated(conn_type)));
 
-                                // Keep a clone for resubscription since connect_inner
-                                // consumes `client`.
-                                let client_ref = client.clone();
-                                match client_ref.connect(conn_type).await {
-                                    Ok(()) => {
-                                        if !public_topics.is_empty() {
-                                            if let Err(e) = client_ref
-                                                .send_subscribe(conn_type, public_topics)
-                                                .await
-                                            {
-                                                error!(
-                                                    "WS {conn_type} resubscribe failed: {e}"
-                                                );
+                                    if !pending.is_empty() {
+                                        let req = WsSubRequest::subscribe(pending);
+                                        if let Ok(json) = serde_json::to_string(&req) {
+                                            let tx = write_txs.read().await.get(conn_type).cloned();
+                                            if let Some(tx) = tx {
+                                                if let Err(e) = tx.send(json).await {
+                                                    warn!(
+                                                        "WS {conn_type} resubscribe not sent: {e}"
+                                                    );
+                                                }
                                             }
                                         }
                                     }
-                                    Err(e) => {
-                                        error!("WS {conn_type} reconnect failed: {e}");
-                                    }
+                                } else {
+                                    error!("WS {conn_type} login failed: {:?}", evt.msg);
                                 }
-                            });
+                            }
+                            WsMessage::Event(evt)
+                                if evt.event == "error"
+                                    && evt.code.as_deref().is_some_and(is_session_error) =>
+                            {
+                                warn!("WS {conn_type} session rejected: {:?}", evt.msg);
+                                client.schedule_relogin(conn_type).await;
+                            }
+                            WsMessage::ApiResponse(resp) => {
+                                if is_session_error(&resp.code) {
+                                    warn!("WS {conn_type} session rejected: {}", resp.msg);
+                                    client.schedule_relogin(conn_type).await;
+                                }
+                                let mut pending = pending_requests.lock().await;
+                                pending.resolve(&resp.id, resp.clone());
+                            }
+                            WsMessage::ChannelConnCount(count) => {
+                                if count.conn_count * 5 >= constants::WS_CHANNEL_CONN_LIMIT * 4 {
+                                    warn!(
+                                        "WS {conn_type} {} has {} of {} allowed connections",
+                                        count.channel,
+                                        count.conn_count,
+                                        constants::WS_CHANNEL_CONN_LIMIT
+                                    );
+                                }
+                                let mut s = store.write().await;
+                                s.get_or_create(conn_type)
+                                    .channel_conn_counts
+                                    .insert(count.channel.clone(), count.conn_count);
+                            }
+                            WsMessage::ChannelConnCountError(count) => {
+                                error!(
+                                    "WS {conn_type} {} refused: {} connections already subscribed",
+                                    count.channel, count.conn_count
+                                );
+                                let mut s = store.write().await;
+                                s.get_or_create(conn_type)
+                                    .channel_conn_counts
+                                    .insert(count.channel.clone(), count.conn_count);
+                            }
+                            WsMessage::Notice(notice) => {
+                                warn!("WS {conn_type} notice {}: {}", notice.code, notice.msg);
+                            }
+                            WsMessage::Disconnected(info) => {
+                                warn!("WS {conn_type} disconnected");
+                                client.clone().handle_disconnect(info.clone()).await;
+                                break;
+                            }
+                            _ => {}
                         }
 
-                        break;
+                        let _ = event_tx.send(Arc::new(msg));
+                        if let Some(gap) = gap {
+                            warn!(
+                                "WS {conn_type} {} gap (expected seqId {}, got {}), resubscribing",
+                                gap.arg.channel, gap.expected, gap.received
+                            );
+                            if !client.resubscribe_raw(conn_type, gap.arg.clone()).await {
+                                // No snapshot is coming; check the next pushes again.
+                                sequences.reset(&gap.arg);
+                            }
+                            let _ = event_tx.send(Arc::new(WsMessage::DataGap(gap)));
+                        }
                     }
-                    _ => {}
-                }
 
-                let _ = event_tx.send(msg);
+                    let _ = hb_stop_tx.send(());
+                    Ok(())
+                },
+                on_failure,
+            );
+
+            let attempt = {
+                let mut s = self.store.write().await;
+                let conn = s.get_or_create(conn_type);
+                conn.state = ConnectionState::Connected;
+                std::mem::take(&mut conn.reconnect_attempts)
+            };
+
+            if conn_type != WsConnectionType::Public {
+                self.send_login(conn_type).await?;
             }
 
-            let _ = hb_stop_tx.send(());
-        });
+            let _ = self
+                .event_tx
+                .send(Arc::new(WsMessage::Connected(ConnectInfo {
+                    conn_type,
+                    attempt,
+                })));
+
+            info!("WS {conn_type} connected");
+            Ok(())
+        })
+    }
+
+    /// Send a login request on a connection, if credentials are configured.
+    async fn send_login(&self, conn_type: WsConnectionType) -> OkxResult<()> {
+        let Some(creds) = self.config.client_config.credentials.as_ref() else {
+            return Ok(());
+        };
+        let algorithm = self.config.client_config.effective_signing_algorithm();
+        let login_req = auth::build_login_request(creds, algorithm)?;
+        let json = serde_json::to_string(&login_req)?;
+        if let Some(tx) = self.writer(conn_type).await {
+            tx.send(json).await?;
+        }
+        Ok(())
+    }
 
+    /// Log in again after OKX rejected the session.
+    ///
+    /// Auth-requiring topics are moved back to pending so the login handler
+    /// resubscribes them, and WS API requests wait for the new login. Gives
+    /// up after [`MAX_RELOGIN_ATTEMPTS`] consecutive failures.
+    async fn schedule_relogin(&self, conn_type: WsConnectionType) {
+        if conn_type == WsConnectionType::Public || self.config.client_config.credentials.is_none()
         {
-            let mut s = self.store.write().await;
-            s.get_or_create(conn_type).state = ConnectionState::Connected;
+            return;
         }
 
-        if conn_type != WsConnectionType::Public {
-            if let Some(creds) = self.config.client_config.credentials.clone() {
-                let login_req = auth::build_login_request(&creds)?;
-                let json = serde_json::to_string(&login_req)?;
-                let write_txs = self.write_txs.read().await;
-                if let Some(tx) = write_txs.get(conn_type) {
-                    tx.send(json)
-                        .map_err(|_| OkxError::Ws("write channel closed".into()))?;
+        let attempt = {
+            let mut s = self.store.write().await;
+            let conn = s.get_or_create(conn_type);
+            if conn.relogin_pending {
+                return;
+            }
+            match conn.begin_relogin(MAX_RELOGIN_ATTEMPTS) {
+                Some(attempt) => attempt,
+                None => {
+                    error!(
+                        "WS {conn_type} re-login failed {MAX_RELOGIN_ATTEMPTS} times, giving up"
+                    );
+                    return;
                 }
             }
-        }
+        };
 
-        let _ = self.event_tx.send(WsMessage::Connected(conn_type));
+        let delay = self.config.reconnect_delay * attempt;
+        let client = self.clone();
+        self.supervisor.spawn(
+            conn_type,
+            WsTaskKind::Relogin,
+            async move {
+                info!("WS {conn_type} logging in again in {delay:?}");
+                tokio::time::sleep(delay).await;
+                {
+                    let mut s = client.store.write().await;
+                    s.get_or_create(conn_type).relogin_pending = false;
+                }
+                client
+                    .send_login(conn_type)
+                    .await
+                    .map_err(|e| format!("re-login failed: {e}"))
+            },
+            self.failure_handler(conn_type),
+        );
+    }
 
-        info!("WS {conn_type} connected");
-        Ok(())
+    /// Build the handler invoked when a supervised task of a connection fails.
+    ///
+    /// Emits [`WsMessage::InternalError`], then either tears the connection
+    /// down so the reconnect policy applies or, if the failed task was a
+    /// reconnect attempt, schedules the next attempt.
+    fn failure_handler(&self, conn_type: WsConnectionType) -> FailureHandler {
+        let client = self.clone();
+        Arc::new(move |task, message| {
+            let mut disconnect = DisconnectInfo::new(conn_type);
+            disconnect.error = Some(message.clone());
+            let _ = client.event_tx.send(Arc::new(WsMessage::InternalError {
+                conn_type,
+                task,
+                message,
+            }));
+            if task == WsTaskKind::Reconnect {
+                let client = client.clone();
+                tokio::spawn(async move { client.schedule_reconnect(conn_type).await });
+            } else {
+                tokio::spawn(client.clone().handle_disconnect(disconnect));
+            }
+        })
+    }
+
+    /// Tear down a dead connection and schedule a reconnect if enabled.
+    fn handle_disconnect(self, mut info: DisconnectInfo) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            let conn_type = info.conn_type;
+            {
+                let mut s = self.store.write().await;
+                let conn = s.get_or_create(conn_type);
+                if conn.state == ConnectionState::Reconnecting {
+                    return;
+                }
+                let reconnect = self.config.auto_reconnect && !self.is_shut_down();
+                conn.state = if reconnect {
+                    ConnectionState::Reconnecting
+                } else {
+                    ConnectionState::Disconnected
+                };
+                conn.is_authenticated = false;
+                conn.relogin_attempts = 0;
+                conn.relogin_pending = false;
+                info.will_reconnect = reconnect;
+                info.attempt = if reconnect {
+                    conn.reconnect_attempts + 1
+                } else {
+                    0
+                };
+            }
+            let _ = self.event_tx.send(Arc::new(WsMessage::Disconnected(info)));
+
+            self.supervisor.abort(
+                conn_type,
+                &[
+                    WsTaskKind::Read,
+                    WsTaskKind::Write,
+                    WsTaskKind::Heartbeat,
+                    WsTaskKind::Relogin,
+                ],
+            );
+
+            {
+                let mut pending = self.pending_requests.lock().await;
+                pending.reject_connection(conn_type);
+            }
+
+            {
+                let mut wt = self.write_txs.write().await;
+                wt.remove(conn_type);
+            }
+
+            self.schedule_reconnect(conn_type).await;
         })
     }
 
+    /// Spawn the next reconnect attempt of a connection.
+    ///
+    /// Attempts back off linearly from [`WsConfig::reconnect_delay`] up to
+    /// [`MAX_RECONNECT_BACKOFF`] times that delay, and continue until one
+    /// succeeds, auto-reconnect is off, or the client is shut down.
+    async fn schedule_reconnect(&self, conn_type: WsConnectionType) {
+        if !self.config.auto_reconnect || self.is_shut_down() {
+            return;
+        }
+        let attempt = {
+            let mut s = self.store.write().await;
+            let conn = s.get_or_create(conn_type);
+            conn.state = ConnectionState::Reconnecting;
+            conn.reconnect_attempts += 1;
+            conn.reconnect_attempts
+        };
+
+        let delay = self.config.reconnect_delay * attempt.min(MAX_RECONNECT_BACKOFF);
+        let client = self.clone();
+        self.supervisor.spawn(
+            conn_type,
+            WsTaskKind::Reconnect,
+            async move {
+                info!("WS {conn_type} reconnecting in {delay:?}");
+                tokio::time::sleep(delay).await;
+
+                // Replay every topic on the new connection. `send_subscribe`
+                // sends those that need no login right away and queues the rest
+                // until the login handler confirms the session. The topics stay
+                // recorded until the connection is up, so a failed attempt does
+                // not lose them.
+                let topics: Vec<_> = {
+                    let s = client.store.read().await;
+                    s.get(conn_type)
+                        .map(|c| c.subscribed_topics.iter().cloned().collect())
+                        .unwrap_or_default()
+                };
+
+                client
+                    .connect(conn_type)
+                    .await
+                    .map_err(|e| format!("reconnect failed: {e}"))?;
+                if !topics.is_empty() {
+                    {
+                        let mut s = client.store.write().await;
+                        let conn = s.get_or_create(conn_type);
+                        for topic in &topics {
+                            conn.subscribed_topics.remove(topic);
+                        }
+                    }
+                    client
+                        .send_subscribe(conn_type, topics)
+                        .await
+                        .map_err(|e| format!("resubscribe failed: {e}"))?;
+                }
+                Ok(())
+            },
+            self.failure_handler(conn_type),
+        );
+    }
+
+    /// Report the state of all supervised background tasks.
+    pub fn health(&self) -> Vec<TaskHealth> {
+        self.supervisor.health()
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called on this client
+    /// or any of its clones.
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::Acquire)
+    }
+
+    /// Shut the client down and wait for its background tasks to exit.
+    ///
+    /// Auto-reconnect and re-login stop, pending WS API requests fail with
+    /// [`OkxError::WsDisconnected`], and each connection is closed with a
+    /// close frame. Tasks still running after [`WsConfig::api_timeout`] are
+    /// aborted. Afterwards subscriptions and WS API requests fail; the event
+    /// bus stays open for existing receivers.
+    pub async fn shutdown(&self) {
+        if self.shut_down.swap(true, Ordering::AcqRel) {
+            self.supervisor.wait_idle().await;
+            return;
+        }
+        info!("Shutting down WS client");
+
+        for conn_type in [
+            WsConnectionType::Public,
+            WsConnectionType::Private,
+            WsConnectionType::Business,
+        ] {
+            self.supervisor.abort(
+                conn_type,
+                &[
+                    WsTaskKind::Heartbeat,
+                    WsTaskKind::Relogin,
+                    WsTaskKind::Reconnect,
+                ],
+            );
+            self.pending_requests
+                .lock()
+                .await
+                .reject_connection(conn_type);
+        }
+
+        // Dropping the last senders lets each write loop send a close frame;
+        // the read loops end when the server acknowledges it.
+        self.close_all().await;
+
+        let idle = self.supervisor.wait_idle();
+        if tokio::time::timeout(self.config.api_timeout, idle)
+            .await
+            .is_err()
+        {
+            warn!("WS tasks did not stop in time, aborting them");
+            self.supervisor.abort_all();
+            self.supervisor.wait_idle().await;
+        }
+        info!("WS client shut down");
+    }
+
     /// Close all connections.
     pub async fn close_all(&self) {
         let mut write_txs = self.write_txs.write().await;
@@ -502,3 +1326,24 @@ impl WebsocketClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn shutdown_blocks_new_connections() {
+        let client = WebsocketClient::new(WsConfig::default());
+        client.shutdown().await;
+        assert!(client.clone().is_shut_down());
+
+        let err = client
+            .subscribe(vec![WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT")])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OkxError::Ws(_)));
+
+        // A second call returns once tasks are idle.
+        client.shutdown().await;
+    }
+}
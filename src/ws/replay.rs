@@ -0,0 +1,238 @@
+//! Recording of inbound WebSocket frames and replay onto the event bus.
+//!
+//! A [`WsRecorder`] set as [`WsConfig::recorder`](super::types::WsConfig::recorder)
+//! appends every text frame the client receives to a JSON Lines file. A
+//! [`WsReplayer`] reads such a file back and publishes the frames to a
+//! client's event bus at their original pace, faster, or all at once, so
+//! consumer logic can be run deterministically against a captured session.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::jsonl;
+use crate::time::now_ms;
+use crate::types::ws::events::{WsConnectionType, WsMessage};
+
+use super::connection::parse_ws_message;
+use super::WebsocketClient;
+
+/// One recorded inbound frame, stored as a line of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Receive time, Unix timestamp in milliseconds.
+    pub ts: u64,
+    /// Connection the frame arrived on.
+    pub conn: WsConnectionType,
+    /// Raw frame text.
+    pub text: String,
+}
+
+/// Appends inbound frames to a JSON Lines file.
+///
+/// Cheap to clone; clones write to the same file. Buffered output is flushed
+/// when the last clone is dropped or on [`flush`](Self::flush).
+#[derive(Clone)]
+pub struct WsRecorder {
+    path: PathBuf,
+    out: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl WsRecorder {
+    /// Create (or truncate) the recording file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> OkxResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)
+            .map_err(|e| OkxError::io(format!("cannot create {}", path.display()), e))?;
+        Ok(Self {
+            path,
+            out: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Path of the recording file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one frame received now on `conn`.
+    pub fn record(&self, conn: WsConnectionType, text: &str) {
+        self.write(&RecordedFrame {
+            ts: now_ms() as u64,
+            conn,
+            text: text.to_string(),
+        });
+    }
+
+    /// Write buffered frames to the file.
+    pub fn flush(&self) -> OkxResult<()> {
+        self.lock()
+            .flush()
+            .map_err(|e| OkxError::io("recording flush failed", e))
+    }
+
+    fn write(&self, frame: &RecordedFrame) {
+        let mut out = self.lock();
+        if let Err(e) = jsonl::write_line(&mut *out, frame) {
+            warn!("Failed to record WS frame to {}: {e}", self.path.display());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BufWriter<File>> {
+        self.out.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for WsRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WsRecorder")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Feeds a recording back through [`parse_ws_message`] onto an event bus.
+pub struct WsReplayer {
+    frames: Vec<RecordedFrame>,
+    speed: f64,
+}
+
+impl WsReplayer {
+    /// Read a recording written by [`WsRecorder`].
+    pub fn open(path: impl AsRef<Path>) -> OkxResult<Self> {
+        Ok(Self::from_frames(jsonl::read(path.as_ref())?))
+    }
+
+    /// Replay frames that are already in memory.
+    pub fn from_frames(frames: Vec<RecordedFrame>) -> Self {
+        Self { frames, speed: 1.0 }
+    }
+
+    /// Playback speed relative to the recording: `1.0` keeps the original
+    /// gaps, `10.0` is ten times faster, and `0.0` or less replays without
+    /// any delay (default: `1.0`).
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The recorded frames.
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Parsed messages of the recording, without any delay. Frames that do
+    /// not parse are skipped.
+    pub fn messages(&self) -> impl Iterator<Item = WsMessage> + '_ {
+        self.frames.iter().filter_map(|f| parse_ws_message(&f.text))
+    }
+
+    /// Publish every frame to `client`'s event bus, waiting between frames
+    /// according to [`speed`](Self::speed).
+    ///
+    /// The client does not need to be connected; receivers obtained from
+    /// [`WebsocketClient::event_receiver`] see the frames as if they had
+    /// arrived live. Returns the number of messages published.
+    pub async fn replay(&self, client: &WebsocketClient) -> usize {
+        let mut published = 0;
+        let mut prev_ts = None;
+        for frame in &self.frames {
+            if let Some(prev) = prev_ts {
+                let delay = self.delay(prev, frame.ts);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            prev_ts = Some(frame.ts);
+
+            if let Some(msg) = parse_ws_message(&frame.text) {
+                let _ = client.event_tx.send(Arc::new(msg));
+                published += 1;
+            }
+        }
+        published
+    }
+
+    fn delay(&self, prev_ts: u64, ts: u64) -> Duration {
+        if self.speed <= 0.0 || !self.speed.is_finite() {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(ts.saturating_sub(prev_ts) as f64 / 1000.0 / self.speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::types::WsConfig;
+
+    fn frame(ts: u64, text: &str) -> RecordedFrame {
+        RecordedFrame {
+            ts,
+            conn: WsConnectionType::Public,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn recorder_output_reads_back() {
+        let path = std::env::temp_dir().join(format!("okx-ws-rec-{}.jsonl", std::process::id()));
+        let recorder = WsRecorder::create(&path).unwrap();
+        recorder.record(WsConnectionType::Business, "pong");
+        recorder.record(
+            WsConnectionType::Public,
+            r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"last":"1"}]}"#,
+        );
+        recorder.flush().unwrap();
+
+        let replayer = WsReplayer::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replayer.frames().len(), 2);
+        assert_eq!(replayer.frames()[0].conn, WsConnectionType::Business);
+        assert!(replayer.frames()[0].ts <= replayer.frames()[1].ts);
+
+        let messages: Vec<_> = replayer.messages().collect();
+        assert!(matches!(messages[0], WsMessage::Pong));
+        assert!(matches!(&messages[1], WsMessage::Data(d) if d.arg.channel == "tickers"));
+    }
+
+    #[test]
+    fn speed_scales_gaps() {
+        let replayer = WsReplayer::from_frames(Vec::new());
+        assert_eq!(replayer.delay(1_000, 3_000), Duration::from_secs(2));
+        let replayer = replayer.speed(4.0);
+        assert_eq!(replayer.delay(1_000, 3_000), Duration::from_millis(500));
+        assert_eq!(replayer.delay(3_000, 1_000), Duration::ZERO);
+        let replayer = replayer.speed(0.0);
+        assert_eq!(replayer.delay(1_000, 3_000), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn replay_publishes_to_event_bus_with_scaled_gaps() {
+        let client = WebsocketClient::new(WsConfig::default());
+        let mut rx = client.event_receiver();
+        let replayer = WsReplayer::from_frames(vec![
+            frame(
+                1_000,
+                r#"{"event":"subscribe","arg":{"channel":"tickers"}}"#,
+            ),
+            frame(1_000, "not json"),
+            frame(61_000, "pong"),
+        ])
+        .speed(1_000.0);
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(replayer.replay(&client).await, 2);
+        assert!(start.elapsed() >= Duration::from_millis(60));
+
+        assert!(matches!(*rx.recv().await.unwrap(), WsMessage::Event(_)));
+        assert!(matches!(*rx.recv().await.unwrap(), WsMessage::Pong));
+    }
+}
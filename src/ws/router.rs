@@ -1,6 +1,8 @@
 use crate::types::ws::channels::WsSubscriptionArg;
 use crate::types::ws::events::WsConnectionType;
 
+use super::types::RouteOverride;
+
 /// Route a subscription to the correct connection type (public, private, or business).
 pub fn route_subscription(arg: &WsSubscriptionArg) -> WsConnectionType {
     if arg.is_private() {
@@ -12,9 +14,20 @@ pub fn route_subscription(arg: &WsSubscriptionArg) -> WsConnectionType {
     WsConnectionType::Public
 }
 
+/// Route a subscription, consulting the user-supplied override first.
+pub fn resolve_route(
+    arg: &WsSubscriptionArg,
+    route_override: Option<RouteOverride>,
+) -> WsConnectionType {
+    route_override
+        .and_then(|f| f(arg))
+        .unwrap_or_else(|| route_subscription(arg))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::enums::{Bar, GridAlgoOrderType};
 
     #[test]
     fn test_route_public() {
@@ -22,6 +35,12 @@ mod tests {
         assert_eq!(route_subscription(&arg), WsConnectionType::Public);
     }
 
+    #[test]
+    fn test_route_liquidation_orders_public() {
+        let arg = WsSubscriptionArg::liquidation_orders("SWAP");
+        assert_eq!(route_subscription(&arg), WsConnectionType::Public);
+    }
+
     #[test]
     fn test_route_private() {
         let arg = WsSubscriptionArg::channel_only("orders");
@@ -29,6 +48,12 @@ mod tests {
 
         let arg = WsSubscriptionArg::channel_only("account");
         assert_eq!(route_subscription(&arg), WsConnectionType::Private);
+
+        let arg = WsSubscriptionArg::adl_warning("SWAP", None);
+        assert_eq!(route_subscription(&arg), WsConnectionType::Private);
+
+        let arg = WsSubscriptionArg::liquidation_warning("SWAP");
+        assert_eq!(route_subscription(&arg), WsConnectionType::Private);
     }
 
     #[test]
@@ -38,5 +63,76 @@ mod tests {
 
         let arg = WsSubscriptionArg::channel_only("deposit-info");
         assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+
+        let arg = WsSubscriptionArg::index_candle("BTC-USDT", Bar::H1);
+        assert_eq!(arg.channel, "index-candle1H");
+        assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+
+        let arg = WsSubscriptionArg::mark_price_candle("BTC-USDT-SWAP", Bar::M1);
+        assert_eq!(arg.channel, "mark-price-candle1m");
+        assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+        assert!(!arg.requires_auth());
+    }
+
+    #[test]
+    fn test_route_grid_business() {
+        for grid_type in [
+            GridAlgoOrderType::SpotGrid,
+            GridAlgoOrderType::ContractGrid,
+            GridAlgoOrderType::MoonGrid,
+        ] {
+            let arg = WsSubscriptionArg::grid_orders(grid_type, "ANY", None, None);
+            assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+            assert!(arg.requires_auth());
+        }
+
+        let arg =
+            WsSubscriptionArg::grid_orders(GridAlgoOrderType::ContractGrid, "SWAP", None, None);
+        assert_eq!(arg.channel, "grid-orders-contract");
+
+        for arg in [
+            WsSubscriptionArg::grid_positions("449327675342323712"),
+            WsSubscriptionArg::grid_sub_orders("449327675342323712"),
+        ] {
+            assert!(!arg.is_private());
+            assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+            assert!(arg.requires_auth());
+        }
+    }
+
+    #[test]
+    fn test_route_sprd_business() {
+        let sprd_id = "BTC-USDT_BTC-USDT-SWAP";
+        for arg in [
+            WsSubscriptionArg::sprd_public_trades(sprd_id),
+            WsSubscriptionArg::sprd_bbo_tbt(sprd_id),
+            WsSubscriptionArg::sprd_books5(sprd_id),
+            WsSubscriptionArg::sprd_tickers(sprd_id),
+        ] {
+            assert_eq!(route_subscription(&arg), WsConnectionType::Business);
+            assert!(!arg.requires_auth());
+        }
+    }
+
+    #[test]
+    fn test_route_override_takes_precedence() {
+        fn grid_on_private(arg: &WsSubscriptionArg) -> Option<WsConnectionType> {
+            arg.channel
+                .starts_with("grid-")
+                .then_some(WsConnectionType::Private)
+        }
+
+        let grid = WsSubscriptionArg::channel_only("grid-orders-spot");
+        assert_eq!(
+            resolve_route(&grid, Some(grid_on_private)),
+            WsConnectionType::Private
+        );
+        assert_eq!(resolve_route(&grid, None), WsConnectionType::Business);
+
+        let tickers = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
+        assert_eq!(
+            resolve_route(&tickers, Some(grid_on_private)),
+            WsConnectionType::Public
+        );
     }
 }
@@ -0,0 +1,194 @@
+//! Sequence gap detection for order book and trades channels.
+//!
+//! Order book pushes carry `seqId` and `prevSeqId`; each update must
+//! continue from the previous push's `seqId`. Trades pushes carry a `seqId`
+//! that grows by one per push. A push that does not continue the sequence
+//! means data was lost and the local view is no longer consistent.
+
+use std::collections::HashMap;
+
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::{DataGap, WsConnectionType, WsDataEvent};
+
+/// Order book channels whose pushes carry `prevSeqId`.
+const BOOK_CHANNELS: &[&str] = &["books", "books-l2-tbt", "books50-l2-tbt", "books-elp"];
+
+/// Trades channels whose pushes carry a contiguous `seqId`.
+const TRADE_CHANNELS: &[&str] = &["trades", "trades-all"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cursor {
+    /// Last `seqId` seen.
+    At(i64),
+    /// A book gap was reported; ignore pushes until the next snapshot.
+    Resyncing,
+}
+
+/// Per-subscription sequence state of one connection.
+#[derive(Debug)]
+pub struct SequenceTracker {
+    conn_type: WsConnectionType,
+    cursors: HashMap<WsSubscriptionArg, Cursor>,
+}
+
+impl SequenceTracker {
+    pub fn new(conn_type: WsConnectionType) -> Self {
+        Self {
+            conn_type,
+            cursors: HashMap::new(),
+        }
+    }
+
+    /// Check a push. Returns the gap if it does not continue the sequence.
+    ///
+    /// After a gap, book pushes are ignored until the next snapshot, and
+    /// the next trades push starts a new sequence.
+    pub fn check(&mut self, evt: &WsDataEvent) -> Option<DataGap> {
+        let book = BOOK_CHANNELS.contains(&evt.arg.channel.as_str());
+        if !book && !TRADE_CHANNELS.contains(&evt.arg.channel.as_str()) {
+            return None;
+        }
+        let snapshot = evt.action.as_deref() == Some("snapshot");
+
+        for item in &evt.data {
+            let Some(seq_id) = item.get("seqId").and_then(as_i64) else {
+                continue;
+            };
+            let prev_seq_id = item.get("prevSeqId").and_then(as_i64);
+            let cursor = self.cursors.get(&evt.arg).copied();
+
+            // Book snapshots (prevSeqId -1) and the first trades push after
+            // a (re)subscribe start a new sequence.
+            let restart = if book {
+                snapshot || prev_seq_id == Some(-1)
+            } else {
+                cursor.is_none()
+            };
+            if restart {
+                self.cursors.insert(evt.arg.clone(), Cursor::At(seq_id));
+                continue;
+            }
+
+            let last = match cursor {
+                Some(Cursor::At(last)) => last,
+                Some(Cursor::Resyncing) => return None,
+                None => {
+                    // Pick the sequence up from here, e.g. after a reset.
+                    self.cursors.insert(evt.arg.clone(), Cursor::At(seq_id));
+                    continue;
+                }
+            };
+            let (expected, received) = if book {
+                (last, prev_seq_id.unwrap_or(last))
+            } else {
+                (last + 1, seq_id)
+            };
+            if expected != received {
+                if book {
+                    self.cursors.insert(evt.arg.clone(), Cursor::Resyncing);
+                } else {
+                    self.cursors.remove(&evt.arg);
+                }
+                return Some(DataGap {
+                    conn_type: self.conn_type,
+                    arg: evt.arg.clone(),
+                    expected,
+                    received,
+                });
+            }
+            self.cursors.insert(evt.arg.clone(), Cursor::At(seq_id));
+        }
+        None
+    }
+
+    /// Forget the sequence of `arg`, so a book that could not be
+    /// resubscribed after a gap is checked again from its next push.
+    pub fn reset(&mut self, arg: &WsSubscriptionArg) {
+        self.cursors.remove(arg);
+    }
+}
+
+fn as_i64(value: &serde_json::Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(channel: &str, action: Option<&str>, data: serde_json::Value) -> WsDataEvent {
+        WsDataEvent {
+            arg: WsSubscriptionArg::with_inst_id(channel, "BTC-USDT"),
+            data: vec![data],
+            action: action.map(String::from),
+        }
+    }
+
+    #[test]
+    fn book_updates_must_chain_from_the_previous_seq_id() {
+        let mut tracker = SequenceTracker::new(WsConnectionType::Public);
+        let book = |action, prev: i64, seq: i64| {
+            push(
+                "books-l2-tbt",
+                Some(action),
+                serde_json::json!({"prevSeqId": prev, "seqId": seq}),
+            )
+        };
+
+        assert!(tracker.check(&book("snapshot", -1, 10)).is_none());
+        assert!(tracker.check(&book("update", 10, 15)).is_none());
+        // No-change heartbeat repeats the sequence ID.
+        assert!(tracker.check(&book("update", 15, 15)).is_none());
+
+        let gap = tracker.check(&book("update", 17, 20)).unwrap();
+        assert_eq!((gap.expected, gap.received), (15, 17));
+        assert_eq!(gap.arg.channel, "books-l2-tbt");
+
+        // Ignored until the resubscribe snapshot arrives.
+        assert!(tracker.check(&book("update", 30, 31)).is_none());
+        assert!(tracker.check(&book("snapshot", -1, 40)).is_none());
+        assert!(tracker.check(&book("update", 40, 41)).is_none());
+    }
+
+    #[test]
+    fn reset_book_is_checked_again_from_the_next_push() {
+        let mut tracker = SequenceTracker::new(WsConnectionType::Public);
+        let book = |action, prev: i64, seq: i64| {
+            push(
+                "books",
+                Some(action),
+                serde_json::json!({"prevSeqId": prev, "seqId": seq}),
+            )
+        };
+        assert!(tracker.check(&book("snapshot", -1, 10)).is_none());
+        let gap = tracker.check(&book("update", 12, 13)).unwrap();
+
+        tracker.reset(&gap.arg);
+        assert!(tracker.check(&book("update", 13, 14)).is_none());
+        assert!(tracker.check(&book("update", 16, 17)).is_some());
+    }
+
+    #[test]
+    fn trades_seq_id_must_grow_by_one() {
+        let mut tracker = SequenceTracker::new(WsConnectionType::Public);
+        let trade = |seq: i64| push("trades", None, serde_json::json!({"seqId": seq}));
+
+        assert!(tracker.check(&trade(100)).is_none());
+        assert!(tracker.check(&trade(101)).is_none());
+        let gap = tracker.check(&trade(103)).unwrap();
+        assert_eq!((gap.expected, gap.received), (102, 103));
+
+        assert!(tracker.check(&trade(200)).is_none());
+        assert!(tracker.check(&trade(201)).is_none());
+    }
+
+    #[test]
+    fn other_channels_are_ignored() {
+        let mut tracker = SequenceTracker::new(WsConnectionType::Public);
+        let ticker = |seq: i64| push("tickers", None, serde_json::json!({"seqId": seq}));
+        assert!(tracker.check(&ticker(1)).is_none());
+        assert!(tracker.check(&ticker(5)).is_none());
+    }
+}
@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::types::ws::channels::WsSubscriptionArg;
 use crate::types::ws::events::WsConnectionType;
@@ -13,6 +15,33 @@ pub enum ConnectionState {
     Reconnecting,
 }
 
+/// Time the last inbound message of a connection was seen.
+///
+/// Clones share the timestamp, so the dispatch task can update it without
+/// taking the store lock.
+#[derive(Debug, Clone)]
+pub struct LastMessageAt(Arc<Mutex<Instant>>);
+
+impl LastMessageAt {
+    pub fn now() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    /// Record a message received now.
+    pub fn touch(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    pub fn get(&self) -> Instant {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Time since the last message.
+    pub fn elapsed(&self) -> Duration {
+        self.get().elapsed()
+    }
+}
+
 /// Per-connection state.
 #[derive(Debug)]
 pub struct ConnectionStore {
@@ -21,6 +50,20 @@ pub struct ConnectionStore {
     pub subscribed_topics: HashSet<WsSubscriptionArg>,
     pub pending_topics: HashSet<WsSubscriptionArg>,
     pub is_authenticated: bool,
+    /// Re-login attempts since the last successful login.
+    pub relogin_attempts: u32,
+    /// Whether a re-login has been scheduled but not yet sent.
+    pub relogin_pending: bool,
+    /// Reconnect attempts since the connection was last established.
+    pub reconnect_attempts: u32,
+    /// Number of outstanding `subscribe` calls per topic.
+    pub topic_refs: HashMap<WsSubscriptionArg, usize>,
+    /// Last inbound message, pongs included. Reset when a connection is
+    /// established.
+    pub last_message_at: LastMessageAt,
+    /// Connections subscribed to each private channel, as last reported
+    /// by `channel-conn-count`.
+    pub channel_conn_counts: HashMap<String, u32>,
 }
 
 impl ConnectionStore {
@@ -31,7 +74,71 @@ impl ConnectionStore {
             subscribed_topics: HashSet::new(),
             pending_topics: HashSet::new(),
             is_authenticated: false,
+            relogin_attempts: 0,
+            relogin_pending: false,
+            reconnect_attempts: 0,
+            topic_refs: HashMap::new(),
+            last_message_at: LastMessageAt::now(),
+            channel_conn_counts: HashMap::new(),
+        }
+    }
+
+    /// Take a reference on each topic. Returns the topics that had no
+    /// references before and therefore need a subscribe frame.
+    pub fn acquire_topics(&mut self, args: Vec<WsSubscriptionArg>) -> Vec<WsSubscriptionArg> {
+        let mut new_topics = Vec::new();
+        for arg in args {
+            let refs = self.topic_refs.entry(arg.clone()).or_insert(0);
+            *refs += 1;
+            if *refs == 1 {
+                new_topics.push(arg);
+            }
+        }
+        new_topics
+    }
+
+    /// Drop a reference on each topic. Returns the topics whose last
+    /// reference was dropped and therefore need an unsubscribe frame.
+    /// Topics that were never acquired are ignored.
+    pub fn release_topics(&mut self, args: Vec<WsSubscriptionArg>) -> Vec<WsSubscriptionArg> {
+        let mut released = Vec::new();
+        for arg in args {
+            if let Some(refs) = self.topic_refs.get_mut(&arg) {
+                *refs -= 1;
+                if *refs == 0 {
+                    self.topic_refs.remove(&arg);
+                    released.push(arg);
+                }
+            }
+        }
+        released
+    }
+
+    /// Mark the session as logged out ahead of a re-login.
+    ///
+    /// Moves auth-requiring topics back to pending so they are resubscribed
+    /// after the new login. Returns the attempt number, or `None` once
+    /// `max_attempts` consecutive attempts have been made.
+    pub fn begin_relogin(&mut self, max_attempts: u32) -> Option<u32> {
+        if self.relogin_attempts >= max_attempts {
+            return None;
+        }
+        self.relogin_attempts += 1;
+        self.relogin_pending = true;
+        self.is_authenticated = false;
+        self.state = ConnectionState::Connected;
+
+        let topics: Vec<_> = self
+            .subscribed_topics
+            .iter()
+            .filter(|t| t.requires_auth())
+            .cloned()
+            .collect();
+        for topic in topics {
+            self.subscribed_topics.remove(&topic);
+            self.pending_topics.insert(topic);
         }
+        Some(self.relogin_attempts)
     }
 }
 
@@ -71,6 +178,15 @@ impl WsStore {
             WsConnectionType::Business => self.business.as_ref(),
         }
     }
+
+    /// Get the mutable connection store for a given type (if it exists).
+    pub fn get_mut(&mut self, conn_type: WsConnectionType) -> Option<&mut ConnectionStore> {
+        match conn_type {
+            WsConnectionType::Public => self.public.as_mut(),
+            WsConnectionType::Private => self.private.as_mut(),
+            WsConnectionType::Business => self.business.as_mut(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -113,4 +229,49 @@ mod tests {
         assert!(store.get(WsConnectionType::Private).is_some());
         assert!(store.get(WsConnectionType::Business).is_some());
     }
+
+    #[test]
+    fn test_topic_refcounting() {
+        let mut conn = ConnectionStore::new(WsConnectionType::Public);
+        let tickers = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
+        let trades = WsSubscriptionArg::with_inst_id("trades", "BTC-USDT");
+
+        assert_eq!(conn.acquire_topics(vec![tickers.clone()]), vec![tickers.clone()]);
+        assert_eq!(
+            conn.acquire_topics(vec![tickers.clone(), trades.clone()]),
+            vec![trades.clone()]
+        );
+
+        assert!(conn.release_topics(vec![tickers.clone()]).is_empty());
+        assert_eq!(
+            conn.release_topics(vec![tickers.clone(), trades.clone()]),
+            vec![tickers.clone(), trades]
+        );
+
+        // Releasing an unknown topic does not underflow.
+        assert!(conn.release_topics(vec![tickers]).is_empty());
+        assert!(conn.topic_refs.is_empty());
+    }
+
+    #[test]
+    fn begin_relogin_requeues_auth_topics_and_caps_attempts() {
+        let mut store = ConnectionStore::new(WsConnectionType::Business);
+        store.is_authenticated = true;
+        store
+            .subscribed_topics
+            .insert(WsSubscriptionArg::channel_only("deposit-info"));
+        store
+            .subscribed_topics
+            .insert(WsSubscriptionArg::with_inst_id("candle1m", "BTC-USDT"));
+
+        assert_eq!(store.begin_relogin(2), Some(1));
+        assert!(!store.is_authenticated);
+        assert_eq!(store.subscribed_topics.len(), 1);
+        assert!(store
+            .pending_topics
+            .contains(&WsSubscriptionArg::channel_only("deposit-info")));
+
+        assert_eq!(store.begin_relogin(2), Some(2));
+        assert_eq!(store.begin_relogin(2), None);
+    }
 }
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+use tracing::error;
+
+use crate::types::ws::events::{WsConnectionType, WsTaskKind};
+
+/// Callback invoked when a supervised task panics or exits unexpectedly.
+pub type FailureHandler = Arc<dyn Fn(WsTaskKind, String) + Send + Sync>;
+
+/// Last known state of a supervised task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    /// The task is still running.
+    Running,
+    /// The task finished normally.
+    Exited,
+    /// The task was stopped by the client (e.g. on reconnect or close).
+    Aborted,
+    /// The task stopped with an error.
+    Failed(String),
+    /// The task panicked.
+    Panicked(String),
+}
+
+/// Health of a single supervised task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskHealth {
+    pub conn_type: WsConnectionType,
+    pub kind: WsTaskKind,
+    pub state: TaskState,
+}
+
+#[derive(Debug)]
+struct TaskEntry {
+    generation: u64,
+    state: TaskState,
+    abort: AbortHandle,
+}
+
+#[derive(Debug, Default)]
+struct SupervisorInner {
+    next_generation: u64,
+    tasks: HashMap<(WsConnectionType, WsTaskKind), TaskEntry>,
+}
+
+/// Spawns and monitors the background tasks of the WebSocket client.
+///
+/// Each task is joined by a watcher that records how it ended. Panics and
+/// error exits are reported to the task's [`FailureHandler`].
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    inner: Arc<Mutex<SupervisorInner>>,
+    /// Number of tasks, including their watchers, that have not finished.
+    running: Arc<watch::Sender<usize>>,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self {
+            inner: Arc::default(),
+            running: Arc::new(watch::Sender::new(0)),
+        }
+    }
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a supervised task, replacing any previous task of the same kind
+    /// on the same connection.
+    ///
+    /// The task returns `Err(reason)` to signal an unexpected exit.
+    pub fn spawn<F>(
+        &self,
+        conn_type: WsConnectionType,
+        kind: WsTaskKind,
+        task: F,
+        on_failure: FailureHandler,
+    ) where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.running.send_modify(|n| *n += 1);
+        let handle = tokio::spawn(task);
+
+        let generation = {
+            let mut inner = self.lock();
+            inner.next_generation += 1;
+            let generation = inner.next_generation;
+            inner.tasks.insert(
+                (conn_type, kind),
+                TaskEntry {
+                    generation,
+                    state: TaskState::Running,
+                    abort: handle.abort_handle(),
+                },
+            );
+            generation
+        };
+
+        let supervisor = self.clone();
+        tokio::spawn(async move {
+            let state = match handle.await {
+                Ok(Ok(())) => TaskState::Exited,
+                Ok(Err(reason)) => TaskState::Failed(reason),
+                Err(e) if e.is_panic() => TaskState::Panicked(panic_message(e.into_panic())),
+                Err(_) => TaskState::Aborted,
+            };
+
+            let current = supervisor.set_state(conn_type, kind, generation, state.clone());
+            match state {
+                TaskState::Failed(reason) if current => {
+                    error!("WS {conn_type} {kind} task failed: {reason}");
+                    on_failure(kind, reason);
+                }
+                TaskState::Panicked(msg) if current => {
+                    error!("WS {conn_type} {kind} task panicked: {msg}");
+                    on_failure(kind, format!("panicked: {msg}"));
+                }
+                _ => {}
+            }
+            supervisor.running.send_modify(|n| *n -= 1);
+        });
+    }
+
+    /// Abort the given task kinds of a connection.
+    pub fn abort(&self, conn_type: WsConnectionType, kinds: &[WsTaskKind]) {
+        let inner = self.lock();
+        for kind in kinds {
+            if let Some(entry) = inner.tasks.get(&(conn_type, *kind)) {
+                entry.abort.abort();
+            }
+        }
+    }
+
+    /// Abort every task of every connection.
+    pub fn abort_all(&self) {
+        for entry in self.lock().tasks.values() {
+            entry.abort.abort();
+        }
+    }
+
+    /// Wait until every spawned task has finished and been recorded.
+    pub async fn wait_idle(&self) {
+        let mut running = self.running.subscribe();
+        let _ = running.wait_for(|n| *n == 0).await;
+    }
+
+    /// Snapshot of all supervised tasks.
+    pub fn health(&self) -> Vec<TaskHealth> {
+        let inner = self.lock();
+        let mut out: Vec<_> = inner
+            .tasks
+            .iter()
+            .map(|((conn_type, kind), entry)| TaskHealth {
+                conn_type: *conn_type,
+                kind: *kind,
+                state: entry.state.clone(),
+            })
+            .collect();
+        out.sort_by_key(|h| (h.conn_type as u8, h.kind as u8));
+        out
+    }
+
+    /// Record the final state of a task. Returns `false` if the task has
+    /// already been replaced by a newer one.
+    fn set_state(
+        &self,
+        conn_type: WsConnectionType,
+        kind: WsTaskKind,
+        generation: u64,
+        state: TaskState,
+    ) -> bool {
+        let mut inner = self.lock();
+        match inner.tasks.get_mut(&(conn_type, kind)) {
+            Some(entry) if entry.generation == generation => {
+                entry.state = state;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SupervisorInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = panic.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn recorder() -> (FailureHandler, mpsc::UnboundedReceiver<(WsTaskKind, String)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handler: FailureHandler = Arc::new(move |kind, msg| {
+            let _ = tx.send((kind, msg));
+        });
+        (handler, rx)
+    }
+
+    #[tokio::test]
+    async fn reports_panics_and_records_state() {
+        let supervisor = Supervisor::new();
+        let (handler, mut rx) = recorder();
+
+        supervisor.spawn(
+            WsConnectionType::Public,
+            WsTaskKind::Read,
+            async { panic!("boom") },
+            handler,
+        );
+
+        let (kind, msg) = rx.recv().await.unwrap();
+        assert_eq!(kind, WsTaskKind::Read);
+        assert!(msg.contains("boom"));
+
+        let health = supervisor.health();
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].state, TaskState::Panicked("boom".into()));
+    }
+
+    #[tokio::test]
+    async fn reports_error_exit_but_not_normal_exit_or_abort() {
+        let supervisor = Supervisor::new();
+        let (handler, mut rx) = recorder();
+
+        supervisor.spawn(
+            WsConnectionType::Private,
+            WsTaskKind::Heartbeat,
+            async { Ok(()) },
+            handler.clone(),
+        );
+        supervisor.spawn(
+            WsConnectionType::Private,
+            WsTaskKind::Dispatch,
+            std::future::pending(),
+            handler.clone(),
+        );
+        supervisor.abort(WsConnectionType::Private, &[WsTaskKind::Dispatch]);
+        supervisor.spawn(
+            WsConnectionType::Private,
+            WsTaskKind::Write,
+            async { Err("socket closed".to_string()) },
+            handler,
+        );
+
+        let (kind, msg) = rx.recv().await.unwrap();
+        assert_eq!(kind, WsTaskKind::Write);
+        assert_eq!(msg, "socket closed");
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let states: Vec<_> = supervisor.health().into_iter().map(|h| h.state).collect();
+        assert_eq!(
+            states,
+            vec![
+                TaskState::Failed("socket closed".into()),
+                TaskState::Exited,
+                TaskState::Aborted,
+            ]
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn wait_idle_returns_after_abort_all() {
+        let supervisor = Supervisor::new();
+        let (handler, _rx) = recorder();
+        for kind in [WsTaskKind::Read, WsTaskKind::Write] {
+            supervisor.spawn(
+                WsConnectionType::Public,
+                kind,
+                std::future::pending(),
+                handler.clone(),
+            );
+        }
+
+        let idle = tokio::time::timeout(Duration::from_millis(20), supervisor.wait_idle());
+        assert!(idle.await.is_err());
+
+        supervisor.abort_all();
+        supervisor.wait_idle().await;
+        assert!(supervisor
+            .health()
+            .iter()
+            .all(|h| h.state == TaskState::Aborted));
+    }
+}
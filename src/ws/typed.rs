@@ -0,0 +1,199 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::stream::{self, BoxStream};
+use serde::de::DeserializeOwned;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::error::OkxResult;
+use crate::types::ws::channels::WsSubscriptionArg;
+use crate::types::ws::events::WsMessage;
+
+use super::WebsocketClient;
+
+/// Push data of one subscription, deserialized into `T`.
+///
+/// Created by [`WebsocketClient::subscribe_typed`] and the channel-specific
+/// helpers built on it. Other events on the bus are skipped.
+///
+/// Dropping the subscription releases its topic in the background, as
+/// [`unsubscribe`](Self::unsubscribe) does.
+pub struct TypedSubscription<T> {
+    client: WebsocketClient,
+    arg: WsSubscriptionArg,
+    rx: broadcast::Receiver<Arc<WsMessage>>,
+    released: bool,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> TypedSubscription<T> {
+    pub(crate) fn new(
+        client: WebsocketClient,
+        arg: WsSubscriptionArg,
+        rx: broadcast::Receiver<Arc<WsMessage>>,
+    ) -> Self {
+        Self {
+            client,
+            arg,
+            rx,
+            released: false,
+            _data: PhantomData,
+        }
+    }
+
+    /// The subscription arg this stream was created with.
+    pub fn arg(&self) -> &WsSubscriptionArg {
+        &self.arg
+    }
+
+    /// Wait for the next push of this subscription.
+    ///
+    /// Returns `None` once the client's event bus is closed. If the receiver
+    /// falls behind, skipped events are logged and dropped.
+    pub async fn recv(&mut self) -> Option<OkxResult<Vec<T>>> {
+        loop {
+            match self.rx.recv().await {
+                Ok(msg) => {
+                    if let WsMessage::Data(evt) = msg.as_ref() {
+                        if self.arg.matches(&evt.arg) {
+                            return Some(evt.parse_data());
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!(
+                        "{} subscription lagged, {n} events dropped",
+                        self.arg.channel
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Convert into a stream of pushes.
+    pub fn into_stream(self) -> BoxStream<'static, OkxResult<Vec<T>>>
+    where
+        T: Send + 'static,
+    {
+        Box::pin(stream::unfold(self, |mut sub| async move {
+            sub.recv().await.map(|item| (item, sub))
+        }))
+    }
+
+    /// Release this subscription. See [`WebsocketClient::unsubscribe`].
+    pub async fn unsubscribe(mut self) -> OkxResult<()> {
+        self.released = true;
+        self.client.unsubscribe(vec![self.arg.clone()]).await
+    }
+}
+
+impl<T> Drop for TypedSubscription<T> {
+    fn drop(&mut self) {
+        if !self.released {
+            release_in_background(self.client.clone(), vec![self.arg.clone()]);
+        }
+    }
+}
+
+/// Background task of a feed, aborted when the handle is dropped.
+///
+/// Dropping the task drops the [`TypedSubscription`]s it owns, releasing
+/// their topics. Topics the feed subscribed to directly with
+/// [`WebsocketClient::subscribe`] are released by
+/// [`releasing`](Self::releasing).
+pub(crate) struct FeedTask {
+    task: JoinHandle<()>,
+    topics: Option<(WebsocketClient, Vec<WsSubscriptionArg>)>,
+}
+
+impl FeedTask {
+    pub(crate) fn spawn(task: impl Future<Output = ()> + Send + 'static) -> Self {
+        Self {
+            task: tokio::spawn(task),
+            topics: None,
+        }
+    }
+
+    /// Also unsubscribe `topics` from `ws` when dropped.
+    pub(crate) fn releasing(
+        mut self,
+        ws: &WebsocketClient,
+        topics: Vec<WsSubscriptionArg>,
+    ) -> Self {
+        self.topics = Some((ws.clone(), topics));
+        self
+    }
+}
+
+impl Drop for FeedTask {
+    fn drop(&mut self) {
+        self.task.abort();
+        if let Some((ws, topics)) = self.topics.take() {
+            release_in_background(ws, topics);
+        }
+    }
+}
+
+/// Unsubscribe `args` from a destructor, which cannot wait for the result.
+pub(crate) fn release_in_background(client: WebsocketClient, args: Vec<WsSubscriptionArg>) {
+    // Without a runtime the client's tasks are gone too, so there is
+    // nothing left to unsubscribe from.
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    runtime.spawn(async move {
+        if let Err(e) = client.unsubscribe(args).await {
+            debug!("Unsubscribe of dropped subscription failed: {e}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ws::data::DepositInfo;
+    use crate::types::ws::events::WsDataEvent;
+    use crate::ws::types::WsConfig;
+
+    fn push(arg: WsSubscriptionArg, data: serde_json::Value) -> Arc<WsMessage> {
+        Arc::new(WsMessage::Data(WsDataEvent {
+            arg,
+            data: vec![data],
+            action: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn recv_skips_other_channels_and_parses_data() {
+        let client = WebsocketClient::new(WsConfig::default());
+        let arg = WsSubscriptionArg::deposit_info(Some("USDT"));
+        let mut sub =
+            TypedSubscription::<DepositInfo>::new(client.clone(), arg, client.event_tx.subscribe());
+
+        let tx = &client.event_tx;
+        tx.send(push(
+            WsSubscriptionArg::withdrawal_info(None),
+            serde_json::json!({"ccy": "USDT"}),
+        ))
+        .unwrap();
+        tx.send(push(
+            WsSubscriptionArg::deposit_info(None).uid("42"),
+            serde_json::json!({
+                "uid": "42", "subAcct": "", "pTime": "1", "ccy": "USDT",
+                "chain": "USDT-TRC20", "amt": "100", "txId": "0xabc",
+                "state": "2", "depId": "7", "ts": "1"
+            }),
+        ))
+        .unwrap();
+
+        let deposits = sub.recv().await.unwrap().unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].uid, "42");
+        assert_eq!(deposits[0].record.dep_id, "7");
+        assert_eq!(deposits[0].record.amt, "100");
+    }
+}
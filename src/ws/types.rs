@@ -1,8 +1,54 @@
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::config::{ClientConfig, Region, TradingMode};
+use crate::config::{ClientConfig, Region, TlsConfig, TradingMode};
 use crate::constants::ws_urls;
+use crate::persist::StateStorage;
+use crate::types::ws::channels::WsSubscriptionArg;
 use crate::types::ws::events::WsConnectionType;
+use crate::ws::replay::WsRecorder;
+
+/// Routing override consulted before the built-in channel routing.
+///
+/// Return `Some` to force a subscription onto a connection, or `None` to fall
+/// back to [`route_subscription`](super::router::route_subscription).
+pub type RouteOverride = fn(&WsSubscriptionArg) -> Option<WsConnectionType>;
+
+/// What a send does when a connection's outbound queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteOverflowPolicy {
+    /// Wait until the write task makes room.
+    #[default]
+    Block,
+    /// Discard the oldest queued frame to make room. Can drop a
+    /// subscription or order request, so only suits best-effort traffic.
+    DropOldest,
+    /// Fail the send with an error.
+    Error,
+}
+
+/// How the heartbeat keeps a connection alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeartbeatMode {
+    /// Text `ping` frames, answered by OKX with text `pong`.
+    #[default]
+    Text,
+    /// Protocol-level Ping frames, answered with Pong frames.
+    Frame,
+    /// Both a text `ping` and a Ping frame on every tick.
+    Both,
+}
+
+impl HeartbeatMode {
+    pub(crate) fn sends_text(self) -> bool {
+        matches!(self, Self::Text | Self::Both)
+    }
+
+    pub(crate) fn sends_frame(self) -> bool {
+        matches!(self, Self::Frame | Self::Both)
+    }
+}
 
 /// Configuration for the WebSocket client.
 #[derive(Debug, Clone)]
@@ -13,10 +59,50 @@ pub struct WsConfig {
     pub ping_interval: Duration,
     /// Pong timeout (default: 5 seconds).
     pub pong_timeout: Duration,
-    /// Reconnect delay (default: 500ms).
+    /// Pings sent every [`ping_interval`](Self::ping_interval). Either
+    /// kind of pong arrives as [`WsMessage::Pong`](crate::types::ws::events::WsMessage::Pong)
+    /// (default: [`HeartbeatMode::Text`]).
+    pub heartbeat: HeartbeatMode,
+    /// Reconnect when no message, pongs included, has arrived for this
+    /// long. Checked every [`ping_interval`](Self::ping_interval); catches
+    /// half-open connections the heartbeat alone would not. `None` disables
+    /// the check (default: 30 seconds).
+    pub idle_timeout: Option<Duration>,
+    /// Delay before the first reconnect attempt. Each failed attempt adds
+    /// another delay, up to ten times this value (default: 500ms).
     pub reconnect_delay: Duration,
     /// Whether auto-reconnect is enabled (default: true).
     pub auto_reconnect: bool,
+    /// Default time to wait for a WS API response (default: 10 seconds).
+    pub api_timeout: Duration,
+    /// Maximum number of WS API requests in flight at once. Further requests
+    /// wait for a free slot within their timeout (default: unlimited).
+    pub max_in_flight_api_requests: Option<usize>,
+    /// Custom subscription routing for new or ambiguous channels (default: none).
+    pub route_override: Option<RouteOverride>,
+    /// `brokerId` query parameter sent on demo trading connections. `None`
+    /// omits it (default: `"9999"`).
+    pub demo_broker_id: Option<String>,
+    /// Extra query parameters appended to every WebSocket URL (default: none).
+    pub extra_query_params: Vec<(String, String)>,
+    /// TLS settings for individual connections, overriding
+    /// [`ClientConfig::tls`] (default: none).
+    pub tls_overrides: HashMap<WsConnectionType, TlsConfig>,
+    /// Base URLs for individual connections, e.g. a local mock server,
+    /// replacing the region and trading mode URL (default: none).
+    pub url_overrides: HashMap<WsConnectionType, String>,
+    /// Recorder that every inbound text frame is written to (default: none).
+    pub recorder: Option<WsRecorder>,
+    /// Outbound frames queued per connection before
+    /// [`write_overflow`](Self::write_overflow) applies (default: 1024).
+    pub write_buffer: usize,
+    /// What to do when a connection's outbound queue is full
+    /// (default: [`WriteOverflowPolicy::Block`]).
+    pub write_overflow: WriteOverflowPolicy,
+    /// Storage the subscribed topics are saved to whenever they change, for
+    /// [`restore_subscriptions`](super::WebsocketClient::restore_subscriptions)
+    /// after a restart (default: none).
+    pub state_storage: Option<Arc<dyn StateStorage>>,
 }
 
 impl WsConfig {
@@ -25,13 +111,55 @@ impl WsConfig {
             client_config,
             ping_interval: Duration::from_secs(10),
             pong_timeout: Duration::from_secs(5),
+            heartbeat: HeartbeatMode::Text,
+            idle_timeout: Some(Duration::from_secs(30)),
             reconnect_delay: Duration::from_millis(500),
             auto_reconnect: true,
+            api_timeout: Duration::from_secs(10),
+            max_in_flight_api_requests: None,
+            route_override: None,
+            demo_broker_id: Some(ws_urls::DEMO_BROKER_ID.to_string()),
+            extra_query_params: Vec::new(),
+            tls_overrides: HashMap::new(),
+            url_overrides: HashMap::new(),
+            recorder: None,
+            write_buffer: 1024,
+            write_overflow: WriteOverflowPolicy::Block,
+            state_storage: None,
         }
     }
 
-    /// Get the WebSocket URL for a given connection type.
-    pub fn ws_url(&self, conn_type: WsConnectionType) -> &str {
+    /// TLS settings used for a given connection type.
+    pub fn tls(&self, conn_type: WsConnectionType) -> &TlsConfig {
+        self.tls_overrides
+            .get(&conn_type)
+            .unwrap_or(&self.client_config.tls)
+    }
+
+    /// Get the WebSocket URL for a given connection type, including the demo
+    /// `brokerId` and any extra query parameters.
+    pub fn ws_url(&self, conn_type: WsConnectionType) -> String {
+        let base = self.base_ws_url(conn_type);
+        let demo = self.client_config.trading_mode == TradingMode::Demo;
+        let broker_id = self.demo_broker_id.as_deref().filter(|_| demo);
+        if broker_id.is_none() && self.extra_query_params.is_empty() {
+            return base.to_string();
+        }
+
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(broker_id) = broker_id {
+            query.append_pair("brokerId", broker_id);
+        }
+        for (key, value) in &self.extra_query_params {
+            query.append_pair(key, value);
+        }
+        format!("{base}?{}", query.finish())
+    }
+
+    fn base_ws_url(&self, conn_type: WsConnectionType) -> &str {
+        if let Some(url) = self.url_overrides.get(&conn_type) {
+            return url;
+        }
         if self.client_config.trading_mode == TradingMode::Demo {
             return match conn_type {
                 WsConnectionType::Public => ws_urls::DEMO_PUBLIC,
@@ -59,3 +187,55 @@ impl Default for WsConfig {
         Self::new(ClientConfig::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ClientConfigBuilder;
+
+    #[test]
+    fn demo_url_carries_broker_id_and_extra_params() {
+        let mut config = WsConfig::new(ClientConfigBuilder::new().demo().build());
+        assert_eq!(
+            config.ws_url(WsConnectionType::Public),
+            "wss://wspap.okx.com:8443/ws/v5/public?brokerId=9999"
+        );
+
+        config.demo_broker_id = Some("42".into());
+        config.extra_query_params = vec![("foo".into(), "a b".into())];
+        assert_eq!(
+            config.ws_url(WsConnectionType::Private),
+            "wss://wspap.okx.com:8443/ws/v5/private?brokerId=42&foo=a+b"
+        );
+    }
+
+    #[test]
+    fn live_url_ignores_demo_broker_id() {
+        let config = WsConfig::default();
+        assert_eq!(
+            config.ws_url(WsConnectionType::Business),
+            "wss://ws.okx.com:8443/ws/v5/business"
+        );
+    }
+
+    #[test]
+    fn tls_override_applies_per_connection() {
+        let mut config = WsConfig::new(
+            ClientConfigBuilder::new()
+                .add_root_certificate("client")
+                .build(),
+        );
+        let business = TlsConfig {
+            extra_root_certificates: vec![b"business".to_vec()],
+        };
+        config
+            .tls_overrides
+            .insert(WsConnectionType::Business, business.clone());
+
+        assert_eq!(config.tls(WsConnectionType::Business), &business);
+        assert_eq!(
+            config.tls(WsConnectionType::Public).extra_root_certificates,
+            vec![b"client".to_vec()]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Bounded outbound frame queue between the client and a connection's write
+//! task.
+//!
+//! A stalled socket fills the queue instead of growing memory without
+//! limit. What happens to further frames is decided by the connection's
+//! [`WriteOverflowPolicy`].
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+use tracing::warn;
+
+use crate::error::{OkxError, OkxResult};
+use crate::types::ws::events::WsConnectionType;
+
+use super::types::WriteOverflowPolicy;
+
+/// An outbound frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteFrame {
+    Text(String),
+    /// Protocol-level Ping.
+    Ping,
+}
+
+struct Shared {
+    conn_type: WsConnectionType,
+    frames: Mutex<VecDeque<WriteFrame>>,
+    capacity: usize,
+    policy: WriteOverflowPolicy,
+    senders: AtomicUsize,
+    receiver_closed: AtomicBool,
+    /// Signalled when a frame is queued or the last sender is dropped.
+    readable: Notify,
+    /// Signalled when a frame is taken or the receiver is dropped.
+    writable: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<WriteFrame>> {
+        self.frames.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Sending half of a connection's outbound queue. Cheap to clone.
+pub struct WriteSender {
+    shared: Arc<Shared>,
+}
+
+/// Receiving half, owned by the connection's write task.
+pub struct WriteReceiver {
+    shared: Arc<Shared>,
+}
+
+/// Create a queue holding up to `capacity` frames (at least one).
+pub fn write_queue(
+    conn_type: WsConnectionType,
+    capacity: usize,
+    policy: WriteOverflowPolicy,
+) -> (WriteSender, WriteReceiver) {
+    let shared = Arc::new(Shared {
+        conn_type,
+        frames: Mutex::new(VecDeque::new()),
+        capacity: capacity.max(1),
+        policy,
+        senders: AtomicUsize::new(1),
+        receiver_closed: AtomicBool::new(false),
+        readable: Notify::new(),
+        writable: Notify::new(),
+    });
+    (
+        WriteSender {
+            shared: shared.clone(),
+        },
+        WriteReceiver { shared },
+    )
+}
+
+impl WriteSender {
+    /// Queue a text frame for sending.
+    ///
+    /// Fails if the write task has stopped, or if the queue is full and the
+    /// policy is [`WriteOverflowPolicy::Error`]. With
+    /// [`WriteOverflowPolicy::Block`] this waits for space.
+    pub async fn send(&self, frame: String) -> OkxResult<()> {
+        self.send_frame(WriteFrame::Text(frame)).await
+    }
+
+    /// Queue any frame for sending, as [`send`](Self::send) does.
+    pub async fn send_frame(&self, frame: WriteFrame) -> OkxResult<()> {
+        let shared = &self.shared;
+        loop {
+            let writable = shared.writable.notified();
+            tokio::pin!(writable);
+            writable.as_mut().enable();
+
+            if shared.receiver_closed.load(Ordering::Acquire) {
+                return Err(OkxError::Ws("write channel closed".into()));
+            }
+            {
+                let mut frames = shared.lock();
+                if frames.len() < shared.capacity {
+                    frames.push_back(frame);
+                    drop(frames);
+                    shared.readable.notify_one();
+                    return Ok(());
+                }
+                match shared.policy {
+                    WriteOverflowPolicy::Block => {}
+                    WriteOverflowPolicy::DropOldest => {
+                        frames.pop_front();
+                        frames.push_back(frame);
+                        drop(frames);
+                        warn!(
+                            "WS {} write buffer full, dropped oldest frame",
+                            shared.conn_type
+                        );
+                        shared.readable.notify_one();
+                        return Ok(());
+                    }
+                    WriteOverflowPolicy::Error => {
+                        return Err(OkxError::Ws(format!(
+                            "{} write buffer full ({} frames)",
+                            shared.conn_type, shared.capacity
+                        )));
+                    }
+                }
+            }
+            writable.await;
+        }
+    }
+
+    /// Whether the write task has stopped.
+    pub fn is_closed(&self) -> bool {
+        self.shared.receiver_closed.load(Ordering::Acquire)
+    }
+
+    /// Number of frames waiting to be written.
+    pub fn len(&self) -> usize {
+        self.shared.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Clone for WriteSender {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::AcqRel);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl Drop for WriteSender {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.readable.notify_one();
+        }
+    }
+}
+
+impl WriteReceiver {
+    /// Next frame, or `None` once every sender is gone and the queue is
+    /// drained.
+    pub async fn recv(&mut self) -> Option<WriteFrame> {
+        let shared = &self.shared;
+        loop {
+            let readable = shared.readable.notified();
+            tokio::pin!(readable);
+            readable.as_mut().enable();
+
+            if let Some(frame) = shared.lock().pop_front() {
+                shared.writable.notify_one();
+                return Some(frame);
+            }
+            if shared.senders.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            readable.await;
+        }
+    }
+}
+
+impl Drop for WriteReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_closed.store(true, Ordering::Release);
+        self.shared.writable.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn queue(policy: WriteOverflowPolicy) -> (WriteSender, WriteReceiver) {
+        write_queue(WsConnectionType::Public, 2, policy)
+    }
+
+    fn text(frame: &str) -> Option<WriteFrame> {
+        Some(WriteFrame::Text(frame.into()))
+    }
+
+    #[tokio::test]
+    async fn full_queue_follows_policy() {
+        let (tx, mut rx) = queue(WriteOverflowPolicy::Error);
+        tx.send("a".into()).await.unwrap();
+        tx.send("b".into()).await.unwrap();
+        assert!(tx.send("c".into()).await.is_err());
+        assert_eq!(rx.recv().await, text("a"));
+
+        let (tx, mut rx) = queue(WriteOverflowPolicy::DropOldest);
+        for frame in ["a", "b", "c"] {
+            tx.send(frame.into()).await.unwrap();
+        }
+        assert_eq!(tx.len(), 2);
+        assert_eq!(rx.recv().await, text("b"));
+        assert_eq!(rx.recv().await, text("c"));
+    }
+
+    #[tokio::test]
+    async fn blocked_sender_waits_for_space() {
+        let (tx, mut rx) = queue(WriteOverflowPolicy::Block);
+        tx.send("a".into()).await.unwrap();
+        tx.send("b".into()).await.unwrap();
+
+        let blocked = tokio::spawn({
+            let tx = tx.clone();
+            async move { tx.send("c".into()).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!blocked.is_finished());
+
+        assert_eq!(rx.recv().await, text("a"));
+        blocked.await.unwrap().unwrap();
+        assert_eq!(tx.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn closing_either_side_ends_the_queue() {
+        let (tx, mut rx) = queue(WriteOverflowPolicy::Block);
+        tx.send("a".into()).await.unwrap();
+        drop(tx);
+        assert_eq!(rx.recv().await, text("a"));
+        assert_eq!(rx.recv().await, None);
+
+        let (tx, rx) = queue(WriteOverflowPolicy::Block);
+        tx.send("a".into()).await.unwrap();
+        tx.send("b".into()).await.unwrap();
+        let blocked = tokio::spawn({
+            let tx = tx.clone();
+            async move { tx.send("c".into()).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(rx);
+        assert!(blocked.await.unwrap().is_err());
+    }
+}
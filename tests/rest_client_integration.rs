@@ -1,11 +1,55 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use okx_client::auth;
 use okx_client::constants;
 use okx_client::error::OkxError;
-use okx_client::types::enums::PosMode;
-use okx_client::types::request::account::{GetBalanceRequest, SetPositionModeRequest};
+use okx_client::maintenance::{MaintenanceConfig, MaintenanceGuard};
+use okx_client::orders::{OrderTracker, OrderUpdate};
+use okx_client::reconcile::{reconcile, ExpectedOrder, ExpectedState, ReconcileOptions};
+use okx_client::rest::archive::ArchiveOptions;
+use okx_client::rest::download::DownloadOptions;
+use okx_client::rest::kill_switch::{FlattenFailure, FlattenOptions};
+use okx_client::retry::RetryPolicy;
+use okx_client::test_util::{
+    mock_balance, mock_cancelled_order, mock_order_rejected, mock_order_result,
+    mock_pending_algo_order, mock_pending_order, mock_position, okx_ok, MockOkxRest,
+    ValidSignature, MOCK_API_KEY, MOCK_API_SECRET, MOCK_PASSPHRASE,
+};
+use okx_client::types::enums::{
+    AutoEarnAction, Bar, BillType, BorrowRepaySide, EarnType, FeeType, OrderSide, PosMode, Quarter,
+    TradeMode, TransferAccount, TransferStatus,
+};
+use okx_client::types::request::account::{
+    BillsArchiveRequest, BorrowRepayRequest, GetBalanceRequest, GetInterestLimitsRequest,
+    GetMmpConfigRequest, GetQuickMarginBorrowRepayHistoryRequest, MmpResetRequest,
+    SetAutoEarnRequest, SetFeeTypeRequest, SetPositionModeRequest, SimulatedMarginRequest,
+    SimulatedPosition,
+};
+use okx_client::types::request::block_trading::{
+    GetMovePositionsHistoryRequest, MovePositionFrom, MovePositionLeg, MovePositionTo,
+    MovePositionsRequest,
+};
+use okx_client::types::request::broker::{
+    BrokerDeleteSubAccountRequest, BrokerGetSubAccountsRequest, BrokerRebatePerOrdersRequest,
+};
+use okx_client::types::request::funding::{
+    CancelWithdrawalRequest, ConvertDustAssetsRequest, GetAssetBillsRequest,
+    GetAssetValuationRequest, GetDepositWithdrawStatusRequest,
+};
+use okx_client::types::request::signal_bot::{
+    GetSignalBotEventHistoryRequest, GetSignalBotPositionsRequest, SignalAmendTpSlRequest,
+    SignalExitSetting, SignalMarginBalanceRequest,
+};
+use okx_client::types::request::trade::{
+    EasyConvertRequest, FillsArchiveRequest, GetOneClickRepayHistoryRequest,
+    OneClickRepayV2Request, OrderRequest,
+};
 use okx_client::{ClientConfigBuilder, RestClient, TradingMode};
+use secrecy::SecretString;
 use serde_json::Value;
-use wiremock::matchers::{method, path};
-use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Match, Mock, MockServer, ResponseTemplate};
 
 fn header_value(request: &wiremock::Request, name: &str) -> String {
     request
@@ -92,6 +136,111 @@ async fn signed_get_includes_auth_and_demo_headers() {
     assert_eq!(header_value(request, "x-simulated-trading"), "1");
 }
 
+#[tokio::test]
+async fn trading_mode_can_differ_per_client_on_one_pool() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0", "msg": "", "data": [{"ts": "1"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new().base_url(&server.uri()).build();
+    let live = RestClient::new(config).expect("client should build");
+    let demo = live.with_trading_mode(TradingMode::Demo);
+    live.get_server_time().await.unwrap();
+    demo.get_server_time().await.unwrap();
+    live.get_server_time().await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let simulated: Vec<String> = requests
+        .iter()
+        .map(|r| header_value(r, "x-simulated-trading"))
+        .collect();
+    assert_eq!(simulated, ["", "1", ""]);
+}
+
+#[tokio::test]
+async fn user_agent_and_extra_headers_are_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0", "msg": "", "data": [{"ts": "1"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new()
+        .base_url(&server.uri())
+        .user_agent("my-bot/1.0")
+        .extra_header("X-Broker-Id", "acme")
+        .build();
+    let client = RestClient::new(config).expect("client should build");
+    client.get_server_time().await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(header_value(&requests[0], "user-agent"), "my-bot/1.0");
+    assert_eq!(header_value(&requests[0], "x-broker-id"), "acme");
+}
+
+#[tokio::test]
+async fn signed_get_signs_the_exact_query_sent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/account/balance"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [{}]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new()
+        .base_url(&server.uri())
+        .credentials("test-api-key", "test-api-secret", "test-passphrase")
+        .build();
+    let client = RestClient::new(config).expect("client should build");
+    let secret = SecretString::from("test-api-secret".to_string());
+
+    let values = ["BTC,ETH", "BTC ETH", "Ünï☃de", "a+b/c?d=e&f"];
+    for ccy in values {
+        client
+            .get_balance(&GetBalanceRequest {
+                ccy: Some(ccy.to_string()),
+            })
+            .await
+            .expect("signed request should succeed");
+    }
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("should capture requests");
+    assert_eq!(requests.len(), values.len());
+
+    for (request, ccy) in requests.iter().zip(values) {
+        let pairs: Vec<_> = request.url.query_pairs().collect();
+        assert_eq!(pairs, vec![("ccy".into(), ccy.into())]);
+
+        let request_path = format!("{}?{}", request.url.path(), request.url.query().unwrap());
+        let expected = auth::sign_rest(
+            &header_value(request, "ok-access-timestamp"),
+            "GET",
+            &request_path,
+            "",
+            &secret,
+            None,
+        )
+        .unwrap();
+        assert_eq!(header_value(request, "ok-access-sign"), expected, "ccy={ccy}");
+    }
+}
+
 #[tokio::test]
 async fn signed_post_injects_program_tag() {
     let server = MockServer::start().await;
@@ -139,6 +288,47 @@ async fn signed_post_injects_program_tag() {
     assert!(!header_value(request, "ok-access-sign").is_empty());
 }
 
+#[tokio::test]
+async fn gzip_responses_are_decompressed() {
+    use std::io::Write;
+
+    let server = MockServer::start().await;
+
+    let json = serde_json::json!({
+        "code": "0",
+        "msg": "",
+        "data": [{ "ts": "1700000000000" }]
+    });
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(json.to_string().as_bytes()).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Encoding", "gzip")
+                .set_body_raw(gzipped, "application/json"),
+        )
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new().base_url(&server.uri()).build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let result = client
+        .get_server_time()
+        .await
+        .expect("request should succeed");
+    assert_eq!(result[0].ts, "1700000000000");
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("should capture requests");
+    assert!(header_value(&requests[0], "accept-encoding").contains("gzip"));
+}
+
 #[tokio::test]
 async fn private_endpoint_without_credentials_fails_before_http_request() {
     let server = MockServer::start().await;
@@ -158,3 +348,1454 @@ async fn private_endpoint_without_credentials_fails_before_http_request() {
         .expect("should capture requests");
     assert!(requests.is_empty());
 }
+
+#[tokio::test]
+async fn api_error_carries_request_context() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .insert_header("x-request-id", "a1b2c3")
+                .set_body_json(serde_json::json!({
+                    "code": "50014",
+                    "msg": "Parameter instId can not be empty",
+                    "data": []
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new().base_url(&server.uri()).build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let err = client.get_server_time().await.expect_err("must fail");
+    assert_eq!(err.api_code(), Some("50014"));
+
+    let context = err.request_context().expect("should carry context");
+    assert_eq!(context.method, "GET");
+    assert_eq!(context.endpoint, "/api/v5/public/time");
+    assert_eq!(context.status, Some(400));
+    assert!(context.body.as_deref().unwrap().contains("50014"));
+    assert_eq!(err.request_id(), Some("a1b2c3"));
+    assert!(err.to_string().starts_with("GET /api/v5/public/time (HTTP 400) [request a1b2c3]"));
+}
+
+#[tokio::test]
+async fn only_idempotent_requests_are_resent() {
+    let mock = MockOkxRest::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "50011",
+            "msg": "Rate limit reached",
+            "data": []
+        })))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(mock.server())
+        .await;
+    mock.respond(
+        "GET",
+        "/api/v5/public/time",
+        vec![serde_json::json!({ "ts": "1" })],
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v5/trade/order"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(mock.server())
+        .await;
+
+    let policy = RetryPolicy {
+        min_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(1),
+        ..RetryPolicy::default()
+    };
+    let mut config = mock.config();
+    config.retry_policy = policy;
+    let client = RestClient::new(config).expect("client should build");
+
+    // A rate limit code in an HTTP 200 body is retried.
+    let time = client.get_server_time().await.expect("retried");
+    assert_eq!(time[0].ts, "1");
+
+    // A POST that got a 5xx may have been executed, so it is sent once.
+    let err = client
+        .place_order(&OrderRequest::buy_spot_with_quote("BTC-USDT", "100"))
+        .await
+        .expect_err("must fail");
+    assert_eq!(err.request_context().unwrap().status, Some(500));
+
+    let requests = mock.received_requests().await;
+    let count = |m: &str| requests.iter().filter(|r| r.method.as_str() == m).count();
+    assert_eq!(count("GET"), 2);
+    assert_eq!(count("POST"), 1);
+}
+
+#[tokio::test]
+async fn fills_archive_workflow_applies_polls_and_parses_download() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v5/trade/fills-archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [{ "result": "true", "ts": "1700000000000" }]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/trade/fills-archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [{
+                "fileHref": format!("{}/files/fills.csv", server.uri()),
+                "state": "finished",
+                "ts": "1700000000000"
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let csv = "instType,instId,tradeId,ordId,clOrdId,billId,tag,fillPx,fillSz,side,posSide,\
+execType,feeCcy,fee,ts,fillTime,fillPnl,fillPxVol,fillPxUsd,fillMarkVol,fillFwdPx,fillMarkPx\n\
+SPOT,BTC-USDT,1,2,,3,,42000,0.5,buy,net,T,BTC,-0.0001,1700000000000,1700000000000,0,,,,,\n";
+    Mock::given(method("GET"))
+        .and(path("/files/fills.csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(csv))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new()
+        .base_url(&server.uri())
+        .credentials("test-api-key", "test-api-secret", "test-passphrase")
+        .build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let fills: Vec<_> = client
+        .download_fills_archive(
+            &FillsArchiveRequest {
+                year: "2024".to_string(),
+                quarter: Quarter::Q1,
+            },
+            &ArchiveOptions::default(),
+        )
+        .await
+        .expect("archive should download")
+        .collect()
+        .await;
+
+    assert_eq!(fills.len(), 1);
+    let fill = fills[0].as_ref().expect("fill should parse");
+    assert_eq!(fill.inst_id, "BTC-USDT");
+    assert_eq!(fill.fill_sz, "0.5");
+}
+
+#[tokio::test]
+async fn bills_history_archive_workflow_applies_polls_and_parses_download() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v5/account/bills-history-archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [{ "result": "true", "ts": "1700000000000" }]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/account/bills-history-archive"))
+        .and(query_param("year", "2024"))
+        .and(query_param("quarter", "Q2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [{
+                "fileHref": format!("{}/files/bills.csv", server.uri()),
+                "state": "finished",
+                "ts": "1700000000000"
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let csv = "billId,instType,instId,ccy,mgnMode,type,subType,ts,balChg,bal,sz,px,fee\n\
+1,SPOT,BTC-USDT,USDT,isolated,2,1,1700000000000,-21000,79000,0.5,42000,-0.0001\n";
+    Mock::given(method("GET"))
+        .and(path("/files/bills.csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(csv))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new()
+        .base_url(&server.uri())
+        .credentials("test-api-key", "test-api-secret", "test-passphrase")
+        .build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let bills: Vec<_> = client
+        .download_bills_history_archive(
+            &BillsArchiveRequest {
+                year: "2024".to_string(),
+                quarter: Quarter::Q2,
+            },
+            &ArchiveOptions::default(),
+        )
+        .await
+        .expect("archive should download")
+        .collect()
+        .await;
+
+    assert_eq!(bills.len(), 1);
+    let bill = bills[0].as_ref().expect("bill should parse");
+    assert_eq!(bill.bill_id, "1");
+    assert_eq!(bill.type_, BillType::Trade);
+    assert_eq!(bill.bal_chg, "-21000");
+}
+
+#[tokio::test]
+async fn candle_downloader_chunks_range_and_yields_oldest_first() {
+    let server = MockServer::start().await;
+    let minute = 60_000i64;
+    let row = |ts: i64| serde_json::json!([ts.to_string(), "1", "1", "1", "1", "1", "1", "1", "1"]);
+
+    // Window [0, 100m): OKX returns newest first.
+    Mock::given(method("GET"))
+        .and(path("/api/v5/market/history-candles"))
+        .and(query_param("after", (100 * minute).to_string()))
+        .and(query_param("before", "-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [row(99 * minute), row(minute), row(0)]
+        })))
+        .mount(&server)
+        .await;
+
+    // Window [100m, 150m).
+    Mock::given(method("GET"))
+        .and(path("/api/v5/market/history-candles"))
+        .and(query_param("after", (150 * minute).to_string()))
+        .and(query_param("before", (100 * minute - 1).to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0",
+            "msg": "",
+            "data": [row(100 * minute)]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new().base_url(&server.uri()).build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let candles = client
+        .candle_downloader("BTC-USDT", Bar::M1)
+        .options(DownloadOptions {
+            request_interval: std::time::Duration::ZERO,
+        })
+        .collect(0..150 * minute)
+        .await
+        .expect("download should succeed");
+
+    let ts: Vec<i64> = candles.iter().map(|c| c.ts.parse().unwrap()).collect();
+    assert_eq!(ts, vec![0, minute, 99 * minute, 100 * minute]);
+}
+
+#[tokio::test]
+async fn download_trades_paginates_by_trade_id_and_dedups() {
+    let server = MockServer::start().await;
+    let trade = |id: u32, ts: i64| {
+        serde_json::json!({
+            "instId": "BTC-USDT", "tradeId": id.to_string(), "px": "1",
+            "sz": "1", "side": "buy", "ts": ts.to_string()
+        })
+    };
+
+    // First page by timestamp: 100 trades with IDs 300..201, all at ts 2000.
+    let first: Vec<_> = (201..=300).rev().map(|id| trade(id, 2000)).collect();
+    Mock::given(method("GET"))
+        .and(path("/api/v5/market/history-trades"))
+        .and(query_param("type", "2"))
+        .and(query_param("after", "3000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0", "msg": "", "data": first
+        })))
+        .mount(&server)
+        .await;
+
+    // Next page by trade ID overlaps the first by one trade and crosses the start.
+    Mock::given(method("GET"))
+        .and(path("/api/v5/market/history-trades"))
+        .and(query_param("type", "1"))
+        .and(query_param("after", "201"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": "0", "msg": "",
+            "data": [trade(201, 2000), trade(200, 1500), trade(199, 500)]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = ClientConfigBuilder::new().base_url(&server.uri()).build();
+    let client = RestClient::new(config).expect("client should build");
+
+    let trades = client
+        .download_trades(
+            "BTC-USDT",
+            1000,
+            3000,
+            &DownloadOptions {
+                request_interval: std::time::Duration::ZERO,
+            },
+        )
+        .await
+        .expect("download should succeed");
+
+    assert_eq!(trades.len(), 101);
+    assert_eq!(trades[0].trade_id, "200");
+    assert_eq!(trades[100].trade_id, "300");
+}
+
+#[tokio::test]
+async fn mock_rest_fixtures_require_valid_signatures() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/balance",
+        vec![mock_balance("USDT", "1000")],
+    )
+    .await;
+
+    let balance = mock
+        .client()
+        .get_balance(&GetBalanceRequest {
+            ccy: Some("USDT".to_string()),
+        })
+        .await
+        .expect("signed request should match");
+    assert_eq!(balance[0].details[0].ccy, "USDT");
+    assert_eq!(balance[0].details[0].avail_eq, "1000");
+
+    let wrong_secret = RestClient::new(
+        ClientConfigBuilder::new()
+            .base_url(&mock.uri())
+            .credentials(MOCK_API_KEY, "other-secret", MOCK_PASSPHRASE)
+            .build(),
+    )
+    .unwrap();
+    let err = wrong_secret
+        .get_balance(&GetBalanceRequest::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_code(), Some("50113"), "{err:?}");
+}
+
+#[tokio::test]
+async fn mock_rest_signature_covers_post_body() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/set-position-mode",
+        vec![serde_json::json!({"posMode": "long_short_mode"})],
+    )
+    .await;
+
+    mock.client()
+        .set_position_mode(&SetPositionModeRequest {
+            pos_mode: PosMode::LongShortMode,
+        })
+        .await
+        .expect("signed POST should match");
+
+    let requests = mock.received_requests().await;
+    assert!(
+        ValidSignature::new(MOCK_API_KEY, MOCK_API_SECRET, MOCK_PASSPHRASE).matches(&requests[0])
+    );
+
+    let mut tampered = requests[0].clone();
+    tampered.body = br#"{"posMode":"net_mode"}"#.to_vec();
+    assert!(
+        !ValidSignature::new(MOCK_API_KEY, MOCK_API_SECRET, MOCK_PASSPHRASE).matches(&tampered)
+    );
+}
+
+#[tokio::test]
+async fn sub_account_balances_aggregate_per_currency() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/users/subaccount/list",
+        vec![
+            serde_json::json!({"subAcct": "alpha", "ts": "1700000000002"}),
+            serde_json::json!({"subAcct": "beta", "ts": "1700000000001"}),
+        ],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/subaccount/balances",
+        vec![mock_balance("USDT", "100")],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/asset/subaccount/balances",
+        vec![
+            serde_json::json!({"ccy": "USDT", "bal": "5", "availBal": "5"}),
+            serde_json::json!({"ccy": "BTC", "bal": "0.5", "availBal": "0.5"}),
+        ],
+    )
+    .await;
+
+    let totals = mock
+        .client()
+        .get_all_subaccount_balances()
+        .await
+        .expect("aggregation should succeed");
+
+    let usdt = &totals["USDT"];
+    assert_eq!(usdt.trading, 200.0);
+    assert_eq!(usdt.funding, 10.0);
+    assert_eq!(usdt.total(), 210.0);
+    assert_eq!(usdt.by_sub_account["alpha"], (100.0, 5.0));
+    assert_eq!(totals["BTC"].funding, 1.0);
+    assert_eq!(totals["BTC"].by_sub_account.len(), 2);
+
+    let requests = mock.received_requests().await;
+    assert!(requests
+        .iter()
+        .any(|r| r.url.query().is_some_and(|q| q.contains("subAcct=beta"))));
+}
+
+#[tokio::test]
+async fn transfer_to_subaccount_polls_state() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/asset/transfer",
+        vec![serde_json::json!({"transId": "754147", "ccy": "USDT", "amt": "1.5"})],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/asset/transfer-state",
+        vec![serde_json::json!({
+            "transId": "754147", "ccy": "USDT", "amt": "1.5", "type": "1",
+            "from": "6", "to": "18", "subAcct": "sub1", "state": "success",
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let rejected = client
+        .transfer_to_subaccount(
+            "",
+            "USDT",
+            "1.5",
+            TransferAccount::Funding,
+            TransferAccount::Trading,
+        )
+        .await;
+    assert!(matches!(rejected, Err(OkxError::Config(_))));
+
+    let transfer = client
+        .transfer_to_subaccount(
+            "sub1",
+            "USDT",
+            "1.5",
+            TransferAccount::Funding,
+            TransferAccount::Trading,
+        )
+        .await
+        .expect("transfer should be accepted");
+    assert_eq!(transfer.trans_id(), "754147");
+    let state = transfer
+        .wait(Duration::from_millis(10), Duration::from_secs(1))
+        .await
+        .expect("state should resolve");
+    assert_eq!(state.state, TransferStatus::Success);
+    assert_eq!(state.sub_acct, "sub1");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["type"], "1");
+    assert_eq!(body["subAcct"], "sub1");
+    assert_eq!(body["to"], "18");
+    assert!(requests[1].url.query().unwrap().contains("transId=754147"));
+}
+
+#[tokio::test]
+async fn asset_bills_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/asset/bills",
+        vec![serde_json::json!({
+            "billId": "12344", "ccy": "BTC", "clientId": "", "balChg": "2",
+            "bal": "12", "type": "1", "notes": "", "ts": "1597026383085",
+        })],
+    )
+    .await;
+
+    let bills = mock
+        .client()
+        .get_asset_bills(&GetAssetBillsRequest {
+            ccy: Some("BTC".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("bills should parse");
+    assert_eq!(bills[0].bill_id, "12344");
+    assert_eq!(bills[0].bal_chg, "2");
+    assert_eq!(bills[0].type_, "1");
+
+    let requests = mock.received_requests().await;
+    assert_eq!(requests[0].url.query(), Some("ccy=BTC"));
+}
+
+#[tokio::test]
+async fn asset_valuation_and_dust_conversion_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/asset/asset-valuation",
+        vec![serde_json::json!({
+            "totalBal": "24.5", "ts": "1637746405000",
+            "details": {"funding": "0.5", "trading": "24", "classic": "0", "earn": "0"},
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/asset/convert-dust-assets",
+        vec![serde_json::json!({
+            "totalCnvAmt": "0.01",
+            "details": [{"ccy": "ADA", "amt": "0.3", "cnvAmt": "0.01", "fee": "0"}],
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let valuation = client
+        .get_asset_valuation(&GetAssetValuationRequest {
+            ccy: Some("USDT".into()),
+        })
+        .await
+        .expect("valuation should parse");
+    assert_eq!(valuation[0].total_bal, "24.5");
+    assert_eq!(valuation[0].details.trading, "24");
+
+    let converted = client
+        .convert_dust_assets(&ConvertDustAssetsRequest {
+            ccy: vec!["ADA".into()],
+        })
+        .await
+        .expect("conversion should parse");
+    assert_eq!(converted[0].details[0].cnv_amt, "0.01");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body["ccy"], serde_json::json!(["ADA"]));
+}
+
+#[tokio::test]
+async fn cancel_withdrawal_and_status_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/asset/cancel-withdrawal",
+        vec![serde_json::json!({"wdId": "1123456"})],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/asset/deposit-withdraw-status",
+        vec![serde_json::json!({
+            "wdId": "1123456", "txId": "", "state": "Withdrawal cancelled",
+            "estCompleteTime": "",
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let cancelled = client
+        .cancel_withdrawal(&CancelWithdrawalRequest {
+            wd_id: "1123456".into(),
+        })
+        .await
+        .expect("cancel should parse");
+    assert_eq!(cancelled[0].wd_id, "1123456");
+
+    let status = client
+        .get_deposit_withdraw_status(&GetDepositWithdrawStatusRequest {
+            wd_id: Some("1123456".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("status should parse");
+    assert_eq!(status[0].state, "Withdrawal cancelled");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["wdId"], "1123456");
+    assert_eq!(requests[1].url.query(), Some("wdId=1123456"));
+}
+
+#[tokio::test]
+async fn batch_partial_success_keeps_top_level_code() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_json(
+        "POST",
+        "/api/v5/trade/batch-orders",
+        serde_json::json!({
+            "code": "2",
+            "msg": "",
+            "data": [
+                mock_order_result("1", "a"),
+                mock_order_rejected("b", "51008", "Insufficient balance"),
+            ],
+        }),
+    )
+    .await;
+    let orders = vec![OrderRequest::default(), OrderRequest::default()];
+
+    let err = mock
+        .client()
+        .place_multiple_orders(&orders)
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_code(), Some("2"));
+
+    let resp = mock
+        .client()
+        .place_multiple_orders_with_meta(&orders)
+        .await
+        .unwrap();
+    assert!(resp.is_partial());
+    assert_eq!(resp.data[0].s_code, "0");
+    assert_eq!(resp.data[1].s_code, "51008");
+}
+
+#[tokio::test]
+async fn blocking_client_mirrors_signed_requests() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/balance",
+        vec![mock_balance("USDT", "1000")],
+    )
+    .await;
+    let config = mock.config();
+
+    let balance = tokio::task::spawn_blocking(move || {
+        let client = okx_client::rest::blocking::RestClient::new(config).unwrap();
+        client.get_balance(&GetBalanceRequest::default())
+    })
+    .await
+    .unwrap()
+    .expect("signed request should match");
+    assert_eq!(balance[0].details[0].avail_eq, "1000");
+}
+
+#[tokio::test]
+async fn maintenance_guard_rejects_orders_of_paused_families() {
+    let mock = MockOkxRest::start().await;
+    mock.respond(
+        "GET",
+        "/api/v5/system/status",
+        vec![serde_json::json!({
+            "title": "Spot and swap trading upgrade",
+            "state": "ongoing",
+            "begin": "0",
+            "end": "99999999999999",
+            "serviceType": "5",
+            "system": "classic",
+            "env": "1"
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/order",
+        vec![mock_order_result("1", "")],
+    )
+    .await;
+
+    let guard = MaintenanceGuard::new(MaintenanceConfig::default().inst_families(["BTC-USDT"]));
+    let mut config = mock.config();
+    config.maintenance_guard = Some(guard.clone());
+    let client = RestClient::new(config).unwrap();
+    guard.refresh(&client).await.unwrap();
+    assert_eq!(guard.windows().len(), 1);
+
+    let err = client
+        .place_order(&OrderRequest::buy_spot_with_quote("BTC-USDT", "100"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OkxError::Maintenance(_)), "{err:?}");
+
+    client
+        .place_order(&OrderRequest::buy_spot_with_quote("ETH-USDT", "100"))
+        .await
+        .expect("other families trade normally");
+    let orders = mock
+        .received_requests()
+        .await
+        .into_iter()
+        .filter(|r| r.url.path() == "/api/v5/trade/order")
+        .count();
+    assert_eq!(orders, 1);
+}
+
+#[tokio::test]
+async fn emergency_flatten_cancels_orders_and_closes_positions() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/trade/orders-pending",
+        vec![
+            mock_pending_order("BTC-USDT", "11", "30000", "0.1"),
+            mock_pending_order("ETH-USDT-SWAP", "12", "2000", "3"),
+        ],
+    )
+    .await;
+    mock.respond_json(
+        "POST",
+        "/api/v5/trade/cancel-batch-orders",
+        serde_json::json!({
+            "code": "2",
+            "msg": "",
+            "data": [
+                mock_cancelled_order("11", ""),
+                { "ordId": "12", "clOrdId": "", "ts": "", "sCode": "51400", "sMsg": "Order already filled" },
+            ],
+        }),
+    )
+    .await;
+    // Only the conditional/oco listing has an order; the other types are empty.
+    Mock::given(method("GET"))
+        .and(path("/api/v5/trade/orders-algo-pending"))
+        .and(query_param("ordType", "conditional,oco"))
+        .and(ValidSignature::new(
+            MOCK_API_KEY,
+            MOCK_API_SECRET,
+            MOCK_PASSPHRASE,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(okx_ok(vec![
+            mock_pending_algo_order("BTC-USDT-SWAP", "21", "conditional"),
+        ])))
+        .with_priority(1)
+        .mount(mock.server())
+        .await;
+    mock.respond_signed("GET", "/api/v5/trade/orders-algo-pending", vec![])
+        .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/cancel-algos",
+        vec![serde_json::json!({ "algoId": "21", "algoClOrdId": "", "sCode": "0", "sMsg": "" })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/positions",
+        vec![
+            mock_position("BTC-USDT-SWAP", "long", "5"),
+            mock_position("ETH-USDT-SWAP", "net", "0"),
+        ],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/close-position",
+        vec![serde_json::json!({ "instId": "BTC-USDT-SWAP", "posSide": "long" })],
+    )
+    .await;
+    let client = mock.client();
+
+    let preview = client
+        .emergency_flatten(&FlattenOptions { dry_run: true })
+        .await
+        .unwrap();
+    assert!(preview.dry_run && preview.is_complete());
+    assert_eq!(preview.cancelled_algos.len(), 1);
+    assert_eq!(preview.cancelled.len(), 2);
+    assert_eq!(preview.closed.len(), 1);
+    assert!(mock
+        .received_requests()
+        .await
+        .iter()
+        .all(|r| r.method.as_str() == "GET"));
+
+    let report = client
+        .emergency_flatten(&FlattenOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(report.cancelled_algos.len(), 1);
+    assert_eq!(report.cancelled_algos[0].algo_id, "21");
+    assert_eq!(report.cancelled.len(), 1);
+    assert_eq!(report.cancelled[0].ord_id, "11");
+    assert_eq!(report.closed.len(), 1);
+    assert_eq!(report.closed[0].inst_id, "BTC-USDT-SWAP");
+    match &report.failures[..] {
+        [FlattenFailure::Cancel { order, error }] => {
+            assert_eq!(order.ord_id, "12");
+            assert!(error.contains("51400"), "{error}");
+        }
+        other => panic!("unexpected failures: {other:?}"),
+    }
+
+    let requests = mock.received_requests().await;
+    let close = requests
+        .iter()
+        .find(|r| r.url.path() == "/api/v5/trade/close-position")
+        .unwrap();
+    let algo_cancel = requests
+        .iter()
+        .find(|r| r.url.path() == "/api/v5/trade/cancel-algos")
+        .unwrap();
+    let body: Value = serde_json::from_slice(&algo_cancel.body).unwrap();
+    assert_eq!(body[0]["algoId"], "21");
+    assert_eq!(body[0]["instId"], "BTC-USDT-SWAP");
+    let algo_listings = requests
+        .iter()
+        .filter(|r| r.url.path() == "/api/v5/trade/orders-algo-pending")
+        .count();
+    assert_eq!(algo_listings, 12);
+
+    let body: Value = serde_json::from_slice(&close.body).unwrap();
+    assert_eq!(body["posSide"], "long");
+    assert_eq!(body["mgnMode"], "cross");
+    assert_eq!(body["autoCxl"], true);
+}
+
+#[tokio::test]
+async fn order_tracker_load_merges_snapshot_by_update_time() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/trade/orders-pending",
+        vec![
+            mock_pending_order("BTC-USDT", "2", "30000", "0.1"),
+            mock_pending_order("BTC-USDT", "4", "30000", "0.1"),
+        ],
+    )
+    .await;
+    let update = |ord_id: &str, state: &str, u_time: &str| -> OrderUpdate {
+        serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT", "ordId": ord_id, "side": "buy", "px": "30000",
+            "sz": "0.1", "state": state, "uTime": u_time,
+        }))
+        .unwrap()
+    };
+    let tracker = OrderTracker::new();
+    // Gone from the snapshot, so closed while nobody was watching.
+    tracker.apply(&update("1", "live", "1"));
+    // Closed after the snapshot was taken.
+    tracker.apply(&update("2", "canceled", "1700000000001"));
+    // Placed after the snapshot was requested.
+    tracker.apply(&update("3", "live", "9999999999999"));
+
+    tracker.load(&mock.client()).await.unwrap();
+
+    let mut open: Vec<_> = tracker
+        .open_orders()
+        .into_iter()
+        .map(|o| o.ord_id)
+        .collect();
+    open.sort();
+    assert_eq!(open, ["3", "4"]);
+}
+
+#[tokio::test]
+async fn reconcile_classifies_and_cancels_orphans() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/trade/orders-pending",
+        vec![
+            mock_pending_order("BTC-USDT", "11", "30000", "0.1"),
+            mock_pending_order("BTC-USDT", "12", "29000", "0.1"),
+        ],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/positions",
+        vec![
+            mock_position("BTC-USDT-SWAP", "long", "5"),
+            mock_position("ETH-USDT-SWAP", "short", "2"),
+        ],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/cancel-batch-orders",
+        vec![mock_cancelled_order("12", "")],
+    )
+    .await;
+
+    let expected = ExpectedState {
+        orders: vec![
+            ExpectedOrder {
+                inst_id: "BTC-USDT".into(),
+                ord_id: "11".into(),
+                ..Default::default()
+            },
+            ExpectedOrder {
+                inst_id: "BTC-USDT".into(),
+                cl_ord_id: "gone".into(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+    .position("BTC-USDT-SWAP", 5.0);
+
+    let report = reconcile(
+        &mock.client(),
+        &expected,
+        &ReconcileOptions {
+            cancel_orphans: true,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(!report.is_consistent());
+    assert_eq!(report.orphan_orders.len(), 1);
+    assert_eq!(report.orphan_orders[0].ord_id, "12");
+    assert_eq!(report.missing_orders.len(), 1);
+    assert_eq!(report.missing_orders[0].cl_ord_id, "gone");
+    assert_eq!(report.unexpected_positions.len(), 1);
+    let position = &report.unexpected_positions[0];
+    assert_eq!(position.inst_id, "ETH-USDT-SWAP");
+    assert_eq!((position.actual, position.expected), (-2.0, 0.0));
+    assert_eq!(report.cancelled.len(), 1);
+    assert!(report.cancel_failures.is_empty());
+}
+
+#[tokio::test]
+async fn fee_type_and_auto_earn_settings_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/set-fee-type",
+        vec![serde_json::json!({"feeType": "1"})],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/set-auto-earn",
+        vec![serde_json::json!({
+            "earnType": "0", "ccy": "USDT", "action": "turn_on", "apr": "0.01",
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let fee_type = client
+        .set_fee_type(&SetFeeTypeRequest {
+            fee_type: FeeType::QuoteCurrency,
+        })
+        .await
+        .expect("fee type should parse");
+    assert_eq!(fee_type[0].fee_type, FeeType::QuoteCurrency);
+
+    let auto_earn = client
+        .set_auto_earn(&SetAutoEarnRequest {
+            earn_type: None,
+            ccy: "USDT".into(),
+            action: AutoEarnAction::TurnOn,
+        })
+        .await
+        .expect("auto earn should parse");
+    assert_eq!(auto_earn[0].earn_type, EarnType::AutoEarn);
+    assert_eq!(auto_earn[0].action, Some(AutoEarnAction::TurnOn));
+    assert_eq!(auto_earn[0].apr, "0.01");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["feeType"], "1");
+    let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body["action"], "turn_on");
+    assert!(body.get("earnType").is_none());
+}
+
+#[tokio::test]
+async fn borrow_repay_and_interest_limits_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/borrow-repay",
+        vec![serde_json::json!({
+            "ccy": "USDT", "side": "borrow", "amt": "100", "ordId": "1", "state": "1",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/quick-margin-borrow-repay-history",
+        vec![serde_json::json!({
+            "instId": "BTC-USDT", "ccy": "USDT", "side": "repay", "amt": "50",
+            "accBorrowed": "50", "refId": "7", "ts": "1700000000000",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/interest-limits",
+        vec![serde_json::json!({
+            "debt": "100", "interest": "0.1", "nextDiscountTime": "", "nextInterestTime": "",
+            "loanAlloc": "", "records": [{"ccy": "USDT", "rate": "0.0001", "usedLmt": "100"}],
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let borrowed = client
+        .borrow_repay(&BorrowRepayRequest {
+            ccy: "USDT".into(),
+            side: BorrowRepaySide::Borrow,
+            amt: "100".into(),
+            ..Default::default()
+        })
+        .await
+        .expect("borrow should parse");
+    assert_eq!(borrowed[0].side, BorrowRepaySide::Borrow);
+    assert_eq!(borrowed[0].ord_id, "1");
+
+    let history = client
+        .get_quick_margin_borrow_repay_history(&GetQuickMarginBorrowRepayHistoryRequest {
+            inst_id: Some("BTC-USDT".into()),
+            side: Some(BorrowRepaySide::Repay),
+            ..Default::default()
+        })
+        .await
+        .expect("quick margin history should parse");
+    assert_eq!(history[0].side, BorrowRepaySide::Repay);
+    assert_eq!(history[0].acc_borrowed, "50");
+
+    let limits = client
+        .get_interest_limits(&GetInterestLimitsRequest {
+            ccy: Some("USDT".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("interest limits should parse");
+    assert_eq!(limits[0].records[0].used_lmt, "100");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["side"], "borrow");
+    assert_eq!(requests[1].url.query(), Some("instId=BTC-USDT&side=repay"));
+}
+
+#[tokio::test]
+async fn simulated_margin_sends_virtual_positions() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/simulated_margin",
+        vec![serde_json::json!({
+            "imr": "1200", "mmr": "800", "mr1": "700", "riskUnit": "BTC-USD",
+            "posData": [{
+                "instId": "BTC-USDT-SWAP", "instType": "SWAP", "mgnMode": "cross",
+                "pos": "-10", "notionalUsd": "4200",
+            }],
+            "ts": "1700000000000",
+        })],
+    )
+    .await;
+
+    let margin = mock
+        .client()
+        .simulated_margin(&SimulatedMarginRequest {
+            incl_real_pos: Some(false),
+            sim_pos: vec![SimulatedPosition {
+                inst_id: "BTC-USDT-SWAP".into(),
+                pos: "-10".into(),
+            }],
+            ..Default::default()
+        })
+        .await
+        .expect("simulated margin should parse");
+    assert_eq!(margin[0].mmr, "800");
+    assert_eq!(margin[0].pos_data[0].pos, "-10");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["inclRealPos"], false);
+    assert_eq!(body["simPos"][0]["instId"], "BTC-USDT-SWAP");
+    assert!(body.get("instType").is_none());
+}
+
+#[tokio::test]
+async fn mmp_frozen_state_and_reset_are_typed() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/account/mmp-config",
+        vec![serde_json::json!({
+            "instFamily": "BTC-USD", "timeInterval": "5000", "frozenInterval": "2000",
+            "qtyLimit": "100", "mmpFrozen": true, "mmpFrozenUntil": "1700000002000",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/account/mmp-reset",
+        vec![serde_json::json!({"result": true})],
+    )
+    .await;
+
+    let client = mock.client();
+    let config = client
+        .get_mmp_config(&GetMmpConfigRequest {
+            inst_family: Some("BTC-USD".into()),
+        })
+        .await
+        .expect("MMP config should parse");
+    assert!(config[0].mmp_frozen);
+    assert_eq!(config[0].mmp_frozen_until, "1700000002000");
+
+    let reset = client
+        .reset_mmp(&MmpResetRequest {
+            inst_family: "BTC-USD".into(),
+            ..Default::default()
+        })
+        .await
+        .expect("MMP reset should parse");
+    assert!(reset[0].result);
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body["instFamily"], "BTC-USD");
+    assert!(body.get("instType").is_none());
+}
+
+#[tokio::test]
+async fn move_positions_round_trip() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/rfq/move-positions",
+        vec![serde_json::json!({
+            "clientId": "move1", "blockTdId": "9", "state": "pending",
+            "fromAcct": "0", "toAcct": "sub1", "ts": "1700000000000",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/rfq/move-positions-history",
+        vec![serde_json::json!({
+            "clientId": "move1", "blockTdId": "9", "state": "filled",
+            "fromAcct": "0", "toAcct": "sub1", "ts": "1700000000000",
+            "legs": [{
+                "from": {
+                    "posId": "1", "instId": "BTC-USDT-SWAP", "px": "42000", "side": "sell",
+                    "sz": "1", "tdMode": "", "posSide": "", "ccy": "",
+                },
+                "to": {
+                    "posId": "", "instId": "BTC-USDT-SWAP", "px": "42000", "side": "buy",
+                    "sz": "1", "tdMode": "cross", "posSide": "net", "ccy": "",
+                },
+            }],
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let moved = client
+        .move_positions(&MovePositionsRequest {
+            client_id: "move1".into(),
+            from_acct: "0".into(),
+            to_acct: "sub1".into(),
+            legs: vec![MovePositionLeg {
+                from: MovePositionFrom {
+                    pos_id: "1".into(),
+                    sz: "1".into(),
+                    side: OrderSide::Sell,
+                },
+                to: MovePositionTo {
+                    td_mode: Some(TradeMode::Cross),
+                    ..Default::default()
+                },
+            }],
+        })
+        .await
+        .expect("move should parse");
+    assert_eq!(moved[0].block_td_id, "9");
+
+    let history = client
+        .get_move_positions_history(&GetMovePositionsHistoryRequest {
+            client_id: Some("move1".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("history should parse");
+    assert_eq!(history[0].state, "filled");
+    assert_eq!(history[0].legs[0].to.td_mode, "cross");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["legs"][0]["from"]["side"], "sell");
+    assert_eq!(
+        body["legs"][0]["to"],
+        serde_json::json!({"tdMode": "cross"})
+    );
+    assert_eq!(requests[1].url.query(), Some("clientId=move1"));
+}
+
+#[tokio::test]
+async fn signal_bot_margin_tp_sl_positions_and_events_round_trip() {
+    let mock = MockOkxRest::start().await;
+    for endpoint in [
+        "/api/v5/tradingBot/signal/margin-balance",
+        "/api/v5/tradingBot/signal/amendTPSL",
+    ] {
+        mock.respond_signed("POST", endpoint, vec![serde_json::json!({"algoId": "77"})])
+            .await;
+    }
+    mock.respond_signed(
+        "GET",
+        "/api/v5/tradingBot/signal/positions",
+        vec![serde_json::json!({
+            "algoId": "77", "instId": "BTC-USDT-SWAP", "instType": "SWAP", "mgnMode": "cross",
+            "posSide": "net", "pos": "2", "avgPx": "42000", "lever": "5", "upl": "12",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/tradingBot/signal/event-history",
+        vec![serde_json::json!({
+            "algoId": "77", "alertMsg": "{}", "eventType": "signal_processing",
+            "eventStatus": "success", "triggeredOrdData": [{"clOrdId": "abc"}],
+            "eventCtime": "1700000000000", "eventUtime": "1700000000000",
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let margin = client
+        .adjust_signal_bot_margin(&SignalMarginBalanceRequest {
+            algo_id: "77".into(),
+            type_: "add".into(),
+            amt: "10".into(),
+            ..Default::default()
+        })
+        .await
+        .expect("margin adjustment should parse");
+    assert_eq!(margin[0].algo_id, "77");
+
+    client
+        .amend_signal_bot_tp_sl(&SignalAmendTpSlRequest {
+            algo_id: "77".into(),
+            exit_setting_param: SignalExitSetting {
+                tp_sl_type: "pnl".into(),
+                tp_pct: Some("0.1".into()),
+                ..Default::default()
+            },
+        })
+        .await
+        .expect("TP/SL amendment should parse");
+
+    let positions = client
+        .get_signal_bot_positions(&GetSignalBotPositionsRequest {
+            algo_ord_type: "contract".into(),
+            algo_id: "77".into(),
+        })
+        .await
+        .expect("positions should parse");
+    assert_eq!(positions[0].pos, "2");
+    assert_eq!(positions[0].lever, "5");
+
+    let events = client
+        .get_signal_bot_event_history(&GetSignalBotEventHistoryRequest {
+            algo_id: "77".into(),
+            ..Default::default()
+        })
+        .await
+        .expect("events should parse");
+    assert_eq!(events[0].triggered_ord_data[0].cl_ord_id, "abc");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["type"], "add");
+    let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(body["exitSettingParam"]["tpPct"], "0.1");
+    assert!(body["exitSettingParam"].get("slPct").is_none());
+    assert_eq!(
+        requests[2].url.query(),
+        Some("algoOrdType=contract&algoId=77")
+    );
+}
+
+#[tokio::test]
+async fn broker_sub_accounts_and_rebate_files_round_trip() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/broker/nd/subaccount-info",
+        vec![serde_json::json!({
+            "totalPage": "1", "page": "1",
+            "details": [{
+                "subAcct": "desk1", "uid": "42", "label": "desk", "acctLv": "2",
+                "ts": "1700000000000",
+            }],
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/broker/nd/delete-subaccount",
+        vec![serde_json::json!({"subAcct": "desk1"})],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/broker/nd/rebate-per-orders",
+        vec![serde_json::json!({"type": "false", "ts": "1700000000000"})],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/broker/nd/rebate-per-orders",
+        vec![serde_json::json!({
+            "fileHref": "https://example.com/rebate.csv", "type": "2", "ts": "1700000000000",
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let page = client
+        .broker_get_sub_accounts(&BrokerGetSubAccountsRequest {
+            sub_acct: Some("desk1".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("sub-accounts should parse");
+    assert_eq!(page[0].details[0].uid, "42");
+
+    let deleted = client
+        .broker_delete_sub_account(&BrokerDeleteSubAccountRequest {
+            sub_acct: "desk1".into(),
+        })
+        .await
+        .expect("delete should parse");
+    assert_eq!(deleted[0].sub_acct, "desk1");
+
+    let range = BrokerRebatePerOrdersRequest {
+        begin: "20240101".into(),
+        end: "20240131".into(),
+    };
+    let applied = client
+        .broker_apply_rebate_per_orders(&range)
+        .await
+        .expect("rebate request should parse");
+    assert_eq!(applied[0].type_, "false");
+    let file = client
+        .broker_get_rebate_per_orders(&range)
+        .await
+        .expect("rebate file should parse");
+    assert_eq!(file[0].type_, "2");
+    assert_eq!(file[0].file_href, "https://example.com/rebate.csv");
+
+    let requests = mock.received_requests().await;
+    assert_eq!(requests[0].url.query(), Some("subAcct=desk1"));
+    let body: Value = serde_json::from_slice(&requests[2].body).unwrap();
+    assert_eq!(body["begin"], "20240101");
+    assert_eq!(requests[3].url.query(), Some("begin=20240101&end=20240131"));
+}
+
+#[tokio::test]
+async fn easy_convert_and_one_click_repay_v2_round_trip() {
+    let mock = MockOkxRest::start().await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/trade/easy-convert-currency-list",
+        vec![serde_json::json!({
+            "fromData": [{"fromCcy": "ADA", "fromAmt": "0.5"}],
+            "toCcy": ["USDT", "BTC", "OKB"],
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/easy-convert",
+        vec![serde_json::json!({
+            "fromCcy": "ADA", "fillFromSz": "0.5", "toCcy": "OKB", "fillToSz": "0.01",
+            "status": "running", "acct": "18", "uTime": "1700000000000",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "POST",
+        "/api/v5/trade/one-click-repay-v2",
+        vec![serde_json::json!({
+            "debtCcy": "USDC", "repayCcyList": ["USDT", "BTC"], "ts": "1700000000000",
+        })],
+    )
+    .await;
+    mock.respond_signed(
+        "GET",
+        "/api/v5/trade/one-click-repay-history-v2",
+        vec![serde_json::json!({
+            "debtCcy": "USDC", "fillDebtSz": "100", "repayCcyList": ["USDT", "BTC"],
+            "status": "filled", "ts": "1700000000000",
+            "ordIdInfo": [{
+                "ordId": "1", "instId": "USDC-USDT", "ordType": "ioc", "side": "buy",
+                "px": "1.001", "sz": "100", "fillPx": "1", "fillSz": "100",
+                "state": "filled", "cTime": "1700000000000",
+            }],
+        })],
+    )
+    .await;
+
+    let client = mock.client();
+    let list = client
+        .get_easy_convert_currency_list()
+        .await
+        .expect("currency list should parse");
+    assert_eq!(list[0].from_data[0].from_ccy, "ADA");
+    assert_eq!(list[0].to_ccy, ["USDT", "BTC", "OKB"]);
+
+    let converted = client
+        .easy_convert(&EasyConvertRequest {
+            from_ccy: vec!["ADA".into()],
+            to_ccy: "OKB".into(),
+        })
+        .await
+        .expect("convert should parse");
+    assert_eq!(converted[0].fill_to_sz, "0.01");
+
+    let repaid = client
+        .one_click_repay_v2(&OneClickRepayV2Request {
+            debt_ccy: "USDC".into(),
+            repay_ccy_list: vec!["USDT".into(), "BTC".into()],
+        })
+        .await
+        .expect("repay should parse");
+    assert_eq!(repaid[0].repay_ccy_list, ["USDT", "BTC"]);
+
+    let history = client
+        .get_one_click_repay_history_v2(&GetOneClickRepayHistoryRequest {
+            limit: Some("10".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("history should parse");
+    assert_eq!(history[0].ord_id_info[0].fill_sz, "100");
+
+    let requests = mock.received_requests().await;
+    let body: Value = serde_json::from_slice(&requests[2].body).unwrap();
+    assert_eq!(body["repayCcyList"], serde_json::json!(["USDT", "BTC"]));
+    assert_eq!(requests[3].url.query(), Some("limit=10"));
+}
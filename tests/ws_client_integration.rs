@@ -0,0 +1,868 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use okx_client::book::OrderBookFeed;
+use okx_client::candles::CandleAggregator;
+use okx_client::capture::{read_json_lines, Capture, JsonLinesSink};
+use okx_client::config::Credentials;
+use okx_client::error::OkxError;
+use okx_client::fills::{FillFeed, FillFeedOptions};
+use okx_client::oco::{place_oco, OcoLeg, OcoOutcome, OcoSide};
+use okx_client::persist::FileStorage;
+use okx_client::quoting::{QuoteEngine, QuoteLevel, TargetQuote};
+use okx_client::retry::RetryPolicy;
+use okx_client::risk::{RiskGuard, RiskLimits, RiskViolation};
+use okx_client::test_util::{mock_order_result, MockOkxRest, MockOkxWs};
+use okx_client::types::enums::{AlgoOrderType, Bar, OrderSide, OrderType, TradeMode};
+use okx_client::types::request::trade::{AlgoOrderRequest, OrderRequest};
+use okx_client::types::response::market::Ticker;
+use okx_client::types::ws::channels::{WsChannel, WsSubscriptionArg};
+use okx_client::types::ws::events::{WsConnectionType, WsMessage};
+use okx_client::ws::supervisor::TaskState;
+use okx_client::ws::types::{HeartbeatMode, WsConfig};
+use okx_client::ws::WebsocketClient;
+use okx_client::{ClientConfigBuilder, WsApiClient};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn business_topics_without_login_are_replayed_after_reconnect() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        reconnect_delay: Duration::from_millis(20),
+        ..mock.ws_config()
+    });
+
+    let arg = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    client.subscribe(vec![arg.clone()]).await.unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+    assert_eq!(mock.connections_accepted(), 1);
+
+    mock.disconnect_all();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .expect("topic not replayed after reconnect");
+    assert_eq!(mock.connections_accepted(), 2);
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn dropped_feeds_release_their_topics() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(mock.ws_config());
+
+    let books = WsSubscriptionArg::new(WsChannel::Books).inst_id("BTC-USDT");
+    let trades = WsSubscriptionArg::new(WsChannel::Trades).inst_id("BTC-USDT");
+    let book = OrderBookFeed::start(&client, "BTC-USDT", 10.0)
+        .await
+        .unwrap();
+    let candles = CandleAggregator::start(&client, &["BTC-USDT"], Duration::from_secs(10))
+        .await
+        .unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&trades))
+        .await
+        .unwrap();
+    assert!(mock.subscriptions().contains(&books));
+
+    drop(book);
+    drop(candles);
+    tokio::time::timeout(TIMEOUT, async {
+        while !mock.subscriptions().is_empty() {
+            mock.next_frame().await.unwrap();
+        }
+    })
+    .await
+    .expect("topics not unsubscribed");
+    assert!(client.subscriptions().await.is_empty());
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn failed_subscribe_releases_topics_of_every_connection() {
+    let mut mock = MockOkxWs::start().await;
+    let mut config = mock.ws_config();
+    config
+        .url_overrides
+        .insert(WsConnectionType::Business, "ws://127.0.0.1:1".into());
+    let client = WebsocketClient::new(config);
+
+    let tickers = WsSubscriptionArg::new(WsChannel::Tickers).inst_id("BTC-USDT");
+    let candles = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    let result = client
+        .subscribe(vec![tickers.clone(), candles.clone()])
+        .await;
+    assert!(result.is_err());
+    assert!(client.subscriptions().await.is_empty());
+
+    // The public topic that did go out is unsubscribed again.
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            let (_, frame) = mock.next_frame().await.unwrap();
+            if frame["op"] == "unsubscribe" {
+                break;
+            }
+        }
+    })
+    .await
+    .expect("public topic not unsubscribed");
+    assert!(mock.subscriptions().is_empty());
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn ping_frames_are_answered_with_pong() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        ping_interval: Duration::from_millis(20),
+        heartbeat: HeartbeatMode::Frame,
+        ..mock.ws_config()
+    });
+    let mut events = client.event_receiver();
+
+    // The mock only answers text pings, so a pong here came back for a
+    // Ping frame.
+    let arg = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    client.subscribe(vec![arg.clone()]).await.unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+    tokio::time::timeout(TIMEOUT, async {
+        while !matches!(*events.recv().await.unwrap(), WsMessage::Pong) {}
+    })
+    .await
+    .expect("no pong for ping frame");
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn handshake_sends_user_agent_and_extra_headers() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        client_config: ClientConfigBuilder::new()
+            .user_agent("my-bot/1.0")
+            .extra_header("x-desk", "alpha")
+            .build(),
+        ..mock.ws_config()
+    });
+
+    let arg = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    client.subscribe(vec![arg.clone()]).await.unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+
+    let headers = mock.handshake_headers(1).expect("handshake recorded");
+    assert_eq!(headers["user-agent"], "my-bot/1.0");
+    assert_eq!(headers["x-desk"], "alpha");
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn idle_connection_is_reconnected() {
+    let mut mock = MockOkxWs::start().await;
+    mock.ignore_pings(true);
+    let client = WebsocketClient::new(WsConfig {
+        ping_interval: Duration::from_millis(20),
+        idle_timeout: Some(Duration::from_millis(100)),
+        reconnect_delay: Duration::from_millis(20),
+        ..mock.ws_config()
+    });
+
+    let arg = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    client.subscribe(vec![arg.clone()]).await.unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+    tokio::time::timeout(TIMEOUT, async {
+        while mock.connections_accepted() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("idle connection not replaced");
+    mock.ignore_pings(false);
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+
+    client.shutdown().await;
+}
+
+/// Next `Connected` or `Disconnected` event.
+async fn next_lifecycle_event(
+    events: &mut tokio::sync::broadcast::Receiver<Arc<WsMessage>>,
+) -> Arc<WsMessage> {
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            let msg = events.recv().await.unwrap();
+            if matches!(*msg, WsMessage::Connected(_) | WsMessage::Disconnected(_)) {
+                return msg;
+            }
+        }
+    })
+    .await
+    .expect("no lifecycle event")
+}
+
+#[tokio::test]
+async fn lifecycle_events_carry_reconnect_details() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        reconnect_delay: Duration::from_millis(20),
+        ..mock.ws_config()
+    });
+    let mut events = client.event_receiver();
+
+    let arg = WsSubscriptionArg::candle("BTC-USDT", Bar::M1);
+    client.subscribe(vec![arg.clone()]).await.unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+    let WsMessage::Connected(info) = &*next_lifecycle_event(&mut events).await else {
+        panic!("expected Connected");
+    };
+    assert_eq!(info.attempt, 0);
+
+    mock.disconnect_all();
+    let WsMessage::Disconnected(info) = &*next_lifecycle_event(&mut events).await else {
+        panic!("expected Disconnected");
+    };
+    assert_eq!(info.conn_type, WsConnectionType::Business);
+    assert!(info.will_reconnect);
+    assert_eq!(info.attempt, 1);
+    let WsMessage::Connected(info) = &*next_lifecycle_event(&mut events).await else {
+        panic!("expected Connected");
+    };
+    assert_eq!(info.attempt, 1);
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn pushes_reach_typed_subscribers() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(mock.ws_config());
+
+    let mut candles = client
+        .subscribe_index_candles("BTC-USDT", Bar::H1)
+        .await
+        .unwrap();
+    let arg = candles.arg().clone();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        mock.push(
+            &arg,
+            vec![serde_json::json!(["1", "2", "3", "1", "2", "1"])]
+        ),
+        1
+    );
+    let batch = tokio::time::timeout(TIMEOUT, candles.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(batch[0].h, "3");
+    assert!(batch[0].is_confirmed());
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn ws_api_requests_get_canned_replies() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply(
+        "mass-cancel",
+        "0",
+        "",
+        vec![serde_json::json!({"result": true})],
+    );
+    let client = WebsocketClient::new(mock.ws_config());
+
+    let resp = client
+        .send_api_request(
+            "mass-cancel",
+            vec![serde_json::json!({"instType": "OPTION", "instFamily": "BTC-USD"})],
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.data, vec![serde_json::json!({"result": true})]);
+
+    let (_, frame) = mock.next_frame().await.unwrap();
+    assert_eq!(frame["op"], "mass-cancel");
+
+    tokio::time::timeout(TIMEOUT, client.shutdown())
+        .await
+        .expect("shutdown did not finish");
+    assert!(client
+        .health()
+        .iter()
+        .all(|h| h.state != TaskState::Running));
+}
+
+#[tokio::test]
+async fn ws_api_requests_rejected_by_the_session_are_resent_after_relogin() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply("mass-cancel", "60011", "Please log in", vec![]);
+    let mut config = mock.ws_config();
+    config.client_config.credentials = Some(Credentials::new("key", "secret", "pass"));
+    config.reconnect_delay = Duration::from_millis(50);
+    let client = WebsocketClient::new(config);
+
+    let request = tokio::spawn({
+        let client = client.clone();
+        async move {
+            client
+                .send_api_request(
+                    "mass-cancel",
+                    vec![serde_json::json!({"instType": "OPTION"})],
+                )
+                .await
+        }
+    });
+    let mut ops = Vec::new();
+    while ops.len() < 2 {
+        let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+            .await
+            .unwrap()
+            .unwrap();
+        ops.push(frame["op"].as_str().unwrap().to_string());
+    }
+    // The re-login is delayed by `reconnect_delay`, so the resend sees this.
+    mock.reply(
+        "mass-cancel",
+        "0",
+        "",
+        vec![serde_json::json!({"result": true})],
+    );
+
+    let resp = tokio::time::timeout(TIMEOUT, request)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(resp.data, vec![serde_json::json!({"result": true})]);
+    while ops.len() < 4 {
+        let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+            .await
+            .unwrap()
+            .unwrap();
+        ops.push(frame["op"].as_str().unwrap().to_string());
+    }
+    assert_eq!(ops, ["login", "mass-cancel", "login", "mass-cancel"]);
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn topics_released_before_login_are_not_subscribed_after_it() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reject_logins(true);
+    let mut config = mock.ws_config();
+    config.client_config.credentials = Some(Credentials::new("key", "secret", "pass"));
+    config.reconnect_delay = Duration::from_millis(100);
+    let client = WebsocketClient::new(config);
+
+    let orders = WsSubscriptionArg::channel_only("orders").inst_type("ANY");
+    let _rx = client.subscribe(vec![orders.clone()]).await.unwrap();
+    let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame["op"], "login");
+
+    // Released while queued for the re-login.
+    client.unsubscribe(vec![orders.clone()]).await.unwrap();
+    mock.reject_logins(false);
+    client
+        .send_api_request(
+            "mass-cancel",
+            vec![serde_json::json!({"instType": "OPTION"})],
+        )
+        .await
+        .unwrap();
+
+    let mut ops = Vec::new();
+    while ops.last().map(String::as_str) != Some("mass-cancel") {
+        let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+            .await
+            .unwrap()
+            .unwrap();
+        ops.push(frame["op"].as_str().unwrap().to_string());
+    }
+    assert_eq!(ops, ["login", "mass-cancel"]);
+    assert!(client.subscriptions().await.is_empty());
+    assert!(mock.subscriptions().is_empty());
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn ws_api_orders_are_not_resent_after_server_errors() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply("order", "50013", "Systems are busy", vec![]);
+    mock.reply("mass-cancel", "50011", "Rate limit reached", vec![]);
+    let mut config = mock.ws_config();
+    config.client_config.credentials = Some(Credentials::new("key", "secret", "pass"));
+    config.client_config.retry_policy = RetryPolicy {
+        min_backoff: Duration::from_millis(10),
+        ..RetryPolicy::default()
+    };
+    let client = WebsocketClient::new(config);
+
+    // OKX may have placed the order before failing, so it is not resent.
+    let err = client
+        .send_api_request("order", vec![serde_json::json!({"instId": "BTC-USDT"})])
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_code(), Some("50013"));
+    // Rate limit rejections are returned before executing, so they are.
+    let err = client
+        .send_api_request(
+            "mass-cancel",
+            vec![serde_json::json!({"instType": "OPTION"})],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.api_code(), Some("50011"));
+
+    let mut ops = Vec::new();
+    while ops.len() < 6 {
+        let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+            .await
+            .unwrap()
+            .unwrap();
+        ops.push(frame["op"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        ops,
+        [
+            "login",
+            "order",
+            "mass-cancel",
+            "mass-cancel",
+            "mass-cancel",
+            "mass-cancel"
+        ]
+    );
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn capture_writes_pushes_to_json_lines() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(mock.ws_config());
+    let path = std::env::temp_dir().join(format!("okx-capture-{}.jsonl", std::process::id()));
+
+    let arg = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
+    let capture = Capture::start(
+        &client,
+        vec![arg.clone()],
+        JsonLinesSink::create(&path).unwrap(),
+    )
+    .await
+    .unwrap();
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&arg))
+        .await
+        .unwrap();
+
+    for last in ["1", "2"] {
+        mock.push(
+            &arg,
+            vec![serde_json::json!({"instId": "BTC-USDT", "last": last})],
+        );
+    }
+    tokio::time::timeout(TIMEOUT, async {
+        while capture.captured() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    capture.stop().await.unwrap();
+
+    let records = read_json_lines(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let lasts: Vec<String> = records
+        .iter()
+        .map(|r| r.parse_data::<Ticker>().unwrap()[0].last.clone())
+        .collect();
+    assert_eq!(lasts, ["1", "2"]);
+    assert_eq!(records[0].event.arg, arg);
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn oco_cancels_stop_when_take_profit_fills() {
+    let rest_mock = MockOkxRest::start().await;
+    rest_mock
+        .respond_signed(
+            "POST",
+            "/api/v5/trade/order",
+            vec![mock_order_result("tp-1", "")],
+        )
+        .await;
+    let algo = serde_json::json!({"algoId": "sl-1", "algoClOrdId": "", "sCode": "0", "sMsg": ""});
+    rest_mock
+        .respond_signed("POST", "/api/v5/trade/order-algo", vec![algo.clone()])
+        .await;
+    rest_mock
+        .respond_signed("POST", "/api/v5/trade/cancel-algos", vec![algo])
+        .await;
+
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        client_config: rest_mock.config(),
+        ..mock.ws_config()
+    });
+
+    let mut oco = place_oco(
+        &Arc::new(rest_mock.client()),
+        &client,
+        OcoLeg::Order(OrderRequest {
+            inst_id: "BTC-USDT".into(),
+            side: OrderSide::Sell,
+            px: Some("50000".into()),
+            sz: "1".into(),
+            ..Default::default()
+        }),
+        OcoLeg::Trigger(AlgoOrderRequest {
+            inst_id: "BTC-USDT".into(),
+            side: OrderSide::Sell,
+            ord_type: AlgoOrderType::Trigger,
+            sz: "1".into(),
+            trigger_px: Some("40000".into()),
+            order_px: Some("-1".into()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap();
+
+    let orders = WsSubscriptionArg::channel_only("orders")
+        .inst_type("ANY")
+        .inst_id("BTC-USDT");
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&orders))
+        .await
+        .unwrap();
+    mock.push(
+        &orders,
+        vec![serde_json::json!({"ordId": "tp-1", "algoId": "", "state": "filled"})],
+    );
+
+    let outcome = tokio::time::timeout(TIMEOUT, oco.wait()).await.unwrap();
+    assert_eq!(
+        outcome,
+        Some(OcoOutcome::Filled {
+            side: OcoSide::TakeProfit,
+            sibling_cancelled: true,
+        })
+    );
+    let cancels: Vec<_> = rest_mock
+        .received_requests()
+        .await
+        .into_iter()
+        .filter(|r| r.url.path() == "/api/v5/trade/cancel-algos")
+        .collect();
+    assert_eq!(cancels.len(), 1);
+    let body: serde_json::Value = serde_json::from_slice(&cancels[0].body).unwrap();
+    assert_eq!(body[0]["algoId"], "sl-1");
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn quote_engine_amends_only_moved_sides() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply(
+        "batch-orders",
+        "0",
+        "",
+        vec![mock_order_result("1", ""), mock_order_result("2", "")],
+    );
+    mock.reply(
+        "batch-amend-orders",
+        "0",
+        "",
+        vec![
+            serde_json::json!({"ordId": "2", "clOrdId": "", "reqId": "", "sCode": "0", "sMsg": ""}),
+        ],
+    );
+    let engine = QuoteEngine::new(WsApiClient::new(mock.ws_config()), TradeMode::Cross)
+        .batch_interval(Duration::ZERO);
+    let target = |ask: &str| {
+        (
+            "BTC-USDT-SWAP".to_string(),
+            TargetQuote::two_sided(QuoteLevel::new("100", "1"), QuoteLevel::new(ask, "1")),
+        )
+    };
+
+    let report = engine.update([target("101")]).await.unwrap();
+    assert_eq!(report.placed, 2);
+    let (_, frame) = mock.next_frame().await.unwrap();
+    assert_eq!(frame["op"], "batch-orders");
+    assert_eq!(frame["args"][0]["ordType"], "post_only");
+
+    let report = engine.update([target("102")]).await.unwrap();
+    assert_eq!((report.placed, report.amended), (0, 1));
+    let (_, frame) = mock.next_frame().await.unwrap();
+    assert_eq!(frame["op"], "batch-amend-orders");
+    assert_eq!(frame["args"].as_array().unwrap().len(), 1);
+    assert_eq!(frame["args"][0]["newPx"], "102");
+
+    let ask = engine
+        .live_quotes()
+        .await
+        .into_iter()
+        .find(|q| q.side == OrderSide::Sell)
+        .unwrap();
+    assert_eq!(ask.level.px, "102");
+}
+
+#[tokio::test]
+async fn quote_engine_cancels_orders_of_a_failed_batch_before_replacing_them() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply("batch-orders", "1", "Operation failed", vec![]);
+    let engine = QuoteEngine::new(WsApiClient::new(mock.ws_config()), TradeMode::Cross)
+        .batch_interval(Duration::ZERO);
+    let target = (
+        "BTC-USDT-SWAP".to_string(),
+        TargetQuote {
+            bid: Some(QuoteLevel::new("100", "1")),
+            ask: None,
+        },
+    );
+
+    assert!(engine.update([target.clone()]).await.is_err());
+    assert!(engine.live_quotes().await.is_empty());
+    let (_, failed) = mock.next_frame().await.unwrap();
+    let cl_ord_id = failed["args"][0]["clOrdId"].clone();
+
+    mock.reply("batch-orders", "0", "", vec![mock_order_result("2", "")]);
+    mock.reply(
+        "batch-cancel-orders",
+        "0",
+        "",
+        vec![serde_json::json!({"ordId": "1", "clOrdId": cl_ord_id, "sCode": "0", "sMsg": ""})],
+    );
+    let report = engine.update([target]).await.unwrap();
+    assert_eq!((report.cancelled, report.placed), (1, 1));
+    let (_, cancel) = mock.next_frame().await.unwrap();
+    assert_eq!(cancel["op"], "batch-cancel-orders");
+    assert_eq!(cancel["args"][0]["clOrdId"], cl_ord_id);
+    let (_, place) = mock.next_frame().await.unwrap();
+    assert_eq!(place["op"], "batch-orders");
+    assert_ne!(place["args"][0]["clOrdId"], cl_ord_id);
+}
+
+#[tokio::test]
+async fn book_gap_is_reported_and_resubscribed() {
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(mock.ws_config());
+    let arg = WsSubscriptionArg::with_inst_id("books-l2-tbt", "BTC-USDT");
+    let mut rx = client.subscribe(vec![arg.clone()]).await.unwrap();
+    mock.wait_for_subscription(&arg).await;
+
+    for (prev, seq) in [(-1, 10), (10, 11), (13, 14)] {
+        mock.push(
+            &arg,
+            vec![serde_json::json!({"asks": [], "bids": [], "prevSeqId": prev, "seqId": seq})],
+        );
+    }
+
+    let gap = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            if let WsMessage::DataGap(gap) = &*rx.recv().await.unwrap() {
+                return gap.clone();
+            }
+        }
+    })
+    .await
+    .expect("no gap reported");
+    assert_eq!((gap.expected, gap.received), (11, 13));
+    assert_eq!(gap.arg, arg);
+
+    let ops = tokio::time::timeout(TIMEOUT, async {
+        let mut ops = Vec::new();
+        while ops.len() < 2 {
+            let (_, frame) = mock.next_frame().await.unwrap();
+            if frame["args"][0]["channel"] == "books-l2-tbt" {
+                ops.push(frame["op"].as_str().unwrap_or_default().to_string());
+            }
+        }
+        ops
+    })
+    .await
+    .expect("channel not resubscribed");
+    assert_eq!(ops, ["unsubscribe", "subscribe"]);
+}
+
+#[tokio::test]
+async fn risk_limits_reject_orders_before_sending() {
+    let mut mock = MockOkxWs::start().await;
+    mock.reply("order", "0", "", vec![mock_order_result("1", "")]);
+    let guard = RiskGuard::new(RiskLimits {
+        price_collar: Some(0.05),
+        ..Default::default()
+    });
+    guard.set_mark_price("BTC-USDT", 100.0);
+    let mut config = mock.ws_config();
+    config.client_config.risk_guard = Some(guard);
+    let api = WsApiClient::new(config);
+    let order = |px: &str| OrderRequest {
+        inst_id: "BTC-USDT".into(),
+        td_mode: TradeMode::Cash,
+        side: OrderSide::Buy,
+        ord_type: OrderType::Limit,
+        sz: "1".into(),
+        px: Some(px.into()),
+        ..Default::default()
+    };
+
+    let err = api.place_order(order("120")).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            OkxError::RiskRejected(RiskViolation::PriceCollar { mark_px, .. }) if mark_px == 100.0
+        ),
+        "{err:?}"
+    );
+
+    let placed = api.place_order(order("101")).await.unwrap();
+    assert_eq!(placed.ord_id, "1");
+    let (_, frame) = tokio::time::timeout(TIMEOUT, mock.next_frame())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame["op"], "order");
+    assert_eq!(frame["args"][0]["px"], "101");
+}
+
+fn fill_json(trade_id: &str, bill_id: &str, fill_time: i64) -> serde_json::Value {
+    serde_json::json!({
+        "instType": "SPOT", "instId": "BTC-USDT", "tradeId": trade_id, "ordId": "1",
+        "clOrdId": "", "billId": bill_id, "tag": "", "fillPx": "100", "fillSz": "1",
+        "side": "buy", "posSide": "net", "execType": "T", "feeCcy": "BTC", "fee": "-0.001",
+        "ts": fill_time.to_string(), "fillTime": fill_time.to_string(), "fillPnl": "0",
+        "fillPxVol": "", "fillPxUsd": "", "fillMarkVol": "", "fillFwdPx": "", "fillMarkPx": "",
+    })
+}
+
+#[tokio::test]
+async fn fill_feed_merges_pushes_and_backfills_in_order() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let rest_mock = MockOkxRest::start().await;
+    // Fill 2 was missed on the WebSocket; fill 1 arrives on both.
+    rest_mock
+        .respond_signed(
+            "GET",
+            "/api/v5/trade/fills",
+            vec![
+                fill_json("2", "b2", now - 3_000),
+                fill_json("1", "b1", now - 4_000),
+            ],
+        )
+        .await;
+
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        client_config: rest_mock.config(),
+        ..mock.ws_config()
+    });
+    let mut feed = FillFeed::start(
+        Arc::new(rest_mock.client()),
+        &client,
+        FillFeedOptions {
+            settle_delay: Duration::from_millis(50),
+            since: Some(now - 10_000),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let orders = WsSubscriptionArg::channel_only("orders").inst_type("ANY");
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&orders))
+        .await
+        .unwrap();
+    let mut fill_1 = fill_json("1", "", now - 4_000);
+    fill_1["fillFee"] = "-0.001".into();
+    let fill_3 = serde_json::json!({
+        "instId": "BTC-USDT", "ordId": "1", "tradeId": "3", "fillSz": "1",
+        "fillTime": (now - 2_000).to_string(), "state": "filled",
+    });
+    let no_fill = serde_json::json!({"instId": "BTC-USDT", "ordId": "2", "state": "live"});
+    mock.push(&orders, vec![fill_1, fill_3, no_fill]);
+
+    let mut received = Vec::new();
+    while received.len() < 3 {
+        let fill = tokio::time::timeout(TIMEOUT, feed.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        received.push((fill.trade_id, fill.bill_id));
+    }
+    assert_eq!(
+        received,
+        [
+            ("1".to_string(), "b1".to_string()),
+            ("2".to_string(), "b2".to_string()),
+            ("3".to_string(), String::new()),
+        ]
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), feed.recv())
+            .await
+            .is_err()
+    );
+
+    client.shutdown().await;
+}
+
+#[tokio::test]
+async fn saved_subscriptions_are_restored_by_a_new_client() {
+    let dir = std::env::temp_dir().join(format!("okx-ws-state-{}", std::process::id()));
+    let storage = Arc::new(FileStorage::new(&dir).unwrap());
+    let tickers = WsSubscriptionArg::with_inst_id("tickers", "BTC-USDT");
+    let trades = WsSubscriptionArg::with_inst_id("trades", "BTC-USDT");
+
+    let mut mock = MockOkxWs::start().await;
+    let client = WebsocketClient::new(WsConfig {
+        state_storage: Some(storage.clone()),
+        ..mock.ws_config()
+    });
+    client
+        .subscribe(vec![tickers.clone(), trades.clone()])
+        .await
+        .unwrap();
+    client.unsubscribe(vec![trades]).await.unwrap();
+    client.shutdown().await;
+
+    let restarted = WebsocketClient::new(WsConfig {
+        state_storage: Some(storage),
+        ..mock.ws_config()
+    });
+    let restored = restarted.restore_subscriptions().await.unwrap();
+    assert_eq!(restored, vec![tickers.clone()]);
+    tokio::time::timeout(TIMEOUT, mock.wait_for_subscription(&tickers))
+        .await
+        .unwrap();
+    assert_eq!(restarted.subscriptions().await, [tickers]);
+
+    restarted.shutdown().await;
+    std::fs::remove_dir_all(&dir).unwrap();
+}